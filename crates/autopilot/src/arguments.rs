@@ -1,14 +1,16 @@
 use {
     crate::infra,
+    model::signature::SigningScheme,
     primitive_types::{H160, U256},
     shared::{
-        arguments::{display_list, display_option, ExternalSolver},
+        arguments::{display_list, display_option, display_secret_option, ExternalSolver},
         bad_token::token_owner_finder,
         http_client,
         price_estimation::{self, NativePriceEstimators},
     },
     std::{net::SocketAddr, num::NonZeroUsize, str::FromStr, time::Duration},
     url::Url,
+    web3::signing::SecretKey,
 };
 
 #[derive(clap::Parser)]
@@ -39,6 +41,22 @@ pub struct Arguments {
     #[clap(long, env)]
     pub ethflow_indexing_start: Option<u64>,
 
+    /// Additional `CoWSwapOnchainOrders` compatible contracts whose placed
+    /// orders should be indexed. Each contract is specified as
+    /// `<ADDRESS>[:<START>[:<GAS_LIMIT>[:<PARSER>[:<SIGNING_SCHEME>]]]]`,
+    /// where empty parts take their default value:
+    /// - the start is the block from which to index events (defaults to the
+    ///   settlement contract deployment)
+    /// - the gas limit is the gas used for verifying EIP-1271 signatures when
+    ///   quoting the orders
+    /// - the parser is how the custom event data of the orders is parsed,
+    ///   either `quote-id` (default) when it starts with the big-endian encoded
+    ///   quote id, or `ethflow` when it is encoded like for EthFlow orders
+    /// - the signing scheme is the only scheme accepted for the orders, either
+    ///   `eip1271` or `presign` (defaults to accepting both)
+    #[clap(long, env, use_value_delimiter = true)]
+    pub onchain_order_contracts: Vec<OnchainOrderContract>,
+
     /// A tracing Ethereum node URL to connect to, allowing a separate node URL
     /// to be used exclusively for tracing calls.
     #[clap(long, env)]
@@ -153,6 +171,13 @@ pub struct Arguments {
     #[clap(long, env, use_value_delimiter = true)]
     pub drivers: Vec<ExternalSolver>,
 
+    /// The hex encoded private key used to sign requests to the drivers, so
+    /// that drivers can verify that requests originate from this autopilot.
+    /// Drivers that are not configured to authenticate requests ignore the
+    /// signature.
+    #[clap(long, env, value_parser = parse_secret_key)]
+    pub driver_signing_key: Option<SecretKey>,
+
     /// The maximum number of blocks to wait for a settlement to appear on
     /// chain.
     #[clap(long, env, default_value = "5")]
@@ -230,6 +255,7 @@ impl std::fmt::Display for Arguments {
             tracing_node_url,
             ethflow_contract,
             ethflow_indexing_start,
+            onchain_order_contracts,
             metrics_address,
             skip_event_sync,
             allowed_tokens,
@@ -245,6 +271,7 @@ impl std::fmt::Display for Arguments {
             trusted_tokens,
             trusted_tokens_update_interval,
             drivers,
+            driver_signing_key,
             submission_deadline,
             additional_deadline_for_rewards,
             score_cap,
@@ -269,6 +296,7 @@ impl std::fmt::Display for Arguments {
         display_option(f, "tracing_node_url", tracing_node_url)?;
         writeln!(f, "ethflow_contract: {:?}", ethflow_contract)?;
         writeln!(f, "ethflow_indexing_start: {:?}", ethflow_indexing_start)?;
+        writeln!(f, "onchain_order_contracts: {:?}", onchain_order_contracts)?;
         writeln!(f, "metrics_address: {}", metrics_address)?;
         let _intentionally_ignored = db_url;
         writeln!(f, "db_url: SECRET")?;
@@ -302,6 +330,7 @@ impl std::fmt::Display for Arguments {
             trusted_tokens_update_interval
         )?;
         display_list(f, "drivers", drivers.iter())?;
+        display_secret_option(f, "driver_signing_key", driver_signing_key)?;
         writeln!(f, "submission_deadline: {}", submission_deadline)?;
         writeln!(
             f,
@@ -409,3 +438,96 @@ impl FromStr for FeePolicyKind {
         }
     }
 }
+
+/// A `CoWSwapOnchainOrders` compatible contract whose placed orders get
+/// indexed.
+#[derive(Debug, Clone)]
+pub struct OnchainOrderContract {
+    pub address: H160,
+    pub indexing_start: Option<u64>,
+    pub verification_gas_limit: u64,
+    pub parser: OnchainOrderParserKind,
+    /// The only signing scheme accepted for orders placed through the
+    /// contract. All on-chain schemes are accepted if this is not set.
+    pub signing_scheme: Option<SigningScheme>,
+}
+
+/// How the custom event data of orders placed through a
+/// `CoWSwapOnchainOrders` contract is parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnchainOrderParserKind {
+    /// The custom data starts with the big-endian encoded quote id.
+    #[default]
+    QuoteId,
+    /// The custom data is encoded like for EthFlow orders, and the orders are
+    /// stored as EthFlow orders.
+    EthFlow,
+}
+
+impl FromStr for OnchainOrderParserKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quote-id" => Ok(Self::QuoteId),
+            "ethflow" => Ok(Self::EthFlow),
+            _ => Err(format!("invalid onchain order parser: {}", s)),
+        }
+    }
+}
+
+impl FromStr for OnchainOrderContract {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let address = parts
+            .next()
+            .ok_or("missing contract address")?
+            .parse::<H160>()
+            .map_err(|e| format!("invalid contract address: {}", e))?;
+        let indexing_start = parts
+            .next()
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<u64>())
+            .transpose()
+            .map_err(|e| format!("invalid indexing start: {}", e))?;
+        let verification_gas_limit = parts
+            .next()
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<u64>())
+            .transpose()
+            .map_err(|e| format!("invalid verification gas limit: {}", e))?
+            .unwrap_or_else(model::quote::default_verification_gas_limit);
+        let parser = parts
+            .next()
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse())
+            .transpose()?
+            .unwrap_or_default();
+        let signing_scheme = parts
+            .next()
+            .filter(|part| !part.is_empty())
+            .map(|part| match part {
+                "eip1271" => Ok(SigningScheme::Eip1271),
+                "presign" => Ok(SigningScheme::PreSign),
+                _ => Err(format!("invalid onchain signing scheme: {}", part)),
+            })
+            .transpose()?;
+        if parts.next().is_some() {
+            return Err(format!("too many parts in onchain order contract: {}", s));
+        }
+        Ok(Self {
+            address,
+            indexing_start,
+            verification_gas_limit,
+            parser,
+            signing_scheme,
+        })
+    }
+}
+
+fn parse_secret_key(s: &str) -> anyhow::Result<SecretKey> {
+    let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))?;
+    Ok(SecretKey::from_slice(&bytes)?)
+}
@@ -5,11 +5,15 @@ use {
         auction_participants::Participant,
         auction_prices::AuctionPrice,
         byte_array::ByteArray,
+        jit_orders::JitOrder,
         settlement_call_data::SettlementCallData,
         settlement_scores::Score,
     },
     derivative::Derivative,
-    model::{order::OrderUid, solver_competition::SolverCompetitionDB},
+    model::{
+        order::OrderUid,
+        solver_competition::{self, SolverCompetitionDB},
+    },
     number::conversions::u256_to_big_decimal,
     primitive_types::{H160, U256},
     std::collections::{BTreeMap, HashSet},
@@ -56,6 +60,8 @@ pub struct Competition {
     /// chain before this block height.
     pub block_deadline: u64,
     pub order_executions: Vec<OrderExecution>,
+    /// JIT orders provided by the winner that are not part of the auction.
+    pub jit_orders: Vec<solver_competition::JitOrder>,
     pub competition_simulation_block: u64,
     /// Winner settlement call data
     #[derivative(Debug(format_with = "shared::debug_bytes"))]
@@ -94,6 +100,22 @@ impl super::Postgres {
             .context("order_execution::save")?;
         }
 
+        database::jit_orders::insert(
+            &mut ex,
+            competition
+                .jit_orders
+                .iter()
+                .map(|order| JitOrder {
+                    auction_id: competition.auction_id,
+                    uid: ByteArray(order.id.0),
+                    owner: ByteArray(order.owner.0),
+                })
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )
+        .await
+        .context("jit_orders::insert")?;
+
         database::settlement_scores::insert(
             &mut ex,
             Score {
@@ -159,3 +181,39 @@ impl super::Postgres {
         ex.commit().await.context("commit")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::database::Postgres};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_save_competition_with_jit_orders() {
+        let db = Postgres::with_defaults().await.unwrap();
+        database::clear_DANGER(&db.pool).await.unwrap();
+
+        let order = |id: u8| solver_competition::JitOrder {
+            id: OrderUid([id; 56]),
+            owner: H160([id; 20]),
+        };
+        let competition = Competition {
+            auction_id: 1,
+            // The same JIT order can be settled multiple times.
+            jit_orders: vec![order(1), order(2), order(1)],
+            ..Default::default()
+        };
+        db.save_competition(&competition).await.unwrap();
+
+        let mut ex = db.pool.acquire().await.unwrap();
+        let mut jit_orders = database::jit_orders::fetch(&mut ex, 1).await.unwrap();
+        jit_orders.sort_by_key(|order| order.uid.0);
+        assert_eq!(
+            jit_orders,
+            [1, 2].map(|id| JitOrder {
+                auction_id: 1,
+                uid: ByteArray([id; 56]),
+                owner: ByteArray([id; 20]),
+            }),
+        );
+    }
+}
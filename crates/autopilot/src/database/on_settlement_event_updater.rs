@@ -14,6 +14,9 @@ pub struct AuctionData {
     pub effective_gas_price: U256,
     pub surplus: U256,
     pub fee: U256,
+    // surplus of CoW-only orders that were matched against on-chain liquidity,
+    // only recorded for the reward computation
+    pub cow_only_penalty: U256,
     // pairs <order id, fee> for orders with solver computed fees (limit orders)
     pub order_executions: Vec<(OrderUid, U256)>,
 }
@@ -126,6 +129,14 @@ impl super::Postgres {
                     .await
                     .context("insert_missing_order_executions")?;
                 }
+
+                database::settlement_scores::update_cow_only_penalty(
+                    ex,
+                    auction_data.auction_id.assume_verified(),
+                    &u256_to_big_decimal(&auction_data.cow_only_penalty),
+                )
+                .await
+                .context("update_cow_only_penalty")?;
             }
         }
         Ok(())
@@ -1,5 +1,6 @@
 pub mod ethflow_events;
 pub mod event_retriever;
+pub mod generic_events;
 
 use {
     super::{
@@ -61,6 +62,8 @@ pub struct OnchainOrderParser<EventData: Send + Sync, EventRow: Send + Sync> {
     db: Postgres,
     web3: Web3,
     quoter: Arc<dyn OrderQuoting>,
+    /// The `CoWSwapOnchainOrders` contract whose events are indexed.
+    contract: H160,
     custom_onchain_data_parser: Box<dyn OnchainOrderParsing<EventData, EventRow>>,
     domain_separator: DomainSeparator,
     settlement_contract: H160,
@@ -77,6 +80,7 @@ where
         db: Postgres,
         web3: Web3,
         quoter: Arc<dyn OrderQuoting>,
+        contract: H160,
         custom_onchain_data_parser: Box<dyn OnchainOrderParsing<EventData, EventRow>>,
         domain_separator: DomainSeparator,
         settlement_contract: H160,
@@ -86,6 +90,7 @@ where
             db,
             web3,
             quoter,
+            contract,
             custom_onchain_data_parser,
             domain_separator,
             settlement_contract,
@@ -95,6 +100,29 @@ where
     }
 }
 
+impl Postgres {
+    /// Attributes onchain order placements and invalidations that were indexed
+    /// before the emitting contract was stored (i.e. EthFlow events) to the
+    /// specified contract.
+    pub async fn set_missing_onchain_order_contract(&self, contract: H160) -> Result<()> {
+        let _timer = DatabaseMetrics::get()
+            .database_queries
+            .with_label_values(&["set_missing_onchain_order_contract"])
+            .start_timer();
+
+        let contract = ByteArray(contract.0);
+        let mut transaction = self.pool.begin().await?;
+        database::onchain_broadcasted_orders::set_missing_contract(&mut transaction, &contract)
+            .await
+            .context("set_missing_contract for placements failed")?;
+        database::onchain_invalidations::set_missing_contract(&mut transaction, &contract)
+            .await
+            .context("set_missing_contract for invalidations failed")?;
+        transaction.commit().await.context("commit")?;
+        Ok(())
+    }
+}
+
 // The following struct describes the return type from the custom order parsing
 // logic. All parser must return a quote_id, as this is currently required by
 // the protocol.
@@ -104,9 +132,10 @@ pub struct OnchainOrderCustomData<T> {
 }
 
 // The following trait allows to implement custom onchain order parsing for
-// differently placed orders. E.g., there will be a implementation for ethflow
-// and presign orders. For each of the customs types, the trait allows to
-// implement parsing the on-chain data and storing the event data
+// differently placed orders. E.g., there is an implementation for ethflow
+// and one for generic `CoWSwapOnchainOrders` contracts. For each of the
+// customs types, the trait allows to implement parsing the on-chain data and
+// storing the event data
 
 // The generic EventData stores the result of the custom event parsing
 // The generic EvenDataForDB contains the prepared data that will be appended
@@ -145,6 +174,16 @@ where
         hashmap: &HashMap<EventIndex, EventData>,
         onchain_order_placement: &OnchainOrderPlacement,
     ) -> EventRow;
+
+    // The gas limit used for verifying EIP-1271 signatures of the orders when
+    // quoting them. Contracts whose orders are subsidized (like ethflow) can
+    // return 0 here.
+    fn verification_gas_limit(&self) -> u64;
+
+    // The only signing scheme accepted for orders placed through the contract.
+    // Orders using another scheme are dropped. All schemes are accepted if
+    // this returns `None`.
+    fn signing_scheme(&self) -> Option<SigningScheme>;
 }
 
 #[async_trait::async_trait]
@@ -176,8 +215,10 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
 
         let mut transaction = self.db.pool.begin().await?;
 
+        let contract = ByteArray(self.contract.0);
         database::onchain_broadcasted_orders::mark_as_reorged(
             &mut transaction,
+            &contract,
             *range.start() as i64,
         )
         .await
@@ -185,6 +226,7 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
 
         database::onchain_invalidations::delete_invalidations(
             &mut transaction,
+            &contract,
             *range.start() as i64,
         )
         .await
@@ -192,6 +234,7 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
 
         database::onchain_invalidations::insert_onchain_invalidations(
             &mut transaction,
+            &contract,
             invalided_order_uids.as_slice(),
         )
         .await
@@ -256,6 +299,7 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
 
         database::onchain_invalidations::insert_onchain_invalidations(
             &mut transaction,
+            &ByteArray(self.contract.0),
             invalided_order_uids.as_slice(),
         )
         .await
@@ -297,9 +341,10 @@ impl<T: Sync + Send + Clone, W: Sync + Send + Clone> EventStoring<ContractEvent>
             .start_timer();
 
         let mut con = self.db.pool.acquire().await?;
-        let block_number = database::onchain_broadcasted_orders::last_block(&mut con)
-            .await
-            .context("block_number_of_most_recent_event failed")?;
+        let block_number =
+            database::onchain_broadcasted_orders::last_block(&mut con, &ByteArray(self.contract.0))
+                .await
+                .context("block_number_of_most_recent_event failed")?;
         block_number.try_into().context("block number is negative")
     }
 }
@@ -348,6 +393,9 @@ impl<T: Send + Sync + Clone, W: Send + Sync> OnchainOrderParser<T, W> {
         let onchain_order_data = parse_general_onchain_order_placement_data(
             &*self.quoter,
             events_and_quotes,
+            self.contract,
+            self.custom_onchain_data_parser.verification_gas_limit(),
+            self.custom_onchain_data_parser.signing_scheme(),
             self.domain_separator,
             self.settlement_contract,
             &self.liquidity_order_owners,
@@ -447,9 +495,13 @@ type GeneralOnchainOrderPlacementData = (
     OnchainOrderPlacement,
     Order,
 );
+#[allow(clippy::too_many_arguments)]
 async fn parse_general_onchain_order_placement_data<'a>(
     quoter: &'a dyn OrderQuoting,
     order_placement_events_and_quotes_zipped: Vec<(EthContractEvent<ContractEvent>, i64, i64)>,
+    contract: H160,
+    verification_gas_limit: u64,
+    accepted_signing_scheme: Option<SigningScheme>,
     domain_separator: DomainSeparator,
     settlement_contract: H160,
     liquidity_order_owners: &'a HashSet<H160>,
@@ -478,10 +530,22 @@ async fn parse_general_onchain_order_placement_data<'a>(
                 metrics.inc_onchain_order_errors("bad_parsing");
             }
             let (order_data, owner, signing_scheme, order_uid) = detailed_order_data?;
+            if matches!(accepted_signing_scheme, Some(accepted) if accepted != signing_scheme) {
+                metrics.inc_onchain_order_errors("unsupported_signing_scheme");
+                bail!("order {order_uid} uses unsupported signing scheme {signing_scheme:?}");
+            }
 
-            let quote_result = get_quote(quoter, order_data, signing_scheme, &quote_id).await;
+            let quote_result = get_quote(
+                quoter,
+                order_data,
+                signing_scheme,
+                verification_gas_limit,
+                &quote_id,
+            )
+            .await;
             let order_data = convert_onchain_order_placement(
                 &event,
+                contract,
                 event_timestamp,
                 quote_result.clone(),
                 order_data,
@@ -538,16 +602,17 @@ async fn get_quote(
     quoter: &dyn OrderQuoting,
     order_data: OrderData,
     signing_scheme: SigningScheme,
+    verification_gas_limit: u64,
     quote_id: &i64,
 ) -> Result<Quote, OnchainOrderPlacementError> {
     let quote_signing_scheme = convert_signing_scheme_into_quote_signing_scheme(
         signing_scheme,
         false,
-        // Currently, only ethflow orders are indexed with this onchain
-        // parser. For ethflow orders, we are okay to subsidize the
-        // orders and allow them to set the verification limit to 0.
-        // For general orders, this could result in a too big subsidy.
-        0u64,
+        // The verification gas limit depends on the contract that placed the
+        // order. For ethflow orders, we are okay to subsidize the orders and
+        // allow them to set the verification limit to 0. For general orders,
+        // this could result in a too big subsidy.
+        verification_gas_limit,
     )
     .map_err(onchain_order_placement_error_from)?;
 
@@ -582,6 +647,7 @@ async fn get_quote(
 #[allow(clippy::too_many_arguments)]
 fn convert_onchain_order_placement(
     order_placement: &ContractOrderPlacement,
+    contract: H160,
     event_timestamp: i64,
     quote: Result<Quote, OnchainOrderPlacementError>,
     order_data: OrderData,
@@ -655,6 +721,7 @@ fn convert_onchain_order_placement(
         order_uid: ByteArray(order_uid.0),
         sender: ByteArray(order_placement.sender.0),
         placement_error: quote.err(),
+        contract: ByteArray(contract.0),
     };
     (onchain_order_placement_event, order)
 }
@@ -907,6 +974,7 @@ mod test {
         let event_timestamp = 234354345;
         let (onchain_order_placement, order) = convert_onchain_order_placement(
             &order_placement,
+            H160::from([10u8; 20]),
             event_timestamp,
             Ok(quote),
             order_data,
@@ -935,6 +1003,7 @@ mod test {
             order_uid: ByteArray(order_uid.0),
             sender: ByteArray(order_placement.sender.0),
             placement_error: None,
+            contract: ByteArray([10u8; 20]),
         };
         let expected_order = database::orders::Order {
             uid: ByteArray(order_uid.0),
@@ -1020,6 +1089,7 @@ mod test {
         let event_timestamp = 234325345;
         let (onchain_order_placement, order) = convert_onchain_order_placement(
             &order_placement,
+            H160::from([10u8; 20]),
             event_timestamp,
             Ok(quote),
             order_data,
@@ -1048,6 +1118,7 @@ mod test {
             order_uid: ByteArray(order_uid.0),
             sender: ByteArray(order_placement.sender.0),
             placement_error: None,
+            contract: ByteArray([10u8; 20]),
         };
         let expected_order = database::orders::Order {
             uid: ByteArray(order_uid.0),
@@ -1131,6 +1202,7 @@ mod test {
         let signing_scheme = SigningScheme::Eip1271;
         let (onchain_order_placement, order) = convert_onchain_order_placement(
             &order_placement,
+            H160::from([10u8; 20]),
             345634,
             Ok(quote),
             order_data,
@@ -1159,6 +1231,7 @@ mod test {
             order_uid: ByteArray(order_uid.0),
             sender: ByteArray(order_placement.sender.0),
             placement_error: None,
+            contract: ByteArray([10u8; 20]),
         };
         let expected_order = database::orders::Order {
             uid: ByteArray(order_uid.0),
@@ -1256,6 +1329,8 @@ mod test {
                 (event_data_1.clone(), 23452345, quote_id_1),
                 (event_data_2.clone(), 234125345, quote_id_2),
             ],
+            H160::zero(),
+            0,
             domain_separator,
             settlement_contract,
             &Default::default(),
@@ -1346,6 +1421,7 @@ mod test {
             buy_amount,
             fee_amount,
             full_fee_amount: 24.into(),
+            spread_bps: 0,
         };
         let cloned_quote = quote.clone();
         order_quoter
@@ -1372,6 +1448,12 @@ mod test {
         custom_onchain_order_parser
             .expect_customized_event_data_for_event_index()
             .returning(|_, _, _, _| 1u8);
+        custom_onchain_order_parser
+            .expect_verification_gas_limit()
+            .returning(|| 0);
+        custom_onchain_order_parser
+            .expect_signing_scheme()
+            .returning(|| None);
         let web3 = Web3::new(create_env_test_transport());
         let onchain_order_parser = OnchainOrderParser {
             db: Postgres {
@@ -1382,6 +1464,7 @@ mod test {
             },
             web3,
             quoter: Arc::new(order_quoter),
+            contract: H160::zero(),
             custom_onchain_data_parser: Box::new(custom_onchain_order_parser),
             domain_separator,
             settlement_contract: H160::zero(),
@@ -1429,6 +1512,7 @@ mod test {
                     order_uid: ByteArray(expected_uid.0),
                     sender: ByteArray(sender.0),
                     placement_error: None,
+                    contract: ByteArray([0; 20]),
                 },
             )]
         );
@@ -20,6 +20,7 @@ use {
         Web3,
     },
     hex_literal::hex,
+    model::signature::SigningScheme,
     shared::contracts::settlement_deployment_block_number_hash,
     sqlx::types::BigDecimal,
     std::{collections::HashMap, convert::TryInto},
@@ -29,7 +30,25 @@ use {
 // https://github.com/cowprotocol/ethflowcontract/blob/main/src/CoWSwapEthFlow.sol#L57
 pub const WRAP_ALL_SELECTOR: [u8; 4] = hex!("4c84c1c8");
 
-pub struct EthFlowOnchainOrderParser;
+/// Parses orders placed through the EthFlow contract, or through other
+/// contracts encoding their custom event data the same way.
+#[derive(Default)]
+pub struct EthFlowOnchainOrderParser {
+    verification_gas_limit: u64,
+    signing_scheme: Option<SigningScheme>,
+}
+
+impl EthFlowOnchainOrderParser {
+    /// Creates a parser for a contract other than the EthFlow contract, whose
+    /// orders may not be subsidized. The default parser is used for the
+    /// EthFlow contract itself.
+    pub fn new(verification_gas_limit: u64, signing_scheme: Option<SigningScheme>) -> Self {
+        Self {
+            verification_gas_limit,
+            signing_scheme,
+        }
+    }
+}
 
 #[derive(Copy, Debug, Clone)]
 pub struct EthFlowData {
@@ -131,6 +150,16 @@ impl OnchainOrderParsing<EthFlowData, EthFlowDataForDb> for EthFlowOnchainOrderP
             },
         }
     }
+
+    fn verification_gas_limit(&self) -> u64 {
+        // EthFlow orders are subsidized, so by default their signature
+        // verification does not need to be accounted for in the quote.
+        self.verification_gas_limit
+    }
+
+    fn signing_scheme(&self) -> Option<SigningScheme> {
+        self.signing_scheme
+    }
 }
 
 fn convert_to_quote_id_and_user_valid_to(
@@ -231,7 +260,7 @@ mod test {
                 ..Default::default()
             }),
         };
-        let ethflow_onchain_order_parser = EthFlowOnchainOrderParser {};
+        let ethflow_onchain_order_parser = EthFlowOnchainOrderParser::default();
         let result = ethflow_onchain_order_parser
             .parse_custom_event_data(vec![event_data].as_slice())
             .unwrap();
@@ -0,0 +1,144 @@
+//! Parsing of orders placed through arbitrary `CoWSwapOnchainOrders`
+//! compatible contracts.
+//!
+//! Contrary to EthFlow orders, these orders don't require any additional data
+//! to be stored in the database. The only requirement on the custom event data
+//! is that it starts with the big-endian encoded quote id of the order, any
+//! data following it is ignored.
+
+use {
+    super::{OnchainOrderCustomData, OnchainOrderParsing},
+    crate::database::events::meta_to_event_index,
+    anyhow::{anyhow, Result},
+    contracts::cowswap_onchain_orders::{
+        event_data::OrderPlacement as ContractOrderPlacement,
+        Event as ContractEvent,
+    },
+    database::{
+        events::EventIndex,
+        onchain_broadcasted_orders::OnchainOrderPlacement,
+        orders::Order,
+        PgTransaction,
+    },
+    ethcontract::Event as EthContractEvent,
+    model::signature::SigningScheme,
+    std::collections::HashMap,
+};
+
+pub struct GenericOnchainOrderParser {
+    verification_gas_limit: u64,
+    signing_scheme: Option<SigningScheme>,
+}
+
+impl GenericOnchainOrderParser {
+    pub fn new(verification_gas_limit: u64, signing_scheme: Option<SigningScheme>) -> Self {
+        Self {
+            verification_gas_limit,
+            signing_scheme,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OnchainOrderParsing<(), ()> for GenericOnchainOrderParser {
+    fn parse_custom_event_data(
+        &self,
+        contract_events: &[EthContractEvent<ContractEvent>],
+    ) -> Result<Vec<(EventIndex, OnchainOrderCustomData<()>)>> {
+        contract_events
+            .iter()
+            .filter_map(|EthContractEvent { data, meta }| {
+                let meta = match meta {
+                    Some(meta) => meta,
+                    None => return Some(Err(anyhow!("event without metadata"))),
+                };
+                let event = match data {
+                    ContractEvent::OrderPlacement(event) => event,
+                    _ => return None,
+                };
+                match convert_to_quote_id(event) {
+                    Ok(quote_id) => Some(Ok((
+                        meta_to_event_index(meta),
+                        OnchainOrderCustomData {
+                            quote_id,
+                            additional_data: None,
+                        },
+                    ))),
+                    Err(err) => {
+                        tracing::debug!("Error while converting quote id: {:?}", err);
+                        None
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    async fn append_custom_order_info_to_db<'a>(
+        &self,
+        _ex: &mut PgTransaction<'a>,
+        _custom_onchain_data: Vec<()>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn customized_event_data_for_event_index(
+        &self,
+        _event_index: &EventIndex,
+        _order: &Order,
+        _hashmap: &HashMap<EventIndex, ()>,
+        _onchain_order_placement: &OnchainOrderPlacement,
+    ) {
+    }
+
+    fn verification_gas_limit(&self) -> u64 {
+        self.verification_gas_limit
+    }
+
+    fn signing_scheme(&self) -> Option<SigningScheme> {
+        self.signing_scheme
+    }
+}
+
+fn convert_to_quote_id(order_placement: &ContractOrderPlacement) -> Result<i64> {
+    let data = order_placement.data.0.as_slice();
+    let quote_id = data
+        .get(0..8)
+        .ok_or_else(|| anyhow!("invalid data length"))?;
+    Ok(i64::from_be_bytes(quote_id.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, ethcontract::EventMetadata};
+
+    #[test]
+    fn parses_quote_id_prefix() {
+        let event = |data: Vec<u8>| EthContractEvent {
+            data: ContractEvent::OrderPlacement(ContractOrderPlacement {
+                data: ethcontract::Bytes(data),
+                ..Default::default()
+            }),
+            meta: Some(EventMetadata {
+                block_number: 1,
+                log_index: 0usize,
+                ..Default::default()
+            }),
+        };
+        let parser = GenericOnchainOrderParser::new(0, None);
+
+        let result = parser
+            .parse_custom_event_data(&[
+                event(vec![0, 0, 0, 0, 0, 0, 1, 2]),
+                event(vec![0, 0, 0, 0, 0, 0, 1, 3, 42]),
+                event(vec![0, 0, 1]),
+            ])
+            .unwrap();
+        assert_eq!(
+            result
+                .iter()
+                .map(|(_, data)| data.quote_id)
+                .collect::<Vec<_>>(),
+            [0x0102, 0x0103],
+        );
+    }
+}
@@ -1,9 +1,9 @@
 use {
     super::Postgres,
-    crate::decoded_settlement::OrderExecution,
+    crate::decoded_settlement::{JitOrder, OrderExecution},
     anyhow::{Context, Result},
     database::{byte_array::ByteArray, OrderUid},
-    ethcontract::H256,
+    ethcontract::{H160, H256},
     futures::{TryFutureExt, TryStreamExt},
     model::{auction::AuctionId, order::Order},
     shared::db_order_conversions::full_order_into_model_order,
@@ -48,4 +48,25 @@ impl Postgres {
 
         Ok(order_executions)
     }
+
+    /// Returns the JIT orders the winning solver revealed for an auction.
+    pub async fn jit_orders_for_auction(
+        ex: &mut PgConnection,
+        auction_id: AuctionId,
+    ) -> Result<Vec<JitOrder>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["jit_orders_for_auction"])
+            .start_timer();
+
+        let orders = database::jit_orders::fetch(ex, auction_id)
+            .await?
+            .into_iter()
+            .map(|order| JitOrder {
+                order_uid: model::order::OrderUid(order.uid.0),
+                owner: H160(order.owner.0),
+            })
+            .collect();
+        Ok(orders)
+    }
 }
@@ -14,7 +14,7 @@ use {
     },
     num::BigRational,
     number::conversions::{big_decimal_to_u256, big_rational_to_u256, u256_to_big_rational},
-    shared::{conversions::U256Ext, external_prices::ExternalPrices},
+    shared::{conversions::U256Ext, cow_only::TokenFlow, external_prices::ExternalPrices},
     web3::ethabi::{Function, Token},
 };
 
@@ -68,13 +68,15 @@ pub struct DecodedTrade {
 }
 
 impl DecodedTrade {
-    fn matches_execution(
+    /// Computes the UID of the order settled by this trade assuming it was
+    /// signed by `owner`.
+    fn uid(
         &self,
-        order: &OrderExecution,
         tokens: &[Address],
         domain_separator: &DomainSeparator,
-    ) -> bool {
-        let uid = OrderData {
+        owner: &H160,
+    ) -> OrderUid {
+        OrderData {
             sell_token: tokens[self.sell_token_index.as_u64() as usize],
             buy_token: tokens[self.buy_token_index.as_u64() as usize],
             sell_amount: self.sell_amount,
@@ -88,8 +90,16 @@ impl DecodedTrade {
             sell_token_balance: self.flags.sell_token_balance(),
             buy_token_balance: self.flags.buy_token_balance(),
         }
-        .uid(domain_separator, &order.owner);
-        let matches_order = uid == order.order_uid;
+        .uid(domain_separator, owner)
+    }
+
+    fn matches_execution(
+        &self,
+        order: &OrderExecution,
+        tokens: &[Address],
+        domain_separator: &DomainSeparator,
+    ) -> bool {
+        let matches_order = self.uid(tokens, domain_separator, &order.owner) == order.order_uid;
 
         // the `executed_amount` field is ignored by the smart contract for
         // fill-or-kill orders, so only check that executed amounts match for
@@ -177,6 +187,8 @@ pub struct OrderExecution {
     pub owner: H160,
     pub executed_amount: U256,
     pub executed_fee: ExecutedFee,
+    /// Whether the order may only be matched peer-to-peer.
+    pub cow_only: bool,
 }
 
 impl OrderExecution {
@@ -191,10 +203,19 @@ impl OrderExecution {
             } else {
                 ExecutedFee::Order(order.metadata.solver_fee)
             },
+            cow_only: order.metadata.cow_only,
         }
     }
 }
 
+/// A just-in-time liquidity order that the solver settled alongside the
+/// auction orders. Trades for these orders are attributed to the solver.
+#[derive(Debug, Clone)]
+pub struct JitOrder {
+    pub order_uid: OrderUid,
+    pub owner: H160,
+}
+
 impl DecodedSettlement {
     /// Number of bytes that may be appended to the calldata to store an auction
     /// id.
@@ -267,16 +288,111 @@ impl DecodedSettlement {
     }
 
     /// Returns the total surplus denominated in the native asset for the
-    /// solution.
-    pub fn total_surplus(&self, external_prices: &ExternalPrices) -> U256 {
-        self.trades.iter().fold(0.into(), |acc, trade| {
-            acc + match surplus(trade, &self.tokens, &self.clearing_prices, external_prices) {
-                Some(surplus) => surplus,
-                None => {
-                    tracing::warn!("possible incomplete surplus calculation");
-                    0.into()
+    /// solution. Trades of JIT orders are not user trades and therefore don't
+    /// contribute any surplus.
+    pub fn total_surplus(&self, external_prices: &ExternalPrices, jit_orders: &[JitOrder]) -> U256 {
+        self.trades
+            .iter()
+            .filter(|trade| self.jit_order(trade, jit_orders).is_none())
+            .fold(0.into(), |acc, trade| {
+                acc + match surplus(trade, &self.tokens, &self.clearing_prices, external_prices) {
+                    Some(surplus) => surplus,
+                    None => {
+                        tracing::warn!("possible incomplete surplus calculation");
+                        0.into()
+                    }
                 }
+            })
+    }
+
+    /// Returns the executions of the JIT orders settled by this solution. The
+    /// fees of JIT orders are signed as part of the order and scaled by the
+    /// executed amount for partially fillable orders.
+    pub fn jit_order_executions(&self, jit_orders: &[JitOrder]) -> Vec<OrderExecution> {
+        self.trades
+            .iter()
+            .filter_map(|trade| {
+                let order = self.jit_order(trade, jit_orders)?;
+                Some(OrderExecution {
+                    order_uid: order.order_uid,
+                    owner: order.owner,
+                    executed_amount: trade.executed_amount,
+                    executed_fee: ExecutedFee::Order(executed_signed_fee(trade)?),
+                    cow_only: false,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the surplus, denominated in the native asset, of CoW-only
+    /// trades that were not covered by the other trades in a settlement that
+    /// interacts with on-chain liquidity. Imbalances in settlements without
+    /// interactions can only be covered by the settlement contract buffers and
+    /// are therefore fine.
+    ///
+    /// The exact token flows of the interactions are not known after the fact,
+    /// so this conservatively assumes that any imbalance in the tokens of a
+    /// CoW-only trade was covered by on-chain liquidity. Fees may, but don't
+    /// have to, be paid out to buyers and small differences are tolerated to
+    /// account for rounding.
+    pub fn cow_only_penalty(
+        &self,
+        external_prices: &ExternalPrices,
+        orders: &[OrderExecution],
+    ) -> U256 {
+        if self.interactions.iter().all(Vec::is_empty) || !orders.iter().any(|order| order.cow_only)
+        {
+            return 0.into();
+        }
+
+        let mut flows = vec![TokenFlow::default(); self.tokens.len()];
+        for trade in &self.trades {
+            let sell_index = trade.sell_token_index.as_u64() as usize;
+            let buy_index = trade.buy_token_index.as_u64() as usize;
+            let (Some((sell, buy)), Some(fee)) = (
+                traded_amounts(trade, &self.clearing_prices),
+                executed_signed_fee(trade),
+            ) else {
+                tracing::warn!("possible incomplete cow-only verification");
+                continue;
+            };
+            if let Some(flow) = flows.get_mut(sell_index) {
+                flow.sell(sell, fee);
+            }
+            if let Some(flow) = flows.get_mut(buy_index) {
+                flow.buy(buy);
             }
+        }
+        let is_covered = |index: U256| {
+            flows
+                .get(index.as_u64() as usize)
+                .is_some_and(TokenFlow::is_covered)
+        };
+
+        self.trades
+            .iter()
+            .filter(|trade| {
+                orders.iter().any(|order| {
+                    order.cow_only
+                        && trade.matches_execution(order, &self.tokens, &self.domain_separator)
+                })
+            })
+            .filter(|trade| {
+                !is_covered(trade.sell_token_index) || !is_covered(trade.buy_token_index)
+            })
+            .fold(0.into(), |acc, trade| {
+                acc + surplus(trade, &self.tokens, &self.clearing_prices, external_prices)
+                    .unwrap_or_default()
+            })
+    }
+
+    fn jit_order<'a>(
+        &self,
+        trade: &DecodedTrade,
+        jit_orders: &'a [JitOrder],
+    ) -> Option<&'a JitOrder> {
+        jit_orders.iter().find(|order| {
+            trade.uid(&self.tokens, &self.domain_separator, &order.owner) == order.order_uid
         })
     }
 
@@ -476,6 +592,51 @@ fn surplus(
     big_rational_to_u256(&normalized_surplus).ok()
 }
 
+/// The part of the signed fee of a trade that was executed. The fee is scaled
+/// by the executed amount for partially fillable orders.
+fn executed_signed_fee(trade: &DecodedTrade) -> Option<U256> {
+    if !trade.flags.partially_fillable() {
+        return Some(trade.fee_amount);
+    }
+    let target = match trade.flags.order_kind() {
+        OrderKind::Sell => trade.sell_amount,
+        OrderKind::Buy => trade.buy_amount,
+    };
+    trade
+        .fee_amount
+        .checked_mul(trade.executed_amount)?
+        .checked_div(target)
+}
+
+/// Returns the amounts of sell and buy token exchanged by a trade at the
+/// settlement's clearing prices, excluding fees.
+fn traded_amounts(trade: &DecodedTrade, clearing_prices: &[U256]) -> Option<(U256, U256)> {
+    let sell_price = *clearing_prices.get(trade.sell_token_index.as_u64() as usize)?;
+    let buy_price = *clearing_prices.get(trade.buy_token_index.as_u64() as usize)?;
+    let executed_amount = if trade.flags.partially_fillable() {
+        trade.executed_amount
+    } else {
+        match trade.flags.order_kind() {
+            OrderKind::Sell => trade.sell_amount,
+            OrderKind::Buy => trade.buy_amount,
+        }
+    };
+    match trade.flags.order_kind() {
+        OrderKind::Sell => Some((
+            executed_amount,
+            executed_amount
+                .checked_mul(sell_price)?
+                .checked_div(buy_price)?,
+        )),
+        OrderKind::Buy => Some((
+            executed_amount
+                .checked_mul(buy_price)?
+                .checked_div(sell_price)?,
+            executed_amount,
+        )),
+    }
+}
+
 fn trade_surplus(
     kind: OrderKind,
     sell_amount: &BigRational,
@@ -672,8 +833,10 @@ mod tests {
         let native_token = addr!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
         let external_prices =
             ExternalPrices::try_from_auction_prices(native_token, auction_external_prices).unwrap();
-        let surplus = settlement.total_surplus(&external_prices).to_f64_lossy(); // to_f64_lossy() to mimic what happens when value is saved for solver
-                                                                                 // competition
+        let surplus = settlement
+            .total_surplus(&external_prices, &[])
+            .to_f64_lossy(); // to_f64_lossy() to mimic what happens when value is saved for solver
+                             // competition
         assert_eq!(surplus, 33350701806766732.);
     }
 
@@ -757,20 +920,37 @@ mod tests {
                 order_uid: OrderUid::from_str("0xa8b0c9be7320d1314c6412e6557efd062bb9f97f2f4187f8b513f50ff63597cae995e2a9ae5210feb6dd07618af28ec38b2d7ce163f4d8c4").unwrap(),
                 owner: addr!("E995E2A9Ae5210FEb6DD07618af28ec38B2D7ce1"),
                 executed_amount: 14955083027u128.into(),
-                executed_fee: ExecutedFee::Order(48263037u128.into())
+                executed_fee: ExecutedFee::Order(48263037u128.into()),
+                cow_only: false,
             },
             OrderExecution {
                 order_uid: OrderUid::from_str("0x82582487739d1331572710a9283dc244c134d323f309eb0aac6c842ff5227e90f352bffb3e902d78166a79c9878e138a65022e1163f4d8bb").unwrap(),
                 owner: addr!("f352bFFB3E902d78166a79C9878e138a65022e11"),
                 executed_amount: 5701912712048588025933u128.into(),
-                executed_fee: ExecutedFee::Order(127253135942751092736u128.into())
+                executed_fee: ExecutedFee::Order(127253135942751092736u128.into()),
+                cow_only: false,
             }
         ];
         let fees = settlement
-            .total_fees(&external_prices, orders)
+            .total_fees(&external_prices, orders.clone())
             .to_f64_lossy(); // to_f64_lossy() to mimic what happens when value is saved for solver
                              // competition
         assert_eq!(fees, 45377573614605000.);
+
+        // Orders revealed as JIT liquidity are attributed to the solver instead.
+        let jit_orders = [JitOrder {
+            order_uid: orders[1].order_uid,
+            owner: orders[1].owner,
+        }];
+        let trade = settlement
+            .trades
+            .iter()
+            .find(|trade| settlement.jit_order(trade, &jit_orders).is_some())
+            .unwrap();
+        let executions = settlement.jit_order_executions(&jit_orders);
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].order_uid, orders[1].order_uid);
+        assert_eq!(executions[0].executed_amount, trade.executed_amount);
     }
 
     #[test]
@@ -862,7 +1042,8 @@ mod tests {
                 order_uid: OrderUid::from_str("0xaa6ff3f3f755e804eefc023967be5d7f8267674d4bae053eaca01be5801854bf6c7f534c81dfedf90c9e42effb410a44e4f8ef1064690e05").unwrap(),
                 owner: addr!("6c7f534c81dfedf90c9e42effb410a44e4f8ef10"),
                 executed_amount: 134069619089011499167823218927u128.into(),
-                executed_fee: ExecutedFee::Surplus
+                executed_fee: ExecutedFee::Surplus,
+                cow_only: false,
             },
         ];
         let fees = settlement
@@ -971,7 +1152,8 @@ mod tests {
                 order_uid: OrderUid::from_str("0x999d6ff17fb145220fd96c97493fd6013ecb7874dffc3b57837131a92a36dc02b70cd1ebd3b24aeeaf90c6041446630338536e7f643d6a39").unwrap(),
                 owner: addr!("b70cd1ebd3b24aeeaf90c6041446630338536e7f"),
                 executed_amount: 0.into(),
-                executed_fee: ExecutedFee::Order(463182886014406361088u128.into())
+                executed_fee: ExecutedFee::Order(463182886014406361088u128.into()),
+                cow_only: false,
             },
         ];
         let fees = settlement
@@ -1344,10 +1526,128 @@ mod tests {
             order_uid: OrderUid::from_str("0x77425bd23d5fbb24d32229b1c343807bee572f0555429632161350a56811d263c001d00d425fa92c4f840baa8f1e0c27c4297a0b65782608").unwrap(),
             owner: addr!("c001d00d425fa92c4f840baa8f1e0c27c4297a0b"),
             executed_amount: 1558319022273364070254u128.into(),
-            executed_fee: ExecutedFee::Surplus
+            executed_fee: ExecutedFee::Surplus,
+            cow_only: false,
         }];
 
         let fees = decoded.order_executions(&external_prices, orders);
         assert_eq!(fees[0].sell, 7487413756444483822u128.into());
     }
+
+    #[test]
+    fn cow_only_penalty() {
+        let tokens = vec![H160([0xaa; 20]), H160([0xbb; 20]), H160([0xcc; 20])];
+        let trade =
+            |sell_token_index: u64, buy_token_index: u64, sell_amount: u64, buy_amount: u64| {
+                DecodedTrade {
+                    sell_token_index: sell_token_index.into(),
+                    buy_token_index: buy_token_index.into(),
+                    receiver: Default::default(),
+                    sell_amount: sell_amount.into(),
+                    buy_amount: buy_amount.into(),
+                    valid_to: u32::MAX,
+                    app_data: Bytes([0; 32]),
+                    fee_amount: 0.into(),
+                    flags: TradeFlags(0.into()),
+                    executed_amount: sell_amount.into(),
+                    signature: Bytes(vec![]),
+                }
+            };
+        let interaction = || {
+            vec![DecodedInteraction {
+                target: H160([0x42; 20]),
+                value: 0.into(),
+                call_data: Bytes(vec![]),
+            }]
+        };
+        let settlement = |trades: Vec<DecodedTrade>, interactions: [Vec<DecodedInteraction>; 3]| {
+            DecodedSettlement {
+                tokens: tokens.clone(),
+                clearing_prices: vec![1.into(), 1.into(), 1.into()],
+                trades,
+                interactions,
+                metadata: None,
+                domain_separator: MAINNET_DOMAIN_SEPARATOR,
+            }
+        };
+        let external_prices = ExternalPrices::try_from_auction_prices(
+            H160([0xee; 20]),
+            tokens
+                .iter()
+                .map(|token| (*token, U256::exp10(18)))
+                .collect(),
+        )
+        .unwrap();
+
+        let owner = H160([0x01; 20]);
+        let order = |settlement: &DecodedSettlement, cow_only: bool| OrderExecution {
+            order_uid: settlement.trades[0].uid(
+                &settlement.tokens,
+                &settlement.domain_separator,
+                &owner,
+            ),
+            owner,
+            executed_amount: 0.into(),
+            executed_fee: ExecutedFee::Surplus,
+            cow_only,
+        };
+        let penalty = |settlement: &DecodedSettlement, cow_only: bool| {
+            settlement.cow_only_penalty(&external_prices, &[order(settlement, cow_only)])
+        };
+
+        // Fully matched by the counter order.
+        let matched = settlement(
+            vec![trade(0, 1, 100_000, 90_000), trade(1, 0, 100_000, 100_000)],
+            [vec![], interaction(), vec![]],
+        );
+        assert_eq!(penalty(&matched, true), 0.into());
+
+        // Small differences are tolerated.
+        let rounded = settlement(
+            vec![trade(0, 1, 100_000, 90_000), trade(1, 0, 99_990, 99_990)],
+            [vec![], interaction(), vec![]],
+        );
+        assert_eq!(penalty(&rounded, true), 0.into());
+
+        // Half of the sell amount has to be covered by on-chain liquidity, so
+        // the surplus of the CoW-only order doesn't count. This is the case
+        // for pre- and post-interactions as well.
+        let unmatched = |interactions| {
+            settlement(
+                vec![trade(0, 1, 100_000, 90_000), trade(1, 0, 50_000, 50_000)],
+                interactions,
+            )
+        };
+        for interactions in [
+            [interaction(), vec![], vec![]],
+            [vec![], interaction(), vec![]],
+            [vec![], vec![], interaction()],
+        ] {
+            assert_eq!(penalty(&unmatched(interactions), true), 10_000.into());
+        }
+        assert_eq!(
+            penalty(&unmatched([vec![], interaction(), vec![]]), false),
+            0.into()
+        );
+        // Without interactions the imbalance is covered by the buffers.
+        assert_eq!(penalty(&unmatched(Default::default()), true), 0.into());
+
+        // The fee of the CoW-only order can be paid out to buyers, but more
+        // than that has to come from on-chain liquidity.
+        let with_fee = |fee: u64| {
+            settlement(
+                vec![
+                    DecodedTrade {
+                        fee_amount: fee.into(),
+                        ..trade(0, 1, 100_000, 90_000)
+                    },
+                    trade(1, 0, 100_000, 100_000),
+                    trade(2, 0, 5_000, 5_000),
+                ],
+                [vec![], interaction(), vec![]],
+            )
+        };
+        assert_eq!(penalty(&with_fee(5_000), true), 0.into());
+        assert_eq!(penalty(&with_fee(0), true), 10_000.into());
+    }
 }
@@ -3,21 +3,28 @@ use {
     anyhow::{anyhow, Context, Result},
     reqwest::Client,
     shared::{arguments::ExternalSolver, http_client::response_body_with_size_limit},
-    std::time::Duration,
+    std::time::{Duration, SystemTime, UNIX_EPOCH},
     url::Url,
+    web3::signing::{self, SecretKey, SecretKeyRef},
 };
 
 const RESPONSE_SIZE_LIMIT: usize = 10_000_000;
 const RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(60);
 
+/// Headers used for authenticating requests to drivers.
+const TIMESTAMP_HEADER: &str = "X-Auth-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Auth-Signature";
+
 pub struct Driver {
     pub name: String,
     pub url: Url,
     client: Client,
+    /// Key for signing requests so the driver can authenticate them.
+    signing_key: Option<SecretKey>,
 }
 
 impl Driver {
-    pub fn new(driver: ExternalSolver) -> Self {
+    pub fn new(driver: ExternalSolver, signing_key: Option<SecretKey>) -> Self {
         Self {
             name: driver.name,
             url: driver.url,
@@ -25,6 +32,7 @@ impl Driver {
                 .timeout(RESPONSE_TIME_LIMIT)
                 .build()
                 .unwrap(),
+            signing_key,
         }
     }
 
@@ -60,7 +68,22 @@ impl Driver {
             body=%serde_json::to_string_pretty(request).unwrap(),
             "request",
         );
-        let mut request = self.client.post(url.clone()).json(request);
+        let body = serde_json::to_vec(request).context("serialize")?;
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(key) = &self.signing_key {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("system time before unix epoch")?
+                .as_secs();
+            let signature = sign(key, path, timestamp, &body)?;
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp)
+                .header(SIGNATURE_HEADER, signature);
+        }
+        let mut request = request.body(body);
 
         if let Some(timeout) = timeout {
             request = request.timeout(timeout);
@@ -80,3 +103,26 @@ impl Driver {
         serde_json::from_slice(&body).with_context(|| format!("bad json {}", context()))
     }
 }
+
+/// Signs a request to a driver endpoint. The signed message is the Keccak-256
+/// hash of the endpoint path, a zero byte, the big-endian UNIX timestamp in
+/// seconds and the JSON body. Drivers reject requests with old timestamps and
+/// signatures they have already seen, which protects against replays.
+///
+/// Returns the `0x` prefixed hex encoding of the `r`, `s` and `v` values.
+fn sign(key: &SecretKey, path: &str, timestamp: u64, body: &[u8]) -> Result<String> {
+    let mut message = Vec::with_capacity(path.len() + 9 + body.len());
+    message.extend_from_slice(path.as_bytes());
+    message.push(0);
+    message.extend_from_slice(&timestamp.to_be_bytes());
+    message.extend_from_slice(body);
+    let signature = SecretKeyRef::new(key)
+        .sign(&signing::keccak256(&message), None)
+        .context("sign request")?;
+
+    let mut bytes = [0u8; 65];
+    bytes[..32].copy_from_slice(signature.r.as_bytes());
+    bytes[32..64].copy_from_slice(signature.s.as_bytes());
+    bytes[64] = signature.v as u8;
+    Ok(format!("0x{}", hex::encode(bytes)))
+}
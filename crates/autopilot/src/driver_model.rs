@@ -103,6 +103,8 @@ pub mod solve {
         #[serde_as(as = "HexOrDecimalU256")]
         pub user_fee: U256,
         pub valid_to: u32,
+        /// The UNIX timestamp of when the order was created.
+        pub created: u32,
         pub kind: OrderKind,
         pub receiver: Option<H160>,
         pub owner: H160,
@@ -120,6 +122,9 @@ pub mod solve {
         /// The types of fees that will be collected by the protocol.
         /// Multiple fees are applied in the order they are listed
         pub fee_policies: Vec<FeePolicy>,
+        /// The order may only be matched against other orders or the
+        /// settlement contract buffers.
+        pub cow_only: bool,
     }
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -224,7 +229,8 @@ pub mod solve {
 
 pub mod reveal {
     use {
-        model::bytes_hex,
+        model::{bytes_hex, order::OrderUid},
+        primitive_types::H160,
         serde::{Deserialize, Serialize},
         serde_with::serde_as,
     };
@@ -252,6 +258,17 @@ pub mod reveal {
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     pub struct Response {
         pub calldata: Calldata,
+        /// Just-in-time liquidity orders of the solution that are not part of
+        /// the auction. Older drivers don't report them.
+        #[serde(default)]
+        pub jit_orders: Vec<JitOrder>,
+    }
+
+    #[derive(Clone, Debug, Default, Deserialize)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct JitOrder {
+        pub uid: OrderUid,
+        pub owner: H160,
     }
 }
 
@@ -270,6 +287,8 @@ pub mod settle {
         /// Unique ID of the solution (per driver competition), to settle.
         #[serde_as(as = "serde_with::DisplayFromStr")]
         pub solution_id: u64,
+        /// The last block in which the settlement may be mined.
+        pub submission_deadline_latest_block: u64,
     }
 
     #[serde_as]
@@ -277,7 +296,20 @@ pub mod settle {
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     pub struct Response {
         pub calldata: Calldata,
-        pub tx_hash: H256,
+        /// The hash of the mined transaction. Missing if the submission
+        /// expired.
+        pub tx_hash: Option<H256>,
+        pub outcome: Outcome,
+    }
+
+    #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub enum Outcome {
+        #[default]
+        Mined,
+        Reverted,
+        Cancelled,
+        Expired,
     }
 
     #[serde_as]
@@ -187,6 +187,8 @@ impl OnSettlementEventUpdater {
             let orders =
                 Postgres::order_executions_for_tx(&mut ex, &hash, auction_id.assume_verified())
                     .await?;
+            let jit_orders =
+                Postgres::jit_orders_for_auction(&mut ex, auction_id.assume_verified()).await?;
             let external_prices = ExternalPrices::try_from_auction_prices(
                 self.native_token,
                 auction_external_prices.clone(),
@@ -196,6 +198,7 @@ impl OnSettlementEventUpdater {
                 ?auction_id,
                 ?auction_external_prices,
                 ?orders,
+                ?jit_orders,
                 ?external_prices,
                 "observations input"
             );
@@ -203,7 +206,20 @@ impl OnSettlementEventUpdater {
             // surplus and fees calculation
             match DecodedSettlement::new(&transaction.input.0, &domain_separator) {
                 Ok(settlement) => {
-                    let surplus = settlement.total_surplus(&external_prices);
+                    // JIT orders don't generate user surplus, but their fees are
+                    // accounted to the solver.
+                    let surplus = settlement.total_surplus(&external_prices, &jit_orders);
+                    // Surplus of CoW-only orders that were matched against on-chain
+                    // liquidity doesn't count towards the solver's score. It is
+                    // recorded with the scores of the auction.
+                    let cow_only_penalty = settlement.cow_only_penalty(&external_prices, &orders);
+                    if !cow_only_penalty.is_zero() {
+                        tracing::warn!(?hash, ?cow_only_penalty, "cow-only orders traded on-chain");
+                    }
+                    let orders = orders
+                        .into_iter()
+                        .chain(settlement.jit_order_executions(&jit_orders))
+                        .collect::<Vec<_>>();
                     let fee = settlement.total_fees(&external_prices, orders.clone());
                     let order_executions = settlement.order_executions(&external_prices, orders);
 
@@ -211,6 +227,7 @@ impl OnSettlementEventUpdater {
                         auction_id,
                         surplus,
                         fee,
+                        cow_only_penalty,
                         gas_used,
                         effective_gas_price,
                         order_executions: order_executions
@@ -1,11 +1,12 @@
 use {
     crate::{
-        arguments::Arguments,
+        arguments::{Arguments, OnchainOrderParserKind},
         database::{
             ethflow_events::event_retriever::EthFlowRefundRetriever,
             onchain_order_events::{
                 ethflow_events::{determine_ethflow_indexing_start, EthFlowOnchainOrderParser},
                 event_retriever::CoWSwapOnchainOrdersContract,
+                generic_events::GenericOnchainOrderParser,
                 OnchainOrderParser,
             },
             Postgres,
@@ -50,6 +51,7 @@ use {
                 BalancerFactoryKind,
                 BalancerPoolFetcher,
             },
+            solidly::{self, SolidlyBaselineSourceParameters},
             uniswap_v2::{pool_cache::PoolCache, UniV2BaselineSourceParameters},
             uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
             BaselineSource,
@@ -309,6 +311,36 @@ pub async fn run(args: Arguments) {
         .expect("failed to create pool cache"),
     );
     let block_retriever = args.shared.current_block.retriever(web3.clone());
+    let solidly_sources = baseline_sources
+        .iter()
+        .filter_map(|source: &BaselineSource| {
+            SolidlyBaselineSourceParameters::from_baseline_source(*source, &network)
+        });
+    let solidly_pool_fetchers: Vec<_> = futures::stream::iter(solidly_sources)
+        .then(|source: SolidlyBaselineSourceParameters| {
+            let (web3, block_retriever) = (&web3, &block_retriever);
+            async move {
+                source
+                    .into_source(web3, block_retriever.clone())
+                    .await
+                    .unwrap()
+                    .pool_fetching
+            }
+        })
+        .collect()
+        .await;
+    let solidly_pool_fetcher = (!solidly_pool_fetchers.is_empty()).then(|| {
+        Arc::new(
+            solidly::pool_cache::PoolCache::new(
+                cache_config,
+                Arc::new(solidly::pool_fetching::PoolAggregator {
+                    pool_fetchers: solidly_pool_fetchers,
+                }),
+                current_block_stream.clone(),
+            )
+            .expect("failed to create Solidly pool cache"),
+        )
+    });
     let token_info_fetcher = Arc::new(CachedTokenInfoFetcher::new(Arc::new(TokenInfoFetcher {
         web3: web3.clone(),
     })));
@@ -417,6 +449,7 @@ pub async fn run(args: Arguments) {
             uniswap_v2_pools: pool_fetcher.clone(),
             balancer_pools: balancer_pool_fetcher.clone().map(|a| a as _),
             uniswap_v3_pools: uniswap_v3_pool_fetcher.clone().map(|a| a as _),
+            solidly_pools: solidly_pool_fetcher.clone().map(|a| a as _),
             tokens: token_info_fetcher.clone(),
             gas_price: gas_price_estimator.clone(),
             zeroex: zeroex_api.clone(),
@@ -507,6 +540,10 @@ pub async fn run(args: Arguments) {
     ));
 
     if let Some(ethflow_contract) = args.ethflow_contract {
+        db.set_missing_onchain_order_contract(ethflow_contract)
+            .await
+            .expect("failed to attribute existing onchain orders to the ethflow contract");
+
         let start_block = determine_ethflow_indexing_start(
             &skip_event_sync_start,
             args.ethflow_indexing_start,
@@ -529,15 +566,16 @@ pub async fn run(args: Arguments) {
         );
         maintainers.push(refund_event_handler);
 
-        let custom_ethflow_order_parser = EthFlowOnchainOrderParser {};
+        let custom_ethflow_order_parser = EthFlowOnchainOrderParser::default();
         let onchain_order_event_parser = OnchainOrderParser::new(
             db.clone(),
             web3.clone(),
             quoter.clone(),
+            ethflow_contract,
             Box::new(custom_ethflow_order_parser),
             DomainSeparator::new(chain_id, settlement_contract.address()),
             settlement_contract.address(),
-            liquidity_order_owners,
+            liquidity_order_owners.clone(),
         );
         let broadcaster_event_updater = Arc::new(
             EventUpdater::new_skip_blocks_before(
@@ -545,7 +583,7 @@ pub async fn run(args: Arguments) {
                 // interface called CoWSwapOnchainOrders.
                 CoWSwapOnchainOrdersContract::new(web3.clone(), ethflow_contract),
                 onchain_order_event_parser,
-                block_retriever,
+                block_retriever.clone(),
                 start_block,
             )
             .await
@@ -553,6 +591,64 @@ pub async fn run(args: Arguments) {
         );
         maintainers.push(broadcaster_event_updater);
     }
+    for contract in &args.onchain_order_contracts {
+        let start_block = determine_ethflow_indexing_start(
+            &skip_event_sync_start,
+            contract.indexing_start,
+            &web3,
+            chain_id,
+        )
+        .await;
+        let events = CoWSwapOnchainOrdersContract::new(web3.clone(), contract.address);
+        let domain_separator = DomainSeparator::new(chain_id, settlement_contract.address());
+        let broadcaster_event_updater: Arc<dyn Maintaining> = match contract.parser {
+            OnchainOrderParserKind::QuoteId => Arc::new(
+                EventUpdater::new_skip_blocks_before(
+                    events,
+                    OnchainOrderParser::new(
+                        db.clone(),
+                        web3.clone(),
+                        quoter.clone(),
+                        contract.address,
+                        Box::new(GenericOnchainOrderParser::new(
+                            contract.verification_gas_limit,
+                            contract.signing_scheme,
+                        )),
+                        domain_separator,
+                        settlement_contract.address(),
+                        liquidity_order_owners.clone(),
+                    ),
+                    block_retriever.clone(),
+                    start_block,
+                )
+                .await
+                .expect("Should be able to initialize event updater. Database read issues?"),
+            ),
+            OnchainOrderParserKind::EthFlow => Arc::new(
+                EventUpdater::new_skip_blocks_before(
+                    events,
+                    OnchainOrderParser::new(
+                        db.clone(),
+                        web3.clone(),
+                        quoter.clone(),
+                        contract.address,
+                        Box::new(EthFlowOnchainOrderParser::new(
+                            contract.verification_gas_limit,
+                            contract.signing_scheme,
+                        )),
+                        domain_separator,
+                        settlement_contract.address(),
+                        liquidity_order_owners.clone(),
+                    ),
+                    block_retriever.clone(),
+                    start_block,
+                )
+                .await
+                .expect("Should be able to initialize event updater. Database read issues?"),
+            ),
+        };
+        maintainers.push(broadcaster_event_updater);
+    }
     if let Some(uniswap_v3) = uniswap_v3_pool_fetcher {
         maintainers.push(uniswap_v3);
     }
@@ -634,7 +730,11 @@ pub async fn run(args: Arguments) {
         eth,
         solvable_orders_cache,
         database: Arc::new(db),
-        drivers: args.drivers.into_iter().map(Driver::new).collect(),
+        drivers: args
+            .drivers
+            .into_iter()
+            .map(|driver| Driver::new(driver, args.driver_signing_key))
+            .collect(),
         market_makable_token_list,
         submission_deadline: args.submission_deadline as u64,
         additional_deadline_for_rewards: args.additional_deadline_for_rewards as u64,
@@ -657,7 +757,11 @@ async fn shadow_mode(args: Arguments) -> ! {
         args.shadow.expect("missing shadow mode configuration"),
     );
 
-    let drivers = args.drivers.into_iter().map(Driver::new).collect();
+    let drivers = args
+        .drivers
+        .into_iter()
+        .map(|driver| Driver::new(driver, args.driver_signing_key))
+        .collect();
 
     let trusted_tokens = {
         let web3 = shared::ethrpc::web3(
@@ -26,6 +26,7 @@ use {
         order::{OrderClass, OrderUid},
         solver_competition::{
             CompetitionAuction,
+            JitOrder,
             Order,
             Score,
             SolverCompetitionDB,
@@ -178,6 +179,14 @@ impl RunLoop {
                 + self.additional_deadline_for_rewards;
             let call_data = revealed.calldata.internalized.clone();
             let uninternalized_call_data = revealed.calldata.uninternalized.clone();
+            let jit_orders = revealed
+                .jit_orders
+                .iter()
+                .map(|order| JitOrder {
+                    id: order.uid,
+                    owner: order.owner,
+                })
+                .collect::<Vec<_>>();
 
             // Save order executions for all orders in the solution. Surplus fees for
             // limit orders will be saved after settling the order onchain.
@@ -259,10 +268,12 @@ impl RunLoop {
                                 .iter()
                                 .map(|(token, price)| (*token, *price))
                                 .collect(),
+                            jit_orders: vec![],
                             call_data: None,
                             uninternalized_call_data: None,
                         };
                         if is_winner {
+                            settlement.jit_orders = jit_orders.clone();
                             settlement.call_data = Some(revealed.calldata.internalized.clone());
                             settlement.uninternalized_call_data =
                                 Some(revealed.calldata.uninternalized.clone());
@@ -280,6 +291,7 @@ impl RunLoop {
                 prices,
                 block_deadline,
                 order_executions,
+                jit_orders,
                 competition_simulation_block,
                 call_data,
                 uninternalized_call_data,
@@ -294,7 +306,8 @@ impl RunLoop {
 
             tracing::info!(driver = %driver.name, "settling");
             let submission_start = Instant::now();
-            match self.settle(driver, solution).await {
+            let submission_deadline = competition_simulation_block + self.submission_deadline;
+            match self.settle(driver, solution, submission_deadline).await {
                 Ok(()) => Metrics::settle_ok(driver, submission_start.elapsed()),
                 Err(err) => {
                     Metrics::settle_err(driver, &err, submission_start.elapsed());
@@ -430,8 +443,14 @@ impl RunLoop {
     }
 
     /// Execute the solver's solution. Returns Ok when the corresponding
-    /// transaction has been mined.
-    async fn settle(&self, driver: &Driver, solved: &Solution) -> Result<(), SettleError> {
+    /// transaction has been mined. The driver stops submitting the solution
+    /// once the deadline block is reached.
+    async fn settle(
+        &self,
+        driver: &Driver,
+        solved: &Solution,
+        submission_deadline: u64,
+    ) -> Result<(), SettleError> {
         let events = solved
             .order_ids()
             .map(|uid| (*uid, OrderEventLabel::Executing))
@@ -440,13 +459,24 @@ impl RunLoop {
 
         let request = settle::Request {
             solution_id: solved.id,
+            submission_deadline_latest_block: submission_deadline,
         };
 
-        let tx_hash = driver
+        let response = driver
             .settle(&request, self.max_settlement_transaction_wait)
             .await
-            .map_err(SettleError::Failure)?
-            .tx_hash;
+            .map_err(SettleError::Failure)?;
+        let tx_hash = match (response.outcome, response.tx_hash) {
+            (settle::Outcome::Mined, Some(tx_hash)) => tx_hash,
+            (settle::Outcome::Mined, None) => {
+                return Err(SettleError::Failure(anyhow::anyhow!(
+                    "mined settlement without transaction hash"
+                )))
+            }
+            (settle::Outcome::Reverted, tx_hash) => return Err(SettleError::Reverted(tx_hash)),
+            (settle::Outcome::Cancelled, tx_hash) => return Err(SettleError::Cancelled(tx_hash)),
+            (settle::Outcome::Expired, _) => return Err(SettleError::Expired),
+        };
 
         *self.in_flight_orders.lock().unwrap() = InFlightOrders {
             tx_hash,
@@ -539,6 +569,8 @@ pub fn solve_request(
                     solver_fee: order.metadata.full_fee_amount,
                     user_fee: order.data.fee_amount,
                     valid_to: order.data.valid_to,
+                    created: u32::try_from(order.metadata.creation_date.timestamp())
+                        .unwrap_or_default(),
                     kind: order.data.kind,
                     receiver: order.data.receiver,
                     owner: order.metadata.owner,
@@ -556,6 +588,7 @@ pub fn solve_request(
                     app_data: order.data.app_data,
                     signature: order.signature.clone(),
                     fee_policies: fee_policies.get(&order.metadata.uid).unwrap_or_default(),
+                    cow_only: order.metadata.cow_only,
                 }
             })
             .collect(),
@@ -634,6 +667,12 @@ enum RevealError {
 
 #[derive(Debug, thiserror::Error)]
 enum SettleError {
+    #[error("settlement transaction reverted: {0:?}")]
+    Reverted(Option<H256>),
+    #[error("settlement transaction was cancelled: {0:?}")]
+    Cancelled(Option<H256>),
+    #[error("settlement was not mined before the deadline")]
+    Expired,
     #[error(transparent)]
     Failure(anyhow::Error),
 }
@@ -740,6 +779,9 @@ impl Metrics {
 
     fn settle_err(driver: &Driver, err: &SettleError, time: Duration) {
         let label = match err {
+            SettleError::Reverted(_) => "reverted",
+            SettleError::Cancelled(_) => "cancelled",
+            SettleError::Expired => "expired",
             SettleError::Failure(_) => "error",
         };
         Self::get()
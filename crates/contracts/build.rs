@@ -12,7 +12,9 @@ mod paths;
 
 const MAINNET: &str = "1";
 const GOERLI: &str = "5";
+const OPTIMISM: &str = "10";
 const GNOSIS: &str = "100";
+const BASE: &str = "8453";
 const SEPOLIA: &str = "11155111";
 
 fn main() {
@@ -567,6 +569,10 @@ fn main() {
     generate_contract_with_config("PancakeRouter", |builder| {
         builder.add_network_str(MAINNET, "0xEfF92A263d31888d860bD50809A8D171709b7b1c")
     });
+    generate_contract_with_config("PancakeSwapV3SwapRouter", |builder| {
+        // <https://developer.pancakeswap.finance/contracts/v3/addresses>
+        builder.add_network_str(MAINNET, "0x1b81D678ffb9C0263b24A97847620C99d213eB14")
+    });
     generate_contract_with_config("SushiSwapRouter", |builder| {
         // <https://docs.sushi.com/docs/Products/Classic%20AMM/Deployment%20Addresses>
         builder
@@ -574,6 +580,10 @@ fn main() {
             .add_network_str(GOERLI, "0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506")
             .add_network_str(GNOSIS, "0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506")
     });
+    generate_contract_with_config("SushiSwapV3SwapRouter", |builder| {
+        // <https://docs.sushi.com/docs/Products/V3%20AMM/Deployment%20Addresses>
+        builder.add_network_str(MAINNET, "0x2E6cd2d30aa43f40aa81619ff4b6E0a41479B13F")
+    });
     generate_contract_with_config("SwaprRouter", |builder| {
         // <https://swapr.gitbook.io/swapr/contracts>
         builder
@@ -657,6 +667,19 @@ fn main() {
             .add_network_str(SEPOLIA, "0x0625aFB445C3B6B7B929342a04A22599fd5dBB59")
     });
 
+    // Solidly (stable and volatile pool) liquidity.
+    generate_contract("ISolidlyPool");
+    generate_contract("ISolidlyPoolFactory");
+    generate_contract("ISolidlyRouter");
+    generate_contract_with_config("VelodromeV2Router", |builder| {
+        // <https://github.com/velodrome-finance/contracts/blob/main/README.md#deployments>
+        builder.add_network_str(OPTIMISM, "0xa062aE8A9c5e11aaA026fc2670B0D65cCc8B2858")
+    });
+    generate_contract_with_config("AerodromeRouter", |builder| {
+        // <https://github.com/aerodrome-finance/contracts/blob/main/README.md#deployments>
+        builder.add_network_str(BASE, "0xcF77a3Ba9A5CA399B7c97c74d54e5b1Beb874E43")
+    });
+
     // Unofficial Uniswap v2 liquidity on the Sepolia testnet.
     generate_contract_with_config("TestnetUniswapV2Router02", |builder| {
         // <https://github.com/eth-clients/sepolia/issues/47#issuecomment-1681562464>
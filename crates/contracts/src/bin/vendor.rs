@@ -201,7 +201,19 @@ fn run() -> Result<()> {
         .github(
             "ISwaprPair",
             "levelkdev/dxswap-core/3511bab996096f9c9c9bc3af0d94222650fd1e40/build/IDXswapPair.json",
-        )?;
+        )?
+        .manual(
+            "ISolidlyPool",
+            "Manually vendored subset of the Velodrome V2 pool ABI",
+        )
+        .manual(
+            "ISolidlyPoolFactory",
+            "Manually vendored subset of the Velodrome V2 pool factory ABI",
+        )
+        .manual(
+            "ISolidlyRouter",
+            "Manually vendored subset of the Velodrome V2 router ABI",
+        );
 
     Ok(())
 }
@@ -18,6 +18,7 @@ macro_rules! include_contracts {
 }
 
 include_contracts! {
+    AerodromeRouter;
     BalancerV2Authorizer;
     BalancerV2BasePool;
     BalancerV2BasePoolFactory;
@@ -52,19 +53,25 @@ include_contracts! {
     GnosisSafeProxyFactory;
     HoneyswapRouter;
     HooksTrampoline;
+    ISolidlyPool;
+    ISolidlyPoolFactory;
+    ISolidlyRouter;
     ISwaprPair;
     IUniswapLikePair;
     IUniswapLikeRouter;
     IUniswapV3Factory;
     IZeroEx;
     PancakeRouter;
+    PancakeSwapV3SwapRouter;
     SushiSwapRouter;
+    SushiSwapV3SwapRouter;
     SwaprRouter;
     TestnetUniswapV2Router02;
     UniswapV2Factory;
     UniswapV2Router02;
     UniswapV3Pool;
     UniswapV3SwapRouter;
+    VelodromeV2Router;
     WETH9;
 }
 
@@ -92,7 +99,9 @@ pub mod test {
 mod tests {
     const MAINNET: u64 = 1;
     const GOERLI: u64 = 5;
+    const OPTIMISM: u64 = 10;
     const GNOSIS: u64 = 100;
+    const BASE: u64 = 8453;
     const SEPOLIA: u64 = 11155111;
 
     use {
@@ -182,6 +191,8 @@ mod tests {
         assert_has_deployment_address!(BalancerV2StablePoolFactoryV2 for MAINNET);
         assert_has_deployment_address!(BalancerV2LiquidityBootstrappingPoolFactory for MAINNET);
         assert_has_deployment_address!(PancakeRouter for MAINNET);
+        assert_has_deployment_address!(PancakeSwapV3SwapRouter for MAINNET);
+        assert_has_deployment_address!(SushiSwapV3SwapRouter for MAINNET);
         assert_has_deployment_address!(IZeroEx for MAINNET);
 
         // only gnosis
@@ -191,6 +202,12 @@ mod tests {
 
         // only sepolia
         assert_has_deployment_address!(TestnetUniswapV2Router02 for SEPOLIA);
+
+        // only optimism
+        assert_has_deployment_address!(VelodromeV2Router for OPTIMISM);
+
+        // only base
+        assert_has_deployment_address!(AerodromeRouter for BASE);
     }
 
     #[test]
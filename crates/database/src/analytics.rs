@@ -0,0 +1,338 @@
+use {
+    crate::Address,
+    sqlx::{
+        types::chrono::{DateTime, Utc},
+        PgConnection,
+        Postgres,
+        QueryBuilder,
+    },
+};
+
+/// Dimension by which executed trades get grouped in addition to the time
+/// bucket.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GroupBy {
+    /// The `appCode` of the order's full app data.
+    AppCode,
+    /// The solver that settled the trade.
+    Solver,
+    /// The sell and buy token of the order.
+    TokenPair,
+}
+
+/// Length of the time buckets. Values are valid `date_trunc` field names.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interval {
+    Hour,
+    Day,
+    Week,
+}
+
+impl Interval {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Filter {
+    /// Only trades of orders created at or after this point in time.
+    pub from: DateTime<Utc>,
+    /// Only trades of orders created before this point in time.
+    pub to: DateTime<Utc>,
+    /// Only trades of orders whose full app data specifies this `appCode`.
+    pub app_code: Option<String>,
+    /// Only trades settled by this solver.
+    pub solver: Option<Address>,
+}
+
+/// Aggregated values of all trades that fall into the same group. Only the
+/// columns of the requested grouping are set.
+#[derive(Debug, Default, PartialEq, sqlx::FromRow)]
+pub struct Aggregate {
+    /// Start of the time bucket.
+    pub bucket_start: Option<DateTime<Utc>>,
+    pub app_code: Option<String>,
+    pub solver: Option<Address>,
+    pub sell_token: Option<Address>,
+    pub buy_token: Option<Address>,
+    /// Number of distinct orders that were traded.
+    pub order_count: i64,
+    pub trade_count: i64,
+    /// Value of the sold tokens (excluding fees) in wei.
+    pub volume_in_wei: f64,
+    /// Surplus over the orders' limit prices in wei.
+    pub surplus_in_wei: f64,
+    /// Fees taken by the protocol in wei. Includes the signed fee and the
+    /// surplus fee of limit orders.
+    pub fee_in_wei: f64,
+}
+
+/// Per trade values converted to wei using the native prices of the auction
+/// in which the trade got settled. Trades without prices (e.g. settlements
+/// that can't be associated with an auction) count towards the number of
+/// trades but not towards the amounts.
+const TRADES: &str = r#"
+WITH trade_data AS (
+    SELECT
+        o.uid,
+        o.creation_timestamp,
+        o.sell_token,
+        o.buy_token,
+        s.solver,
+        (SELECT convert_from(ad.full_app_data, 'UTF8')::jsonb ->> 'appCode'
+            FROM app_data ad WHERE ad.contract_app_data = o.app_data) AS app_code,
+        (t.sell_amount - t.fee_amount) * sell_price.price / POWER(10, 18) AS volume,
+        CASE o.kind
+            WHEN 'sell' THEN
+                (t.buy_amount - (t.sell_amount - t.fee_amount) * o.buy_amount / o.sell_amount)
+                * buy_price.price
+            WHEN 'buy' THEN
+                (t.buy_amount * o.sell_amount / o.buy_amount - (t.sell_amount - t.fee_amount))
+                * sell_price.price
+        END / POWER(10, 18) AS surplus,
+        (t.fee_amount + COALESCE(oe.surplus_fee, 0)) * sell_price.price / POWER(10, 18) AS fee
+    FROM trades t
+    JOIN orders o ON o.uid = t.order_uid
+    -- the settlement event is emitted after all trade events of a settlement
+    JOIN LATERAL (
+        SELECT solver, tx_from, tx_nonce
+        FROM settlements s
+        WHERE s.block_number = t.block_number AND s.log_index > t.log_index
+        ORDER BY s.log_index ASC
+        LIMIT 1
+    ) s ON true
+    LEFT JOIN auction_transaction atx ON atx.tx_from = s.tx_from AND atx.tx_nonce = s.tx_nonce
+    LEFT JOIN order_execution oe ON oe.order_uid = o.uid AND oe.auction_id = atx.auction_id
+    LEFT JOIN auction_prices sell_price
+        ON sell_price.auction_id = atx.auction_id AND sell_price.token = o.sell_token
+    LEFT JOIN auction_prices buy_price
+        ON buy_price.auction_id = atx.auction_id AND buy_price.token = o.buy_token
+    WHERE o.creation_timestamp >= "#;
+
+/// Aggregates surplus, volume and fees of executed trades by the specified
+/// dimension and time buckets of the creation timestamp of the traded orders.
+/// Order creation is used because it is the only indexed timestamp related to
+/// trades.
+///
+/// The orders of the time range are found with the
+/// `order_creation_timestamp_uid` index. All joined tables are looked up by
+/// index: trades by `trade_order_uid`, settlements, order executions and
+/// auction prices by their primary keys and auction transactions by their
+/// unique `(tx_from, tx_nonce)` constraint.
+pub async fn aggregate(
+    ex: &mut PgConnection,
+    filter: &Filter,
+    group_by: Option<GroupBy>,
+    interval: Option<Interval>,
+) -> Result<Vec<Aggregate>, sqlx::Error> {
+    let mut query = QueryBuilder::<Postgres>::new(TRADES);
+    query.push_bind(filter.from);
+    query.push(" AND o.creation_timestamp < ");
+    query.push_bind(filter.to);
+    if let Some(solver) = filter.solver {
+        query.push(" AND s.solver = ");
+        query.push_bind(solver);
+    }
+    query.push(") SELECT ");
+
+    let mut groups = Vec::new();
+    match interval {
+        Some(interval) => {
+            query.push(format!(
+                "date_trunc('{}', creation_timestamp) AS bucket_start, ",
+                interval.as_str()
+            ));
+            groups.push("bucket_start");
+        }
+        None => {
+            query.push("NULL::timestamptz AS bucket_start, ");
+        }
+    }
+    query.push(match group_by {
+        Some(GroupBy::AppCode) => "app_code, ",
+        _ => "NULL::text AS app_code, ",
+    });
+    query.push(match group_by {
+        Some(GroupBy::Solver) => "solver, ",
+        _ => "NULL::bytea AS solver, ",
+    });
+    query.push(match group_by {
+        Some(GroupBy::TokenPair) => "sell_token, buy_token, ",
+        _ => "NULL::bytea AS sell_token, NULL::bytea AS buy_token, ",
+    });
+    match group_by {
+        Some(GroupBy::AppCode) => groups.push("app_code"),
+        Some(GroupBy::Solver) => groups.push("solver"),
+        Some(GroupBy::TokenPair) => groups.extend(["sell_token", "buy_token"]),
+        None => (),
+    }
+    query.push(
+        "COUNT(DISTINCT uid) AS order_count, COUNT(*) AS trade_count, COALESCE(SUM(volume), \
+         0)::float8 AS volume_in_wei, COALESCE(SUM(surplus), 0)::float8 AS surplus_in_wei, \
+         COALESCE(SUM(fee), 0)::float8 AS fee_in_wei FROM trade_data WHERE true",
+    );
+    if let Some(app_code) = &filter.app_code {
+        query.push(" AND app_code = ");
+        query.push_bind(app_code.clone());
+    }
+    if !groups.is_empty() {
+        let groups = groups.join(", ");
+        query.push(format!(" GROUP BY {groups} ORDER BY {groups}"));
+    }
+
+    query.build_query_as().fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            auction_prices::{self, AuctionPrice},
+            auction_transaction::{insert_settlement_tx_info, upsert_auction_transaction},
+            byte_array::ByteArray,
+            events::{insert_settlement, insert_trade, EventIndex, Settlement, Trade},
+            order_execution,
+            orders::{insert_order, Order, OrderKind},
+        },
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_aggregate_trades() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = Utc::now();
+        let sell_token = ByteArray([1; 20]);
+        let buy_token = ByteArray([2; 20]);
+        let tx_from = ByteArray([3; 20]);
+        let solver = ByteArray([4; 20]);
+        let auction_id = 1;
+
+        // Two sell orders that get settled together. Each receives 10 buy
+        // tokens more than its limit price.
+        for i in 0..2u8 {
+            let uid = ByteArray([i; 56]);
+            insert_order(
+                &mut db,
+                &Order {
+                    uid,
+                    creation_timestamp: now,
+                    kind: OrderKind::Sell,
+                    sell_token,
+                    buy_token,
+                    sell_amount: 100.into(),
+                    buy_amount: 100.into(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            insert_trade(
+                &mut db,
+                &EventIndex {
+                    block_number: 0,
+                    log_index: i.into(),
+                },
+                &Trade {
+                    order_uid: uid,
+                    sell_amount_including_fee: 105.into(),
+                    buy_amount: 110.into(),
+                    fee_amount: 5.into(),
+                },
+            )
+            .await
+            .unwrap();
+            order_execution::save(&mut db, &uid, auction_id, None, None)
+                .await
+                .unwrap();
+        }
+        insert_settlement(
+            &mut db,
+            &EventIndex {
+                block_number: 0,
+                log_index: 2,
+            },
+            &Settlement {
+                solver,
+                transaction_hash: ByteArray([5; 32]),
+            },
+        )
+        .await
+        .unwrap();
+        insert_settlement_tx_info(&mut db, 0, 2, &tx_from, 0)
+            .await
+            .unwrap();
+        upsert_auction_transaction(&mut db, auction_id, &tx_from, 0)
+            .await
+            .unwrap();
+        // Both tokens are worth 2 wei per unit.
+        auction_prices::insert(
+            &mut db,
+            &[
+                AuctionPrice {
+                    auction_id,
+                    token: sell_token,
+                    price: "2000000000000000000".parse().unwrap(),
+                },
+                AuctionPrice {
+                    auction_id,
+                    token: buy_token,
+                    price: "2000000000000000000".parse().unwrap(),
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        let filter = Filter {
+            from: now - chrono::Duration::days(1),
+            to: now + chrono::Duration::days(1),
+            app_code: None,
+            solver: None,
+        };
+        let result = aggregate(&mut db, &filter, Some(GroupBy::Solver), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            [Aggregate {
+                solver: Some(solver),
+                order_count: 2,
+                trade_count: 2,
+                volume_in_wei: 400.,
+                surplus_in_wei: 40.,
+                fee_in_wei: 20.,
+                ..Default::default()
+            }]
+        );
+
+        let result = aggregate(
+            &mut db,
+            &filter,
+            Some(GroupBy::TokenPair),
+            Some(Interval::Day),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].sell_token, Some(sell_token));
+        assert_eq!(result[0].buy_token, Some(buy_token));
+        assert!(result[0].bucket_start.unwrap() <= now);
+
+        let filter = Filter {
+            solver: Some(ByteArray([0; 20])),
+            ..filter
+        };
+        let result = aggregate(&mut db, &filter, None, None).await.unwrap();
+        assert_eq!(result, [Aggregate::default()]);
+    }
+}
@@ -0,0 +1,83 @@
+use {
+    crate::byte_array::ByteArray,
+    sqlx::{Executor, PgConnection},
+};
+
+pub type KeyHash = ByteArray<32>;
+
+/// One row in the `api_keys` table.
+#[derive(Clone, Debug, Default, Eq, PartialEq, sqlx::FromRow)]
+pub struct ApiKey {
+    pub key_hash: KeyHash,
+    pub name: String,
+    pub requests_per_minute: i32,
+    pub burst: i32,
+    pub revoked: bool,
+}
+
+pub async fn upsert(ex: &mut PgConnection, key: &ApiKey) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO api_keys (key_hash, name, requests_per_minute, burst, revoked)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (key_hash) DO UPDATE
+SET name = $2, requests_per_minute = $3, burst = $4, revoked = $5
+;"#;
+    ex.execute(
+        sqlx::query(QUERY)
+            .bind(key.key_hash)
+            .bind(&key.name)
+            .bind(key.requests_per_minute)
+            .bind(key.burst)
+            .bind(key.revoked),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Returns the API key with the specified hash unless it was revoked.
+pub async fn fetch_active(
+    ex: &mut PgConnection,
+    key_hash: &KeyHash,
+) -> Result<Option<ApiKey>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT *
+FROM api_keys
+WHERE key_hash = $1 AND NOT revoked
+;"#;
+    sqlx::query_as(QUERY)
+        .bind(key_hash)
+        .fetch_optional(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_api_keys_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let mut key = ApiKey {
+            key_hash: ByteArray([1; 32]),
+            name: "bot".to_string(),
+            requests_per_minute: 600,
+            burst: 20,
+            revoked: false,
+        };
+        assert_eq!(fetch_active(&mut db, &key.key_hash).await.unwrap(), None);
+
+        upsert(&mut db, &key).await.unwrap();
+        assert_eq!(
+            fetch_active(&mut db, &key.key_hash).await.unwrap(),
+            Some(key.clone())
+        );
+
+        key.revoked = true;
+        upsert(&mut db, &key).await.unwrap();
+        assert_eq!(fetch_active(&mut db, &key.key_hash).await.unwrap(), None);
+    }
+}
@@ -1,6 +1,18 @@
 use {crate::AppId, sqlx::PgConnection};
 
-/// Tries to associate the contract app data with the full app data.
+/// Whether the full app data has been pinned to IPFS.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "AppDataPinStatus")]
+#[sqlx(rename_all = "lowercase")]
+pub enum PinStatus {
+    #[default]
+    Pending,
+    Pinned,
+    Failed,
+}
+
+/// Tries to associate the contract app data with the full app data and whether
+/// orders with it may only be matched peer-to-peer.
 ///
 /// If this contract app data already existed then the existing full app data is
 /// returned, otherwise `None` is returned.
@@ -8,11 +20,12 @@ pub async fn insert(
     ex: &mut PgConnection,
     contract_app_data: &AppId,
     full_app_data: &[u8],
+    cow_only: bool,
 ) -> Result<Option<Vec<u8>>, sqlx::Error> {
     const QUERY: &str = r#"
 WITH inserted AS (
-    INSERT INTO app_data (contract_app_data, full_app_data)
-    VALUES ($1, $2)
+    INSERT INTO app_data (contract_app_data, full_app_data, cow_only)
+    VALUES ($1, $2, $3)
     -- returns null on conflict
     ON CONFLICT DO NOTHING
     -- returns TRUE if the insertion succeeded
@@ -29,6 +42,7 @@ SELECT
     sqlx::query_scalar(QUERY)
         .bind(contract_app_data)
         .bind(full_app_data)
+        .bind(cow_only)
         .fetch_one(ex)
         .await
 }
@@ -48,6 +62,46 @@ WHERE contract_app_data = $1
         .await
 }
 
+/// Records the outcome of an attempt to pin the full app data to IPFS.
+pub async fn record_pin_attempt(
+    ex: &mut PgConnection,
+    contract_app_data: &AppId,
+    status: PinStatus,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+UPDATE app_data
+SET pin_status = $2, pin_attempts = pin_attempts + 1
+WHERE contract_app_data = $1
+;"#;
+    sqlx::query(QUERY)
+        .bind(contract_app_data)
+        .bind(status)
+        .execute(ex)
+        .await
+        .map(|_| ())
+}
+
+/// Fetches up to `limit` app data documents that are not pinned to IPFS yet
+/// and that were attempted to be pinned less than `max_attempts` times.
+pub async fn fetch_unpinned(
+    ex: &mut PgConnection,
+    max_attempts: i32,
+    limit: i64,
+) -> Result<Vec<(AppId, Vec<u8>)>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT contract_app_data, full_app_data
+FROM app_data
+WHERE pin_status <> 'pinned' AND pin_attempts < $1
+ORDER BY pin_attempts
+LIMIT $2
+;"#;
+    sqlx::query_as(QUERY)
+        .bind(max_attempts)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
@@ -66,7 +120,7 @@ mod tests {
         assert!(result.is_none());
 
         let full = vec![1u8];
-        let result = insert(&mut db, &contract, &full).await.unwrap();
+        let result = insert(&mut db, &contract, &full, false).await.unwrap();
         assert_eq!(result, None);
 
         // now exists
@@ -74,11 +128,48 @@ mod tests {
         assert_eq!(result, Some(full.clone()));
 
         // insert again with same app data
-        let result = insert(&mut db, &contract, &full).await.unwrap();
+        let result = insert(&mut db, &contract, &full, false).await.unwrap();
         assert_eq!(result, Some(full.clone()));
 
         // insert again with different app data fails
-        let result = insert(&mut db, &contract, &[4, 2]).await.unwrap();
+        let result = insert(&mut db, &contract, &[4, 2], false).await.unwrap();
         assert_eq!(result, Some(full));
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_app_data_pinning() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut tx = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut tx).await.unwrap();
+
+        let contract = ByteArray([1u8; 32]);
+        let full = vec![1u8];
+        insert(&mut tx, &contract, &full, false).await.unwrap();
+
+        // newly inserted app data is pending
+        let result = fetch_unpinned(&mut tx, 2, 10).await.unwrap();
+        assert_eq!(result, vec![(contract, full.clone())]);
+
+        // failed attempts are retried until the attempt limit is reached
+        record_pin_attempt(&mut tx, &contract, PinStatus::Failed)
+            .await
+            .unwrap();
+        let result = fetch_unpinned(&mut tx, 2, 10).await.unwrap();
+        assert_eq!(result, vec![(contract, full.clone())]);
+        record_pin_attempt(&mut tx, &contract, PinStatus::Failed)
+            .await
+            .unwrap();
+        let result = fetch_unpinned(&mut tx, 2, 10).await.unwrap();
+        assert!(result.is_empty());
+        let result = fetch_unpinned(&mut tx, 3, 10).await.unwrap();
+        assert_eq!(result, vec![(contract, full)]);
+
+        // pinned app data is never returned
+        record_pin_attempt(&mut tx, &contract, PinStatus::Pinned)
+            .await
+            .unwrap();
+        let result = fetch_unpinned(&mut tx, 10, 10).await.unwrap();
+        assert!(result.is_empty());
+    }
 }
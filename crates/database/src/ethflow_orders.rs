@@ -380,6 +380,7 @@ mod tests {
         assert_eq!(orders, vec![order_parts.eth_order]);
         insert_onchain_invalidation(
             &mut db,
+            &Default::default(),
             &EventIndex {
                 block_number: 1,
                 log_index: 1,
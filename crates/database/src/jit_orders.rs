@@ -0,0 +1,81 @@
+use {
+    crate::{auction::AuctionId, Address, OrderUid, PgTransaction},
+    sqlx::PgConnection,
+    std::ops::DerefMut,
+};
+
+/// Just-in-time liquidity order that was part of the winning solution of an
+/// auction.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct JitOrder {
+    pub auction_id: AuctionId,
+    pub uid: OrderUid,
+    pub owner: Address,
+}
+
+/// Inserts the JIT orders of an auction. Orders that are already stored for
+/// the auction are ignored, so solutions settling the same JIT order multiple
+/// times don't fail the insertion.
+pub async fn insert(ex: &mut PgTransaction<'_>, orders: &[JitOrder]) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO jit_orders (auction_id, uid, owner)
+VALUES ($1, $2, $3)
+ON CONFLICT DO NOTHING
+    ;"#;
+    for order in orders {
+        sqlx::query(QUERY)
+            .bind(order.auction_id)
+            .bind(order.uid)
+            .bind(order.owner)
+            .execute(ex.deref_mut())
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn fetch(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+) -> Result<Vec<JitOrder>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT * FROM jit_orders WHERE auction_id = $1"#;
+    sqlx::query_as(QUERY).bind(auction_id).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let input = vec![
+            JitOrder {
+                auction_id: 1,
+                uid: ByteArray([2; 56]),
+                owner: ByteArray([3; 20]),
+            },
+            JitOrder {
+                auction_id: 1,
+                uid: ByteArray([4; 56]),
+                owner: ByteArray([5; 20]),
+            },
+        ];
+        insert(&mut db, &input).await.unwrap();
+        insert(
+            &mut db,
+            &[JitOrder {
+                auction_id: 2,
+                uid: ByteArray([6; 56]),
+                owner: ByteArray([7; 20]),
+            }],
+        )
+        .await
+        .unwrap();
+        let output = fetch(&mut db, 1).await.unwrap();
+        assert_eq!(input, output);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod analytics;
+pub mod api_keys;
 pub mod app_data;
 pub mod auction;
 pub mod auction_participants;
@@ -6,10 +8,12 @@ pub mod auction_transaction;
 pub mod byte_array;
 pub mod ethflow_orders;
 pub mod events;
+pub mod jit_orders;
 pub mod onchain_broadcasted_orders;
 pub mod onchain_invalidations;
 pub mod order_events;
 pub mod order_execution;
+pub mod order_history;
 pub mod orders;
 pub mod quotes;
 pub mod settlement_call_data;
@@ -66,6 +70,8 @@ pub const TABLES: &[&str] = &[
     "auction_prices",
     "auction_participants",
     "app_data",
+    "api_keys",
+    "jit_orders",
 ];
 
 /// The names of potentially big volume tables we use in the db.
@@ -54,6 +54,8 @@ pub struct OnchainOrderPlacement {
     pub order_uid: OrderUid,
     pub sender: Address,
     pub placement_error: Option<OnchainOrderPlacementError>,
+    /// The contract that emitted the order placement event.
+    pub contract: Address,
 }
 
 #[derive(Clone, Debug, Default, sqlx::FromRow, Eq, PartialEq)]
@@ -64,23 +66,45 @@ pub struct OnchainOrderPlacementRow {
     pub is_reorged: bool,
     pub block_number: i64,
     pub log_index: i64,
+    pub contract: Option<Address>,
 }
 
-pub async fn last_block(ex: &mut PgConnection) -> Result<i64, sqlx::Error> {
+/// Returns the last block with an order placement event emitted by the
+/// specified contract.
+pub async fn last_block(ex: &mut PgConnection, contract: &Address) -> Result<i64, sqlx::Error> {
     const QUERY: &str = r#"
-        SELECT COALESCE(MAX(block_number), 0) FROM onchain_placed_orders;
+        SELECT COALESCE(MAX(block_number), 0) FROM onchain_placed_orders
+        WHERE contract = $1;
     "#;
-    sqlx::query_scalar(QUERY).fetch_one(ex).await
+    sqlx::query_scalar(QUERY).bind(contract).fetch_one(ex).await
 }
 
+/// Marks the orders placed by the specified contract from a given block
+/// onwards as reorged.
 pub async fn mark_as_reorged(
     ex: &mut PgTransaction<'_>,
+    contract: &Address,
     mark_from_block_number: i64,
 ) -> Result<(), sqlx::Error> {
-    const QUERY_ONCHAIN_ORDERS: &str =
-        "UPDATE onchain_placed_orders SET is_reorged = true WHERE block_number >= $1;";
-    ex.execute(sqlx::query(QUERY_ONCHAIN_ORDERS).bind(mark_from_block_number))
-        .await?;
+    const QUERY_ONCHAIN_ORDERS: &str = "UPDATE onchain_placed_orders SET is_reorged = true WHERE \
+                                        contract = $1 AND block_number >= $2;";
+    ex.execute(
+        sqlx::query(QUERY_ONCHAIN_ORDERS)
+            .bind(contract)
+            .bind(mark_from_block_number),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Attributes order placements that were indexed before the emitting contract
+/// was stored to the specified contract.
+pub async fn set_missing_contract(
+    ex: &mut PgConnection,
+    contract: &Address,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str = "UPDATE onchain_placed_orders SET contract = $1 WHERE contract IS NULL;";
+    ex.execute(sqlx::query(QUERY).bind(contract)).await?;
     Ok(())
 }
 
@@ -101,11 +125,11 @@ pub async fn insert_onchain_order(
 ) -> Result<(), sqlx::Error> {
     const QUERY: &str = r#"
         INSERT INTO onchain_placed_orders
-            (uid, sender, is_reorged, placement_error, block_number, log_index)
-        VALUES ($1, $2, false, $3, $4, $5)
+            (uid, sender, is_reorged, placement_error, block_number, log_index, contract)
+        VALUES ($1, $2, false, $3, $4, $5, $6)
         ON CONFLICT (uid) DO UPDATE SET
             is_reorged = false, sender = $2, placement_error = $3,
-            block_number = $4, log_index = $5;
+            block_number = $4, log_index = $5, contract = $6;
     "#;
     sqlx::query(QUERY)
         .bind(event.order_uid)
@@ -113,6 +137,7 @@ pub async fn insert_onchain_order(
         .bind(&event.placement_error)
         .bind(index.block_number)
         .bind(index.log_index)
+        .bind(event.contract)
         .execute(ex)
         .await?;
     Ok(())
@@ -156,6 +181,7 @@ mod tests {
                 is_reorged: false,
                 block_number: event_index.block_number,
                 log_index: event_index.log_index,
+                contract: Some(order.contract),
             };
             assert_eq!(expected_row, row);
         }
@@ -179,10 +205,46 @@ mod tests {
             block_number: 1,
             log_index: 0,
         };
-        append(&mut db, &[(event_index, OnchainOrderPlacement::default())])
+        let contract = ByteArray([1; 20]);
+        append(
+            &mut db,
+            &[(
+                event_index,
+                OnchainOrderPlacement {
+                    contract,
+                    ..Default::default()
+                },
+            )],
+        )
+        .await
+        .unwrap();
+        assert_eq!(last_block(&mut db, &contract).await.unwrap(), 1);
+        assert_eq!(last_block(&mut db, &ByteArray([2; 20])).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_set_missing_contract() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let order = OnchainOrderPlacement::default();
+        insert_onchain_order(&mut db, &EventIndex::default(), &order)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE onchain_placed_orders SET contract = NULL;")
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        let contract = ByteArray([1; 20]);
+        set_missing_contract(&mut db, &contract).await.unwrap();
+        let row = read_order(&mut db, &order.order_uid)
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(last_block(&mut db).await.unwrap(), 1);
+        assert_eq!(row.contract, Some(contract));
     }
 
     #[tokio::test]
@@ -205,11 +267,13 @@ mod tests {
             order_uid: ByteArray([1; 56]),
             sender: ByteArray([1; 20]),
             placement_error: None,
+            contract: ByteArray([9; 20]),
         };
         let order_2 = OnchainOrderPlacement {
             order_uid: ByteArray([2; 56]),
             sender: ByteArray([2; 20]),
             placement_error: None,
+            contract: ByteArray([9; 20]),
         };
         append(
             &mut db,
@@ -220,7 +284,9 @@ mod tests {
         )
         .await
         .unwrap();
-        mark_as_reorged(&mut db, 2).await.unwrap();
+        mark_as_reorged(&mut db, &ByteArray([9; 20]), 2)
+            .await
+            .unwrap();
         let row = read_order(&mut db, &order_1.order_uid)
             .await
             .unwrap()
@@ -232,6 +298,7 @@ mod tests {
             is_reorged: false,
             block_number: event_index_1.block_number,
             log_index: event_index_1.log_index,
+            contract: Some(ByteArray([9; 20])),
         };
         assert_eq!(expected_row, row);
         let row = read_order(&mut db, &order_2.order_uid)
@@ -245,6 +312,7 @@ mod tests {
             is_reorged: true, // <-- difference is here
             block_number: event_index_2.block_number,
             log_index: event_index_2.log_index,
+            contract: Some(ByteArray([9; 20])),
         };
         assert_eq!(expected_row, row);
     }
@@ -268,11 +336,14 @@ mod tests {
             order_uid: ByteArray([1; 56]),
             sender: ByteArray([1; 20]),
             placement_error: None,
+            contract: ByteArray([9; 20]),
         };
         append(&mut db, &[(event_index_1, order_1.clone())])
             .await
             .unwrap();
-        mark_as_reorged(&mut db, 1).await.unwrap();
+        mark_as_reorged(&mut db, &ByteArray([9; 20]), 1)
+            .await
+            .unwrap();
         let row = read_order(&mut db, &order_1.order_uid)
             .await
             .unwrap()
@@ -284,12 +355,14 @@ mod tests {
             placement_error: None,
             block_number: event_index_1.block_number,
             log_index: event_index_1.log_index,
+            contract: Some(ByteArray([9; 20])),
         };
         assert_eq!(expected_row, row);
         let reorged_order = OnchainOrderPlacement {
             order_uid: order_1.order_uid,
             sender: ByteArray([2; 20]),
             placement_error: None,
+            contract: ByteArray([9; 20]),
         };
         // Now, we insert the order again and then it should no longer be reorged
         append(&mut db, &[(event_index_2, reorged_order.clone())])
@@ -306,6 +379,7 @@ mod tests {
             placement_error: None,
             block_number: event_index_2.block_number,
             log_index: event_index_2.log_index,
+            contract: Some(ByteArray([9; 20])),
         };
         assert_eq!(expected_row, row);
     }
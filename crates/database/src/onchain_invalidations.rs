@@ -1,5 +1,5 @@
 use {
-    crate::{events::EventIndex, OrderUid, PgTransaction},
+    crate::{events::EventIndex, Address, OrderUid, PgTransaction},
     sqlx::{Executor, PgConnection},
 };
 
@@ -8,47 +8,70 @@ pub struct OnchainInvalidationRow {
     pub uid: OrderUid,
     pub block_number: i64,
     pub log_index: i64,
+    pub contract: Option<Address>,
 }
 
 pub async fn insert_onchain_invalidations(
     ex: &mut PgTransaction<'_>,
+    contract: &Address,
     events: &[(EventIndex, OrderUid)],
 ) -> Result<(), sqlx::Error> {
     for (index, event) in events {
-        insert_onchain_invalidation(ex, index, event).await?;
+        insert_onchain_invalidation(ex, contract, index, event).await?;
     }
     Ok(())
 }
 
+/// Deletes the invalidations emitted by the specified contract from a given
+/// block onwards.
 pub async fn delete_invalidations(
     ex: &mut PgTransaction<'_>,
+    contract: &Address,
     block_number: i64,
 ) -> Result<(), sqlx::Error> {
     const QUERY_INVALIDATION: &str =
-        "DELETE FROM onchain_order_invalidations WHERE block_number >= $1;";
-    ex.execute(sqlx::query(QUERY_INVALIDATION).bind(block_number))
-        .await?;
+        "DELETE FROM onchain_order_invalidations WHERE contract = $1 AND block_number >= $2;";
+    ex.execute(
+        sqlx::query(QUERY_INVALIDATION)
+            .bind(contract)
+            .bind(block_number),
+    )
+    .await?;
     Ok(())
 }
 
 pub async fn insert_onchain_invalidation(
     ex: &mut PgConnection,
+    contract: &Address,
     index: &EventIndex,
     order_uid: &OrderUid,
 ) -> Result<(), sqlx::Error> {
-    const QUERY: &str = "INSERT INTO onchain_order_invalidations (block_number, log_index, uid) \
-                         VALUES ($1, $2, $3) ON CONFLICT (uid) DO UPDATE SET
-         block_number = $1, log_index = $2;
+    const QUERY: &str = "INSERT INTO onchain_order_invalidations (block_number, log_index, uid, \
+                         contract) VALUES ($1, $2, $3, $4) ON CONFLICT (uid) DO UPDATE SET
+         block_number = $1, log_index = $2, contract = $4;
     ;";
     sqlx::query(QUERY)
         .bind(index.block_number)
         .bind(index.log_index)
         .bind(order_uid)
+        .bind(contract)
         .execute(ex)
         .await?;
     Ok(())
 }
 
+/// Attributes invalidations that were indexed before the emitting contract was
+/// stored to the specified contract.
+pub async fn set_missing_contract(
+    ex: &mut PgConnection,
+    contract: &Address,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str =
+        "UPDATE onchain_order_invalidations SET contract = $1 WHERE contract IS NULL;";
+    ex.execute(sqlx::query(QUERY).bind(contract)).await?;
+    Ok(())
+}
+
 pub async fn read_onchain_invalidation(
     ex: &mut PgConnection,
     id: &OrderUid,
@@ -64,6 +87,8 @@ pub async fn read_onchain_invalidation(
 mod tests {
     use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
 
+    const CONTRACT: Address = ByteArray([1; 20]);
+
     #[tokio::test]
     #[ignore]
     async fn postgres_invalidation_roundtrip() {
@@ -73,7 +98,7 @@ mod tests {
 
         let order_uid = OrderUid::default();
         let event_index = EventIndex::default();
-        insert_onchain_invalidation(&mut db, &event_index, &order_uid)
+        insert_onchain_invalidation(&mut db, &CONTRACT, &event_index, &order_uid)
             .await
             .unwrap();
         let row = read_onchain_invalidation(&mut db, &order_uid)
@@ -84,6 +109,7 @@ mod tests {
             uid: order_uid,
             block_number: event_index.block_number,
             log_index: event_index.log_index,
+            contract: Some(CONTRACT),
         };
         assert_eq!(expected_row, row);
     }
@@ -108,11 +134,16 @@ mod tests {
         let order_uid_2: OrderUid = ByteArray([2; 56]);
         insert_onchain_invalidations(
             &mut db,
+            &CONTRACT,
             &[(event_index_1, order_uid_1), (event_index_2, order_uid_2)],
         )
         .await
         .unwrap();
-        delete_invalidations(&mut db, 2).await.unwrap();
+        // Invalidations of other contracts are unaffected.
+        delete_invalidations(&mut db, &ByteArray([2; 20]), 0)
+            .await
+            .unwrap();
+        delete_invalidations(&mut db, &CONTRACT, 2).await.unwrap();
         let row = read_onchain_invalidation(&mut db, &order_uid_1)
             .await
             .unwrap()
@@ -121,6 +152,7 @@ mod tests {
             uid: order_uid_1,
             block_number: event_index_1.block_number,
             log_index: event_index_1.log_index,
+            contract: Some(CONTRACT),
         };
         assert_eq!(expected_row, row);
         let row = read_onchain_invalidation(&mut db, &order_uid_2)
@@ -145,12 +177,12 @@ mod tests {
             log_index: 1,
         };
         let order_uid = ByteArray([1; 56]);
-        insert_onchain_invalidations(&mut db, &[(event_index_1, order_uid)])
+        insert_onchain_invalidations(&mut db, &CONTRACT, &[(event_index_1, order_uid)])
             .await
             .unwrap();
         let reorged_order = order_uid;
         // Now, we insert the order again
-        insert_onchain_invalidations(&mut db, &[(event_index_2, reorged_order)])
+        insert_onchain_invalidations(&mut db, &CONTRACT, &[(event_index_2, reorged_order)])
             .await
             .unwrap();
         let row = read_onchain_invalidation(&mut db, &order_uid)
@@ -161,6 +193,7 @@ mod tests {
             uid: order_uid,
             block_number: event_index_2.block_number,
             log_index: event_index_2.log_index,
+            contract: Some(CONTRACT),
         };
         assert_eq!(expected_row, row);
     }
@@ -0,0 +1,466 @@
+use {
+    crate::{
+        orders::{FullOrder, OrderClass, ORDERS_FROM, ORDERS_SELECT},
+        Address,
+        OrderUid,
+    },
+    sqlx::{
+        types::{
+            chrono::{DateTime, TimeZone, Utc},
+            BigDecimal,
+        },
+        PgConnection,
+        Postgres,
+        QueryBuilder,
+    },
+};
+
+/// Status of an order as reported by the API. The status is not stored in the
+/// database but derived from the order's trades, invalidations and validity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    PresignaturePending,
+    Open,
+    Fulfilled,
+    Cancelled,
+    Expired,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::PresignaturePending => "presignaturePending",
+            Self::Open => "open",
+            Self::Fulfilled => "fulfilled",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// Filters that all returned orders have to match. Unset optional filters
+/// match all orders of the owner.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    /// Orders owned by or placed on-chain by this address.
+    pub owner: Address,
+    /// Orders that have any of these statuses.
+    pub statuses: Vec<Status>,
+    /// Orders that have any of these classes.
+    pub classes: Vec<OrderClass>,
+    /// Orders that either sell or buy this token.
+    pub token: Option<Address>,
+    /// Orders that were created at or after this point in time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Orders that were created before this point in time.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Orders whose full app data specifies this `appCode`.
+    pub app_code: Option<String>,
+    pub partially_fillable: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SortField {
+    #[default]
+    CreationTimestamp,
+    ValidTo,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    #[default]
+    Descending,
+}
+
+/// Position after which the next page of orders starts.
+///
+/// Orders are sorted by the sort field with the order uid as a tie breaker so
+/// the position is unambiguous and pages are stable when new orders get
+/// inserted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    /// Value of the sort field of the last order of the previous page. For
+    /// creation timestamps this is in microseconds since the unix epoch.
+    pub sort_key: i64,
+    pub uid: OrderUid,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sorting {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+impl Sorting {
+    /// Returns the cursor pointing right after the specified order.
+    pub fn cursor(&self, order: &FullOrder) -> Cursor {
+        let sort_key = match self.field {
+            SortField::CreationTimestamp => order.creation_timestamp.timestamp_micros(),
+            SortField::ValidTo => order.valid_to,
+        };
+        Cursor {
+            sort_key,
+            uid: order.uid,
+        }
+    }
+}
+
+/// An order with aggregated information about its executions.
+#[derive(Debug, sqlx::FromRow)]
+pub struct OrderHistoryEntry {
+    #[sqlx(flatten)]
+    pub order: FullOrder,
+    /// Surplus received over the order's limit price. For sell orders this is
+    /// denominated in the buy token and for buy orders in the sell token.
+    pub executed_surplus: BigDecimal,
+}
+
+/// Derives the order status from the columns of `ORDERS_SELECT`. Mirrors the
+/// status computation of the orderbook. Needs to be followed by the current
+/// unix timestamp to determine whether an order is expired.
+const STATUS: &str = r#"
+CASE
+    WHEN (kind = 'sell' AND sum_sell <> 0 AND sum_sell - sum_fee = sell_amount) OR
+         (kind = 'buy' AND sum_buy <> 0 AND sum_buy = buy_amount) THEN 'fulfilled'
+    WHEN invalidated THEN 'cancelled'
+    WHEN COALESCE(ethflow_valid_to, valid_to) < "#;
+
+/// Surplus of all trades of an order compared to the order's limit price.
+const EXECUTED_SURPLUS: &str = r#"
+FLOOR(GREATEST(
+    CASE kind
+        WHEN 'sell' THEN sum_buy - (sum_sell - sum_fee) * buy_amount / sell_amount
+        WHEN 'buy' THEN sum_buy * sell_amount / buy_amount - (sum_sell - sum_fee)
+    END,
+    0
+))
+"#;
+
+/// Returns a page of orders matching the filter. `now` is the unix timestamp
+/// used to determine whether orders are expired.
+pub async fn order_history(
+    ex: &mut PgConnection,
+    filter: &Filter,
+    sorting: Sorting,
+    after: Option<Cursor>,
+    now: i64,
+    limit: i64,
+) -> Result<Vec<OrderHistoryEntry>, sqlx::Error> {
+    let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM (SELECT history.*, ");
+    query.push(STATUS);
+    query.push_bind(now);
+    query.push(
+        " THEN 'expired' WHEN presignature_pending THEN 'presignaturePending' ELSE 'open' END AS \
+         status, ",
+    );
+    query.push(EXECUTED_SURPLUS);
+    query.push(" AS executed_surplus FROM (");
+    // The orders owned by and the orders placed on-chain by the owner are
+    // queried separately so both parts can use an index. Without a status
+    // filter every part only needs to return a full page.
+    let part_limit = filter.statuses.is_empty().then_some(limit);
+    push_orders(&mut query, filter, sorting, after, Owner::Owner, part_limit);
+    query.push(" UNION ");
+    push_orders(
+        &mut query,
+        filter,
+        sorting,
+        after,
+        Owner::Sender,
+        part_limit,
+    );
+    query.push(") AS history) AS history WHERE true");
+    if !filter.statuses.is_empty() {
+        query.push(" AND status = ANY(");
+        query.push_bind(
+            filter
+                .statuses
+                .iter()
+                .map(|status| status.as_str())
+                .collect::<Vec<_>>(),
+        );
+        query.push(")");
+    }
+
+    push_order_by(&mut query, sorting, "");
+    query.push(" LIMIT ");
+    query.push_bind(limit);
+
+    query.build_query_as().fetch_all(ex).await
+}
+
+/// How the owner of the order history is related to the orders.
+enum Owner {
+    /// The owner of the order.
+    Owner,
+    /// The sender of the transaction placing the order on-chain.
+    Sender,
+}
+
+fn push_orders(
+    query: &mut QueryBuilder<Postgres>,
+    filter: &Filter,
+    sorting: Sorting,
+    after: Option<Cursor>,
+    owner: Owner,
+    limit: Option<i64>,
+) {
+    query.push("(SELECT ");
+    query.push(ORDERS_SELECT);
+    query.push(
+        ", (SELECT eth_o.valid_to FROM ethflow_orders eth_o WHERE eth_o.uid = o.uid) AS \
+         ethflow_valid_to FROM ",
+    );
+    query.push(ORDERS_FROM);
+    match owner {
+        Owner::Owner => {
+            query.push(" WHERE o.owner = ");
+        }
+        Owner::Sender => {
+            query.push(
+                " JOIN onchain_placed_orders onchain_o ON onchain_o.uid = o.uid WHERE \
+                 onchain_o.sender = ",
+            );
+        }
+    }
+    query.push_bind(filter.owner);
+    push_order_filter(query, filter);
+    push_cursor(query, sorting, after);
+    if let Some(limit) = limit {
+        push_order_by(query, sorting, "o.");
+        query.push(" LIMIT ");
+        query.push_bind(limit);
+    }
+    query.push(")");
+}
+
+fn push_order_by(query: &mut QueryBuilder<Postgres>, sorting: Sorting, table: &str) {
+    let direction = match sorting.direction {
+        SortDirection::Ascending => "ASC",
+        SortDirection::Descending => "DESC",
+    };
+    query.push(format!(
+        " ORDER BY {table}{} {direction}, {table}uid {direction}",
+        sort_column(sorting.field)
+    ));
+}
+
+fn sort_column(field: SortField) -> &'static str {
+    match field {
+        SortField::CreationTimestamp => "creation_timestamp",
+        SortField::ValidTo => "valid_to",
+    }
+}
+
+fn push_order_filter(query: &mut QueryBuilder<Postgres>, filter: &Filter) {
+    if !filter.classes.is_empty() {
+        query.push(" AND o.class = ANY(");
+        query.push_bind(filter.classes.clone());
+        query.push(")");
+    }
+    if let Some(token) = filter.token {
+        query.push(" AND (o.sell_token = ");
+        query.push_bind(token);
+        query.push(" OR o.buy_token = ");
+        query.push_bind(token);
+        query.push(")");
+    }
+    if let Some(created_after) = filter.created_after {
+        query.push(" AND o.creation_timestamp >= ");
+        query.push_bind(created_after);
+    }
+    if let Some(created_before) = filter.created_before {
+        query.push(" AND o.creation_timestamp < ");
+        query.push_bind(created_before);
+    }
+    if let Some(app_code) = &filter.app_code {
+        query.push(
+            " AND EXISTS (SELECT 1 FROM app_data ad WHERE ad.contract_app_data = o.app_data AND \
+             convert_from(ad.full_app_data, 'UTF8')::jsonb ->> 'appCode' = ",
+        );
+        query.push_bind(app_code.clone());
+        query.push(")");
+    }
+    if let Some(partially_fillable) = filter.partially_fillable {
+        query.push(" AND o.partially_fillable = ");
+        query.push_bind(partially_fillable);
+    }
+}
+
+fn push_cursor(query: &mut QueryBuilder<Postgres>, sorting: Sorting, after: Option<Cursor>) {
+    let Some(cursor) = after else {
+        return;
+    };
+    let comparison = match sorting.direction {
+        SortDirection::Ascending => ">",
+        SortDirection::Descending => "<",
+    };
+    query.push(format!(
+        " AND (o.{}, o.uid) {comparison} (",
+        sort_column(sorting.field)
+    ));
+    match sorting.field {
+        SortField::CreationTimestamp => {
+            query.push_bind(Utc.timestamp_nanos(cursor.sort_key.saturating_mul(1_000)));
+        }
+        SortField::ValidTo => {
+            query.push_bind(cursor.sort_key);
+        }
+    }
+    query.push(", ");
+    query.push_bind(cursor.uid);
+    query.push(")");
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            byte_array::ByteArray,
+            events::{insert_trade, EventIndex, Trade},
+            onchain_broadcasted_orders::{insert_onchain_order, OnchainOrderPlacement},
+            orders::{insert_order, Order, OrderKind},
+        },
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_order_history_pagination() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let owner = ByteArray([1; 20]);
+        let now = Utc::now();
+        for i in 0..5u8 {
+            let order = Order {
+                uid: ByteArray([i; 56]),
+                owner,
+                creation_timestamp: now - chrono::Duration::seconds(i.into()),
+                valid_to: now.timestamp() + 60,
+                kind: OrderKind::Sell,
+                sell_amount: 100.into(),
+                buy_amount: 100.into(),
+                class: if i % 2 == 0 {
+                    OrderClass::Market
+                } else {
+                    OrderClass::Limit
+                },
+                ..Default::default()
+            };
+            insert_order(&mut db, &order).await.unwrap();
+        }
+        insert_trade(
+            &mut db,
+            &EventIndex::default(),
+            &Trade {
+                order_uid: ByteArray([0; 56]),
+                sell_amount_including_fee: 100.into(),
+                buy_amount: 110.into(),
+                fee_amount: 0.into(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let filter = Filter {
+            owner,
+            ..Default::default()
+        };
+        let sorting = Sorting::default();
+        let first = order_history(&mut db, &filter, sorting, None, now.timestamp(), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            first.iter().map(|e| e.order.uid).collect::<Vec<_>>(),
+            [ByteArray([0; 56]), ByteArray([1; 56])]
+        );
+        assert_eq!(first[0].executed_surplus, BigDecimal::from(10));
+        assert_eq!(first[1].executed_surplus, BigDecimal::from(0));
+
+        let cursor = sorting.cursor(&first[1].order);
+        let second = order_history(&mut db, &filter, sorting, Some(cursor), now.timestamp(), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            second.iter().map(|e| e.order.uid).collect::<Vec<_>>(),
+            [ByteArray([2; 56]), ByteArray([3; 56])]
+        );
+
+        let filter = Filter {
+            owner,
+            statuses: vec![Status::Open],
+            classes: vec![OrderClass::Market],
+            ..Default::default()
+        };
+        let open_market = order_history(&mut db, &filter, sorting, None, now.timestamp(), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            open_market.iter().map(|e| e.order.uid).collect::<Vec<_>>(),
+            [ByteArray([2; 56]), ByteArray([4; 56])]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_order_history_includes_onchain_orders() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let owner = ByteArray([1; 20]);
+        let contract = ByteArray([2; 20]);
+        let now = Utc::now();
+        let orders = [
+            (ByteArray([1; 56]), owner),
+            (ByteArray([2; 56]), contract),
+            (ByteArray([3; 56]), contract),
+        ];
+        for (i, (uid, owner)) in orders.into_iter().enumerate() {
+            let order = Order {
+                uid,
+                owner,
+                creation_timestamp: now - chrono::Duration::seconds(i as i64),
+                valid_to: now.timestamp() + 60,
+                ..Default::default()
+            };
+            insert_order(&mut db, &order).await.unwrap();
+        }
+        // Only the second order was placed on-chain by the owner.
+        insert_onchain_order(
+            &mut db,
+            &EventIndex::default(),
+            &OnchainOrderPlacement {
+                order_uid: ByteArray([2; 56]),
+                sender: owner,
+                placement_error: None,
+                contract,
+            },
+        )
+        .await
+        .unwrap();
+
+        let filter = Filter {
+            owner,
+            ..Default::default()
+        };
+        let history = order_history(
+            &mut db,
+            &filter,
+            Sorting::default(),
+            None,
+            now.timestamp(),
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            history.iter().map(|e| e.order.uid).collect::<Vec<_>>(),
+            [ByteArray([1; 56]), ByteArray([2; 56])]
+        );
+    }
+}
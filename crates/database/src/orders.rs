@@ -457,6 +457,9 @@ pub struct FullOrder {
     pub onchain_placement_error: Option<OnchainOrderPlacementError>,
     pub executed_surplus_fee: BigDecimal,
     pub full_app_data: Option<Vec<u8>>,
+    /// Whether the order may only be matched peer-to-peer according to its
+    /// full app data. Orders without known full app data are not.
+    pub cow_only: bool,
 }
 
 impl FullOrder {
@@ -492,7 +495,7 @@ impl FullOrder {
 // SET enable_nestloop = false;
 // to get a better idea of what indexes postgres *could* use even if it decides
 // that with the current amount of data this wouldn't be better.
-const ORDERS_SELECT: &str = r#"
+pub(crate) const ORDERS_SELECT: &str = r#"
 o.uid, o.owner, o.creation_timestamp, o.sell_token, o.buy_token, o.sell_amount, o.buy_amount,
 o.valid_to, o.app_data, o.fee_amount, o.full_fee_amount, o.kind, o.partially_fillable, o.signature,
 o.receiver, o.signing_scheme, o.settlement_contract, o.sell_token_balance, o.buy_token_balance,
@@ -519,10 +522,11 @@ array(Select (p.target, p.value, p.data) from interactions p where p.order_uid =
 (SELECT onchain_o.sender from onchain_placed_orders onchain_o where onchain_o.uid = o.uid limit 1) as onchain_user,
 (SELECT onchain_o.placement_error from onchain_placed_orders onchain_o where onchain_o.uid = o.uid limit 1) as onchain_placement_error,
 COALESCE((SELECT SUM(surplus_fee) FROM order_execution oe WHERE oe.order_uid = o.uid), 0) as executed_surplus_fee,
-(SELECT full_app_data FROM app_data ad WHERE o.app_data = ad.contract_app_data LIMIT 1) as full_app_data
+(SELECT full_app_data FROM app_data ad WHERE o.app_data = ad.contract_app_data LIMIT 1) as full_app_data,
+COALESCE((SELECT cow_only FROM app_data ad WHERE o.app_data = ad.contract_app_data LIMIT 1), false) as cow_only
 "#;
 
-const ORDERS_FROM: &str = "orders o";
+pub(crate) const ORDERS_FROM: &str = "orders o";
 
 pub async fn single_full_order(
     ex: &mut PgConnection,
@@ -856,6 +860,7 @@ mod tests {
                 order_uid: OrderUid::default(),
                 sender,
                 placement_error: None,
+                ..Default::default()
             },
         )
         .await
@@ -1372,9 +1377,14 @@ mod tests {
             .unwrap()
             .unwrap();
         assert!(!result.invalidated);
-        insert_onchain_invalidation(&mut db, &EventIndex::default(), &order.uid)
-            .await
-            .unwrap();
+        insert_onchain_invalidation(
+            &mut db,
+            &Default::default(),
+            &EventIndex::default(),
+            &order.uid,
+        )
+        .await
+        .unwrap();
         let result = single_full_order(&mut db, &order.uid)
             .await
             .unwrap()
@@ -1549,6 +1559,7 @@ mod tests {
                         order_uid: uid,
                         sender: owner,
                         placement_error: None,
+                        ..Default::default()
                     };
                     let event_index = EventIndex::default();
                     insert_onchain_order(&mut db, &event_index, &onchain_order)
@@ -1679,6 +1690,7 @@ mod tests {
             order_uid: ByteArray(orders[0].0),
             sender: owners[2],
             placement_error: None,
+            ..Default::default()
         };
         let event_index = EventIndex::default();
         insert_onchain_order(&mut db, &event_index, &onchain_order)
@@ -2021,8 +2033,9 @@ mod tests {
             .unwrap()
             .unwrap();
         assert!(full_order.full_app_data.is_none());
+        assert!(!full_order.cow_only);
         let full_app_data = vec![0u8, 1, 2];
-        crate::app_data::insert(&mut db, &order.app_data, &full_app_data)
+        crate::app_data::insert(&mut db, &order.app_data, &full_app_data, true)
             .await
             .unwrap();
         let full_order = single_full_order(&mut db, &order.uid)
@@ -2030,5 +2043,6 @@ mod tests {
             .unwrap()
             .unwrap();
         assert_eq!(full_order.full_app_data, Some(full_app_data));
+        assert!(full_order.cow_only);
     }
 }
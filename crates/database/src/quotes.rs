@@ -129,6 +129,29 @@ LIMIT 1
         .await
 }
 
+/// Returns the quoted sell and buy amounts of the most recent quotes for a
+/// token pair, most recent quotes first.
+pub async fn recent_pair_quotes(
+    ex: &mut PgConnection,
+    sell_token: &Address,
+    buy_token: &Address,
+    limit: i64,
+) -> Result<Vec<(BigDecimal, BigDecimal)>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT sell_amount, buy_amount
+FROM quotes
+WHERE sell_token = $1 AND buy_token = $2
+ORDER BY id DESC
+LIMIT $3
+    "#;
+    sqlx::query_as(QUERY)
+        .bind(sell_token)
+        .bind(buy_token)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
 pub async fn remove_expired_quotes(
     ex: &mut PgConnection,
     max_expiry: DateTime<Utc>,
@@ -422,4 +445,41 @@ mod tests {
         search_a.quote_kind = QuoteKind::Standard;
         assert_eq!(find(&mut db, &search_a).await.unwrap(), None,);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_recent_pair_quotes() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = low_precision_now();
+        let quote = |sell_token, buy_token, sell_amount: u32| Quote {
+            id: Default::default(),
+            sell_token: ByteArray([sell_token; 20]),
+            buy_token: ByteArray([buy_token; 20]),
+            sell_amount: sell_amount.into(),
+            buy_amount: 10.into(),
+            gas_amount: 1.,
+            gas_price: 1.,
+            sell_token_price: 1.,
+            order_kind: OrderKind::Sell,
+            expiration_timestamp: now,
+            quote_kind: QuoteKind::Standard,
+            solver: ByteArray([1; 20]),
+        };
+        for quote in [
+            quote(1, 2, 1),
+            quote(2, 1, 2),
+            quote(1, 2, 3),
+            quote(1, 2, 4),
+        ] {
+            save(&mut db, &quote).await.unwrap();
+        }
+
+        let recent = recent_pair_quotes(&mut db, &ByteArray([1; 20]), &ByteArray([2; 20]), 2)
+            .await
+            .unwrap();
+        assert_eq!(recent, vec![(4.into(), 10.into()), (3.into(), 10.into())]);
+    }
 }
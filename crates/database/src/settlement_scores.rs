@@ -40,6 +40,24 @@ pub async fn fetch(
         .await
 }
 
+/// Records the surplus of CoW-only orders that the settlement of the auction
+/// matched against on-chain liquidity. The penalty is only recorded for the
+/// reward computation and isn't read back by the services.
+pub async fn update_cow_only_penalty(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+    penalty: &BigDecimal,
+) -> Result<(), sqlx::Error> {
+    const QUERY: &str =
+        r#"UPDATE settlement_scores SET cow_only_penalty = $2 WHERE auction_id = $1"#;
+    sqlx::query(QUERY)
+        .bind(auction_id)
+        .bind(penalty)
+        .execute(ex)
+        .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, crate::byte_array::ByteArray, sqlx::Connection};
@@ -64,4 +82,37 @@ mod tests {
         let output = fetch(&mut db, 1).await.unwrap().unwrap();
         assert_eq!(input, output);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_cow_only_penalty() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        async fn penalty(db: &mut PgConnection) -> BigDecimal {
+            sqlx::query_scalar(
+                "SELECT cow_only_penalty FROM settlement_scores WHERE auction_id = 1",
+            )
+            .fetch_one(db)
+            .await
+            .unwrap()
+        }
+
+        let score = Score {
+            auction_id: 1,
+            winner: ByteArray([2; 20]),
+            winning_score: 10.into(),
+            reference_score: 9.into(),
+            block_deadline: 1000,
+            simulation_block: 2000,
+        };
+        insert(&mut db, score).await.unwrap();
+        assert_eq!(penalty(&mut db).await, 0.into());
+
+        update_cow_only_penalty(&mut db, 1, &3.into())
+            .await
+            .unwrap();
+        assert_eq!(penalty(&mut db).await, 3.into());
+    }
 }
@@ -64,6 +64,30 @@ ON o.uid = t.order_uid"#;
         .fetch(ex)
 }
 
+/// Returns the executed sell amounts (excluding fees) and buy amounts of the
+/// most recent trades for a token pair, most recent trades first.
+pub async fn recent_pair_executions(
+    ex: &mut PgConnection,
+    sell_token: &Address,
+    buy_token: &Address,
+    limit: i64,
+) -> Result<Vec<(BigDecimal, BigDecimal)>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT t.sell_amount - t.fee_amount, t.buy_amount
+FROM trades t
+JOIN orders o ON o.uid = t.order_uid
+WHERE o.sell_token = $1 AND o.buy_token = $2
+ORDER BY t.block_number DESC, t.log_index DESC
+LIMIT $3
+    "#;
+    sqlx::query_as(QUERY)
+        .bind(sell_token)
+        .bind(buy_token)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -251,6 +275,7 @@ mod tests {
             order_uid: ByteArray(order_ids[0].0),
             sender: owners[3],
             placement_error: None,
+            ..Default::default()
         };
         let event_index = EventIndex::default();
         insert_onchain_order(&mut db, &event_index, &onchain_order)
@@ -263,6 +288,7 @@ mod tests {
             order_uid: ByteArray(order_ids[3].0),
             sender: owners[3],
             placement_error: None,
+            ..Default::default()
         };
         insert_onchain_order(&mut db, &event_index_1, &onchain_order)
             .await
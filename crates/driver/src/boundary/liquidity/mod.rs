@@ -27,6 +27,7 @@ use {
 };
 
 pub mod balancer;
+pub mod solidly;
 pub mod swapr;
 pub mod uniswap;
 mod zeroex;
@@ -55,6 +56,7 @@ pub struct Fetcher {
     blocks: CurrentBlockStream,
     inner: LiquidityCollector,
     swapr_routers: HashSet<eth::ContractAddress>,
+    solidly: Vec<solidly::Source>,
 }
 
 impl Fetcher {
@@ -84,6 +86,12 @@ impl Fetcher {
         )
         .await?;
 
+        let solidly: Vec<_> =
+            future::try_join_all(config.solidly.iter().map(|config| {
+                solidly::source(eth, &block_stream, block_retriever.clone(), config)
+            }))
+            .await?;
+
         let bal_v2: Vec<_> = config
             .balancer_v2
             .iter()
@@ -128,6 +136,7 @@ impl Fetcher {
                 base_tokens: Arc::new(base_tokens),
             },
             swapr_routers,
+            solidly,
         })
     }
 
@@ -137,7 +146,7 @@ impl Fetcher {
         pairs: &HashSet<liquidity::TokenPair>,
         block: infra::liquidity::AtBlock,
     ) -> Result<Vec<liquidity::Liquidity>> {
-        let pairs = pairs
+        let pairs: HashSet<_> = pairs
             .iter()
             .map(|pair| {
                 let (a, b) = pair.get();
@@ -152,9 +161,23 @@ impl Fetcher {
                 recent_block_cache::Block::Number(block_number)
             }
         };
-        let liquidity = self.inner.get_liquidity(pairs, block).await?;
+        // Solidly pools are not supported by the liquidity collector, so they
+        // are fetched separately for the same relevant token pairs.
+        let solidly_pairs = self.inner.base_tokens.relevant_pairs(pairs.iter().copied());
+        let (liquidity, solidly) = future::try_join(
+            self.inner.get_liquidity(pairs, block),
+            future::try_join_all(
+                self.solidly
+                    .iter()
+                    .map(|source| source.fetch(solidly_pairs.clone(), block)),
+            ),
+        )
+        .await?;
 
-        let liquidity = liquidity
+        // Solidly liquidity IDs continue after the IDs of the collected
+        // liquidity, so they stay unique even if some of it gets ignored.
+        let solidly_ids = liquidity.len()..;
+        let liquidity: Vec<_> = liquidity
             .into_iter()
             .enumerate()
             .filter_map(|(index, liquidity)| {
@@ -177,7 +200,12 @@ impl Fetcher {
                 .ok()
             })
             .collect();
-        Ok(liquidity)
+        let solidly = solidly
+            .into_iter()
+            .flatten()
+            .zip(solidly_ids)
+            .map(|((pool, gas), index)| solidly::to_domain(liquidity::Id(index), pool, gas));
+        Ok(liquidity.into_iter().chain(solidly).collect())
     }
 }
 
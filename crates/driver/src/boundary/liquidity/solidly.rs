@@ -0,0 +1,158 @@
+use {
+    crate::{
+        boundary::{self, Result},
+        domain::{
+            eth,
+            liquidity::{self, solidly},
+        },
+        infra::{self, blockchain::Ethereum},
+    },
+    contracts::{ISolidlyPoolFactory, ISolidlyRouter},
+    ethrpc::current_block::{BlockRetrieving, CurrentBlockStream},
+    model::TokenPair,
+    shared::{
+        recent_block_cache::Block,
+        sources::solidly::{
+            pool_cache::PoolCache,
+            pool_fetching::{self, PoolFetcher, PoolFetching},
+        },
+    },
+    std::{collections::HashSet, sync::Arc},
+};
+
+/// The base unit for basis points, i.e. how many basis points in 100%.
+const BPS_BASE: u32 = 10_000;
+
+/// Rough estimate of the gas used per Solidly router swap.
+const GAS_PER_SWAP: u64 = 120_000;
+
+/// Solidly liquidity for a single router and its default pool factory.
+pub struct Source {
+    router: eth::ContractAddress,
+    factory: eth::ContractAddress,
+    pools: Arc<dyn PoolFetching>,
+}
+
+impl Source {
+    /// Fetches the Solidly pools for the specified token pairs.
+    pub async fn fetch(
+        &self,
+        pairs: HashSet<TokenPair>,
+        block: Block,
+    ) -> Result<Vec<(solidly::Pool, eth::Gas)>> {
+        Ok(self
+            .pools
+            .fetch(pairs, block)
+            .await?
+            .into_iter()
+            .filter_map(|pool| {
+                // Ignore "bad" liquidity - this allows the driver to continue
+                // solving with the other good stuff.
+                to_domain_pool(self.router, self.factory, pool).ok()
+            })
+            .map(|pool| (pool, GAS_PER_SWAP.into()))
+            .collect())
+    }
+}
+
+pub fn to_domain(id: liquidity::Id, pool: solidly::Pool, gas: eth::Gas) -> liquidity::Liquidity {
+    liquidity::Liquidity {
+        id,
+        gas,
+        kind: liquidity::Kind::Solidly(pool),
+    }
+}
+
+fn to_domain_pool(
+    router: eth::ContractAddress,
+    factory: eth::ContractAddress,
+    pool: pool_fetching::Pool,
+) -> Result<solidly::Pool> {
+    anyhow::ensure!(
+        (pool.fee.numer() * BPS_BASE) % pool.fee.denom() == 0,
+        "invalid Solidly fee ratio; does not have exact BPS representation",
+    );
+    let bps = (pool.fee.numer() * BPS_BASE) / pool.fee.denom();
+
+    Ok(solidly::Pool {
+        address: pool.address.into(),
+        router,
+        factory,
+        reserves: solidly::Reserves::new(
+            solidly::Reserve {
+                asset: eth::Asset {
+                    token: pool.tokens.get().0.into(),
+                    amount: pool.reserves.0.into(),
+                },
+                decimals: pool.decimals.0,
+            },
+            solidly::Reserve {
+                asset: eth::Asset {
+                    token: pool.tokens.get().1.into(),
+                    amount: pool.reserves.1.into(),
+                },
+                decimals: pool.decimals.1,
+            },
+        )?,
+        stable: pool.stable,
+        fee: solidly::Fee::new(bps)?,
+    })
+}
+
+pub fn to_interaction(
+    pool: &solidly::Pool,
+    input: &liquidity::MaxInput,
+    output: &liquidity::ExactOutput,
+    receiver: &eth::Address,
+) -> eth::Interaction {
+    let router = ISolidlyRouter::at(&ethrpc::dummy::web3(), pool.router.into());
+    let tx = router
+        .swap_exact_tokens_for_tokens(
+            input.0.amount,
+            output.0.amount,
+            vec![(
+                input.0.token.into(),
+                output.0.token.into(),
+                pool.stable,
+                pool.factory.into(),
+            )],
+            receiver.0,
+            eth::U256::max_value(),
+        )
+        .tx;
+
+    eth::Interaction {
+        target: pool.router.into(),
+        value: eth::Ether(0.into()),
+        call_data: tx.data.expect("router swap call data").0.into(),
+    }
+}
+
+pub async fn source(
+    eth: &Ethereum,
+    blocks: &CurrentBlockStream,
+    block_retriever: Arc<dyn BlockRetrieving>,
+    config: &infra::liquidity::config::Solidly,
+) -> Result<Source> {
+    let router = eth.contract_at::<ISolidlyRouter>(config.router);
+    let web3 = router.raw_instance().web3().clone();
+    let factory = router.default_factory().call().await?;
+
+    let pool_fetcher = PoolFetcher::new(
+        ISolidlyPoolFactory::at(&web3, factory),
+        web3.clone(),
+        block_retriever,
+    )
+    .await?;
+    let pools = Arc::new(PoolCache::new(
+        boundary::liquidity::cache_config(),
+        Arc::new(pool_fetcher),
+        blocks.clone(),
+    )?);
+
+    Ok(Source {
+        router: config.router,
+        factory: factory.into(),
+        pools,
+    })
+}
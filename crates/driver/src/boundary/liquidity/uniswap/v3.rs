@@ -8,7 +8,11 @@ use {
                 uniswap::v3::{Fee, Liquidity, LiquidityNet, Pool, SqrtPrice, Tick},
             },
         },
-        infra::{self, blockchain::Ethereum},
+        infra::{
+            self,
+            blockchain::Ethereum,
+            liquidity::config::{UniswapV3PoolDiscovery, UniswapV3SwapEvent},
+        },
     },
     anyhow::Context,
     contracts::{GPv2Settlement, UniswapV3SwapRouter},
@@ -17,7 +21,11 @@ use {
     shared::{
         http_solver::model::TokenAmount,
         interaction::Interaction,
-        sources::uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
+        sources::uniswap_v3::{
+            event_fetching::SwapEvent,
+            onchain,
+            pool_fetching::UniswapV3PoolFetcher,
+        },
     },
     solver::{
         interactions::allowances::Allowances,
@@ -133,18 +141,39 @@ async fn init_liquidity(
     let web3 = boundary::web3(eth);
     let router = UniswapV3SwapRouter::at(&web3, config.router.0);
 
-    let pool_fetcher = Arc::new(
-        UniswapV3PoolFetcher::new(
-            &config.graph_api_base_url,
-            eth.network().chain.into(),
-            web3.clone(),
-            boundary::liquidity::http_client(),
-            block_retriever,
-            config.max_pools_to_initialize,
-        )
-        .await
-        .context("failed to initialise UniswapV3 liquidity")?,
-    );
+    let pool_fetcher = match &config.pool_discovery {
+        UniswapV3PoolDiscovery::Subgraph(graph_api_base_url) => {
+            UniswapV3PoolFetcher::new(
+                graph_api_base_url,
+                eth.network().chain.into(),
+                web3.clone(),
+                boundary::liquidity::http_client(),
+                block_retriever,
+                config.max_pools_to_initialize,
+            )
+            .await
+        }
+        UniswapV3PoolDiscovery::Factory(factory) => {
+            UniswapV3PoolFetcher::from_factory(
+                onchain::Factory {
+                    address: factory.address.into(),
+                    deployer: factory.deployer.into(),
+                    init_code_digest: factory.pool_code.into(),
+                    fee_tiers: factory.fee_tiers.clone(),
+                    swap_event: match factory.swap_event {
+                        UniswapV3SwapEvent::UniswapV3 => SwapEvent::UniswapV3,
+                        UniswapV3SwapEvent::PancakeSwapV3 => SwapEvent::PancakeSwapV3,
+                    },
+                },
+                web3.clone(),
+                block_retriever,
+                config.max_pools_to_initialize,
+            )
+            .await
+        }
+    }
+    .context("failed to initialise UniswapV3 liquidity")?;
+    let pool_fetcher = Arc::new(pool_fetcher);
 
     Ok(UniswapV3Liquidity::new(
         router,
@@ -29,7 +29,7 @@ use {
 };
 pub use {gas_estimation::GasPriceEstimating, solver::settlement_submission::GlobalTxPool};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub additional_tip_percentage: f64,
     pub gas_price_cap: f64,
@@ -39,7 +39,7 @@ pub struct Config {
     pub kind: Kind,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Kind {
     /// The public mempool of the [`Ethereum`] node.
     Public(RevertProtection),
@@ -66,7 +66,7 @@ impl Kind {
 /// This can be enabled to avoid MEV when private transaction
 /// submission strategies are available. If private submission strategies
 /// are not available, revert protection is always disabled.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RevertProtection {
     Enabled,
     Disabled,
@@ -133,18 +133,22 @@ impl Mempool {
         })
     }
 
-    /// Publish the settlement and wait for it to be confirmed.
+    /// Publish the settlement and wait for it to be confirmed. Once the
+    /// deadline block is reached, the submission stops and the pending
+    /// transaction gets cancelled.
     pub async fn execute(
         &self,
         solver: &Solver,
         settlement: Settlement,
+        nonce: eth::U256,
+        deadline: Option<eth::BlockNo>,
     ) -> Result<eth::TxId, mempools::Error> {
         let web3 = boundary::web3(&self.eth);
-        let nonce = web3
-            .eth()
-            .transaction_count(solver.address().into(), None)
-            .await
-            .map_err(anyhow::Error::from)?;
+        let account = solver
+            .accounts()
+            .get(settlement.solver())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("settlement signed by unknown solver account"))?;
         let max_fee_per_gas = eth::U256::from(settlement.gas.price).to_f64_lossy();
         let gas_price_estimator = SubmitterGasPriceEstimator {
             inner: self.gas_price_estimator.as_ref(),
@@ -170,7 +174,6 @@ impl Mempool {
         };
         let estimator = AccessListEstimator(settlement.access_list.clone());
         // TODO: move tx submission logic from legacy code into the driver (#1543)
-        let account = solver.account();
         let submitter = Submitter::new(
             self.eth.contracts().settlement(),
             &account,
@@ -189,6 +192,7 @@ impl Mempool {
                     target_confirm_time: self.config.target_confirm_time,
                     gas_estimate: settlement.gas.estimate.into(),
                     deadline: Some(std::time::Instant::now() + self.config.max_confirm_time),
+                    deadline_block: deadline.map(|block| block.0),
                     retry_interval: self.config.retry_interval,
                     network_id: self.eth.network().id.to_string(),
                     additional_call_data: settlement.auction_id.to_be_bytes().into_iter().collect(),
@@ -199,12 +203,7 @@ impl Mempool {
                 "mempool",
                 kind = self.config.kind.format_variant()
             ))
-            .await
-            .map_err(|err| match err {
-                SubmissionError::SimulationRevert(_) => mempools::Error::SimulationRevert,
-                SubmissionError::Revert(hash) => mempools::Error::Revert(hash.into()),
-                _ => mempools::Error::Other(anyhow::Error::from(err)),
-            })?;
+            .await?;
         Ok(receipt.transaction_hash.into())
     }
 
@@ -213,6 +212,18 @@ impl Mempool {
     }
 }
 
+impl From<SubmissionError> for mempools::Error {
+    fn from(err: SubmissionError) -> Self {
+        match err {
+            SubmissionError::SimulationRevert(_) => Self::SimulationRevert,
+            SubmissionError::Revert(hash) => Self::Revert(hash.into()),
+            SubmissionError::Canceled(hash) => Self::Cancelled(hash.into()),
+            SubmissionError::Timeout => Self::Expired,
+            _ => Self::Other(anyhow::Error::from(err)),
+        }
+    }
+}
+
 struct AccessListEstimator(eth::AccessList);
 
 #[async_trait]
@@ -227,3 +238,33 @@ impl AccessListEstimating for AccessListEstimator {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, primitive_types::H256};
+
+    #[test]
+    fn maps_submission_errors() {
+        let hash = H256([1; 32]);
+        assert!(matches!(
+            mempools::Error::from(SubmissionError::Timeout),
+            mempools::Error::Expired
+        ));
+        assert!(matches!(
+            mempools::Error::from(SubmissionError::Revert(hash)),
+            mempools::Error::Revert(tx) if tx.0 == hash
+        ));
+        assert!(matches!(
+            mempools::Error::from(SubmissionError::Canceled(hash)),
+            mempools::Error::Cancelled(tx) if tx.0 == hash
+        ));
+        assert!(matches!(
+            mempools::Error::from(SubmissionError::SimulationRevert(None)),
+            mempools::Error::SimulationRevert
+        ));
+        assert!(matches!(
+            mempools::Error::from(SubmissionError::Other(anyhow::anyhow!("error"))),
+            mempools::Error::Other(_)
+        ));
+    }
+}
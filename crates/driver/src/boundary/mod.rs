@@ -37,7 +37,7 @@ pub use {
     mempool::Mempool,
     model::order::OrderData,
     settlement::Settlement,
-    shared::ethrpc::Web3,
+    shared::{cow_only, ethrpc::Web3},
 };
 
 /// Returns a Web3 instance with a trait object transport needed by various
@@ -58,6 +58,9 @@ use {
 pub struct Settlement {
     pub(super) inner: solver::settlement::Settlement,
     pub solver: eth::Address,
+    /// The solver provided orders that are settled alongside the auction
+    /// orders.
+    pub jit_orders: Vec<competition::JitOrder>,
 }
 
 impl Settlement {
@@ -85,6 +88,7 @@ impl Settlement {
                 .collect(),
         );
 
+        let mut jit_orders = Vec::new();
         for trade in solution.trades() {
             let (boundary_order, execution) = match trade {
                 competition::solution::Trade::Fulfillment(trade) => {
@@ -103,13 +107,21 @@ impl Settlement {
                         },
                     )
                 }
-                competition::solution::Trade::Jit(trade) => (
-                    to_boundary_jit_order(&DomainSeparator(domain.0), trade.order()),
-                    LimitOrderExecution {
-                        filled: trade.executed().into(),
-                        scoring_fee: 0.into(),
-                    },
-                ),
+                competition::solution::Trade::Jit(trade) => {
+                    let order = to_boundary_jit_order(&DomainSeparator(domain.0), trade.order());
+                    jit_orders.push(competition::JitOrder {
+                        uid: order.metadata.uid.0.into(),
+                        owner: order.metadata.owner.into(),
+                    });
+
+                    (
+                        order,
+                        LimitOrderExecution {
+                            filled: trade.executed().into(),
+                            scoring_fee: 0.into(),
+                        },
+                    )
+                }
             };
 
             let boundary_limit_order = order_converter.normalize_limit_order(
@@ -172,6 +184,7 @@ impl Settlement {
         Ok(Self {
             inner: settlement,
             solver: solution.solver().address(),
+            jit_orders,
         })
     }
 
@@ -244,6 +257,11 @@ impl Settlement {
         self.inner.merge(other.inner).map(|inner| Self {
             inner,
             solver: self.solver,
+            jit_orders: self
+                .jit_orders
+                .into_iter()
+                .chain(other.jit_orders)
+                .collect(),
         })
     }
 
@@ -310,6 +328,7 @@ fn to_boundary_order(order: &competition::Order) -> Order {
             onchain_order_data: Default::default(),
             is_liquidity_order: order.is_liquidity(),
             full_app_data: Default::default(),
+            cow_only: order.cow_only,
         },
         signature: to_boundary_signature(&order.signature),
         interactions: Interactions {
@@ -451,6 +470,9 @@ pub fn to_boundary_interaction(
                 liquidity::Kind::Swapr(pool) => pool
                     .swap(&input, &output, &settlement_contract.into())
                     .context("invalid swapr execution")?,
+                liquidity::Kind::Solidly(pool) => pool
+                    .swap(&input, &output, &settlement_contract.into())
+                    .context("invalid solidly execution")?,
                 liquidity::Kind::ZeroEx(_) => todo!(),
             };
 
@@ -114,12 +114,69 @@ impl Auction {
     }
 }
 
+/// A stage of the pipeline which prepares the orders of an auction before it
+/// is sent to a solver.
+///
+/// Sorting is stable, so later sorting stages take precedence and earlier
+/// sorting stages only break ties.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Sort orders by their price achievability using the reference prices
+    /// of the auction, market orders first.
+    Price,
+    /// Sort orders by their creation time, oldest first.
+    Age,
+    /// Sort orders by the surplus they can generate at the reference prices
+    /// of the auction, largest first.
+    Surplus,
+    /// Allocate the balances of the owners to their orders in the current
+    /// order, removing orders which can't be funded.
+    Balance,
+    /// Remove orders with a token that has no reference price. The autopilot
+    /// only prices tokens for which it finds liquidity.
+    Liquidity,
+    /// Keep at most this many orders per owner.
+    MaxOrdersPerOwner(usize),
+    /// Keep at most this many orders.
+    MaxOrders(usize),
+}
+
+/// The stages which are applied to the orders of an auction, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pipeline(Vec<Stage>);
+
+impl Pipeline {
+    pub fn new(stages: Vec<Stage>) -> Self {
+        Self(stages)
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.0
+    }
+
+    fn needs_balances(&self) -> bool {
+        self.0.contains(&Stage::Balance)
+    }
+}
+
+impl Default for Pipeline {
+    /// Prioritize well priced orders and filter out orders which can't be
+    /// funded.
+    fn default() -> Self {
+        Self(vec![Stage::Price, Stage::Balance])
+    }
+}
+
 #[derive(Clone)]
 pub struct AuctionProcessor(Arc<Mutex<Inner>>);
 
 struct Inner {
     auction: auction::Id,
-    fut: Shared<BoxFuture<'static, Arc<Auction>>>,
+    /// The running processing task of the current auction per pipeline.
+    tasks: HashMap<Pipeline, Shared<BoxFuture<'static, Arc<Auction>>>>,
+    /// The balances of the current auction, which are fetched once and shared
+    /// by all pipelines.
+    balances: Option<Shared<BoxFuture<'static, Arc<Balances>>>>,
     eth: Arc<infra::Ethereum>,
 }
 
@@ -127,9 +184,13 @@ type BalanceGroup = (order::Trader, eth::TokenAddress, order::SellTokenBalance);
 type Balances = HashMap<BalanceGroup, order::SellAmount>;
 
 impl AuctionProcessor {
-    /// Prioritize well priced and filter out unfillable orders from the given
-    /// auction.
-    pub fn prioritize(&self, mut auction: Auction) -> Shared<BoxFuture<'static, Arc<Auction>>> {
+    /// Prioritize and filter the orders of the given auction with the stages
+    /// of the pipeline.
+    pub fn prioritize(
+        &self,
+        mut auction: Auction,
+        pipeline: &Pipeline,
+    ) -> Shared<BoxFuture<'static, Arc<Auction>>> {
         let new_id = auction
             .id()
             .expect("auctions used for quoting do not have to be prioritized");
@@ -140,20 +201,39 @@ impl AuctionProcessor {
             tracing::error!(?current_id, ?new_id, "received an outdated auction");
         }
         if current_id.0 == new_id.0 {
-            tracing::debug!("await running prioritization task");
-            return lock.fut.clone();
+            if let Some(fut) = lock.tasks.get(pipeline) {
+                tracing::debug!("await running prioritization task");
+                return fut.clone();
+            }
+        } else {
+            lock.auction = new_id;
+            lock.tasks.clear();
+            lock.balances = None;
         }
 
         let eth = lock.eth.clone();
+        let weth = eth.contracts().weth_address();
+        let balances = pipeline.needs_balances().then(|| {
+            lock.balances
+                .get_or_insert_with(|| {
+                    let eth = eth.clone();
+                    let orders = auction.orders.clone();
+                    async move { Arc::new(Self::fetch_balances(&eth, &orders).await) }
+                        .boxed()
+                        .shared()
+                })
+                .clone()
+        });
         let rt = tokio::runtime::Handle::current();
+        let stages = pipeline.clone();
         // Use spawn_blocking() because a lot of CPU bound computations are happening
         // and we don't want to block the runtime for too long.
         let fut = tokio::task::spawn_blocking(move || {
             let start = std::time::Instant::now();
-            Self::sort(&mut auction);
-            let mut balances =
-                rt.block_on(async { Self::fetch_balances(&eth, &auction.orders).await });
-            Self::filter_orders(&mut balances, &mut auction.orders, &eth);
+            let mut balances = balances.map(|balances| (*rt.block_on(balances)).clone());
+            for stage in stages.stages() {
+                Self::apply(stage, &mut auction, balances.as_mut(), weth);
+            }
             tracing::debug!(auction_id = new_id.0, time =? start.elapsed(), "auction preprocessing done");
             Arc::new(auction)
         })
@@ -167,12 +247,66 @@ impl AuctionProcessor {
         .shared();
 
         tracing::debug!("started new prioritization task");
-        lock.auction = new_id;
-        lock.fut = fut.clone();
+        lock.tasks.insert(pipeline.clone(), fut.clone());
 
         fut
     }
 
+    fn apply(
+        stage: &Stage,
+        auction: &mut Auction,
+        balances: Option<&mut Balances>,
+        weth: eth::WethAddress,
+    ) {
+        match stage {
+            Stage::Price => Self::sort(auction),
+            Stage::Age => auction.orders.sort_by_key(|order| order.created.0),
+            Stage::Surplus => auction.orders.sort_by_cached_key(|order| {
+                std::cmp::Reverse(order.surplus_potential(&auction.tokens))
+            }),
+            Stage::Balance => Self::filter_orders(
+                balances.expect("balances are fetched for pipelines with a balance stage"),
+                &mut auction.orders,
+                weth,
+            ),
+            Stage::Liquidity => auction.orders.retain(|order| {
+                let priced = auction.tokens.get(order.sell.token).price.is_some()
+                    && auction.tokens.get(order.buy.token).price.is_some();
+                if !priced {
+                    observe::order_excluded_from_auction(
+                        order,
+                        observe::OrderExcludedFromAuctionReason::MissingPrice,
+                    );
+                }
+                priced
+            }),
+            Stage::MaxOrdersPerOwner(max) => {
+                let mut counts = HashMap::<_, usize>::new();
+                auction.orders.retain(|order| {
+                    let count = counts.entry(order.trader()).or_default();
+                    *count += 1;
+                    if *count > *max {
+                        observe::order_excluded_from_auction(
+                            order,
+                            observe::OrderExcludedFromAuctionReason::OwnerOrderLimit,
+                        );
+                        return false;
+                    }
+                    true
+                });
+            }
+            Stage::MaxOrders(max) => {
+                for order in auction.orders.iter().skip(*max) {
+                    observe::order_excluded_from_auction(
+                        order,
+                        observe::OrderExcludedFromAuctionReason::OrderLimit,
+                    );
+                }
+                auction.orders.truncate(*max);
+            }
+        }
+    }
+
     /// Sort orders based on their price achievability using the reference
     /// prices contained in the auction (in the money first).
     fn sort(auction: &mut Auction) {
@@ -198,7 +332,7 @@ impl AuctionProcessor {
     fn filter_orders(
         balances: &mut Balances,
         orders: &mut Vec<order::Order>,
-        eth: &infra::Ethereum,
+        weth: eth::WethAddress,
     ) {
         // The auction that we receive from the `autopilot` assumes that there
         // is sufficient balance to completely cover all the orders. **This is
@@ -208,7 +342,6 @@ impl AuctionProcessor {
         // to each order, and potentially scaling the order's `available` amount
         // down in case the available user balance is only enough to partially
         // cover the rest of the order.
-        let weth = eth.contracts().weth_address();
         orders.retain_mut(|order| {
             let remaining_balance = match balances.get_mut(&(
                 order.trader(),
@@ -334,7 +467,8 @@ impl AuctionProcessor {
     pub fn new(eth: Arc<infra::Ethereum>) -> Self {
         Self(Arc::new(Mutex::new(Inner {
             auction: Id(0),
-            fut: futures::future::pending().boxed().shared(),
+            tasks: Default::default(),
+            balances: None,
             eth,
         })))
     }
@@ -449,3 +583,232 @@ pub enum Error {
     #[error("blockchain error: {0:?}")]
     Blockchain(#[from] blockchain::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::infra::solver::Timeouts};
+
+    fn token(byte: u8) -> eth::TokenAddress {
+        eth::H160::repeat_byte(byte).into()
+    }
+
+    fn weth() -> eth::WethAddress {
+        eth::WethAddress(token(0xef))
+    }
+
+    fn order(uid: u8, owner: u8, sell: u64, buy: u64) -> order::Order {
+        order::Order {
+            uid: [uid; order::UID_LEN].into(),
+            receiver: None,
+            valid_to: util::Timestamp(u32::MAX),
+            created: util::Timestamp(uid.into()),
+            buy: eth::Asset {
+                token: token(0xb),
+                amount: eth::U256::from(buy).into(),
+            },
+            sell: eth::Asset {
+                token: token(0xa),
+                amount: eth::U256::from(sell).into(),
+            },
+            side: order::Side::Sell,
+            fee: Default::default(),
+            kind: order::Kind::Market,
+            app_data: Default::default(),
+            partial: order::Partial::No,
+            pre_interactions: Default::default(),
+            post_interactions: Default::default(),
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: order::signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: eth::Address(eth::H160::repeat_byte(owner)),
+            },
+            fee_policies: Default::default(),
+            cow_only: false,
+        }
+    }
+
+    /// An auction where the sell and the buy token have the same price.
+    fn auction(orders: Vec<order::Order>) -> Auction {
+        let priced = |address, price: Option<u64>| {
+            (
+                address,
+                Token {
+                    decimals: None,
+                    symbol: None,
+                    address,
+                    price: price.map(|price| eth::U256::from(price).into()),
+                    available_balance: Default::default(),
+                    trusted: false,
+                },
+            )
+        };
+        Auction {
+            id: Some(Id(1)),
+            orders,
+            tokens: Tokens(
+                [priced(token(0xa), Some(1)), priced(token(0xb), Some(1))]
+                    .into_iter()
+                    .collect(),
+            ),
+            gas_price: eth::GasPrice {
+                max: eth::U256::from(2).into(),
+                tip: eth::U256::from(1).into(),
+                base: eth::U256::from(1).into(),
+            },
+            deadline: time::Deadline::new(
+                infra::time::now(),
+                Timeouts {
+                    http_delay: chrono::Duration::zero(),
+                    solving_share_of_deadline: 1.0.try_into().unwrap(),
+                },
+            ),
+            score_cap: Score(eth::NonZeroU256::try_from(1u128).unwrap()),
+        }
+    }
+
+    fn apply(stage: Stage, auction: &mut Auction) {
+        AuctionProcessor::apply(&stage, auction, None, weth());
+    }
+
+    fn uids(auction: &Auction) -> Vec<u8> {
+        auction
+            .orders
+            .iter()
+            .map(|order| order.uid.0 .0[0])
+            .collect()
+    }
+
+    #[test]
+    fn price_stage_sorts_by_class_and_likelihood() {
+        let limit = order::Order {
+            kind: order::Kind::Limit,
+            ..order(1, 1, 300, 100)
+        };
+        let mut auction = auction(vec![limit, order(2, 1, 100, 100), order(3, 1, 200, 100)]);
+        apply(Stage::Price, &mut auction);
+        assert_eq!(uids(&auction), [3, 2, 1]);
+    }
+
+    #[test]
+    fn age_stage_sorts_oldest_first() {
+        let mut auction = auction(vec![
+            order(3, 1, 1, 1),
+            order(1, 1, 1, 1),
+            order(2, 1, 1, 1),
+        ]);
+        apply(Stage::Age, &mut auction);
+        assert_eq!(uids(&auction), [1, 2, 3]);
+    }
+
+    #[test]
+    fn surplus_stage_sorts_largest_surplus_first() {
+        let mut auction = auction(vec![
+            order(1, 1, 100, 150),
+            order(2, 1, 300, 100),
+            order(3, 1, 200, 150),
+        ]);
+        apply(Stage::Surplus, &mut auction);
+        assert_eq!(uids(&auction), [2, 3, 1]);
+    }
+
+    #[test]
+    fn surplus_potential() {
+        let auction = auction(Default::default());
+        assert_eq!(
+            order(1, 1, 300, 100).surplus_potential(&auction.tokens),
+            eth::Ether(200.into())
+        );
+        // Out of the money.
+        assert_eq!(
+            order(1, 1, 100, 300).surplus_potential(&auction.tokens),
+            eth::Ether(0.into())
+        );
+        // Missing price.
+        let unpriced = order::Order {
+            buy: eth::Asset {
+                token: token(0xc),
+                amount: eth::U256::from(1).into(),
+            },
+            ..order(1, 1, 300, 100)
+        };
+        assert_eq!(
+            unpriced.surplus_potential(&auction.tokens),
+            eth::Ether(0.into())
+        );
+    }
+
+    #[test]
+    fn balance_stage_allocates_balances_in_order() {
+        let partial = order::Order {
+            partial: order::Partial::Yes {
+                available: eth::U256::from(100).into(),
+            },
+            ..order(3, 1, 100, 100)
+        };
+        let mut auction = auction(vec![
+            order(1, 1, 100, 100),
+            order(2, 1, 100, 100),
+            partial,
+            order(4, 2, 100, 100),
+        ]);
+        let mut balances = Balances::from([(
+            (
+                auction.orders[0].trader(),
+                token(0xa),
+                order::SellTokenBalance::Erc20,
+            ),
+            order::SellAmount(150.into()),
+        )]);
+        AuctionProcessor::apply(&Stage::Balance, &mut auction, Some(&mut balances), weth());
+
+        // The second order can't be funded completely and the partially
+        // fillable order is scaled down to the remaining balance. The owner
+        // of the last order has no balance.
+        assert_eq!(uids(&auction), [1, 3]);
+        assert_eq!(
+            auction.orders[1].partial,
+            order::Partial::Yes {
+                available: eth::U256::from(50).into()
+            }
+        );
+    }
+
+    #[test]
+    fn liquidity_stage_removes_unpriced_orders() {
+        let unpriced = order::Order {
+            sell: eth::Asset {
+                token: token(0xc),
+                amount: eth::U256::from(1).into(),
+            },
+            ..order(2, 1, 1, 1)
+        };
+        let mut auction = auction(vec![order(1, 1, 1, 1), unpriced]);
+        apply(Stage::Liquidity, &mut auction);
+        assert_eq!(uids(&auction), [1]);
+    }
+
+    #[test]
+    fn max_orders_per_owner_stage_keeps_first_orders_of_owner() {
+        let mut auction = auction(vec![
+            order(1, 1, 1, 1),
+            order(2, 2, 1, 1),
+            order(3, 1, 1, 1),
+            order(4, 1, 1, 1),
+        ]);
+        apply(Stage::MaxOrdersPerOwner(2), &mut auction);
+        assert_eq!(uids(&auction), [1, 2, 3]);
+    }
+
+    #[test]
+    fn max_orders_stage_keeps_first_orders() {
+        let mut auction = auction(vec![
+            order(1, 1, 1, 1),
+            order(2, 2, 1, 1),
+            order(3, 1, 1, 1),
+        ]);
+        apply(Stage::MaxOrders(2), &mut auction);
+        assert_eq!(uids(&auction), [1, 2]);
+    }
+}
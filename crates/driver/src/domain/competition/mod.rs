@@ -1,6 +1,10 @@
 use {
     self::solution::settlement,
-    super::{time, time::Remaining, Mempools},
+    super::{
+        mempools,
+        time::{self, Remaining},
+        Mempools,
+    },
     crate::{
         domain::{competition::solution::Settlement, eth},
         infra::{
@@ -17,7 +21,7 @@ use {
     itertools::Itertools,
     std::{
         collections::{HashMap, HashSet},
-        sync::Mutex,
+        sync::{Arc, Mutex},
     },
     tap::TapFallible,
 };
@@ -47,30 +51,53 @@ pub struct Competition {
     pub solver: Solver,
     pub eth: Ethereum,
     pub liquidity: infra::liquidity::Fetcher,
+    pub liquidity_feed: infra::liquidity::Feed,
+    pub bundles: infra::debug::Bundles,
     pub simulator: Simulator,
     pub mempools: Mempools,
-    pub settlement: Mutex<Option<Settlement>>,
+    /// The settlement of the latest solution. This is shared with the
+    /// competition replacing this one when the driver configuration is
+    /// reloaded, so that solutions can be settled across reloads.
+    pub settlement: Arc<Mutex<Option<Settlement>>>,
 }
 
 impl Competition {
     /// Solve an auction as part of this competition.
     pub async fn solve(&self, auction: &Auction) -> Result<Option<Solved>, Error> {
-        let liquidity = match self.solver.liquidity() {
-            solver::Liquidity::Fetch => {
+        let (liquidity, feed) = match self.solver.liquidity() {
+            solver::Liquidity::Fetch => (
                 self.liquidity
                     .fetch(
                         &auction.liquidity_pairs(),
                         infra::liquidity::AtBlock::Latest,
                     )
-                    .await
+                    .await,
+                None,
+            ),
+            solver::Liquidity::Stream => {
+                let liquidity = self
+                    .liquidity
+                    .fetch(
+                        &auction.liquidity_pairs(),
+                        infra::liquidity::AtBlock::Latest,
+                    )
+                    .await;
+                let (liquidity, feed) = self
+                    .liquidity_feed
+                    .publish(liquidity, &infra::liquidity::AtBlock::Latest);
+                (liquidity, Some(feed))
             }
             solver::Liquidity::Skip => Default::default(),
         };
 
-        // Fetch the solutions from the solver.
+        let debug = self.bundles.recorder(&self.solver, auction.id());
+        let debug = debug.as_ref();
+
+        // Fetch the solutions from the solver. The account which settles a
+        // solution is selected when it is encoded, see [`Settlement::new`].
         let solutions = self
             .solver
-            .solve(auction, &liquidity)
+            .solve(auction, &liquidity, feed, debug)
             .await
             .tap_err(|err| {
                 if err.is_timeout() {
@@ -86,6 +113,9 @@ impl Competition {
             if !ids.insert(solution.id()) {
                 observe::duplicated_solution_id(self.solver.name(), solution.id());
                 notify::duplicated_solution_id(&self.solver, auction.id(), solution.id());
+                if let Some(debug) = debug {
+                    debug.discarded(solution.id(), "duplicated solution ID");
+                }
                 false
             } else {
                 true
@@ -97,6 +127,9 @@ impl Competition {
             if solution.is_empty() {
                 observe::empty_solution(self.solver.name(), solution.id());
                 notify::empty_solution(&self.solver, auction.id(), solution.id());
+                if let Some(debug) = debug {
+                    debug.discarded(solution.id(), "empty solution");
+                }
                 false
             } else {
                 true
@@ -114,42 +147,44 @@ impl Competition {
             .collect::<FuturesUnordered<_>>()
             .filter_map(|(id, result)| async move {
                 result
+                    .tap_ok(|settlement| {
+                        if let Some(debug) = debug {
+                            debug.encoded(settlement, &self.eth);
+                        }
+                    })
                     .tap_err(|err| {
                         observe::encoding_failed(self.solver.name(), id, err);
                         notify::encoding_failed(&self.solver, auction.id(), id, err);
+                        if let Some(debug) = debug {
+                            debug.encoding_failed(id, err);
+                        }
                     })
                     .ok()
             });
 
         // Merge settlements as they arrive until there are no more new settlements or
-        // timeout is reached.
+        // timeout is reached. Every settlement is scored once, as it is added.
         let mut settlements = Vec::new();
         if tokio::time::timeout(
             auction.deadline().driver().remaining().unwrap_or_default(),
-            merge_settlements(&mut settlements, encoded, &self.eth, &self.simulator),
+            self.merge_settlements(&mut settlements, encoded, auction),
         )
         .await
         .is_err()
         {
-            observe::postprocessing_timed_out(&settlements)
+            observe::postprocessing_timed_out(settlements.len())
         }
 
-        // Score the settlements.
-        let scores = settlements
-            .into_iter()
-            .map(|settlement| {
-                observe::scoring(&settlement);
-                (
-                    settlement.score(&self.eth, auction, &self.mempools.revert_protection()),
-                    settlement,
-                )
-            })
-            .collect_vec();
+        if let Some(debug) = debug {
+            for (settlement, score) in &settlements {
+                debug.scored(settlement, &self.eth, auction, score);
+            }
+        }
 
         // Filter out settlements which failed scoring.
-        let scores = scores
+        let scores = settlements
             .into_iter()
-            .filter_map(|(result, settlement)| {
+            .filter_map(|(settlement, result)| {
                 result
                     .tap_err(|err| {
                         observe::scoring_failed(self.solver.name(), err);
@@ -180,6 +215,7 @@ impl Competition {
                         score,
                         trades: settlement.orders(),
                         prices: settlement.prices(),
+                        solver: settlement.solver(),
                     },
                     settlement,
                 )
@@ -207,16 +243,14 @@ impl Competition {
                         self.simulate_settlement(&settlement).await
                     {
                         observe::winner_voided(block, &err);
+                        let err = infra::simulator::Error::Revert(err);
+                        if let Some(debug) = debug {
+                            debug.simulation_failed(&settlement, &err);
+                        }
                         *score_ref = None;
                         *self.settlement.lock().unwrap() = None;
                         if let Some(id) = settlement.notify_id() {
-                            notify::simulation_failed(
-                                &self.solver,
-                                auction.id(),
-                                id,
-                                &infra::simulator::Error::Revert(err),
-                                true,
-                            );
+                            notify::simulation_failed(&self.solver, auction.id(), id, &err, true);
                         }
                         return;
                     }
@@ -249,12 +283,14 @@ impl Competition {
                     settlement::Internalization::Disable,
                 )
                 .into(),
+            jit_orders: settlement.jit_orders().to_vec(),
         })
     }
 
     /// Execute the solution generated as part of this competition. Use
-    /// [`Competition::solve`] to generate the solution.
-    pub async fn settle(&self) -> Result<Settled, Error> {
+    /// [`Competition::solve`] to generate the solution. The settlement is not
+    /// submitted past the deadline block.
+    pub async fn settle(&self, deadline: Option<eth::BlockNo>) -> Result<Settled, Error> {
         let settlement = self
             .settlement
             .lock()
@@ -262,7 +298,25 @@ impl Competition {
             .take()
             .ok_or(Error::SolutionNotAvailable)?;
 
-        let executed = self.mempools.execute(&self.solver, &settlement).await;
+        // Reserve the solver account, so that it isn't selected for other
+        // settlements until this one is submitted. The settlement can't be
+        // submitted if the account is still submitting another settlement, since
+        // it would either replace that settlement or be stuck behind it.
+        let nonce = self
+            .eth
+            .nonce(settlement.solver())
+            .await
+            .map_err(|_| Error::SubmissionError)?;
+        let pending = self
+            .solver
+            .accounts()
+            .submitting(settlement.solver(), nonce)?;
+
+        let executed = self
+            .mempools
+            .execute(&self.solver, &settlement, pending.nonce(), deadline)
+            .await;
+        drop(pending);
         notify::executed(
             &self.solver,
             settlement.auction_id,
@@ -272,7 +326,7 @@ impl Competition {
 
         match executed {
             Err(_) => Err(Error::SubmissionError),
-            Ok(tx_hash) => Ok(Settled {
+            Ok(outcome) => Ok(Settled {
                 internalized_calldata: settlement
                     .calldata(
                         self.eth.contracts().settlement(),
@@ -285,7 +339,7 @@ impl Competition {
                         settlement::Internalization::Disable,
                     )
                     .into(),
-                tx_hash,
+                outcome,
             }),
         }
     }
@@ -306,7 +360,7 @@ impl Competition {
     ) -> Result<(), infra::simulator::Error> {
         self.simulator
             .gas(eth::Tx {
-                from: self.solver.address(),
+                from: settlement.solver(),
                 to: settlement.solver(),
                 value: eth::Ether(0.into()),
                 input: crate::util::Bytes(settlement.calldata(
@@ -318,37 +372,70 @@ impl Competition {
             .await
             .map(|_| ())
     }
-}
 
-/// Tries to merge the incoming stream of new settlements into existing ones.
-/// Always adds the new settlement by itself.
-async fn merge_settlements(
-    merged: &mut Vec<Settlement>,
-    new: impl Stream<Item = Settlement>,
-    eth: &Ethereum,
-    simulator: &Simulator,
-) {
-    let mut new = std::pin::pin!(new);
-    while let Some(settlement) = new.next().await {
-        // Try to merge [`settlement`] into some settlements.
-        for other in merged.iter_mut() {
-            match other.merge(&settlement, eth, simulator).await {
-                Ok(m) => {
-                    *other = m;
-                    observe::merged(&settlement, other);
-                    // could possibly break here if we want to avoid merging
-                    // into multiple settlements
-                }
-                Err(err) => {
-                    observe::not_merged(&settlement, other, err);
+    /// Tries to merge the incoming stream of new settlements into existing
+    /// ones. Always adds the new settlement by itself. Settlements are added
+    /// together with their score so that each one is only scored once.
+    ///
+    /// A merged settlement only replaces the existing one if its score is
+    /// strictly higher than the scores of both of its parts. Otherwise, the
+    /// parts are better off competing on their own.
+    async fn merge_settlements(
+        &self,
+        merged: &mut Vec<(Settlement, Result<Score, score::Error>)>,
+        new: impl Stream<Item = Settlement>,
+        auction: &Auction,
+    ) {
+        let score = |settlement: &Settlement| {
+            observe::scoring(settlement);
+            settlement.score(&self.eth, auction, &self.mempools.revert_protection())
+        };
+        let mut new = std::pin::pin!(new);
+        while let Some(settlement) = new.next().await {
+            let settlement_score = score(&settlement);
+            // Try to merge [`settlement`] into some settlements.
+            for (other, other_score) in merged.iter_mut() {
+                match other.merge(&settlement, &self.eth, &self.simulator).await {
+                    Ok(m) => {
+                        let parts = [
+                            other_score.as_ref().ok().copied(),
+                            settlement_score.as_ref().ok().copied(),
+                        ];
+                        let merged_score = score(&m);
+                        if dominates(merged_score.as_ref().ok().copied(), parts) {
+                            observe::merged(self.solver.name(), &settlement, other, &m);
+                            *other = m;
+                            *other_score = merged_score;
+                            // could possibly break here if we want to avoid
+                            // merging into multiple settlements
+                        } else {
+                            observe::merge_not_dominating(
+                                self.solver.name(),
+                                &settlement,
+                                other,
+                                merged_score.ok(),
+                                parts,
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        observe::not_merged(self.solver.name(), &settlement, other, err);
+                    }
                 }
             }
+            // add [`settlement`] by itself
+            merged.push((settlement, settlement_score));
         }
-        // add [`settlement`] by itself
-        merged.push(settlement);
     }
 }
 
+/// Whether a merged settlement scores strictly higher than each of the
+/// settlements it was merged from. A settlement that failed scoring never
+/// dominates, but any score dominates a part that failed scoring.
+fn dominates(merged: Option<Score>, parts: [Option<Score>; 2]) -> bool {
+    merged.is_some() && parts.iter().all(|part| merged > *part)
+}
+
 /// Solution information sent to the protocol by the driver before the solution
 /// ranking happens.
 #[derive(Debug)]
@@ -356,6 +443,17 @@ pub struct Solved {
     pub score: Score,
     pub trades: HashMap<order::Uid, Amounts>,
     pub prices: HashMap<eth::TokenAddress, eth::TokenAmount>,
+    /// The solver account which signs the settlement.
+    pub solver: eth::Address,
+}
+
+/// A just-in-time liquidity order which the solver provided as part of its
+/// solution. These orders are not part of the auction.
+#[derive(Debug, Clone, Copy)]
+pub struct JitOrder {
+    pub uid: order::Uid,
+    /// The address that signed the order.
+    pub owner: eth::Address,
 }
 
 #[derive(Debug, Default)]
@@ -375,12 +473,16 @@ pub struct Revealed {
     /// can manually enforce certain rules which can not be enforced
     /// automatically.
     pub uninternalized_calldata: Bytes<Vec<u8>>,
+    /// The JIT orders settled by the solution, so that their trades can be
+    /// attributed to the solver.
+    pub jit_orders: Vec<JitOrder>,
 }
 
 #[derive(Debug)]
 pub struct Settled {
-    /// The transaction hash in which the solution was submitted.
-    pub tx_hash: eth::TxId,
+    /// The outcome of submitting the solution, including the hash of the mined
+    /// transaction.
+    pub outcome: mempools::Outcome,
     pub internalized_calldata: Bytes<Vec<u8>>,
     /// The uninternalized calldata must be known so that the CoW solver team
     /// can manually enforce certain rules which can not be enforced
@@ -401,4 +503,26 @@ pub enum Error {
     Solver(#[from] solver::Error),
     #[error("failed to submit the solution")]
     SubmissionError,
+    #[error(transparent)]
+    NoAccountAvailable(#[from] solver::NoAccountAvailable),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(value: u64) -> Option<Score> {
+        Some(Score(eth::NonZeroU256::new(value.into()).unwrap()))
+    }
+
+    #[test]
+    fn merged_settlement_must_beat_each_part() {
+        assert!(dominates(score(3), [score(1), score(2)]));
+        assert!(!dominates(score(3), [score(3), score(2)]));
+        assert!(!dominates(score(2), [score(1), score(3)]));
+        assert!(dominates(score(1), [None, None]));
+        assert!(dominates(score(2), [score(1), None]));
+        assert!(!dominates(None, [score(1), score(2)]));
+        assert!(!dominates(None, [None, None]));
+    }
 }
@@ -20,6 +20,9 @@ pub struct Order {
     /// The user specified a custom address to receive the output of this order.
     pub receiver: Option<eth::Address>,
     pub valid_to: util::Timestamp,
+    /// When the order was created. This is zero for orders that are not part
+    /// of an auction, such as quotes.
+    pub created: util::Timestamp,
     /// The minimum amount this order must buy when completely filled.
     pub buy: eth::Asset,
     /// The maximum amount this order is allowed to sell when completely filled.
@@ -44,6 +47,10 @@ pub struct Order {
     /// Unless otherwise configured, the driver modifies solutions to take
     /// sufficient fee in the form of positive slippage.
     pub fee_policies: Vec<FeePolicy>,
+    /// The order may only be matched peer-to-peer: its traded amounts must be
+    /// covered by other orders or the settlement contract buffers and never by
+    /// on-chain liquidity.
+    pub cow_only: bool,
 }
 
 /// An amount denominated in the sell token of an [`Order`].
@@ -232,6 +239,24 @@ impl Order {
             _ => num::BigRational::zero(),
         }
     }
+
+    /// The surplus this order generates when filled at the reference prices
+    /// of the auction, i.e. the value of the sold tokens minus the value of
+    /// the bought tokens. Zero if the order is out of the money or a token
+    /// has no price.
+    pub fn surplus_potential(&self, tokens: &auction::Tokens) -> eth::Ether {
+        match (
+            tokens.get(self.buy.token).price,
+            tokens.get(self.sell.token).price,
+        ) {
+            (Some(buy_price), Some(sell_price)) => {
+                let buy = buy_price.apply(self.buy.amount);
+                let sell = sell_price.apply(self.sell.amount);
+                eth::Ether(sell.0.saturating_sub(buy.0))
+            }
+            _ => eth::Ether(0.into()),
+        }
+    }
 }
 
 impl Available {
@@ -433,6 +458,7 @@ mod tests {
             uid: Default::default(),
             receiver: Default::default(),
             valid_to: util::Timestamp(u32::MAX),
+            created: util::Timestamp(0),
             buy: buy(buy_amount),
             sell: sell(sell_amount),
             side: match available {
@@ -459,6 +485,7 @@ mod tests {
                 signer: Default::default(),
             },
             fee_policies: Default::default(),
+            cow_only: false,
         };
 
         assert_eq!(
@@ -51,6 +51,7 @@ impl Interaction {
                     liquidity::Kind::BalancerV2Stable(pool) => pool.vault.into(),
                     liquidity::Kind::BalancerV2Weighted(pool) => pool.vault.into(),
                     liquidity::Kind::Swapr(pool) => pool.base.router.into(),
+                    liquidity::Kind::Solidly(pool) => pool.router.into(),
                     liquidity::Kind::ZeroEx(_) => todo!(),
                 };
                 vec![eth::Allowance {
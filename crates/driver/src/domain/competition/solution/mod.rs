@@ -8,7 +8,7 @@ use {
         infra::{
             blockchain::{self, Ethereum},
             simulator,
-            solver::Solver,
+            solver::{self, Solver},
             Simulator,
         },
     },
@@ -287,10 +287,16 @@ pub enum Error {
     NonBufferableTokensUsed(BTreeSet<TokenAddress>),
     #[error("invalid internalization: uninternalized solution fails to simulate")]
     FailingInternalization,
+    #[error("cow-only orders not covered by other trades for tokens {0:?}")]
+    CowOnlyViolated(BTreeSet<TokenAddress>),
     #[error("insufficient solver account Ether balance, required {0:?}")]
     SolverAccountInsufficientBalance(eth::Ether),
+    #[error(transparent)]
+    NoAccountAvailable(#[from] solver::NoAccountAvailable),
     #[error("attempted to merge settlements generated by different solvers")]
     DifferentSolvers,
+    #[error("conflicting settlements: {0}")]
+    Conflict(#[from] settlement::Conflict),
 }
 
 #[derive(Debug, Error)]
@@ -28,6 +28,9 @@ use {
 ///   the case where no interactions were internalized. Additionally the solver
 ///   account is known to have sufficient Ether to execute the transaction.
 /// - Internalization: internalized interactions only use trusted tokens.
+/// - CoW-only: the amounts traded by orders which may only be matched
+///   peer-to-peer are covered by the other trades of the settlement whenever it
+///   interacts with on-chain liquidity.
 ///
 /// Publishing a settlement which violates these rules would result in slashing
 /// for the solver (earning reduced rewards). Enforcing these rules ensures that
@@ -88,10 +91,19 @@ impl Settlement {
     async fn new(
         auction_id: auction::Id,
         solutions: HashMap<solution::Id, Solution>,
-        settlement: boundary::Settlement,
+        mut settlement: boundary::Settlement,
         eth: &Ethereum,
         simulator: &Simulator,
     ) -> Result<Self, Error> {
+        // CoW-only rule: the amounts traded by CoW-only orders must be covered
+        // by other orders or the settlement contract buffers. Merged settlements
+        // are checked as a whole, since the trades and interactions of one
+        // solution affect the tokens of a CoW-only order of another.
+        let cow_only_tokens = Self::cow_only_violations(solutions.values());
+        if !cow_only_tokens.is_empty() {
+            return Err(Error::CowOnlyViolated(cow_only_tokens));
+        }
+
         // The settlement contract will fail if the receiver is a smart contract.
         // Because of this, if the receiver is a smart contract and we try to
         // estimate the access list, the access list estimation will also fail.
@@ -137,6 +149,17 @@ impl Settlement {
         let price = eth.gas_price().await?;
         let gas = Gas::new(gas, price);
 
+        // Now that the cost of the settlement is known, select the solver account
+        // which submits it. Solver accounts are interchangeable for simulating the
+        // settlement, so this doesn't require simulating it again.
+        let solver = solutions
+            .values()
+            .next()
+            .expect("settlements contain at least one solution")
+            .solver()
+            .select_account(gas.estimate * gas.price)?;
+        settlement.solver = solver.address();
+
         // Ensure that the solver has sufficient balance for the settlement to be mined.
         if eth.balance(settlement.solver).await? < gas.required_balance() {
             return Err(Error::SolverAccountInsufficientBalance(
@@ -176,6 +199,74 @@ impl Settlement {
         })
     }
 
+    /// The tokens traded by CoW-only orders whose traded amounts are not
+    /// covered by the other trades of the settlement, if the settlement
+    /// interacts with on-chain liquidity.
+    ///
+    /// The inputs and outputs of interactions are declared by the solver and
+    /// can't be relied on, so any interaction which isn't internalized is
+    /// assumed to be able to exchange any token. Imbalances in settlements
+    /// without such interactions can only be covered by the settlement contract
+    /// buffers and are therefore fine.
+    fn cow_only_violations<'a>(
+        solutions: impl Iterator<Item = &'a Solution> + Clone,
+    ) -> BTreeSet<eth::TokenAddress> {
+        let mut cow_only_tokens = BTreeSet::new();
+        let mut flows = HashMap::<eth::TokenAddress, boundary::cow_only::TokenFlow>::new();
+        let mut unknown = HashSet::new();
+        for solution in solutions.clone() {
+            for trade in &solution.trades {
+                let (sell, buy, cow_only, sold, bought, fee) = match trade {
+                    solution::Trade::Fulfillment(trade) => (
+                        trade.order().sell.token,
+                        trade.order().buy.token,
+                        trade.order().cow_only,
+                        trade.sell_amount(&solution.prices, solution.weth),
+                        trade.buy_amount(&solution.prices, solution.weth),
+                        Some(trade.fee()),
+                    ),
+                    solution::Trade::Jit(trade) => (
+                        trade.order().sell.token,
+                        trade.order().buy.token,
+                        false,
+                        trade.sell_amount(&solution.prices, solution.weth),
+                        trade.buy_amount(&solution.prices, solution.weth),
+                        trade.fee(),
+                    ),
+                };
+                let sell = sell.wrap(solution.weth);
+                let buy = buy.wrap(solution.weth);
+                if cow_only {
+                    cow_only_tokens.extend([sell, buy]);
+                }
+                let (Some(sold), Some(bought), Some(fee)) = (sold, bought, fee) else {
+                    unknown.extend([sell, buy]);
+                    continue;
+                };
+                let fee = fee.0.min(sold.0);
+                flows.entry(sell).or_default().sell(sold.0 - fee, fee);
+                flows.entry(buy).or_default().buy(bought.0);
+            }
+        }
+
+        if cow_only_tokens.is_empty()
+            || solutions
+                .flat_map(|solution| solution.interactions.iter())
+                .all(|interaction| interaction.internalize())
+        {
+            return Default::default();
+        }
+        cow_only_tokens
+            .into_iter()
+            .filter(|token| {
+                unknown.contains(token)
+                    || !flows
+                        .get(token)
+                        .is_some_and(boundary::cow_only::TokenFlow::is_covered)
+            })
+            .collect()
+    }
+
     /// Simulate executing this settlement on the blockchain. This process
     /// ensures that the settlement does not revert, and calculates the
     /// access list and gas needed to settle the solution.
@@ -272,6 +363,9 @@ impl Settlement {
         if self.boundary.solver != other.boundary.solver {
             return Err(Error::DifferentSolvers);
         }
+        if let Some(conflict) = self.conflict(other) {
+            return Err(Error::Conflict(conflict));
+        }
 
         // Merge the settlements.
         let mut solutions = self.solutions.clone();
@@ -291,6 +385,25 @@ impl Settlement {
         .await
     }
 
+    /// Checks whether this settlement conflicts with another one, in which case
+    /// the two can't be merged.
+    pub fn conflict(&self, other: &Self) -> Option<Conflict> {
+        Footprint::new(self).conflict(&Footprint::new(other))
+    }
+
+    /// The addresses of the liquidity pools which this settlement interacts
+    /// with.
+    fn pools(&self) -> HashSet<eth::Address> {
+        self.solutions
+            .values()
+            .flat_map(|solution| solution.interactions.iter())
+            .filter_map(|interaction| match interaction {
+                solution::Interaction::Liquidity(interaction) => interaction.liquidity.address(),
+                solution::Interaction::Custom(_) => None,
+            })
+            .collect()
+    }
+
     /// The solutions encoded in this settlement. This is a [`HashSet`] because
     /// multiple solutions can be encoded in a single settlement due to
     /// merging. See [`Self::merge`].
@@ -327,6 +440,11 @@ impl Settlement {
             })
     }
 
+    /// The settled JIT orders provided by the solver.
+    pub fn jit_orders(&self) -> &[competition::JitOrder] {
+        &self.boundary.jit_orders
+    }
+
     /// The uniform price vector this settlement proposes
     pub fn prices(&self) -> HashMap<eth::TokenAddress, eth::TokenAmount> {
         self.boundary.clearing_prices()
@@ -343,6 +461,67 @@ impl Settlement {
     }
 }
 
+/// The reason why two settlements can't be merged.
+#[derive(Debug, thiserror::Error)]
+pub enum Conflict {
+    #[error("order {0:?} is settled by both settlements")]
+    Order(order::Uid),
+    #[error("inconsistent clearing prices for token {0:?}")]
+    Price(eth::TokenAddress),
+    #[error("liquidity pool {0:?} is used by both settlements")]
+    Pool(eth::Address),
+}
+
+/// The parts of a settlement that decide whether it can be merged with
+/// another settlement.
+#[derive(Debug, Default)]
+struct Footprint {
+    orders: HashSet<order::Uid>,
+    prices: HashMap<eth::TokenAddress, eth::U256>,
+    pools: HashSet<eth::Address>,
+}
+
+impl Footprint {
+    fn new(settlement: &Settlement) -> Self {
+        Self {
+            orders: settlement.orders().into_keys().collect(),
+            prices: settlement
+                .prices()
+                .into_iter()
+                .map(|(token, price)| (token, price.0))
+                .collect(),
+            pools: settlement.pools(),
+        }
+    }
+
+    fn conflict(&self, other: &Self) -> Option<Conflict> {
+        if let Some(uid) = other.orders.iter().find(|uid| self.orders.contains(uid)) {
+            return Some(Conflict::Order(*uid));
+        }
+
+        // The clearing prices of both settlements need to be proportional for
+        // the tokens they have in common, otherwise they can't be scaled into a
+        // single uniform price vector.
+        let mut common = self
+            .prices
+            .iter()
+            .filter_map(|(token, price)| Some((*token, *price, *other.prices.get(token)?)));
+        if let Some((_, reference, other_reference)) = common.next() {
+            if let Some((token, ..)) = common.find(|(_, price, other_price)| {
+                price.full_mul(other_reference) != other_price.full_mul(reference)
+            }) {
+                return Some(Conflict::Price(token));
+            }
+        }
+
+        if let Some(pool) = other.pools.iter().find(|pool| self.pools.contains(pool)) {
+            return Some(Conflict::Pool(*pool));
+        }
+
+        None
+    }
+}
+
 /// Should the interactions be internalized?
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Internalization {
@@ -418,3 +597,96 @@ impl Gas {
         self.limit * self.price
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uid(byte: u8) -> order::Uid {
+        order::Uid([byte; order::UID_LEN].into())
+    }
+
+    fn token(byte: u8) -> eth::TokenAddress {
+        eth::H160::repeat_byte(byte).into()
+    }
+
+    fn address(byte: u8) -> eth::Address {
+        eth::H160::repeat_byte(byte).into()
+    }
+
+    fn prices(prices: &[(u8, u64)]) -> HashMap<eth::TokenAddress, eth::U256> {
+        prices
+            .iter()
+            .map(|(byte, price)| (token(*byte), (*price).into()))
+            .collect()
+    }
+
+    #[test]
+    fn conflicts_on_shared_orders() {
+        let a = Footprint {
+            orders: [uid(1), uid(2)].into(),
+            ..Default::default()
+        };
+        let b = Footprint {
+            orders: [uid(3)].into(),
+            ..Default::default()
+        };
+        let c = Footprint {
+            orders: [uid(2), uid(4)].into(),
+            ..Default::default()
+        };
+
+        assert!(a.conflict(&b).is_none());
+        assert!(matches!(a.conflict(&c), Some(Conflict::Order(order)) if order == uid(2)));
+    }
+
+    #[test]
+    fn conflicts_on_disproportional_prices() {
+        let a = Footprint {
+            prices: prices(&[(1, 100), (2, 200), (3, 50)]),
+            ..Default::default()
+        };
+        // Scaled by a factor of 3 for the common tokens.
+        let proportional = Footprint {
+            prices: prices(&[(1, 300), (2, 600), (4, 7)]),
+            ..Default::default()
+        };
+        let disproportional = Footprint {
+            prices: prices(&[(1, 300), (2, 600), (3, 151)]),
+            ..Default::default()
+        };
+        let disjoint = Footprint {
+            prices: prices(&[(4, 1), (5, 2)]),
+            ..Default::default()
+        };
+
+        assert!(a.conflict(&proportional).is_none());
+        assert!(proportional.conflict(&a).is_none());
+        // Which of the common tokens is reported depends on the iteration
+        // order of the prices.
+        assert!(matches!(
+            a.conflict(&disproportional),
+            Some(Conflict::Price(_))
+        ));
+        assert!(a.conflict(&disjoint).is_none());
+    }
+
+    #[test]
+    fn conflicts_on_shared_pools() {
+        let a = Footprint {
+            pools: [address(1), address(2)].into(),
+            ..Default::default()
+        };
+        let b = Footprint {
+            pools: [address(3)].into(),
+            ..Default::default()
+        };
+        let c = Footprint {
+            pools: [address(3), address(1)].into(),
+            ..Default::default()
+        };
+
+        assert!(a.conflict(&b).is_none());
+        assert!(matches!(a.conflict(&c), Some(Conflict::Pool(pool)) if pool == address(1)));
+    }
+}
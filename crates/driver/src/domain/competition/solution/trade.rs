@@ -181,6 +181,55 @@ impl Jit {
     pub fn executed(&self) -> order::TargetAmount {
         self.executed
     }
+
+    /// Returns the part of the signed fee that is paid for the executed
+    /// amount. The fee is scaled down for partially fillable orders.
+    pub fn fee(&self) -> Option<order::SellAmount> {
+        if !self.order.partially_fillable {
+            return Some(self.order.fee);
+        }
+        Some(order::SellAmount(
+            self.order
+                .fee
+                .0
+                .checked_mul(self.executed.0)?
+                .checked_div(self.order.target().0)?,
+        ))
+    }
+
+    /// The amount sold at the clearing prices, including the fee.
+    pub fn sell_amount(
+        &self,
+        prices: &HashMap<eth::TokenAddress, eth::U256>,
+        weth: eth::WethAddress,
+    ) -> Option<eth::TokenAmount> {
+        let before_fee = match self.order.side {
+            order::Side::Sell => self.executed.0,
+            order::Side::Buy => self
+                .executed
+                .0
+                .checked_mul(*prices.get(&self.order.buy.token.wrap(weth))?)?
+                .checked_div(*prices.get(&self.order.sell.token.wrap(weth))?)?,
+        };
+        Some(eth::TokenAmount(before_fee.checked_add(self.fee()?.0)?))
+    }
+
+    /// The amount bought at the clearing prices.
+    pub fn buy_amount(
+        &self,
+        prices: &HashMap<eth::TokenAddress, eth::U256>,
+        weth: eth::WethAddress,
+    ) -> Option<eth::TokenAmount> {
+        let amount = match self.order.side {
+            order::Side::Buy => self.executed.0,
+            order::Side::Sell => self
+                .executed
+                .0
+                .checked_mul(*prices.get(&self.order.sell.token.wrap(weth))?)?
+                .checked_div(*prices.get(&self.order.buy.token.wrap(weth))?)?,
+        };
+        Some(eth::TokenAmount(amount))
+    }
 }
 
 /// The amounts executed by a trade.
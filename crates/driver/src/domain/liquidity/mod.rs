@@ -4,6 +4,7 @@
 use {crate::domain::eth, std::cmp::Ordering};
 
 pub mod balancer;
+pub mod solidly;
 pub mod swapr;
 pub mod uniswap;
 pub mod zeroex;
@@ -17,7 +18,23 @@ pub struct Liquidity {
     pub kind: Kind,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl Liquidity {
+    /// The address of the pool contract. Liquidity which isn't backed by an
+    /// onchain pool, such as limit orders, has no address.
+    pub fn address(&self) -> Option<eth::Address> {
+        match &self.kind {
+            Kind::UniswapV2(pool) => Some(pool.address),
+            Kind::UniswapV3(pool) => Some(pool.address.into()),
+            Kind::BalancerV2Stable(pool) => Some(pool.id.address().into()),
+            Kind::BalancerV2Weighted(pool) => Some(pool.id.address().into()),
+            Kind::Swapr(pool) => Some(pool.base.address),
+            Kind::Solidly(pool) => Some(pool.address),
+            Kind::ZeroEx(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Id(pub usize);
 
 impl From<usize> for Id {
@@ -58,6 +75,7 @@ pub enum Kind {
     BalancerV2Stable(balancer::v2::stable::Pool),
     BalancerV2Weighted(balancer::v2::weighted::Pool),
     Swapr(swapr::Pool),
+    Solidly(solidly::Pool),
     ZeroEx(zeroex::LimitOrder),
 }
 
@@ -69,6 +87,7 @@ impl From<&Kind> for &'static str {
             Kind::BalancerV2Stable(_) => "BalancerV2Stable",
             Kind::BalancerV2Weighted(_) => "BalancerV2Weighted",
             Kind::Swapr(_) => "Swapr",
+            Kind::Solidly(_) => "Solidly",
             Kind::ZeroEx(_) => "ZeroExLimitOrder",
         }
     }
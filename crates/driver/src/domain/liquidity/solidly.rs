@@ -0,0 +1,120 @@
+use {
+    crate::{
+        boundary,
+        domain::{eth, liquidity},
+    },
+    std::cmp::Ordering,
+};
+
+/// A Solidly pool [^1].
+///
+/// Solidly forks can have two pools for every token pair: a "volatile" pool
+/// modelled by the constant product invariant `x · y = k` and a "stable" pool
+/// modelled by the invariant `x³y + y³x = k`, which is better suited for
+/// correlated assets. Unlike Uniswap V2, fees are configured per pool.
+///
+/// Note that there are many Solidly clones with identical behaviour from a
+/// liquidity point of view, that are therefore modelled by the same type:
+/// - Velodrome V2 (Optimism)
+/// - Aerodrome (Base)
+///
+/// [^1]: <https://github.com/velodrome-finance/contracts/blob/main/contracts/Pool.sol>
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub address: eth::Address,
+    pub router: eth::ContractAddress,
+    pub factory: eth::ContractAddress,
+    pub reserves: Reserves,
+    pub stable: bool,
+    pub fee: Fee,
+}
+
+impl Pool {
+    /// Encodes a pool swap as an interaction. Returns `Err` if the swap
+    /// parameters are invalid for the pool, specifically if the input and
+    /// output tokens don't correspond to the pool's token pair.
+    ///
+    /// Solidly routers only support exact input swaps, so the swap is encoded
+    /// to sell the full input amount while requiring at least the output
+    /// amount.
+    pub fn swap(
+        &self,
+        input: &liquidity::MaxInput,
+        output: &liquidity::ExactOutput,
+        receiver: &eth::Address,
+    ) -> Result<eth::Interaction, liquidity::InvalidSwap> {
+        if !self.reserves.has_tokens(&input.0.token, &output.0.token) {
+            return Err(liquidity::InvalidSwap);
+        }
+
+        Ok(boundary::liquidity::solidly::to_interaction(
+            self, input, output, receiver,
+        ))
+    }
+}
+
+/// The reserves of a Solidly pool along with the token decimals, which are
+/// required for computing stable pool swaps. These reserves are ordered by
+/// token address and are guaranteed to be for distinct tokens.
+#[derive(Clone, Copy, Debug)]
+pub struct Reserves(Reserve, Reserve);
+
+/// A token reserve of a Solidly pool.
+#[derive(Clone, Copy, Debug)]
+pub struct Reserve {
+    pub asset: eth::Asset,
+    pub decimals: u8,
+}
+
+impl Reserves {
+    /// Creates new Solidly token reserves, returns `Err` if the specified
+    /// token addresses are equal.
+    pub fn new(a: Reserve, b: Reserve) -> Result<Self, InvalidReserves> {
+        match a.asset.token.cmp(&b.asset.token) {
+            Ordering::Less => Ok(Self(a, b)),
+            Ordering::Equal => Err(InvalidReserves),
+            Ordering::Greater => Ok(Self(b, a)),
+        }
+    }
+
+    /// Returns `true` if the reserves correspond to the specified tokens.
+    fn has_tokens(&self, a: &eth::TokenAddress, b: &eth::TokenAddress) -> bool {
+        (&self.0.asset.token == a && &self.1.asset.token == b)
+            || (&self.1.asset.token == a && &self.0.asset.token == b)
+    }
+
+    /// Returns an iterator over the reserves.
+    pub fn iter(&self) -> impl Iterator<Item = Reserve> {
+        [self.0, self.1].into_iter()
+    }
+}
+
+/// A swap fee.
+///
+/// Internally, it is represented in basis points.
+#[derive(Clone, Copy, Debug)]
+pub struct Fee(u32);
+
+impl Fee {
+    /// Creates a new fee from the specified basis points. Returns `Err` for
+    /// invalid fee values (i.e. outside the range `[0, 1000]`).
+    pub fn new(bps: u32) -> Result<Self, InvalidFee> {
+        if !(0..=1000).contains(&bps) {
+            return Err(InvalidFee);
+        }
+        Ok(Self(bps))
+    }
+
+    /// Returns the fee in basis points.
+    pub fn bps(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid Solidly token reserves; assets cannot have the same token address")]
+pub struct InvalidReserves;
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid Solidly fee outside of 0%-10% range")]
+pub struct InvalidFee;
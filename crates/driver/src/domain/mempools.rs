@@ -22,18 +22,24 @@ impl Mempools {
         }
     }
 
-    /// Publish a settlement to the mempools.
+    /// Publish a settlement to the mempools with the specified nonce of the
+    /// solver account. The settlement is not resubmitted after the deadline
+    /// block and any pending transaction gets cancelled.
     pub async fn execute(
         &self,
         solver: &Solver,
         settlement: &Settlement,
-    ) -> Result<eth::TxId, Error> {
+        nonce: eth::U256,
+        deadline: Option<eth::BlockNo>,
+    ) -> Result<Outcome, Error> {
         let auction_id = settlement.auction_id;
         let solver_name = solver.name();
 
-        let (tx_hash, _remaining_futures) = select_ok(self.0.iter().cloned().map(|mempool| {
+        let result = select_ok(self.0.iter().cloned().map(|mempool| {
             async move {
-                let result = mempool.execute(solver, settlement.clone()).await;
+                let result = mempool
+                    .execute(solver, settlement.clone(), nonce, deadline)
+                    .await;
                 observe::mempool_executed(&mempool, settlement, &result);
                 result
             }
@@ -44,9 +50,9 @@ impl Mempools {
             ))
             .boxed()
         }))
-        .await?;
+        .await;
 
-        Ok(tx_hash)
+        Outcome::new(result.map(|(tx_hash, _remaining_futures)| tx_hash))
     }
 
     /// Defines if the mempools are configured in a way that guarantees that
@@ -77,12 +83,85 @@ pub enum RevertProtection {
     Disabled,
 }
 
+/// The outcome of publishing a settlement to the mempools.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The settlement was mined successfully.
+    Mined(eth::TxId),
+    /// The settlement was mined but reverted.
+    Reverted(eth::TxId),
+    /// The pending settlement was replaced by a cancellation transaction.
+    Cancelled(eth::TxId),
+    /// The deadline was reached without any transaction being mined.
+    Expired,
+}
+
+impl Outcome {
+    /// The outcome of a settlement which was published to the mempools. Only
+    /// failures to submit it are errors.
+    fn new(result: Result<eth::TxId, Error>) -> Result<Self, Error> {
+        match result {
+            Ok(tx_hash) => Ok(Self::Mined(tx_hash)),
+            Err(Error::Revert(tx_hash)) => Ok(Self::Reverted(tx_hash)),
+            Err(Error::Cancelled(tx_hash)) => Ok(Self::Cancelled(tx_hash)),
+            Err(Error::Expired) => Ok(Self::Expired),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The hash of the mined transaction, if any.
+    pub fn tx_hash(&self) -> Option<&eth::TxId> {
+        match self {
+            Self::Mined(tx_hash) | Self::Reverted(tx_hash) | Self::Cancelled(tx_hash) => {
+                Some(tx_hash)
+            }
+            Self::Expired => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Mined reverted transaction: {0:?}")]
     Revert(eth::TxId),
+    #[error("Mined cancellation transaction: {0:?}")]
+    Cancelled(eth::TxId),
+    #[error("Deadline reached without mining a transaction")]
+    Expired,
     #[error("Simulation started reverting during submission")]
     SimulationRevert,
     #[error("Failed to submit: {0:?}")]
     Other(#[from] anyhow::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_submission_outcomes() {
+        let tx = || eth::TxId(eth::H256([1; 32]));
+        assert!(matches!(
+            Outcome::new(Ok(tx())),
+            Ok(Outcome::Mined(hash)) if hash.0 == tx().0
+        ));
+        assert!(matches!(
+            Outcome::new(Err(Error::Revert(tx()))),
+            Ok(Outcome::Reverted(hash)) if hash.0 == tx().0
+        ));
+        assert!(matches!(
+            Outcome::new(Err(Error::Cancelled(tx()))),
+            Ok(Outcome::Cancelled(hash)) if hash.0 == tx().0
+        ));
+        assert!(matches!(
+            Outcome::new(Err(Error::Expired)),
+            Ok(Outcome::Expired)
+        ));
+        // The settlement wasn't submitted if it stopped simulating, so this
+        // fails the `/settle` request instead.
+        assert!(matches!(
+            Outcome::new(Err(Error::SimulationRevert)),
+            Err(Error::SimulationRevert)
+        ));
+    }
+}
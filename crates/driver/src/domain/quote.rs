@@ -73,19 +73,34 @@ impl Order {
         eth: &Ethereum,
         solver: &Solver,
         liquidity: &infra::liquidity::Fetcher,
+        liquidity_feed: &infra::liquidity::Feed,
         tokens: &infra::tokens::Fetcher,
     ) -> Result<Quote, Error> {
-        let liquidity = match solver.liquidity() {
-            solver::Liquidity::Fetch => {
+        let (liquidity, feed) = match solver.liquidity() {
+            solver::Liquidity::Fetch => (
                 liquidity
                     .fetch(&self.liquidity_pairs(), infra::liquidity::AtBlock::Recent)
-                    .await
+                    .await,
+                None,
+            ),
+            solver::Liquidity::Stream => {
+                let liquidity = liquidity
+                    .fetch(&self.liquidity_pairs(), infra::liquidity::AtBlock::Recent)
+                    .await;
+                let (liquidity, feed) =
+                    liquidity_feed.publish(liquidity, &infra::liquidity::AtBlock::Recent);
+                (liquidity, Some(feed))
             }
             solver::Liquidity::Skip => Default::default(),
         };
 
         let solutions = solver
-            .solve(&self.fake_auction(eth, tokens).await?, &liquidity)
+            .solve(
+                &self.fake_auction(eth, tokens).await?,
+                &liquidity,
+                feed,
+                None,
+            )
             .await?;
         Quote::new(
             eth,
@@ -115,6 +130,7 @@ impl Order {
                 uid: Default::default(),
                 receiver: None,
                 valid_to: util::Timestamp::MAX,
+                created: util::Timestamp(0),
                 buy: self.buy(),
                 sell: self.sell(),
                 side: self.side,
@@ -132,6 +148,7 @@ impl Order {
                     signer: Default::default(),
                 },
                 fee_policies: Default::default(),
+                cow_only: false,
             }],
             [
                 auction::Token {
@@ -0,0 +1,292 @@
+//! Authentication of requests from the autopilot.
+//!
+//! The autopilot signs the `/solve`, `/reveal` and `/settle` requests with its
+//! key. The signed message is the Keccak-256 hash of the endpoint path, a zero
+//! byte, the big-endian UNIX timestamp in seconds and the JSON body. Requests
+//! with a timestamp that is too far from the current time, or whose signed
+//! message was already used, are rejected to protect against replays.
+
+use {
+    crate::{domain::eth, infra::api},
+    axum::{
+        middleware::Next,
+        response::{IntoResponse, Response},
+    },
+    hyper::{Body, Request, StatusCode},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    web3::signing,
+};
+
+const TIMESTAMP_HEADER: &str = "X-Auth-Timestamp";
+const SIGNATURE_HEADER: &str = "X-Auth-Signature";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// The address of the key the autopilot signs its requests with.
+    pub autopilot: eth::H160,
+    /// How far the timestamp of a request may be from the current time.
+    pub max_age: Duration,
+}
+
+/// Verifies signed requests and remembers the messages that were signed
+/// within the maximum age.
+#[derive(Debug, Clone)]
+pub struct Authenticator(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    config: Config,
+    /// The hashes of the signed messages by their timestamp. ECDSA signatures
+    /// are malleable, so the message is remembered rather than the signature.
+    used: Mutex<HashMap<[u8; 32], u64>>,
+}
+
+/// The authentication headers of a request that is not expired.
+#[derive(Debug)]
+struct Headers {
+    timestamp: u64,
+    signature: [u8; 65],
+}
+
+impl Authenticator {
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(Inner {
+            config,
+            used: Default::default(),
+        }))
+    }
+
+    /// Parses the authentication headers and checks that they are not
+    /// expired. This happens before the body is read, so requests that can't
+    /// be valid are rejected cheaply.
+    fn headers(
+        &self,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        now: u64,
+    ) -> Result<Headers, Error> {
+        let timestamp: u64 = timestamp
+            .ok_or(Error::Missing)?
+            .parse()
+            .map_err(|_| Error::Malformed)?;
+        let signature: [u8; 65] =
+            hex::decode(signature.ok_or(Error::Missing)?.trim_start_matches("0x"))
+                .map_err(|_| Error::Malformed)?
+                .try_into()
+                .map_err(|_| Error::Malformed)?;
+        if timestamp.abs_diff(now) > self.0.config.max_age.as_secs() {
+            return Err(Error::Expired);
+        }
+        Ok(Headers {
+            timestamp,
+            signature,
+        })
+    }
+
+    /// Checks that the request was signed by the autopilot and that the
+    /// signed message was not used before.
+    fn verify(&self, path: &str, headers: &Headers, body: &[u8], now: u64) -> Result<(), Error> {
+        let mut message = Vec::with_capacity(path.len() + 9 + body.len());
+        message.extend_from_slice(path.as_bytes());
+        message.push(0);
+        message.extend_from_slice(&headers.timestamp.to_be_bytes());
+        message.extend_from_slice(body);
+        let hash = signing::keccak256(&message);
+        let recovery_id = i32::from(headers.signature[64])
+            .checked_sub(27)
+            .ok_or(Error::Malformed)?;
+        let signer = signing::recover(&hash, &headers.signature[..64], recovery_id)
+            .map_err(|_| Error::Malformed)?;
+        if signer != self.0.config.autopilot {
+            return Err(Error::InvalidSigner);
+        }
+
+        let max_age = self.0.config.max_age.as_secs();
+        let mut used = self.0.used.lock().unwrap();
+        used.retain(|_, timestamp| timestamp.abs_diff(now) <= max_age);
+        if used.insert(hash, headers.timestamp).is_some() {
+            return Err(Error::Replayed);
+        }
+        Ok(())
+    }
+}
+
+/// Middleware rejecting requests that are not signed by the autopilot.
+pub(super) async fn middleware(
+    axum::extract::State(authenticator): axum::extract::State<Authenticator>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let (parts, body) = req.into_parts();
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = parts.uri.path().trim_start_matches('/');
+    let headers =
+        match authenticator.headers(header(TIMESTAMP_HEADER), header(SIGNATURE_HEADER), now) {
+            Ok(headers) => headers,
+            Err(err) => {
+                tracing::warn!(?err, path, "rejected request");
+                return rejection(err);
+            }
+        };
+    let body =
+        match hyper::body::to_bytes(http_body::Limited::new(body, api::REQUEST_BODY_LIMIT)).await {
+            Ok(body) => body,
+            Err(err) if err.is::<http_body::LengthLimitError>() => {
+                return StatusCode::PAYLOAD_TOO_LARGE.into_response()
+            }
+            Err(_) => return rejection(Error::Malformed),
+        };
+    if let Err(err) = authenticator.verify(path, &headers, &body, now) {
+        tracing::warn!(?err, path, "rejected request");
+        return rejection(err);
+    }
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+fn rejection(err: Error) -> Response {
+    let response: (hyper::StatusCode, axum::Json<api::Error>) = err.into();
+    response.into_response()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("missing authentication headers")]
+    Missing,
+    #[error("malformed authentication headers")]
+    Malformed,
+    #[error("the request timestamp is too old or in the future")]
+    Expired,
+    #[error("the request was not signed by the autopilot")]
+    InvalidSigner,
+    #[error("the signed message was already used")]
+    Replayed,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, web3::signing::SecretKeyRef};
+
+    fn sign(key: &signing::SecretKey, path: &str, timestamp: u64, body: &[u8]) -> String {
+        let mut message = path.as_bytes().to_vec();
+        message.push(0);
+        message.extend_from_slice(&timestamp.to_be_bytes());
+        message.extend_from_slice(body);
+        let signature = SecretKeyRef::new(key)
+            .sign(&signing::keccak256(&message), None)
+            .unwrap();
+        let mut bytes = signature.r.as_bytes().to_vec();
+        bytes.extend_from_slice(signature.s.as_bytes());
+        bytes.push(signature.v as u8);
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    fn authenticate(
+        authenticator: &Authenticator,
+        path: &str,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), Error> {
+        let headers = authenticator.headers(timestamp, signature, now)?;
+        authenticator.verify(path, &headers, body, now)
+    }
+
+    #[test]
+    fn verifies_signed_requests() {
+        let key = signing::SecretKey::from_slice(&[1; 32]).unwrap();
+        let authenticator = Authenticator::new(Config {
+            autopilot: signing::Key::address(&SecretKeyRef::new(&key)),
+            max_age: Duration::from_secs(30),
+        });
+        let now = 1_700_000_000;
+        let timestamp = now.to_string();
+        let body = br#"{"solutionId":"1"}"#;
+        let signature = sign(&key, "settle", now, body);
+        let verify = |path, timestamp, signature, now| {
+            authenticate(&authenticator, path, timestamp, signature, body, now)
+        };
+
+        assert!(matches!(
+            verify("settle", None, None, now),
+            Err(Error::Missing)
+        ));
+        assert!(matches!(
+            verify("settle", Some("1"), Some("0x1234"), now),
+            Err(Error::Malformed)
+        ));
+        assert!(matches!(
+            verify("settle", Some(&timestamp), Some(&signature), now + 31),
+            Err(Error::Expired)
+        ));
+        assert!(matches!(
+            verify("reveal", Some(&timestamp), Some(&signature), now),
+            Err(Error::InvalidSigner)
+        ));
+        assert!(verify("settle", Some(&timestamp), Some(&signature), now + 1).is_ok());
+        assert!(matches!(
+            verify("settle", Some(&timestamp), Some(&signature), now + 2),
+            Err(Error::Replayed)
+        ));
+    }
+
+    #[test]
+    fn rejects_malleated_signatures() {
+        let key = signing::SecretKey::from_slice(&[1; 32]).unwrap();
+        let authenticator = Authenticator::new(Config {
+            autopilot: signing::Key::address(&SecretKeyRef::new(&key)),
+            max_age: Duration::from_secs(30),
+        });
+        let now = 1_700_000_000;
+        let timestamp = now.to_string();
+        let body = br#"{"solutionId":"1"}"#;
+        let signature = sign(&key, "settle", now, body);
+
+        // The signature (r, n - s) with the flipped recovery ID recovers the
+        // same signer for the same message.
+        let mut malleated = hex::decode(signature.trim_start_matches("0x")).unwrap();
+        let n = eth::U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        let s = n - eth::U256::from_big_endian(&malleated[32..64]);
+        s.to_big_endian(&mut malleated[32..64]);
+        malleated[64] = if malleated[64] == 27 { 28 } else { 27 };
+        let malleated = format!("0x{}", hex::encode(malleated));
+
+        assert!(authenticate(
+            &authenticator,
+            "settle",
+            Some(&timestamp),
+            Some(&signature),
+            body,
+            now
+        )
+        .is_ok());
+        assert!(matches!(
+            authenticate(
+                &authenticator,
+                "settle",
+                Some(&timestamp),
+                Some(&malleated),
+                body,
+                now
+            ),
+            Err(Error::Replayed)
+        ));
+    }
+}
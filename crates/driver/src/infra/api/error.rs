@@ -21,6 +21,9 @@ enum Kind {
     ZeroScoreCap,
     QuoteSameTokens,
     FailedToSubmit,
+    Unauthorized,
+    InvalidDebugToken,
+    BundleNotFound,
 }
 
 #[derive(Debug, Serialize)]
@@ -53,9 +56,16 @@ impl From<Kind> for (hyper::StatusCode, axum::Json<Error>) {
             }
             Kind::FailedToSubmit => "Could not submit the solution to the blockchain",
             Kind::ZeroScoreCap => "Score cap is zero",
+            Kind::Unauthorized => "The request is not signed by the autopilot",
+            Kind::InvalidDebugToken => "Missing or invalid debug token",
+            Kind::BundleNotFound => "No debug bundle is available for the solution",
         };
         (
-            hyper::StatusCode::BAD_REQUEST,
+            match value {
+                Kind::Unauthorized | Kind::InvalidDebugToken => hyper::StatusCode::UNAUTHORIZED,
+                Kind::BundleNotFound => hyper::StatusCode::NOT_FOUND,
+                _ => hyper::StatusCode::BAD_REQUEST,
+            },
             axum::Json(Error {
                 kind: value,
                 description,
@@ -83,7 +93,9 @@ impl From<competition::Error> for (hyper::StatusCode, axum::Json<Error>) {
             competition::Error::SolutionNotAvailable => Kind::SolutionNotAvailable,
             competition::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
             competition::Error::Solver(_) => Kind::SolverFailed,
-            competition::Error::SubmissionError => Kind::FailedToSubmit,
+            competition::Error::SubmissionError | competition::Error::NoAccountAvailable(_) => {
+                Kind::FailedToSubmit
+            }
         };
         error.into()
     }
@@ -111,3 +123,19 @@ impl From<api::routes::OrderError> for (hyper::StatusCode, axum::Json<Error>) {
         error.into()
     }
 }
+
+impl From<api::routes::BundleError> for (hyper::StatusCode, axum::Json<Error>) {
+    fn from(value: api::routes::BundleError) -> Self {
+        let error = match value {
+            api::routes::BundleError::InvalidToken => Kind::InvalidDebugToken,
+            api::routes::BundleError::NotFound => Kind::BundleNotFound,
+        };
+        error.into()
+    }
+}
+
+impl From<api::auth::Error> for (hyper::StatusCode, axum::Json<Error>) {
+    fn from(_: api::auth::Error) -> Self {
+        Kind::Unauthorized.into()
+    }
+}
@@ -1,6 +1,6 @@
 use {
     crate::{
-        domain::{self, Mempools},
+        domain::{self, competition::solution::Settlement, Mempools},
         infra::{
             self,
             liquidity,
@@ -10,23 +10,30 @@ use {
             Simulator,
         },
     },
+    axum::response::{IntoResponse, Response},
     error::Error,
     futures::Future,
-    std::{net::SocketAddr, sync::Arc},
-    tokio::sync::oneshot,
+    hyper::{Body, Request, StatusCode, Uri},
+    std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{Arc, Mutex, RwLock},
+    },
+    tokio::sync::{oneshot, watch},
+    tower::ServiceExt,
 };
 
+pub mod auth;
 mod error;
 mod routes;
 
 const REQUEST_BODY_LIMIT: usize = 10 * 1024 * 1024;
 
 pub struct Api {
-    pub solvers: Vec<Solver>,
-    pub liquidity: liquidity::Fetcher,
-    pub simulator: Simulator,
+    /// The solvers and the components they use. A new value is sent whenever
+    /// the driver configuration is reloaded.
+    pub components: watch::Receiver<Components>,
     pub eth: Ethereum,
-    pub mempools: Mempools,
     pub addr: SocketAddr,
     /// If this channel is specified, the bound address will be sent to it. This
     /// allows the driver to bind to 0.0.0.0:0 during testing.
@@ -38,17 +45,15 @@ impl Api {
         self,
         shutdown: impl Future<Output = ()> + Send + 'static,
     ) -> Result<(), hyper::Error> {
-        // Add middleware.
-        let mut app = axum::Router::new().layer(
-            tower::ServiceBuilder::new()
-                .layer(tower_http::limit::RequestBodyLimitLayer::new(
-                    REQUEST_BODY_LIMIT,
-                ))
-                .layer(tower_http::trace::TraceLayer::new_for_http()),
-        );
+        let mut app = axum::Router::new();
 
-        let tokens = tokens::Fetcher::new(self.eth.clone());
-        let pre_processor = domain::competition::AuctionProcessor::new(Arc::new(self.eth.clone()));
+        let shared = Shared {
+            eth: self.eth.clone(),
+            tokens: tokens::Fetcher::new(self.eth.clone()),
+            pre_processor: domain::competition::AuctionProcessor::new(Arc::new(self.eth.clone())),
+            liquidity_feed: liquidity::Feed::new(self.eth.clone()),
+            bundles: Default::default(),
+        };
 
         // Add the metrics and healthz endpoints.
         app = routes::metrics(app);
@@ -58,36 +63,31 @@ impl Api {
         // on the same driver so only one liquidity collector collects the liquidity
         // for all of them. This is important because liquidity collection is
         // computationally expensive for the Ethereum node.
-        for solver in self.solvers {
-            let name = solver.name().clone();
-            let router = axum::Router::new();
-            let router = routes::info(router);
-            let router = routes::quote(router);
-            let router = routes::solve(router);
-            let router = routes::reveal(router);
-            let router = routes::settle(router);
-            let router = router.with_state(State(Arc::new(Inner {
-                eth: self.eth.clone(),
-                solver: solver.clone(),
-                competition: domain::Competition {
-                    solver,
-                    eth: self.eth.clone(),
-                    liquidity: self.liquidity.clone(),
-                    simulator: self.simulator.clone(),
-                    mempools: self.mempools.clone(),
-                    settlement: Default::default(),
-                },
-                liquidity: self.liquidity.clone(),
-                tokens: tokens.clone(),
-                pre_processor: pre_processor.clone(),
-            })));
-            let path = format!("/{name}");
-            infra::observe::mounting_solver(&name, &path);
-            app = app
-                .nest(&path, router)
-                // axum's default body limit needs to be disabled to not have the default limit on top of our custom limit
-                .layer(axum::extract::DefaultBodyLimit::disable());
-        }
+        //
+        // The solver routes are swapped whenever the configuration is reloaded.
+        // Requests that are already being handled keep using the components
+        // they started with, so ongoing settlements are not interrupted.
+        let routers = Routers::default();
+        let mut components = self.components;
+        let mount = {
+            let routers = routers.clone();
+            move |components: &Components| routers.mount(components, &shared)
+        };
+        mount(&components.borrow_and_update());
+        tokio::spawn(async move {
+            while components.changed().await.is_ok() {
+                let update = components.borrow_and_update().clone();
+                mount(&update);
+            }
+        });
+        app = app.fallback(move |req: Request<Body>| {
+            let routers = routers.clone();
+            async move { routers.dispatch(req).await }
+        });
+
+        // Add middleware. Layers only apply to the routes that were added
+        // before them, so this has to happen last.
+        app = app.layer(tower_http::trace::TraceLayer::new_for_http());
 
         let make_svc = observe::make_service_with_task_local_storage!(app);
 
@@ -101,6 +101,138 @@ impl Api {
     }
 }
 
+/// The parts of the driver that can be replaced at runtime by reloading its
+/// configuration.
+#[derive(Clone)]
+pub struct Components {
+    pub solvers: Vec<Solver>,
+    pub liquidity: liquidity::Fetcher,
+    pub simulator: Simulator,
+    pub mempools: Mempools,
+    /// Verifies that competition requests are signed by the autopilot. All
+    /// requests are accepted if this is not set.
+    pub authenticator: Option<auth::Authenticator>,
+}
+
+/// The parts of the driver that are shared by all solvers and are kept when
+/// the configuration is reloaded.
+struct Shared {
+    eth: Ethereum,
+    tokens: tokens::Fetcher,
+    pre_processor: domain::competition::AuctionProcessor,
+    liquidity_feed: liquidity::Feed,
+    bundles: infra::debug::Bundles,
+}
+
+/// The API routes of the currently mounted solvers by name.
+#[derive(Clone, Default)]
+struct Routers(Arc<RwLock<HashMap<String, Mounted>>>);
+
+struct Mounted {
+    router: axum::Router,
+    settlement: Arc<Mutex<Option<Settlement>>>,
+}
+
+impl Routers {
+    /// Replaces the mounted solver routes with routes for the specified
+    /// components. Solvers that are still configured keep their latest
+    /// settlement so it can be settled with the new routes.
+    fn mount(&self, components: &Components, shared: &Shared) {
+        let mut mounted = self.0.write().unwrap();
+        let mut routers = HashMap::new();
+        for solver in &components.solvers {
+            let name = solver.name().clone();
+            let settlement = mounted
+                .get(&name.0)
+                .map(|mounted| mounted.settlement.clone())
+                .unwrap_or_default();
+            let router = axum::Router::new();
+            let router = routes::solve(router);
+            let router = routes::reveal(router);
+            let router = routes::settle(router);
+            // Only the competition routes are used by the autopilot, the
+            // layer does not apply to routes added after it.
+            let router = match &components.authenticator {
+                Some(authenticator) => router.route_layer(axum::middleware::from_fn_with_state(
+                    authenticator.clone(),
+                    auth::middleware,
+                )),
+                None => router,
+            };
+            let router = routes::info(router);
+            let router = routes::quote(router);
+            let router = routes::liquidity_feed(router);
+            let router = routes::debug_bundle(router);
+            let router = router
+                .with_state(State(Arc::new(Inner {
+                    eth: shared.eth.clone(),
+                    solver: solver.clone(),
+                    competition: domain::Competition {
+                        solver: solver.clone(),
+                        eth: shared.eth.clone(),
+                        liquidity: components.liquidity.clone(),
+                        liquidity_feed: shared.liquidity_feed.clone(),
+                        bundles: shared.bundles.clone(),
+                        simulator: components.simulator.clone(),
+                        mempools: components.mempools.clone(),
+                        settlement: settlement.clone(),
+                    },
+                    liquidity: components.liquidity.clone(),
+                    liquidity_feed: shared.liquidity_feed.clone(),
+                    tokens: shared.tokens.clone(),
+                    pre_processor: shared.pre_processor.clone(),
+                })))
+                // Replace axum's default body limit of the extractors with our
+                // own, the authentication middleware reads the body with the
+                // same limit.
+                .layer(axum::extract::DefaultBodyLimit::max(REQUEST_BODY_LIMIT));
+            infra::observe::mounting_solver(&name, &format!("/{name}"));
+            routers.insert(name.0, Mounted { router, settlement });
+        }
+        for name in mounted.keys().filter(|name| !routers.contains_key(*name)) {
+            infra::observe::unmounting_solver(name);
+        }
+        *mounted = routers;
+    }
+
+    /// Forwards a request to the routes of the solver named by the first
+    /// segment of the request path.
+    async fn dispatch(&self, mut req: Request<Body>) -> Response {
+        let Some((name, uri)) = split_solver_uri(req.uri()) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let router = self
+            .0
+            .read()
+            .unwrap()
+            .get(&name)
+            .map(|mounted| mounted.router.clone());
+        let Some(router) = router else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        *req.uri_mut() = uri;
+        match router.oneshot(req).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+/// Splits a request URI into the name of the solver and the URI relative to
+/// the routes of that solver.
+fn split_solver_uri(uri: &Uri) -> Option<(String, Uri)> {
+    let path = uri.path().strip_prefix('/')?;
+    let (name, path) = path.split_once('/').unwrap_or((path, ""));
+    if name.is_empty() {
+        return None;
+    }
+    let uri = match uri.query() {
+        Some(query) => format!("/{path}?{query}"),
+        None => format!("/{path}"),
+    };
+    Some((name.to_owned(), uri.parse().ok()?))
+}
+
 #[derive(Clone)]
 struct State(Arc<Inner>);
 
@@ -121,6 +253,10 @@ impl State {
         &self.0.liquidity
     }
 
+    fn liquidity_feed(&self) -> &liquidity::Feed {
+        &self.0.liquidity_feed
+    }
+
     fn tokens(&self) -> &tokens::Fetcher {
         &self.0.tokens
     }
@@ -139,6 +275,36 @@ struct Inner {
     solver: Solver,
     competition: domain::Competition,
     liquidity: liquidity::Fetcher,
+    liquidity_feed: liquidity::Feed,
     tokens: tokens::Fetcher,
     pre_processor: domain::competition::AuctionProcessor,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_solver_uri() {
+        let split = |uri: &str| {
+            split_solver_uri(&uri.parse().unwrap()).map(|(name, uri)| (name, uri.to_string()))
+        };
+        assert_eq!(
+            split("/solver"),
+            Some(("solver".to_owned(), "/".to_owned()))
+        );
+        assert_eq!(
+            split("/solver/"),
+            Some(("solver".to_owned(), "/".to_owned()))
+        );
+        assert_eq!(
+            split("/solver/solve"),
+            Some(("solver".to_owned(), "/solve".to_owned()))
+        );
+        assert_eq!(
+            split("/solver/quote?sellToken=0x01"),
+            Some(("solver".to_owned(), "/quote?sellToken=0x01".to_owned()))
+        );
+        assert_eq!(split("/"), None);
+    }
+}
@@ -0,0 +1,94 @@
+use {
+    crate::{
+        domain::competition::{auction, solution},
+        infra::{
+            api::{Error, State},
+            debug,
+        },
+    },
+    hyper::HeaderMap,
+};
+
+pub(in crate::infra::api) fn debug_bundle(router: axum::Router<State>) -> axum::Router<State> {
+    router
+        .route("/debug/:auction", axum::routing::get(auction_route))
+        .route(
+            "/debug/:auction/:solution",
+            axum::routing::get(solution_route),
+        )
+}
+
+/// Returns the debug bundle of an auction whose solver response could not be
+/// turned into solutions.
+async fn auction_route(
+    state: axum::extract::State<State>,
+    axum::extract::Path(auction): axum::extract::Path<i64>,
+    headers: HeaderMap,
+) -> Result<axum::Json<debug::Bundle>, (hyper::StatusCode, axum::Json<Error>)> {
+    bundle(&state, &headers, auction::Id(auction), None)
+}
+
+/// Returns the debug bundle of a solution.
+async fn solution_route(
+    state: axum::extract::State<State>,
+    axum::extract::Path((auction, solution)): axum::extract::Path<(i64, u64)>,
+    headers: HeaderMap,
+) -> Result<axum::Json<debug::Bundle>, (hyper::StatusCode, axum::Json<Error>)> {
+    bundle(
+        &state,
+        &headers,
+        auction::Id(auction),
+        Some(solution::Id(solution)),
+    )
+}
+
+/// The request needs to contain the debug token of the solver as a bearer
+/// token.
+fn bundle(
+    state: &State,
+    headers: &HeaderMap,
+    auction: auction::Id,
+    solution: Option<solution::Id>,
+) -> Result<axum::Json<debug::Bundle>, (hyper::StatusCode, axum::Json<Error>)> {
+    let config = state.solver().debug().ok_or(BundleError::NotFound)?;
+    let token = headers
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !token.is_some_and(|token| constant_time_eq(token.as_bytes(), config.token.as_bytes())) {
+        return Err(BundleError::InvalidToken.into());
+    }
+    let bundle = state
+        .competition()
+        .bundles
+        .get(state.solver().name(), auction, solution)
+        .ok_or(BundleError::NotFound)?;
+    Ok(axum::Json(bundle))
+}
+
+/// Compares the bytes in a time which only depends on their length, so the
+/// token can't be guessed byte by byte from response times.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("missing or invalid debug token")]
+    InvalidToken,
+    #[error("no debug bundle is available for the solution")]
+    NotFound,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_tokens() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+    }
+}
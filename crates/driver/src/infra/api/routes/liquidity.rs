@@ -0,0 +1,34 @@
+use {
+    crate::infra::{
+        api::State,
+        liquidity::feed::{self, Update},
+        solver::dto,
+    },
+    axum::response::sse::{Event, KeepAlive, Sse},
+    futures::{Stream, StreamExt},
+};
+
+pub(in crate::infra::api) fn liquidity_feed(app: axum::Router<State>) -> axum::Router<State> {
+    app.route("/liquidity", axum::routing::get(route))
+}
+
+/// Streams the liquidity feed as server-sent events. Subscribers first receive
+/// a `snapshot` event with all liquidity, followed by an `update` event for
+/// every update of the feed. Subscribers which fall behind receive a new
+/// snapshot.
+async fn route(
+    state: axum::extract::State<State>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = state.liquidity_feed().subscribe().map(|event| match event {
+        feed::Event::Snapshot(snapshot) => event("snapshot", &snapshot),
+        feed::Event::Update(update) => event("update", &update),
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+fn event(name: &str, update: &Update) -> Result<Event, axum::Error> {
+    Event::default()
+        .event(name)
+        .id(update.update.to_string())
+        .json_data(dto::LiquidityUpdate::new(update))
+}
@@ -1,5 +1,7 @@
+mod debug;
 mod healthz;
 mod info;
+mod liquidity;
 mod metrics;
 mod quote;
 mod reveal;
@@ -7,8 +9,10 @@ mod settle;
 mod solve;
 
 pub(super) use {
+    debug::{debug_bundle, BundleError},
     healthz::healthz,
     info::info,
+    liquidity::liquidity_feed,
     metrics::metrics,
     quote::{quote, OrderError},
     reveal::reveal,
@@ -29,6 +29,7 @@ async fn route(
                 state.eth(),
                 state.solver(),
                 state.liquidity(),
+                state.liquidity_feed(),
                 state.tokens(),
             )
             .await;
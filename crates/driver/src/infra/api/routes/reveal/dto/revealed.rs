@@ -1,5 +1,8 @@
 use {
-    crate::{domain::competition, util::serialize},
+    crate::{
+        domain::{competition, competition::order, eth},
+        util::serialize,
+    },
     serde::Serialize,
     serde_with::serde_as,
 };
@@ -11,6 +14,14 @@ impl Revealed {
                 internalized: reveal.internalized_calldata.into(),
                 uninternalized: reveal.uninternalized_calldata.into(),
             },
+            jit_orders: reveal
+                .jit_orders
+                .into_iter()
+                .map(|order| JitOrder {
+                    uid: order.uid.into(),
+                    owner: order.owner.into(),
+                })
+                .collect(),
         }
     }
 }
@@ -20,6 +31,8 @@ impl Revealed {
 #[serde(rename_all = "camelCase")]
 pub struct Revealed {
     calldata: Calldata,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    jit_orders: Vec<JitOrder>,
 }
 
 #[serde_as]
@@ -31,3 +44,12 @@ struct Calldata {
     #[serde_as(as = "serialize::Hex")]
     uninternalized: Vec<u8>,
 }
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JitOrder {
+    #[serde_as(as = "serialize::Hex")]
+    uid: [u8; order::UID_LEN],
+    owner: eth::H160,
+}
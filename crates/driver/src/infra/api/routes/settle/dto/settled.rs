@@ -1,5 +1,8 @@
 use {
-    crate::{domain::competition, util::serialize},
+    crate::{
+        domain::{competition, mempools},
+        util::serialize,
+    },
     serde::Serialize,
     serde_with::serde_as,
 };
@@ -11,7 +14,13 @@ impl Settled {
                 internalized: settled.internalized_calldata.into(),
                 uninternalized: settled.uninternalized_calldata.into(),
             },
-            tx_hash: settled.tx_hash.0,
+            tx_hash: settled.outcome.tx_hash().map(|tx_hash| tx_hash.0),
+            outcome: match settled.outcome {
+                mempools::Outcome::Mined(_) => Outcome::Mined,
+                mempools::Outcome::Reverted(_) => Outcome::Reverted,
+                mempools::Outcome::Cancelled(_) => Outcome::Cancelled,
+                mempools::Outcome::Expired => Outcome::Expired,
+            },
         }
     }
 }
@@ -21,7 +30,8 @@ impl Settled {
 #[serde(rename_all = "camelCase")]
 pub struct Settled {
     calldata: CalldataInner,
-    tx_hash: primitive_types::H256,
+    tx_hash: Option<primitive_types::H256>,
+    outcome: Outcome,
 }
 
 #[serde_as]
@@ -33,3 +43,12 @@ struct CalldataInner {
     #[serde_as(as = "serialize::Hex")]
     uninternalized: Vec<u8>,
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Outcome {
+    Mined,
+    Reverted,
+    Cancelled,
+    Expired,
+}
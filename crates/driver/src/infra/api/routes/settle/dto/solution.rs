@@ -1,4 +1,11 @@
-use {serde::Deserialize, serde_with::serde_as};
+use {crate::domain::eth, serde::Deserialize, serde_with::serde_as};
+
+impl Solution {
+    /// The last block in which the settlement may be mined.
+    pub fn deadline(&self) -> Option<eth::BlockNo> {
+        self.submission_deadline_latest_block.map(Into::into)
+    }
+}
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
@@ -8,4 +15,8 @@ pub struct Solution {
     /// Unique ID of the solution (per driver competition), to settle.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     solution_id: u64,
+    /// The last block in which the settlement may be mined. The driver stops
+    /// resubmitting the settlement once this block is reached.
+    #[serde(default)]
+    submission_deadline_latest_block: Option<u64>,
 }
@@ -34,6 +34,7 @@ impl Auction {
                     uid: order.uid.into(),
                     receiver: order.receiver.map(Into::into),
                     valid_to: order.valid_to.into(),
+                    created: order.created.into(),
                     buy: eth::Asset {
                         amount: order.buy_amount.into(),
                         token: order.buy_token.into(),
@@ -131,6 +132,7 @@ impl Auction {
                             }
                         })
                         .collect(),
+                    cow_only: order.cow_only,
                 })
                 .collect(),
             self.tokens.into_iter().map(|token| {
@@ -231,6 +233,8 @@ struct Order {
     #[serde_as(as = "serialize::U256")]
     user_fee: eth::U256,
     valid_to: u32,
+    #[serde(default)]
+    created: u32,
     kind: Kind,
     receiver: Option<eth::H160>,
     owner: eth::H160,
@@ -251,6 +255,8 @@ struct Order {
     #[serde_as(as = "serialize::Hex")]
     signature: Vec<u8>,
     fee_policies: Vec<FeePolicy>,
+    #[serde(default)]
+    cow_only: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,7 +1,6 @@
 use {
     crate::{
         domain::{competition, competition::order, eth},
-        infra::Solver,
         util::serialize,
     },
     serde::Serialize,
@@ -10,10 +9,10 @@ use {
 };
 
 impl Solved {
-    pub fn new(solved: Option<competition::Solved>, solver: &Solver) -> Self {
+    pub fn new(solved: Option<competition::Solved>) -> Self {
         let solutions = solved
             .into_iter()
-            .map(|solved| Solution::new(0, solved, solver))
+            .map(|solved| Solution::new(0, solved))
             .collect();
         Self { solutions }
     }
@@ -27,11 +26,11 @@ pub struct Solved {
 }
 
 impl Solution {
-    pub fn new(solution_id: u64, solved: competition::Solved, solver: &Solver) -> Self {
+    pub fn new(solution_id: u64, solved: competition::Solved) -> Self {
         Self {
             solution_id,
             score: solved.score.0.get(),
-            submission_address: solver.address().into(),
+            submission_address: solved.solver.into(),
             orders: solved
                 .trades
                 .into_iter()
@@ -31,11 +31,14 @@ async fn route(
                 observe::invalid_dto(err, "auction");
             })?;
         tracing::debug!(elapsed = ?start.elapsed(), "auction task execution time");
-        let auction = state.pre_processor().prioritize(auction).await;
+        let auction = state
+            .pre_processor()
+            .prioritize(auction, state.solver().pipeline())
+            .await;
         let competition = state.competition();
         let result = competition.solve(&auction).await;
         observe::solved(state.solver().name(), &result);
-        Ok(axum::Json(dto::Solved::new(result?, &competition.solver)))
+        Ok(axum::Json(dto::Solved::new(result?)))
     };
 
     handle_request
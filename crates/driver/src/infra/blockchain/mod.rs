@@ -115,6 +115,11 @@ impl Ethereum {
 
     /// Returns a type that monitors the block chain to inform about the current
     /// block.
+    /// Returns a reference to the underlying web3 client.
+    pub fn web3(&self) -> &DynWeb3 {
+        &self.web3
+    }
+
     pub fn current_block(&self) -> &CurrentBlockStream {
         &self.current_block
     }
@@ -181,6 +186,15 @@ impl Ethereum {
             .map_err(Into::into)
     }
 
+    /// Returns the nonce of the next transaction of the specified account.
+    pub async fn nonce(&self, address: eth::Address) -> Result<eth::U256, Error> {
+        self.web3
+            .eth()
+            .transaction_count(address.into(), None)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Returns a [`token::Erc20`] for the specified address.
     pub fn erc20(&self, address: eth::TokenAddress) -> token::Erc20 {
         token::Erc20::new(self, address)
@@ -1,6 +1,6 @@
 use {
     reqwest::Url,
-    std::{net::SocketAddr, path::PathBuf},
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 #[derive(Debug, clap::Parser)]
@@ -26,4 +26,12 @@ pub struct Args {
     /// https://github.com/cowprotocol/services/blob/main/crates/driver/example.toml.
     #[clap(long, env)]
     pub config: PathBuf,
+
+    /// How often to check the configuration file for changes. When it
+    /// changes, the solvers, liquidity sources, simulator and mempools are
+    /// reloaded without restarting the driver. Contract addresses and the gas
+    /// price estimator are only configured at startup. Reloading is disabled
+    /// if this is not set.
+    #[clap(long, env, value_parser = humantime::parse_duration)]
+    pub config_reload_interval: Option<Duration>,
 }
@@ -1,12 +1,12 @@
 use {
     crate::{
-        domain::eth,
-        infra::{self, blockchain, config::file, liquidity, mempool, simulator, solver},
+        domain::{competition::auction, eth},
+        infra::{self, api, blockchain, config::file, liquidity, mempool, simulator, solver},
     },
-    futures::future::join_all,
+    futures::future::try_join_all,
     lazy_static::lazy_static,
     reqwest::Url,
-    std::path::Path,
+    std::path::{Path, PathBuf},
     tokio::fs,
 };
 
@@ -19,64 +19,74 @@ lazy_static! {
 /// Load the driver configuration from a TOML file for the specifed Ethereum
 /// network.
 ///
-/// # Panics
-///
-/// This method panics if the config is invalid or on I/O errors.
-pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
+/// This method returns an error if the config is invalid or on I/O errors, so
+/// that it can be used for reloading the configuration of a running driver.
+pub async fn load(network: &blockchain::Network, path: &Path) -> Result<infra::Config, Error> {
     let data = fs::read_to_string(path)
         .await
-        .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
+        .map_err(|err| Error::Io(path.to_owned(), err))?;
 
-    let config: file::Config = toml::de::from_str(&data).unwrap_or_else(|err| {
+    let config: file::Config = toml::de::from_str(&data).map_err(|err| {
         if std::env::var("TOML_TRACE_ERROR").is_ok_and(|v| v == "1") {
-            panic!("failed to parse TOML config at {path:?}: {err:#?}")
+            Error::Parse(path.to_owned(), format!("{err:#?}"))
         } else {
-            panic!(
-                "failed to parse TOML config at: {path:?}. Set TOML_TRACE_ERROR=1 to print \
-                 parsing error but this may leak secrets."
+            Error::Parse(
+                path.to_owned(),
+                "set TOML_TRACE_ERROR=1 to print parsing error but this may leak secrets"
+                    .to_owned(),
             )
         }
-    });
+    })?;
 
-    assert_eq!(
-        config.chain_id.map(eth::ChainId).unwrap_or(network.chain),
-        network.chain,
-        "The configured chain ID does not match connected Ethereum node"
-    );
+    if config.chain_id.map(eth::ChainId).unwrap_or(network.chain) != network.chain {
+        return Err(Error::ChainId);
+    }
     let graph_api_base_url = config
         .liquidity
         .graph_api_base_url
         .unwrap_or(DEFAULT_GRAPH_API_BASE_URL.clone());
-    infra::Config {
-        solvers: join_all(config.solvers.into_iter().map(|config| async move {
-            let account = match config.account {
-                file::Account::PrivateKey(private_key) => ethcontract::Account::Offline(
-                    ethcontract::PrivateKey::from_raw(private_key.0).unwrap(),
-                    None,
-                ),
-                file::Account::Kms(key_id) => {
-                    let config = ethcontract::aws_config::load_from_env().await;
-                    let account =
-                        ethcontract::transaction::kms::Account::new((&config).into(), &key_id.0)
-                            .await
-                            .unwrap_or_else(|_| panic!("Unable to load KMS account {:?}", key_id));
-                    ethcontract::Account::Kms(account, None)
-                }
-                file::Account::Address(address) => ethcontract::Account::Local(address, None),
-            };
-            solver::Config {
+    Ok(infra::Config {
+        solvers: try_join_all(config.solvers.into_iter().map(|config| async move {
+            let accounts = try_join_all(
+                std::iter::once(config.account)
+                    .chain(config.additional_accounts)
+                    .map(account),
+            )
+            .await?;
+            Ok::<_, Error>(solver::Config {
                 endpoint: config.endpoint,
                 name: config.name.into(),
                 slippage: solver::Slippage {
                     relative: config.slippage.relative,
                     absolute: config.slippage.absolute.map(eth::Ether),
                 },
-                liquidity: if config.skip_liquidity {
-                    solver::Liquidity::Skip
-                } else {
-                    solver::Liquidity::Fetch
+                liquidity: match (config.skip_liquidity, config.stream_liquidity) {
+                    (false, false) => solver::Liquidity::Fetch,
+                    (true, false) => solver::Liquidity::Skip,
+                    (false, true) => solver::Liquidity::Stream,
+                    (true, true) => return Err(Error::Liquidity),
                 },
-                account,
+                pipeline: auction::Pipeline::new(
+                    config
+                        .order_pipeline
+                        .into_iter()
+                        .map(|stage| match stage {
+                            file::OrderStage::Price => auction::Stage::Price,
+                            file::OrderStage::Age => auction::Stage::Age,
+                            file::OrderStage::Surplus => auction::Stage::Surplus,
+                            file::OrderStage::Balance => auction::Stage::Balance,
+                            file::OrderStage::Liquidity => auction::Stage::Liquidity,
+                            file::OrderStage::MaxOrdersPerOwner { count } => {
+                                auction::Stage::MaxOrdersPerOwner(count)
+                            }
+                            file::OrderStage::MaxOrders { count } => {
+                                auction::Stage::MaxOrders(count)
+                            }
+                        })
+                        .collect(),
+                ),
+                accounts,
+                account_balance_threshold: config.account_balance_threshold.map(eth::Ether),
                 timeouts: solver::Timeouts {
                     http_delay: chrono::Duration::from_std(config.timeouts.http_time_buffer)
                         .unwrap(),
@@ -84,11 +94,15 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                         .timeouts
                         .solving_share_of_deadline
                         .try_into()
-                        .unwrap(),
+                        .map_err(|_| Error::SolvingShareOfDeadline)?,
                 },
-            }
+                debug: config.debug_bundles.map(|config| infra::debug::Config {
+                    token: config.token,
+                    ttl: config.ttl,
+                }),
+            })
         }))
-        .await,
+        .await?,
         liquidity: liquidity::Config {
             base_tokens: config
                 .liquidity
@@ -102,144 +116,206 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                 .uniswap_v2
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::UniswapV2Config::Preset { preset } => match preset {
-                        file::UniswapV2Preset::UniswapV2 => {
-                            liquidity::config::UniswapV2::uniswap_v2(&network.id)
-                        }
-                        file::UniswapV2Preset::SushiSwap => {
-                            liquidity::config::UniswapV2::sushi_swap(&network.id)
-                        }
-                        file::UniswapV2Preset::Honeyswap => {
-                            liquidity::config::UniswapV2::honeyswap(&network.id)
-                        }
-                        file::UniswapV2Preset::Baoswap => {
-                            liquidity::config::UniswapV2::baoswap(&network.id)
-                        }
-                        file::UniswapV2Preset::PancakeSwap => {
-                            liquidity::config::UniswapV2::pancake_swap(&network.id)
-                        }
-                        file::UniswapV2Preset::TestnetUniswapV2 => {
-                            liquidity::config::UniswapV2::testnet_uniswapv2(&network.id)
+                .map(|config| {
+                    Ok::<_, Error>(match config {
+                        file::UniswapV2Config::Preset { preset } => match preset {
+                            file::UniswapV2Preset::UniswapV2 => {
+                                liquidity::config::UniswapV2::uniswap_v2(&network.id)
+                            }
+                            file::UniswapV2Preset::SushiSwap => {
+                                liquidity::config::UniswapV2::sushi_swap(&network.id)
+                            }
+                            file::UniswapV2Preset::Honeyswap => {
+                                liquidity::config::UniswapV2::honeyswap(&network.id)
+                            }
+                            file::UniswapV2Preset::Baoswap => {
+                                liquidity::config::UniswapV2::baoswap(&network.id)
+                            }
+                            file::UniswapV2Preset::PancakeSwap => {
+                                liquidity::config::UniswapV2::pancake_swap(&network.id)
+                            }
+                            file::UniswapV2Preset::TestnetUniswapV2 => {
+                                liquidity::config::UniswapV2::testnet_uniswapv2(&network.id)
+                            }
                         }
-                    }
-                    .expect("no Uniswap V2 preset for current network"),
-                    file::UniswapV2Config::Manual {
-                        router,
-                        pool_code,
-                        missing_pool_cache_time,
-                    } => liquidity::config::UniswapV2 {
-                        router: router.into(),
-                        pool_code: pool_code.into(),
-                        missing_pool_cache_time,
-                    },
+                        .ok_or(Error::Preset("Uniswap V2"))?,
+                        file::UniswapV2Config::Manual {
+                            router,
+                            pool_code,
+                            missing_pool_cache_time,
+                        } => liquidity::config::UniswapV2 {
+                            router: router.into(),
+                            pool_code: pool_code.into(),
+                            missing_pool_cache_time,
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, _>>()?,
             swapr: config
                 .liquidity
                 .swapr
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::SwaprConfig::Preset { preset } => match preset {
-                        file::SwaprPreset::Swapr => liquidity::config::Swapr::swapr(&network.id),
-                    }
-                    .expect("no Swapr preset for current network"),
-                    file::SwaprConfig::Manual {
-                        router,
-                        pool_code,
-                        missing_pool_cache_time,
-                    } => liquidity::config::Swapr {
-                        router: router.into(),
-                        pool_code: pool_code.into(),
-                        missing_pool_cache_time,
-                    },
+                .map(|config| {
+                    Ok::<_, Error>(match config {
+                        file::SwaprConfig::Preset { preset } => match preset {
+                            file::SwaprPreset::Swapr => {
+                                liquidity::config::Swapr::swapr(&network.id)
+                            }
+                        }
+                        .ok_or(Error::Preset("Swapr"))?,
+                        file::SwaprConfig::Manual {
+                            router,
+                            pool_code,
+                            missing_pool_cache_time,
+                        } => liquidity::config::Swapr {
+                            router: router.into(),
+                            pool_code: pool_code.into(),
+                            missing_pool_cache_time,
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, _>>()?,
             uniswap_v3: config
                 .liquidity
                 .uniswap_v3
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::UniswapV3Config::Preset {
-                        preset,
-                        max_pools_to_initialize,
-                    } => liquidity::config::UniswapV3 {
-                        max_pools_to_initialize,
-                        ..match preset {
-                            file::UniswapV3Preset::UniswapV3 => {
-                                liquidity::config::UniswapV3::uniswap_v3(
-                                    &graph_api_base_url,
-                                    &network.id,
-                                )
+                .map(|config| {
+                    Ok::<_, Error>(match config {
+                        file::UniswapV3Config::Preset {
+                            preset,
+                            max_pools_to_initialize,
+                        } => liquidity::config::UniswapV3 {
+                            max_pools_to_initialize,
+                            ..match preset {
+                                file::UniswapV3Preset::UniswapV3 => {
+                                    liquidity::config::UniswapV3::uniswap_v3(
+                                        &graph_api_base_url,
+                                        &network.id,
+                                    )
+                                }
+                                file::UniswapV3Preset::PancakeSwapV3 => {
+                                    liquidity::config::UniswapV3::pancake_swap_v3(&network.id)
+                                }
+                                file::UniswapV3Preset::SushiSwapV3 => {
+                                    liquidity::config::UniswapV3::sushi_swap_v3(&network.id)
+                                }
                             }
-                        }
-                        .expect("no Uniswap V3 preset for current network")
-                    },
-                    file::UniswapV3Config::Manual {
-                        router,
-                        max_pools_to_initialize,
-                    } => liquidity::config::UniswapV3 {
-                        router: router.into(),
-                        max_pools_to_initialize,
-                        graph_api_base_url: graph_api_base_url.clone(),
-                    },
+                            .ok_or(Error::Preset("Uniswap V3"))?
+                        },
+                        file::UniswapV3Config::Manual {
+                            router,
+                            max_pools_to_initialize,
+                            factory,
+                        } => liquidity::config::UniswapV3 {
+                            router: router.into(),
+                            max_pools_to_initialize,
+                            pool_discovery: match factory {
+                                Some(factory) => liquidity::config::UniswapV3PoolDiscovery::Factory(
+                                    liquidity::config::UniswapV3Factory {
+                                        address: factory.address.into(),
+                                        deployer: factory
+                                            .pool_deployer
+                                            .unwrap_or(factory.address)
+                                            .into(),
+                                        pool_code: factory.pool_code.into(),
+                                        fee_tiers: factory.fee_tiers,
+                                        swap_event: match factory.swap_event {
+                                            file::UniswapV3SwapEvent::UniswapV3 => {
+                                                liquidity::config::UniswapV3SwapEvent::UniswapV3
+                                            }
+                                            file::UniswapV3SwapEvent::PancakeSwapV3 => {
+                                                liquidity::config::UniswapV3SwapEvent::PancakeSwapV3
+                                            }
+                                        },
+                                    },
+                                ),
+                                None => liquidity::config::UniswapV3PoolDiscovery::Subgraph(
+                                    graph_api_base_url.clone(),
+                                ),
+                            },
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, _>>()?,
             balancer_v2: config
                 .liquidity
                 .balancer_v2
                 .iter()
                 .cloned()
-                .map(|config| match config {
-                    file::BalancerV2Config::Preset {
-                        preset,
-                        pool_deny_list,
-                    } => liquidity::config::BalancerV2 {
-                        pool_deny_list: pool_deny_list.clone(),
-                        ..match preset {
-                            file::BalancerV2Preset::BalancerV2 => {
-                                liquidity::config::BalancerV2::balancer_v2(
-                                    &graph_api_base_url,
-                                    &network.id,
-                                )
+                .map(|config| {
+                    Ok::<_, Error>(match config {
+                        file::BalancerV2Config::Preset {
+                            preset,
+                            pool_deny_list,
+                        } => liquidity::config::BalancerV2 {
+                            pool_deny_list: pool_deny_list.clone(),
+                            ..match preset {
+                                file::BalancerV2Preset::BalancerV2 => {
+                                    liquidity::config::BalancerV2::balancer_v2(
+                                        &graph_api_base_url,
+                                        &network.id,
+                                    )
+                                }
+                            }
+                            .ok_or(Error::Preset("Balancer V2"))?
+                        },
+                        file::BalancerV2Config::Manual {
+                            vault,
+                            weighted,
+                            weighted_v3plus,
+                            stable,
+                            liquidity_bootstrapping,
+                            composable_stable,
+                            pool_deny_list,
+                        } => liquidity::config::BalancerV2 {
+                            vault: vault.into(),
+                            weighted: weighted
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            weighted_v3plus: weighted_v3plus
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            stable: stable.into_iter().map(eth::ContractAddress::from).collect(),
+                            liquidity_bootstrapping: liquidity_bootstrapping
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            composable_stable: composable_stable
+                                .into_iter()
+                                .map(eth::ContractAddress::from)
+                                .collect(),
+                            pool_deny_list: pool_deny_list.clone(),
+                            graph_api_base_url: graph_api_base_url.clone(),
+                        },
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            solidly: config
+                .liquidity
+                .solidly
+                .iter()
+                .cloned()
+                .map(|config| {
+                    Ok::<_, Error>(match config {
+                        file::SolidlyConfig::Preset { preset } => match preset {
+                            file::SolidlyPreset::VelodromeV2 => {
+                                liquidity::config::Solidly::velodrome_v2(&network.id)
+                            }
+                            file::SolidlyPreset::Aerodrome => {
+                                liquidity::config::Solidly::aerodrome(&network.id)
                             }
                         }
-                        .expect("no Balancer V2 preset for current network")
-                    },
-                    file::BalancerV2Config::Manual {
-                        vault,
-                        weighted,
-                        weighted_v3plus,
-                        stable,
-                        liquidity_bootstrapping,
-                        composable_stable,
-                        pool_deny_list,
-                    } => liquidity::config::BalancerV2 {
-                        vault: vault.into(),
-                        weighted: weighted
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        weighted_v3plus: weighted_v3plus
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        stable: stable.into_iter().map(eth::ContractAddress::from).collect(),
-                        liquidity_bootstrapping: liquidity_bootstrapping
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        composable_stable: composable_stable
-                            .into_iter()
-                            .map(eth::ContractAddress::from)
-                            .collect(),
-                        pool_deny_list: pool_deny_list.clone(),
-                        graph_api_base_url: graph_api_base_url.clone(),
-                    },
+                        .ok_or(Error::Preset("Solidly"))?,
+                        file::SolidlyConfig::Manual { router } => liquidity::config::Solidly {
+                            router: router.into(),
+                        },
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, _>>()?,
             zeroex: config
                 .liquidity
                 .zeroex
@@ -289,8 +365,8 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                 },
             })
             .collect(),
-        simulator: match (config.tenderly, config.enso) {
-            (Some(config), None) => {
+        simulator: match (config.tenderly, config.enso, config.fork_simulation) {
+            (Some(config), None, false) => {
                 Some(simulator::Config::Tenderly(simulator::tenderly::Config {
                     url: config.url,
                     api_key: config.api_key,
@@ -300,11 +376,12 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
                     save_if_fails: config.save_if_fails,
                 }))
             }
-            (None, Some(config)) => Some(simulator::Config::Enso(simulator::enso::Config {
+            (None, Some(config), false) => Some(simulator::Config::Enso(simulator::enso::Config {
                 url: config.url,
             })),
-            (None, None) => None,
-            (Some(_), Some(_)) => panic!("Cannot configure both Tenderly and Enso"),
+            (None, None, true) => Some(simulator::Config::Fork),
+            (None, None, false) => None,
+            _ => return Err(Error::Simulators),
         },
         contracts: blockchain::contracts::Addresses {
             settlement: config.contracts.gp_v2_settlement.map(Into::into),
@@ -312,5 +389,47 @@ pub async fn load(network: &blockchain::Network, path: &Path) -> infra::Config {
         },
         disable_access_list_simulation: config.disable_access_list_simulation,
         disable_gas_simulation: config.disable_gas_simulation.map(Into::into),
-    }
+        authentication: config.authentication.map(|config| api::auth::Config {
+            autopilot: config.autopilot,
+            max_age: config.max_age,
+        }),
+    })
+}
+
+/// Loads an account for signing settlements.
+async fn account(account: file::Account) -> Result<ethcontract::Account, Error> {
+    Ok(match account {
+        file::Account::PrivateKey(private_key) => ethcontract::Account::Offline(
+            ethcontract::PrivateKey::from_raw(private_key.0).unwrap(),
+            None,
+        ),
+        file::Account::Kms(key_id) => {
+            let config = ethcontract::aws_config::load_from_env().await;
+            let account = ethcontract::transaction::kms::Account::new((&config).into(), &key_id.0)
+                .await
+                .map_err(|_| Error::Kms(key_id.0.clone()))?;
+            ethcontract::Account::Kms(account, None)
+        }
+        file::Account::Address(address) => ethcontract::Account::Local(address, None),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error while reading {0:?}: {1:?}")]
+    Io(PathBuf, std::io::Error),
+    #[error("failed to parse TOML config at {0:?}: {1}")]
+    Parse(PathBuf, String),
+    #[error("the configured chain ID does not match connected Ethereum node")]
+    ChainId,
+    #[error("unable to load KMS account {0:?}")]
+    Kms(String),
+    #[error("invalid solving share of deadline")]
+    SolvingShareOfDeadline,
+    #[error("no {0} preset for current network")]
+    Preset(&'static str),
+    #[error("cannot configure more than one of Tenderly, Enso and fork simulation")]
+    Simulators,
+    #[error("cannot both skip and stream liquidity")]
+    Liquidity,
 }
@@ -1,4 +1,4 @@
-pub use load::load;
+pub use load::{load, Error};
 use {
     crate::{domain::eth, util::serialize},
     reqwest::Url,
@@ -45,11 +45,21 @@ struct Config {
     /// Use Enso for transaction simulation.
     enso: Option<EnsoConfig>,
 
+    /// Simulate transactions with an embedded EVM on a local fork of the
+    /// chain, which lazily fetches state from the Ethereum node. Reverting
+    /// simulations include a call trace with decoded revert reasons.
+    #[serde(default)]
+    fork_simulation: bool,
+
     #[serde(rename = "solver")]
     solvers: Vec<SolverConfig>,
 
     #[serde(default)]
     liquidity: LiquidityConfig,
+
+    /// Only accept `/solve`, `/reveal` and `/settle` requests that are signed
+    /// by the autopilot.
+    authentication: Option<AuthenticationConfig>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -167,9 +177,35 @@ struct SolverConfig {
     #[serde(default)]
     skip_liquidity: bool,
 
+    /// Whether to stream liquidity to this solver through the `/liquidity`
+    /// endpoint instead of including it in every auction.
+    #[serde(default)]
+    stream_liquidity: bool,
+
+    /// The stages which prioritize and filter the orders of auctions before
+    /// they are sent to this solver. By default, orders are sorted by price
+    /// achievability and filtered by the balances of their owners.
+    #[serde(default = "default_order_pipeline")]
+    order_pipeline: Vec<OrderStage>,
+
+    /// Collect debug bundles for the solutions of this solver, which can be
+    /// retrieved from the `/debug/{auction}/{solution}` endpoint.
+    debug_bundles: Option<DebugBundlesConfig>,
+
     /// The account which should be used to sign settlements for this solver.
     account: Account,
 
+    /// Additional accounts which can sign settlements for this solver. Each
+    /// settlement is signed by an account without a pending settlement, so
+    /// that the solver can have multiple pending settlements at a time.
+    #[serde(default)]
+    additional_accounts: Vec<Account>,
+
+    /// Warn if the Ether balance of any of the solver accounts falls below
+    /// this threshold, denominated in wei.
+    #[serde_as(as = "Option<serialize::U256>")]
+    account_balance_threshold: Option<eth::U256>,
+
     /// Timeout configuration for the solver.
     #[serde(default, flatten)]
     timeouts: Timeouts,
@@ -190,6 +226,45 @@ enum Account {
     Address(eth::H160),
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct DebugBundlesConfig {
+    /// The bearer token required for retrieving debug bundles.
+    token: String,
+
+    /// How long debug bundles are kept.
+    #[serde(with = "humantime_serde", default = "default_debug_bundles_ttl")]
+    ttl: Duration,
+}
+
+fn default_debug_bundles_ttl() -> Duration {
+    Duration::from_secs(10 * 60)
+}
+
+fn default_order_pipeline() -> Vec<OrderStage> {
+    vec![OrderStage::Price, OrderStage::Balance]
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "stage", rename_all = "kebab-case", deny_unknown_fields)]
+enum OrderStage {
+    /// Sort orders by their price achievability, market orders first.
+    Price,
+    /// Sort orders by their creation time, oldest first.
+    Age,
+    /// Sort orders by the surplus they can generate, largest first.
+    Surplus,
+    /// Allocate the balances of the owners to their orders in the current
+    /// order, removing orders which can't be funded.
+    Balance,
+    /// Remove orders with a token that has no reference price.
+    Liquidity,
+    /// Keep at most `count` orders per owner.
+    MaxOrdersPerOwner { count: usize },
+    /// Keep at most `count` orders.
+    MaxOrders { count: usize },
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
@@ -232,6 +307,22 @@ struct ContractsConfig {
     weth: Option<eth::H160>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct AuthenticationConfig {
+    /// The address of the key the autopilot signs its requests with.
+    autopilot: eth::H160,
+
+    /// How far the timestamp of a signed request may be from the current
+    /// time. Signatures can't be reused within this time.
+    #[serde(with = "humantime_serde", default = "default_authentication_max_age")]
+    max_age: Duration,
+}
+
+fn default_authentication_max_age() -> Duration {
+    Duration::from_secs(30)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct TenderlyConfig {
@@ -286,6 +377,10 @@ struct LiquidityConfig {
     #[serde(default)]
     balancer_v2: Vec<BalancerV2Config>,
 
+    /// Liquidity provided by a Solidly compatible contract.
+    #[serde(default)]
+    solidly: Vec<SolidlyConfig>,
+
     /// Liquidity provided by 0x API.
     #[serde(default)]
     zeroex: Option<ZeroExConfig>,
@@ -355,6 +450,26 @@ enum SwaprPreset {
     Swapr,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum SolidlyConfig {
+    #[serde(rename_all = "kebab-case")]
+    Preset { preset: SolidlyPreset },
+
+    #[serde(rename_all = "kebab-case")]
+    Manual {
+        /// The address of the Solidly compatible router contract.
+        router: eth::H160,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+enum SolidlyPreset {
+    VelodromeV2,
+    Aerodrome,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged, deny_unknown_fields)]
 enum UniswapV3Config {
@@ -372,16 +487,55 @@ enum UniswapV3Config {
         /// Addresses of Uniswap V3 compatible router contracts.
         router: eth::H160,
 
-        /// How many pools to initialize during start up.
+        /// How many pools to initialize during start up. When pools are
+        /// discovered from a factory, this is the maximum number of pools
+        /// initialized at a time.
         #[serde(default = "uniswap_v3::default_max_pools_to_initialize")]
         max_pools_to_initialize: usize,
+
+        /// The Uniswap V3 compatible factory to discover pools from on-chain.
+        /// When not specified, pools are fetched from the Uniswap V3 subgraph.
+        #[serde(default)]
+        factory: Option<UniswapV3FactoryConfig>,
     },
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct UniswapV3FactoryConfig {
+    /// The address of the factory contract.
+    address: eth::H160,
+
+    /// The address of the contract deploying the pools, if it is not the
+    /// factory itself (e.g. the PancakeSwap V3 pool deployer).
+    #[serde(default)]
+    pool_deployer: Option<eth::H160>,
+
+    /// The digest of the pool initialization code.
+    pool_code: eth::H256,
+
+    /// The supported fee tiers in hundredths of a basis point.
+    fee_tiers: Vec<u32>,
+
+    /// The `Swap` event emitted by the pools, which differs between forks.
+    #[serde(default)]
+    swap_event: UniswapV3SwapEvent,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum UniswapV3SwapEvent {
+    #[default]
+    UniswapV3,
+    PancakeSwapV3,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 enum UniswapV3Preset {
     UniswapV3,
+    PancakeSwapV3,
+    SushiSwapV3,
 }
 
 mod uniswap_v3 {
@@ -1,6 +1,6 @@
 use crate::{
     domain::eth,
-    infra::{blockchain, liquidity, mempool, simulator, solver},
+    infra::{api, blockchain, liquidity, mempool, simulator, solver},
 };
 
 pub mod file;
@@ -15,4 +15,5 @@ pub struct Config {
     pub simulator: Option<simulator::Config>,
     pub mempools: Vec<mempool::Config>,
     pub contracts: blockchain::contracts::Addresses,
+    pub authentication: Option<api::auth::Config>,
 }
@@ -0,0 +1,420 @@
+//! Debug bundles collect everything the driver knows about a solution, so that
+//! solver teams can find out why a solution was rejected without access to the
+//! driver logs. Bundles are only collected for solvers which configure a debug
+//! token and are kept for a limited time.
+//!
+//! If the response of a solver can't be used at all, the request and response
+//! are recorded in a bundle for the whole auction instead.
+
+use {
+    crate::{
+        domain::{
+            competition::{
+                self,
+                auction,
+                score,
+                solution::{self, settlement, Settlement},
+            },
+            eth,
+        },
+        infra::{simulator, solver, Ethereum, Solver},
+        util::serialize,
+    },
+    itertools::Itertools,
+    serde::Serialize,
+    serde_with::serde_as,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The token which solver teams need to provide for retrieving the bundles
+    /// of their solutions.
+    pub token: String,
+    /// How long bundles are kept.
+    pub ttl: Duration,
+}
+
+/// The solver, auction and solution a bundle belongs to. Bundles without a
+/// solution belong to the whole auction.
+type Key = (String, i64, Option<u64>);
+
+/// The debug bundles of all solvers.
+#[derive(Debug, Clone, Default)]
+pub struct Bundles(Arc<Mutex<HashMap<Key, (Instant, Bundle)>>>);
+
+impl Bundles {
+    /// Returns a recorder for the solutions of an auction, or [`None`] if no
+    /// bundles are collected for the solver. Quotes have no bundles.
+    pub fn recorder(&self, solver: &Solver, auction: Option<auction::Id>) -> Option<Recorder> {
+        Some(Recorder {
+            bundles: self.clone(),
+            solver: solver.name().0.clone(),
+            auction: auction?.0,
+            ttl: solver.debug()?.ttl,
+        })
+    }
+
+    /// The bundle of a solution, or of the whole auction if no solution is
+    /// specified, if it was collected and did not expire yet.
+    pub fn get(
+        &self,
+        solver: &solver::Name,
+        auction: auction::Id,
+        solution: Option<solution::Id>,
+    ) -> Option<Bundle> {
+        let mut bundles = self.0.lock().unwrap();
+        bundles.retain(|_, (expiry, _)| *expiry > Instant::now());
+        bundles
+            .get(&(
+                solver.0.clone(),
+                auction.0,
+                solution.map(|solution| solution.0),
+            ))
+            .map(|(_, bundle)| bundle.clone())
+    }
+}
+
+/// Records the debug bundles of the solutions of an auction.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    bundles: Bundles,
+    solver: String,
+    auction: i64,
+    ttl: Duration,
+}
+
+impl Recorder {
+    fn update(&self, solution: Option<solution::Id>, f: impl FnOnce(&mut Bundle)) {
+        let now = Instant::now();
+        let mut bundles = self.bundles.0.lock().unwrap();
+        bundles.retain(|_, (expiry, _)| *expiry > now);
+        let (_, bundle) = bundles
+            .entry((
+                self.solver.clone(),
+                self.auction,
+                solution.map(|solution| solution.0),
+            ))
+            .or_insert_with(|| (now + self.ttl, Default::default()));
+        f(bundle);
+    }
+
+    /// Updates the bundle of every solution of a settlement. Settlements of a
+    /// single solution update the bundle itself, merged settlements update the
+    /// entry of the merged settlement in the bundles of their parts.
+    fn update_settlement(
+        &self,
+        settlement: &Settlement,
+        single: impl Fn(&mut Bundle),
+        merged: impl Fn(&mut Merged),
+    ) {
+        let solutions = settlement.solutions();
+        if solutions.len() == 1 {
+            for solution in solutions {
+                self.update(Some(solution), &single);
+            }
+            return;
+        }
+        let mut ids = solutions.iter().map(|solution| solution.0).collect_vec();
+        ids.sort();
+        for solution in solutions {
+            self.update(Some(solution), |bundle| {
+                let index = match bundle
+                    .merged
+                    .iter()
+                    .position(|merged| merged.solutions == ids)
+                {
+                    Some(index) => index,
+                    None => {
+                        bundle.merged.push(Merged {
+                            solutions: ids.clone(),
+                            ..Default::default()
+                        });
+                        bundle.merged.len() - 1
+                    }
+                };
+                merged(&mut bundle.merged[index]);
+            });
+        }
+    }
+
+    /// Record the auction sent to the solver and its response for all
+    /// solutions of the response.
+    pub fn solved(&self, request: &str, response: &str, solutions: &[solution::Solution]) {
+        let request = Arc::new(json(request));
+        let response = Arc::new(json(response));
+        for solution in solutions {
+            self.update(Some(solution.id()), |bundle| {
+                bundle.auction = Some(request.clone());
+                bundle.solver_response = Some(response.clone());
+            });
+        }
+    }
+
+    /// Record the auction sent to the solver and its response when the
+    /// response could not be turned into solutions.
+    pub fn failed(&self, request: &str, response: &str, err: &solver::Error) {
+        self.update(None, |bundle| {
+            bundle.auction = Some(Arc::new(json(request)));
+            bundle.solver_response = Some(Arc::new(json(response)));
+            bundle
+                .errors
+                .push(format!("invalid solver response: {err}"));
+        });
+    }
+
+    /// Record why a solution was discarded.
+    pub fn discarded(&self, solution: solution::Id, reason: &str) {
+        self.update(Some(solution), |bundle| {
+            bundle.errors.push(reason.to_owned())
+        });
+    }
+
+    /// Record a solution which failed to encode.
+    pub fn encoding_failed(&self, solution: solution::Id, err: &solution::Error) {
+        self.update(Some(solution), |bundle| {
+            bundle.errors.push(format!("encoding failed: {err}"));
+            if let solution::Error::Simulation(err) = err {
+                bundle.simulation = Some(Simulation::new(err));
+            }
+        });
+    }
+
+    /// Record the encoded settlement of a solution, or of a merged settlement.
+    pub fn encoded(&self, settlement: &Settlement, eth: &Ethereum) {
+        let encoded = Encoded::new(settlement, eth);
+        self.update_settlement(
+            settlement,
+            |bundle| bundle.settlement = Some(encoded.clone()),
+            |merged| merged.settlement = Some(encoded.clone()),
+        );
+    }
+
+    /// Record the score of the settlement of a solution, or of a merged
+    /// settlement.
+    pub fn scored(
+        &self,
+        settlement: &Settlement,
+        eth: &Ethereum,
+        auction: &competition::Auction,
+        score: &Result<competition::Score, score::Error>,
+    ) {
+        let scored = Score {
+            quality: settlement
+                .boundary
+                .quality(eth, auction)
+                .ok()
+                .map(|quality| quality.0),
+            gas_price: auction.gas_price().effective().into(),
+            score: score.as_ref().ok().map(|score| score.0.get()),
+            error: score.as_ref().err().map(ToString::to_string),
+        };
+        let encoded = (settlement.solutions().len() > 1).then(|| Encoded::new(settlement, eth));
+        self.update_settlement(
+            settlement,
+            |bundle| bundle.score = Some(scored.clone()),
+            |merged| {
+                // Merged settlements are only encoded when they are scored.
+                merged.settlement = encoded.clone();
+                merged.score = Some(scored.clone());
+            },
+        );
+    }
+
+    /// Record a failed simulation of a settlement after it was encoded, e.g.
+    /// when the winning settlement is re-simulated.
+    pub fn simulation_failed(&self, settlement: &Settlement, err: &simulator::Error) {
+        let simulation = Simulation::new(err);
+        self.update_settlement(
+            settlement,
+            |bundle| {
+                bundle.errors.push(format!("simulation failed: {err}"));
+                bundle.simulation = Some(simulation.clone());
+            },
+            |merged| merged.simulation = Some(simulation.clone()),
+        );
+    }
+}
+
+/// The JSON value of a request or response, or the raw string if it is not
+/// valid JSON.
+fn json(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_owned()))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    /// The auction as it was sent to the solver.
+    auction: Option<Arc<serde_json::Value>>,
+    /// The response of the solver.
+    solver_response: Option<Arc<serde_json::Value>>,
+    /// Why the solution was rejected, in the order the errors occurred.
+    errors: Vec<String>,
+    settlement: Option<Encoded>,
+    simulation: Option<Simulation>,
+    score: Option<Score>,
+    /// The settlements this solution was merged into with other solutions of
+    /// the same solver.
+    merged: Vec<Merged>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Merged {
+    /// The IDs of all solutions of the merged settlement.
+    solutions: Vec<u64>,
+    settlement: Option<Encoded>,
+    simulation: Option<Simulation>,
+    score: Option<Score>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Encoded {
+    #[serde_as(as = "serialize::Hex")]
+    calldata: Vec<u8>,
+    access_list: web3::types::AccessList,
+    #[serde_as(as = "serialize::U256")]
+    gas: eth::U256,
+}
+
+impl Encoded {
+    fn new(settlement: &Settlement, eth: &Ethereum) -> Self {
+        Self {
+            calldata: settlement.calldata(
+                eth.contracts().settlement(),
+                settlement::Internalization::Disable,
+            ),
+            access_list: web3::types::AccessList::from(settlement.access_list.clone()),
+            gas: settlement.gas.estimate.0,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Simulation {
+    block: Option<u64>,
+    from: Option<eth::H160>,
+    to: Option<eth::H160>,
+    #[serde_as(as = "Option<serialize::Hex>")]
+    calldata: Option<Vec<u8>>,
+    access_list: Option<web3::types::AccessList>,
+    error: String,
+}
+
+impl Simulation {
+    fn new(err: &simulator::Error) -> Self {
+        match err {
+            simulator::Error::Revert(revert) => Self {
+                block: Some(revert.block.0),
+                from: Some(revert.tx.from.into()),
+                to: Some(revert.tx.to.into()),
+                calldata: Some(revert.tx.input.clone().into()),
+                access_list: Some(revert.tx.access_list.clone().into()),
+                error: revert.err.to_string(),
+            },
+            simulator::Error::Other(err) => Self {
+                block: None,
+                from: None,
+                to: None,
+                calldata: None,
+                access_list: None,
+                error: err.to_string(),
+            },
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Score {
+    #[serde_as(as = "Option<serialize::U256>")]
+    quality: Option<eth::U256>,
+    #[serde_as(as = "serialize::U256")]
+    gas_price: eth::U256,
+    #[serde_as(as = "Option<serialize::U256>")]
+    score: Option<eth::U256>,
+    error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder(bundles: &Bundles, ttl: Duration) -> Recorder {
+        Recorder {
+            bundles: bundles.clone(),
+            solver: "solver".to_owned(),
+            auction: 1,
+            ttl,
+        }
+    }
+
+    #[test]
+    fn records_bundles_per_solution_and_auction() {
+        let bundles = Bundles::default();
+        let recorder = recorder(&bundles, Duration::from_secs(60));
+        let solver = solver::Name("solver".to_owned());
+
+        recorder.discarded(solution::Id(1), "empty solution");
+        recorder.failed(
+            r#"{"id":"1"}"#,
+            "not json",
+            &solver::Error::Dto(solver::dto::Error("invalid solution")),
+        );
+
+        let bundle = bundles
+            .get(&solver, auction::Id(1), Some(solution::Id(1)))
+            .unwrap();
+        assert_eq!(bundle.errors, ["empty solution"]);
+        assert!(bundle.auction.is_none());
+
+        let bundle = bundles.get(&solver, auction::Id(1), None).unwrap();
+        assert_eq!(
+            bundle.auction.as_deref(),
+            Some(&serde_json::json!({ "id": "1" }))
+        );
+        assert_eq!(
+            bundle.solver_response.as_deref(),
+            Some(&serde_json::Value::String("not json".to_owned()))
+        );
+        assert_eq!(bundle.errors.len(), 1);
+
+        assert!(bundles
+            .get(&solver, auction::Id(1), Some(solution::Id(2)))
+            .is_none());
+        assert!(bundles
+            .get(&solver, auction::Id(2), Some(solution::Id(1)))
+            .is_none());
+        assert!(bundles
+            .get(
+                &solver::Name("other".to_owned()),
+                auction::Id(1),
+                Some(solution::Id(1))
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn expires_bundles() {
+        let bundles = Bundles::default();
+        let recorder = recorder(&bundles, Duration::ZERO);
+        recorder.discarded(solution::Id(1), "empty solution");
+        assert!(bundles
+            .get(
+                &solver::Name("solver".to_owned()),
+                auction::Id(1),
+                Some(solution::Id(1))
+            )
+            .is_none());
+    }
+}
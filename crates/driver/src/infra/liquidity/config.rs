@@ -7,7 +7,7 @@ use {
 };
 
 /// Configuration options for liquidity fetching.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Config {
     /// Liquidity base tokens. These are additional tokens for which liquidity
     /// is always fetched, regardless of whether or not the token appears in the
@@ -29,12 +29,15 @@ pub struct Config {
     /// for.
     pub balancer_v2: Vec<BalancerV2>,
 
+    /// The collection of Solidly compatible exchanges to fetch liquidity for.
+    pub solidly: Vec<Solidly>,
+
     /// 0x liquidity fetcher.
     pub zeroex: Option<ZeroEx>,
 }
 
 /// Uniswap V2 (and Uniswap V2 clone) liquidity fetching options.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UniswapV2 {
     /// The address of the Uniswap V2 compatible router contract.
     pub router: eth::ContractAddress,
@@ -114,7 +117,7 @@ impl UniswapV2 {
 }
 
 /// Swapr (Uniswap V2 clone with a twist) liquidity fetching options.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Swapr {
     /// The address of the Swapr compatible router contract.
     pub router: eth::ContractAddress,
@@ -139,33 +142,154 @@ impl Swapr {
     }
 }
 
+/// Solidly (and Solidly fork) liquidity fetching options.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Solidly {
+    /// The address of the Solidly compatible router contract. Pools are
+    /// indexed from the router's default pool factory.
+    pub router: eth::ContractAddress,
+}
+
+impl Solidly {
+    /// Returns the liquidity configuration for Velodrome V2.
+    pub fn velodrome_v2(network: &eth::NetworkId) -> Option<Self> {
+        Some(Self {
+            router: deployment_address(contracts::VelodromeV2Router::raw_contract(), network)?,
+        })
+    }
+
+    /// Returns the liquidity configuration for Aerodrome.
+    pub fn aerodrome(network: &eth::NetworkId) -> Option<Self> {
+        Some(Self {
+            router: deployment_address(contracts::AerodromeRouter::raw_contract(), network)?,
+        })
+    }
+}
+
 /// Uniswap V3 liquidity fetching options.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct UniswapV3 {
     /// The address of the Uniswap V3 compatible router contract.
     pub router: eth::ContractAddress,
 
-    /// How many pools should be initialized during start up.
+    /// How many pools should be initialized during start up. When pools are
+    /// discovered from a factory, this is the maximum number of pools that
+    /// are initialized at a time.
     pub max_pools_to_initialize: usize,
 
-    /// The base URL used to connect to subgraph clients.
-    pub graph_api_base_url: Url,
+    /// How pools are discovered and initialized.
+    pub pool_discovery: UniswapV3PoolDiscovery,
+}
+
+/// How Uniswap V3 pools are discovered and initialized.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UniswapV3PoolDiscovery {
+    /// Pools are fetched from a Uniswap V3 subgraph with the specified base
+    /// URL.
+    Subgraph(Url),
+
+    /// Pools are discovered on-chain from a Uniswap V3 compatible factory.
+    /// This is used for forks and chains without a subgraph.
+    Factory(UniswapV3Factory),
+}
+
+/// A Uniswap V3 compatible factory for discovering pools on-chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniswapV3Factory {
+    /// The address of the factory contract emitting `PoolCreated` events.
+    pub address: eth::ContractAddress,
+
+    /// The address of the contract deploying the pools. This is the factory
+    /// for Uniswap V3, but a separate pool deployer contract for PancakeSwap
+    /// V3.
+    pub deployer: eth::ContractAddress,
+
+    /// The digest of the pool initialization code. This digest is used for
+    /// computing the deterministic pool addresses per token pair and fee tier.
+    pub pool_code: eth::CodeDigest,
+
+    /// The supported fee tiers in hundredths of a basis point.
+    pub fee_tiers: Vec<u32>,
+
+    /// The `Swap` event emitted by the pools.
+    pub swap_event: UniswapV3SwapEvent,
+}
+
+/// The `Swap` event emitted by the pools of a Uniswap V3 fork.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UniswapV3SwapEvent {
+    UniswapV3,
+    /// PancakeSwap V3 pools additionally emit the protocol fees.
+    PancakeSwapV3,
 }
 
 impl UniswapV3 {
     /// Returns the liquidity configuration for Uniswap V3.
+    ///
+    /// The Uniswap V3 subgraph is only available on Mainnet, so pools are
+    /// discovered from the factory on other networks.
     #[allow(clippy::self_named_constructors)]
     pub fn uniswap_v3(graph_api_base_url: &Url, network: &eth::NetworkId) -> Option<Self> {
+        let pool_discovery = if network.as_str() == "1" {
+            UniswapV3PoolDiscovery::Subgraph(graph_api_base_url.clone())
+        } else {
+            let factory =
+                deployment_address(contracts::IUniswapV3Factory::raw_contract(), network)?;
+            UniswapV3PoolDiscovery::Factory(UniswapV3Factory {
+                address: factory,
+                deployer: factory,
+                pool_code: hex!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54")
+                    .into(),
+                fee_tiers: vec![100, 500, 3000, 10000],
+                swap_event: UniswapV3SwapEvent::UniswapV3,
+            })
+        };
         Some(Self {
             router: deployment_address(contracts::UniswapV3SwapRouter::raw_contract(), network)?,
             max_pools_to_initialize: 100,
-            graph_api_base_url: graph_api_base_url.clone(),
+            pool_discovery,
+        })
+    }
+
+    /// Returns the liquidity configuration for PancakeSwap V3.
+    pub fn pancake_swap_v3(network: &eth::NetworkId) -> Option<Self> {
+        Some(Self {
+            router: deployment_address(
+                contracts::PancakeSwapV3SwapRouter::raw_contract(),
+                network,
+            )?,
+            max_pools_to_initialize: 100,
+            pool_discovery: UniswapV3PoolDiscovery::Factory(UniswapV3Factory {
+                address: eth::H160(hex!("0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865")).into(),
+                deployer: eth::H160(hex!("41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9")).into(),
+                pool_code: hex!("6ce8eb472fa82df5469c6ab6d485f17c3ad13c8cd7af59b3d4a8026c5ce0f7e2")
+                    .into(),
+                fee_tiers: vec![100, 500, 2500, 10000],
+                swap_event: UniswapV3SwapEvent::PancakeSwapV3,
+            }),
+        })
+    }
+
+    /// Returns the liquidity configuration for SushiSwap V3.
+    pub fn sushi_swap_v3(network: &eth::NetworkId) -> Option<Self> {
+        let factory = eth::H160(hex!("bACEB8eC6b9355Dfc0269C18bac9d6E2Bdc29C4F")).into();
+        Some(Self {
+            router: deployment_address(contracts::SushiSwapV3SwapRouter::raw_contract(), network)?,
+            max_pools_to_initialize: 100,
+            pool_discovery: UniswapV3PoolDiscovery::Factory(UniswapV3Factory {
+                address: factory,
+                deployer: factory,
+                pool_code: hex!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54")
+                    .into(),
+                fee_tiers: vec![100, 500, 3000, 10000],
+                swap_event: UniswapV3SwapEvent::UniswapV3,
+            }),
         })
     }
 }
 
 /// Balancer V2 liquidity fetching options.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct BalancerV2 {
     /// The address of the Uniswap V3 compatible router contract.
     pub vault: eth::ContractAddress,
@@ -237,7 +361,7 @@ impl BalancerV2 {
 }
 
 /// ZeroEx liquidity fetching options.
-#[derive(Clone, Derivative)]
+#[derive(Clone, Derivative, PartialEq)]
 #[derivative(Debug)]
 pub struct ZeroEx {
     pub base_url: String,
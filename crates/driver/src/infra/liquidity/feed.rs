@@ -0,0 +1,363 @@
+use {
+    crate::{
+        domain::{eth, liquidity},
+        infra::{blockchain::Ethereum, liquidity::AtBlock, solver::dto},
+    },
+    ethrpc::current_block::CurrentBlockStream,
+    futures::{stream, Stream},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+    tokio::sync::broadcast,
+};
+
+/// The number of updates that are buffered for slow subscribers. Subscribers
+/// that fall further behind receive a new snapshot instead.
+const UPDATE_BUFFER: usize = 64;
+
+/// The number of blocks after which liquidity that wasn't fetched for any
+/// auction is removed from the feed.
+const MAX_STALE_BLOCKS: u64 = 100;
+
+/// A feed of the liquidity that is fetched for auctions, for solver engines
+/// which keep their own liquidity state instead of receiving all liquidity
+/// with every auction.
+///
+/// Liquidity gets stable IDs based on the pool, and the feed only publishes
+/// liquidity which changed since it was last published. Liquidity which isn't
+/// fetched for a while is removed again. Auctions reference the update which
+/// contains their liquidity.
+#[derive(Debug, Clone)]
+pub struct Feed(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    blocks: CurrentBlockStream,
+    state: Mutex<State>,
+    updates: broadcast::Sender<Arc<Update>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    ids: HashMap<eth::Address, liquidity::Id>,
+    /// The next ID to assign. IDs of removed liquidity are not reused.
+    next_id: usize,
+    liquidity: HashMap<liquidity::Id, Published>,
+    block: u64,
+    update: u64,
+}
+
+#[derive(Debug)]
+struct Published {
+    address: eth::Address,
+    value: serde_json::Value,
+    /// The last block at which the liquidity was fetched.
+    block: u64,
+    /// Whether the value is the latest state at that block, as opposed to
+    /// liquidity which might be stale by a few blocks.
+    latest: bool,
+}
+
+/// Liquidity which was published at a block.
+#[derive(Debug)]
+pub struct Update {
+    pub block: u64,
+    /// Sequence number of the update, increasing by one with every update.
+    pub update: u64,
+    pub liquidity: Vec<serde_json::Value>,
+    /// The IDs of the liquidity which was removed from the feed.
+    pub removed: Vec<liquidity::Id>,
+}
+
+/// An event of a feed subscription.
+#[derive(Debug)]
+pub enum Event {
+    /// All liquidity of the feed, replacing any previous state.
+    Snapshot(Update),
+    /// The changes since the previous event.
+    Update(Arc<Update>),
+}
+
+/// References the update of the feed which solver engines need to have
+/// applied for solving an auction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reference {
+    pub block: u64,
+    pub update: u64,
+}
+
+impl Feed {
+    pub fn new(eth: Ethereum) -> Self {
+        Self::with_blocks(eth.current_block().clone())
+    }
+
+    fn with_blocks(blocks: CurrentBlockStream) -> Self {
+        Self(Arc::new(Inner {
+            blocks,
+            state: Default::default(),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }))
+    }
+
+    /// Publishes the liquidity that was fetched for an auction at the
+    /// specified block. Returns the liquidity with the stable IDs of the feed,
+    /// which solutions use to reference it, and the update the auction needs
+    /// to reference.
+    pub fn publish(
+        &self,
+        liquidity: Vec<liquidity::Liquidity>,
+        at: &AtBlock,
+    ) -> (Vec<liquidity::Liquidity>, Reference) {
+        let block = self.0.blocks.borrow().number;
+        let latest = matches!(at, AtBlock::Latest);
+        let mut state = self.0.state.lock().unwrap();
+        let state = &mut *state;
+        let mut changed = Vec::new();
+        let liquidity = liquidity
+            .into_iter()
+            .filter_map(|mut liquidity| {
+                let address = liquidity.address()?;
+                liquidity.id = *state.ids.entry(address).or_insert_with(|| {
+                    state.next_id += 1;
+                    liquidity::Id(state.next_id - 1)
+                });
+                match state.liquidity.get_mut(&liquidity.id) {
+                    // The latest liquidity doesn't change within a block, so it
+                    // only needs to be compared once per block.
+                    Some(published) if published.block == block && published.latest => (),
+                    Some(published) => {
+                        published.block = block;
+                        published.latest = latest;
+                        let value = dto::LiquidityUpdate::serialize(&liquidity);
+                        if published.value != value {
+                            published.value = value.clone();
+                            changed.push(value);
+                        }
+                    }
+                    None => {
+                        let value = dto::LiquidityUpdate::serialize(&liquidity);
+                        state.liquidity.insert(
+                            liquidity.id,
+                            Published {
+                                address,
+                                value: value.clone(),
+                                block,
+                                latest,
+                            },
+                        );
+                        changed.push(value);
+                    }
+                }
+                Some(liquidity)
+            })
+            .collect();
+
+        let mut removed = Vec::new();
+        state.liquidity.retain(|id, published| {
+            let stale = published.block.saturating_add(MAX_STALE_BLOCKS) < block;
+            if stale {
+                state.ids.remove(&published.address);
+                removed.push(*id);
+            }
+            !stale
+        });
+
+        if !changed.is_empty() || !removed.is_empty() || block != state.block {
+            state.block = block;
+            state.update += 1;
+            // Sending only fails if there are no subscribers.
+            let _ = self.0.updates.send(Arc::new(Update {
+                block,
+                update: state.update,
+                liquidity: changed,
+                removed,
+            }));
+        }
+        let reference = Reference {
+            block: state.block,
+            update: state.update,
+        };
+        (liquidity, reference)
+    }
+
+    /// Subscribes to the feed. The first event is a snapshot of all liquidity,
+    /// followed by every update. Subscribers which fall behind receive a new
+    /// snapshot.
+    pub fn subscribe(&self) -> impl Stream<Item = Event> {
+        let (snapshot, updates) = self.snapshot();
+        stream::unfold(
+            (self.clone(), updates, Some(snapshot)),
+            |(feed, mut updates, snapshot)| async move {
+                if let Some(snapshot) = snapshot {
+                    return Some((Event::Snapshot(snapshot), (feed, updates, None)));
+                }
+                match updates.recv().await {
+                    Ok(update) => Some((Event::Update(update), (feed, updates, None))),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "liquidity subscriber lagged behind");
+                        let (snapshot, updates) = feed.snapshot();
+                        Some((Event::Snapshot(snapshot), (feed, updates, None)))
+                    }
+                    Err(broadcast::error::RecvError::Closed) => None,
+                }
+            },
+        )
+    }
+
+    /// Returns a snapshot of all liquidity and the receiver for all following
+    /// updates.
+    fn snapshot(&self) -> (Update, broadcast::Receiver<Arc<Update>>) {
+        let state = self.0.state.lock().unwrap();
+        let snapshot = Update {
+            block: state.block,
+            update: state.update,
+            liquidity: state
+                .liquidity
+                .values()
+                .map(|published| published.value.clone())
+                .collect(),
+            removed: Default::default(),
+        };
+        (snapshot, self.0.updates.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ethrpc::current_block::BlockInfo, futures::StreamExt, tokio::sync::watch};
+
+    fn pool(address: u8, reserve: u128) -> liquidity::Liquidity {
+        let token = |byte| eth::TokenAddress(eth::ContractAddress(eth::H160::repeat_byte(byte)));
+        liquidity::Liquidity {
+            id: liquidity::Id(0),
+            gas: eth::Gas(100_000.into()),
+            kind: liquidity::Kind::UniswapV2(liquidity::uniswap::v2::Pool {
+                address: eth::Address(eth::H160::repeat_byte(address)),
+                router: eth::ContractAddress(eth::H160::zero()),
+                reserves: liquidity::uniswap::v2::Reserves::new(
+                    eth::Asset {
+                        token: token(1),
+                        amount: reserve.into(),
+                    },
+                    eth::Asset {
+                        token: token(2),
+                        amount: reserve.into(),
+                    },
+                )
+                .unwrap(),
+            }),
+        }
+    }
+
+    fn feed() -> (Feed, watch::Sender<BlockInfo>) {
+        let (blocks, receiver) = watch::channel(BlockInfo {
+            number: 1,
+            ..Default::default()
+        });
+        (Feed::with_blocks(receiver), blocks)
+    }
+
+    fn set_block(blocks: &watch::Sender<BlockInfo>, number: u64) {
+        blocks.send_modify(|block| block.number = number);
+    }
+
+    fn ids(liquidity: &[liquidity::Liquidity]) -> Vec<usize> {
+        liquidity.iter().map(|liquidity| liquidity.id.0).collect()
+    }
+
+    #[tokio::test]
+    async fn publishes_changed_liquidity_with_stable_ids() {
+        let (feed, blocks) = feed();
+        let mut events = Box::pin(feed.subscribe());
+        assert!(matches!(events.next().await, Some(Event::Snapshot(_))));
+
+        let (liquidity, reference) = feed.publish(vec![pool(1, 10), pool(2, 10)], &AtBlock::Latest);
+        assert_eq!(ids(&liquidity), [0, 1]);
+        assert_eq!(
+            reference,
+            Reference {
+                block: 1,
+                update: 1
+            }
+        );
+        let Some(Event::Update(update)) = events.next().await else {
+            panic!("expected an update");
+        };
+        assert_eq!(update.liquidity.len(), 2);
+
+        // Unchanged liquidity is not published again and keeps its ID.
+        set_block(&blocks, 2);
+        let (liquidity, reference) = feed.publish(vec![pool(2, 10), pool(1, 20)], &AtBlock::Latest);
+        assert_eq!(ids(&liquidity), [1, 0]);
+        assert_eq!(
+            reference,
+            Reference {
+                block: 2,
+                update: 2
+            }
+        );
+        let Some(Event::Update(update)) = events.next().await else {
+            panic!("expected an update");
+        };
+        assert_eq!(
+            update.liquidity,
+            [dto::LiquidityUpdate::serialize(&liquidity[1])]
+        );
+
+        // Nothing is published for the same liquidity at the same block.
+        let (_, reference) = feed.publish(vec![pool(1, 20)], &AtBlock::Latest);
+        assert_eq!(
+            reference,
+            Reference {
+                block: 2,
+                update: 2
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn removes_stale_liquidity() {
+        let (feed, blocks) = feed();
+        let (liquidity, _) = feed.publish(vec![pool(1, 10), pool(2, 10)], &AtBlock::Latest);
+        assert_eq!(ids(&liquidity), [0, 1]);
+
+        let mut events = Box::pin(feed.subscribe());
+        assert!(matches!(events.next().await, Some(Event::Snapshot(_))));
+        set_block(&blocks, 2 + MAX_STALE_BLOCKS);
+        feed.publish(vec![pool(2, 10)], &AtBlock::Latest);
+        let Some(Event::Update(update)) = events.next().await else {
+            panic!("expected an update");
+        };
+        assert_eq!(update.removed, [liquidity::Id(0)]);
+
+        // Removed liquidity gets a new ID, IDs are never reused.
+        let (liquidity, _) = feed.publish(vec![pool(1, 10), pool(3, 10)], &AtBlock::Latest);
+        assert_eq!(ids(&liquidity), [2, 3]);
+    }
+
+    #[tokio::test]
+    async fn sends_new_snapshot_to_lagging_subscribers() {
+        let (feed, blocks) = feed();
+        let mut events = Box::pin(feed.subscribe());
+        assert!(matches!(events.next().await, Some(Event::Snapshot(_))));
+
+        let updates = UPDATE_BUFFER as u64 + 1;
+        for block in 1..=updates {
+            set_block(&blocks, block);
+            feed.publish(vec![pool(1, block.into())], &AtBlock::Latest);
+        }
+
+        let Some(Event::Snapshot(snapshot)) = events.next().await else {
+            panic!("expected a snapshot");
+        };
+        assert_eq!(snapshot.update, updates);
+        assert_eq!(
+            snapshot.liquidity,
+            [dto::LiquidityUpdate::serialize(&liquidity::Liquidity {
+                id: liquidity::Id(0),
+                ..pool(1, updates.into())
+            })]
+        );
+    }
+}
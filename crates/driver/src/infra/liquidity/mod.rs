@@ -11,9 +11,11 @@
 //!    module.
 
 pub mod config;
+pub mod feed;
 pub mod fetcher;
 
 pub use self::{
     config::Config,
+    feed::Feed,
     fetcher::{AtBlock, Fetcher},
 };
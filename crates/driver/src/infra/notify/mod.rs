@@ -8,7 +8,11 @@ mod notification;
 pub use notification::{Kind, Notification, ScoreKind, Settlement, SimulationSucceededAtLeastOnce};
 use {
     super::simulator,
-    crate::domain::{competition::score, eth, mempools::Error},
+    crate::domain::{
+        competition::score,
+        eth,
+        mempools::{Error, Outcome},
+    },
 };
 
 pub fn solver_timeout(solver: &Solver, auction_id: Option<auction::Id>) {
@@ -79,7 +83,10 @@ pub fn encoding_failed(
         }
         solution::Error::Execution(_) => return,
         solution::Error::FailingInternalization => return,
+        solution::Error::CowOnlyViolated(_) => return,
+        solution::Error::NoAccountAvailable(_) => return,
         solution::Error::DifferentSolvers => return,
+        solution::Error::Conflict(_) => return,
     };
 
     solver.notify(auction_id, Some(solution_id), notification);
@@ -107,15 +114,19 @@ pub fn executed(
     solver: &Solver,
     auction_id: auction::Id,
     solution_id: Option<solution::Id>,
-    res: &Result<eth::TxId, Error>,
+    res: &Result<Outcome, Error>,
 ) {
     if solution_id.is_none() {
         return;
     };
 
     let kind = match res {
-        Ok(hash) => notification::Settlement::Success(hash.clone()),
-        Err(Error::Revert(hash)) => notification::Settlement::Revert(hash.clone()),
+        Ok(Outcome::Mined(hash)) => notification::Settlement::Success(hash.clone()),
+        Ok(Outcome::Reverted(hash)) | Err(Error::Revert(hash)) => {
+            notification::Settlement::Revert(hash.clone())
+        }
+        Ok(Outcome::Cancelled(_) | Outcome::Expired)
+        | Err(Error::Cancelled(_) | Error::Expired) => notification::Settlement::Expired,
         Err(Error::SimulationRevert) => notification::Settlement::SimulationRevert,
         Err(Error::Other(_)) => notification::Settlement::Fail,
     };
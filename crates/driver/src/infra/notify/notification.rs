@@ -77,6 +77,8 @@ pub enum Settlement {
     Revert(TransactionHash),
     /// Transaction started reverting during the submission.
     SimulationRevert,
+    /// The submission deadline was reached before the transaction was mined.
+    Expired,
     /// Winning solver failed to settle the transaction onchain.
     Fail,
 }
@@ -16,6 +16,18 @@ pub struct Metrics {
     /// The results of the quoting process.
     #[metric(labels("solver", "result"))]
     pub quotes: prometheus::IntCounterVec,
+    /// The results of merging settlements.
+    #[metric(labels("solver", "result"))]
+    pub merges: prometheus::IntCounterVec,
+    /// The gas saved by merging settlements.
+    #[metric(
+        labels("solver"),
+        buckets(0, 10_000, 25_000, 50_000, 100_000, 200_000, 500_000)
+    )]
+    pub merge_gas_saved: prometheus::HistogramVec,
+    /// The Ether balances of the solver accounts.
+    #[metric(labels("solver", "account"))]
+    pub account_balance: prometheus::GaugeVec,
     /// The results of the mempool submission.
     #[metric(labels("mempool", "result"))]
     pub mempool_submission: prometheus::IntCounterVec,
@@ -4,7 +4,7 @@
 //! and update the metrics, if the event is worth measuring.
 
 use {
-    super::{simulator, solver::Timeouts, Ethereum, Mempool},
+    super::{blockchain, simulator, solver::Timeouts, Ethereum, Mempool},
     crate::{
         boundary,
         domain::{
@@ -99,11 +99,8 @@ pub fn postprocessing(solutions: &[Solution], deadline: chrono::DateTime<chrono:
 }
 
 // Observe that postprocessing didn't complete before the timeout.
-pub fn postprocessing_timed_out(completed: &[Settlement]) {
-    tracing::debug!(
-        completed = ?completed.len(),
-        "postprocessing solutions timed out"
-    );
+pub fn postprocessing_timed_out(completed: usize) {
+    tracing::debug!(?completed, "postprocessing solutions timed out");
 }
 
 /// Observe that a solution is about to be encoded into a settlement.
@@ -121,22 +118,76 @@ pub fn encoding_failed(solver: &solver::Name, id: solution::Id, err: &solution::
 }
 
 /// Observe that two solutions were merged.
-pub fn merged(settlement: &Settlement, other: &Settlement) {
+pub fn merged(
+    solver: &solver::Name,
+    settlement: &Settlement,
+    other: &Settlement,
+    merged: &Settlement,
+) {
+    // Merging saves the fixed costs of a settlement, such as the transaction
+    // base cost, but the merged settlement can end up more expensive.
+    let gas_saved =
+        (settlement.gas.estimate.0 + other.gas.estimate.0).saturating_sub(merged.gas.estimate.0);
     tracing::debug!(
         settlement_1 = ?settlement.solutions(),
         settlement_2 = ?other.solutions(),
+        %gas_saved,
         "merged solutions"
     );
+    let metrics = metrics::get();
+    metrics
+        .merges
+        .with_label_values(&[solver.as_str(), "Success"])
+        .inc();
+    metrics
+        .merge_gas_saved
+        .with_label_values(&[solver.as_str()])
+        .observe(gas_saved.to_f64_lossy());
 }
 
 /// Observe that it was not possible to merge two solutions.
-pub fn not_merged(settlement: &Settlement, other: &Settlement, err: solution::Error) {
+pub fn not_merged(
+    solver: &solver::Name,
+    settlement: &Settlement,
+    other: &Settlement,
+    err: solution::Error,
+) {
     tracing::debug!(
         ?err,
         settlement_1 = ?settlement.solutions(),
         settlement_2 = ?other.solutions(),
         "solutions can't be merged"
     );
+    let result = match err {
+        solution::Error::Conflict(_) => "Conflict",
+        _ => "Failed",
+    };
+    metrics::get()
+        .merges
+        .with_label_values(&[solver.as_str(), result])
+        .inc();
+}
+
+/// Observe that two solutions were not merged because the merged settlement
+/// doesn't score higher than both of its parts.
+pub fn merge_not_dominating(
+    solver: &solver::Name,
+    settlement: &Settlement,
+    other: &Settlement,
+    score: Option<competition::Score>,
+    parts: [Option<competition::Score>; 2],
+) {
+    tracing::debug!(
+        settlement_1 = ?settlement.solutions(),
+        settlement_2 = ?other.solutions(),
+        ?score,
+        ?parts,
+        "merged solutions don't improve the score"
+    );
+    metrics::get()
+        .merges
+        .with_label_values(&[solver.as_str(), "NotDominating"])
+        .inc();
 }
 
 /// Observe that scoring is about to start.
@@ -203,11 +254,17 @@ pub fn settling() {
 /// Observe the result of the settlement process.
 pub fn settled(solver: &solver::Name, result: &Result<competition::Settled, competition::Error>) {
     match result {
-        Ok(calldata) => {
-            tracing::info!(?calldata, "settled solution");
+        Ok(settled) => {
+            tracing::info!(?settled, "settled solution");
+            let result = match settled.outcome {
+                mempools::Outcome::Mined(_) => "Success",
+                mempools::Outcome::Reverted(_) => "Revert",
+                mempools::Outcome::Cancelled(_) => "Cancelled",
+                mempools::Outcome::Expired => "Expired",
+            };
             metrics::get()
                 .settlements
-                .with_label_values(&[solver.as_str(), "Success"])
+                .with_label_values(&[solver.as_str(), result])
                 .inc();
         }
         Err(err) => {
@@ -293,6 +350,43 @@ pub fn mounting_solver(solver: &solver::Name, path: &str) {
     tracing::debug!(%solver, path, "mounting solver");
 }
 
+/// Observe that the API routes for a solver are removed because it is no
+/// longer configured.
+pub fn unmounting_solver(solver: &str) {
+    tracing::info!(solver, "unmounting solver");
+}
+
+/// Observe that the driver configuration was reloaded.
+pub fn config_reloaded(path: &std::path::Path) {
+    tracing::info!(?path, "reloaded driver configuration");
+}
+
+/// Observe that reloading the driver configuration failed. The driver keeps
+/// running with the previous configuration.
+pub fn config_reload_failed(path: &std::path::Path, err: &dyn std::fmt::Debug) {
+    tracing::warn!(?path, ?err, "failed to reload driver configuration");
+}
+
+/// Observe the Ether balance of a solver account.
+pub fn account_balance(
+    solver: &solver::Name,
+    account: eth::Address,
+    balance: Option<eth::Ether>,
+    threshold: Option<eth::Ether>,
+) {
+    // The balance is unknown until it is fetched for the first time.
+    let Some(balance) = balance else {
+        return;
+    };
+    if threshold.is_some_and(|threshold| balance < threshold) {
+        tracing::warn!(%solver, ?account, ?balance, "solver account balance is low");
+    }
+    metrics::get()
+        .account_balance
+        .with_label_values(&[solver.as_str(), &format!("{:?}", account.0)])
+        .set(balance.0.to_f64_lossy() / 1e18);
+}
+
 /// Observe that a request is about to be sent to the solver.
 pub fn solver_request(endpoint: &Url, req: &str) {
     tracing::trace!(%endpoint, %req, "sending request to solver");
@@ -337,6 +431,8 @@ pub fn mempool_executed(
     let result = match res {
         Ok(_) => "Success",
         Err(mempools::Error::Revert(_) | mempools::Error::SimulationRevert) => "Revert",
+        Err(mempools::Error::Cancelled(_)) => "Cancelled",
+        Err(mempools::Error::Expired) => "Expired",
         Err(mempools::Error::Other(_)) => "Other",
     };
     metrics::get()
@@ -363,6 +459,7 @@ fn competition_error(err: &competition::Error) -> &'static str {
         competition::Error::Solver(solver::Error::Deserialize(_)) => "SolverDeserializeError",
         competition::Error::Solver(solver::Error::Dto(_)) => "SolverDtoError",
         competition::Error::SubmissionError => "SubmissionError",
+        competition::Error::NoAccountAvailable(_) => "NoAccountAvailable",
     }
 }
 
@@ -376,6 +473,9 @@ pub enum OrderExcludedFromAuctionReason {
     CouldNotCalculateMaxSell,
     InsufficientBalance,
     OrderWithZeroAmountRemaining,
+    MissingPrice,
+    OwnerOrderLimit,
+    OrderLimit,
 }
 
 pub fn order_excluded_from_auction(
@@ -10,7 +10,7 @@ pub(super) struct Enso {
     chain_id: eth::ChainId,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     /// The URL of the Transaction Simulator API.
     pub url: reqwest::Url,
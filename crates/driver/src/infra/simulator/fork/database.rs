@@ -0,0 +1,141 @@
+//! An EVM database which lazily fetches the state of a block from the node.
+
+use {
+    super::convert,
+    ethcontract::dyns::DynWeb3,
+    futures::future::try_join3,
+    revm::primitives::{AccountInfo, Address, Bytecode, B256, U256},
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+    web3::types::{BlockId, BlockNumber},
+};
+
+/// The state that was fetched for a block. It is shared by all simulations at
+/// that block, so each account and storage slot is only fetched once.
+#[derive(Debug, Default)]
+pub(super) struct Cache {
+    block: u64,
+    accounts: HashMap<Address, AccountInfo>,
+    code: HashMap<B256, Bytecode>,
+    storage: HashMap<(Address, U256), U256>,
+    block_hashes: HashMap<U256, B256>,
+}
+
+impl Cache {
+    /// Clears the cache if it contains the state of another block.
+    pub(super) fn at(&mut self, block: u64) {
+        if self.block != block {
+            *self = Self {
+                block,
+                ..Default::default()
+            };
+        }
+    }
+}
+
+/// Fetches the state at a block from the node. This is used from blocking
+/// tasks, since the EVM accesses the database synchronously.
+pub(super) struct Database {
+    web3: DynWeb3,
+    block: u64,
+    cache: Arc<Mutex<Cache>>,
+    rt: tokio::runtime::Handle,
+}
+
+impl Database {
+    pub(super) fn new(
+        web3: DynWeb3,
+        block: u64,
+        cache: Arc<Mutex<Cache>>,
+        rt: tokio::runtime::Handle,
+    ) -> Self {
+        Self {
+            web3,
+            block,
+            cache,
+            rt,
+        }
+    }
+
+    fn block(&self) -> Option<BlockNumber> {
+        Some(BlockNumber::Number(self.block.into()))
+    }
+
+    /// Accesses the cache if it still holds the state of the simulated block.
+    fn cached<T>(&self, f: impl FnOnce(&mut Cache) -> T) -> Option<T> {
+        let mut cache = self.cache.lock().unwrap();
+        (cache.block == self.block).then(|| f(&mut cache))
+    }
+}
+
+impl revm::Database for Database {
+    type Error = web3::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(Some(info)) = self.cached(|cache| cache.accounts.get(&address).cloned()) {
+            return Ok(Some(info));
+        }
+        let eth = self.web3.eth();
+        let h160 = convert::h160(address);
+        let (balance, nonce, code) = self.rt.block_on(try_join3(
+            eth.balance(h160, self.block()),
+            eth.transaction_count(h160, self.block()),
+            eth.code(h160, self.block()),
+        ))?;
+        let code = Bytecode::new_raw(code.0.into());
+        let info = AccountInfo::new(
+            convert::u256(balance),
+            nonce.as_u64(),
+            code.hash_slow(),
+            code.clone(),
+        );
+        self.cached(|cache| {
+            cache.code.insert(info.code_hash, code);
+            cache.accounts.insert(address, info.clone());
+        });
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // The code is always fetched together with the account.
+        Ok(self
+            .cached(|cache| cache.code.get(&code_hash).cloned())
+            .flatten()
+            .unwrap_or_default())
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(Some(value)) =
+            self.cached(|cache| cache.storage.get(&(address, index)).copied())
+        {
+            return Ok(value);
+        }
+        let value = self.rt.block_on(self.web3.eth().storage(
+            convert::h160(address),
+            convert::eth_u256(index),
+            self.block(),
+        ))?;
+        let value = U256::from_be_bytes(value.0);
+        self.cached(|cache| cache.storage.insert((address, index), value));
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        if let Some(Some(hash)) = self.cached(|cache| cache.block_hashes.get(&number).copied()) {
+            return Ok(hash);
+        }
+        let block =
+            self.rt
+                .block_on(self.web3.eth().block(BlockId::Number(BlockNumber::Number(
+                    number.to::<u64>().into(),
+                ))))?;
+        let hash = block
+            .and_then(|block| block.hash)
+            .map(|hash| B256::from(hash.0))
+            .unwrap_or_default();
+        self.cached(|cache| cache.block_hashes.insert(number, hash));
+        Ok(hash)
+    }
+}
@@ -0,0 +1,288 @@
+//! Simulates transactions with an embedded EVM on a local fork of the chain.
+//! The state of the current block is lazily fetched from the node, so only the
+//! accounts and storage slots which a transaction accesses are requested.
+
+use {
+    crate::{domain::eth, infra::blockchain::Ethereum},
+    revm::primitives::{
+        EVMError,
+        ExecutionResult,
+        ResultAndState,
+        SpecId,
+        State,
+        TransactTo,
+        B256,
+        U256,
+    },
+    std::sync::{Arc, Mutex},
+    web3::types::{BlockId, BlockNumber},
+};
+
+mod database;
+pub mod trace;
+
+/// The header of the block a transaction is simulated in.
+type Header = web3::types::Block<eth::H256>;
+
+#[derive(Debug, Clone)]
+pub(super) struct Fork {
+    eth: Ethereum,
+    cache: Arc<Mutex<database::Cache>>,
+}
+
+/// The result of a successful simulation.
+#[derive(Debug)]
+pub(super) struct Simulation {
+    pub gas: eth::Gas,
+    /// The accounts and storage slots accessed by the transaction.
+    pub access_list: eth::AccessList,
+    /// The calls of the transaction.
+    pub trace: Option<trace::Call>,
+}
+
+impl Fork {
+    pub(super) fn new(eth: Ethereum) -> Self {
+        Self {
+            eth,
+            cache: Default::default(),
+        }
+    }
+
+    /// Executes the transaction on top of the current block. Like `eth_call`,
+    /// the transaction is executed with the context of that block.
+    pub(super) async fn simulate(&self, tx: eth::Tx) -> Result<Simulation, Error> {
+        let block = self.eth.current_block().borrow().number;
+        let header = self
+            .eth
+            .web3()
+            .eth()
+            .block(BlockId::Number(BlockNumber::Number(block.into())))
+            .await?
+            .ok_or(Error::MissingBlock(block))?;
+        self.cache.lock().unwrap().at(block);
+        let database = database::Database::new(
+            self.eth.web3().clone(),
+            block,
+            self.cache.clone(),
+            tokio::runtime::Handle::current(),
+        );
+        let chain_id = self.eth.network().chain.into();
+        // The EVM accesses the database synchronously, so the simulation runs
+        // on a blocking thread which waits for the state fetched from the node.
+        tokio::task::spawn_blocking(move || execute(database, chain_id, &header, tx))
+            .await
+            .expect(
+                "Either runtime was shut down before spawning the task or no OS threads are \
+                 available; no sense in handling those errors",
+            )
+    }
+}
+
+fn execute(
+    database: database::Database,
+    chain_id: u64,
+    header: &Header,
+    tx: eth::Tx,
+) -> Result<Simulation, Error> {
+    let timestamp = header.timestamp.low_u64();
+    let mut evm = revm::EVM::new();
+    evm.database(database);
+    evm.env.cfg.chain_id = chain_id;
+    evm.env.cfg.spec_id = spec(chain_id, timestamp);
+    // Transactions are simulated without paying for gas, like `eth_call`
+    // without a gas price. The base fee is still visible to contracts.
+    evm.env.cfg.disable_base_fee = true;
+    evm.env.block.number = U256::from(header.number.unwrap_or_default().as_u64());
+    evm.env.block.timestamp = U256::from(timestamp);
+    evm.env.block.gas_limit = convert::u256(header.gas_limit);
+    evm.env.block.basefee = convert::u256(header.base_fee_per_gas.unwrap_or_default());
+    evm.env.block.coinbase = convert::address(header.author);
+    evm.env.block.difficulty = convert::u256(header.difficulty);
+    evm.env.block.prevrandao = header.mix_hash.map(|hash| B256::from(hash.0));
+    evm.env.tx.caller = convert::address(tx.from.into());
+    evm.env.tx.transact_to = TransactTo::Call(convert::address(tx.to.into()));
+    evm.env.tx.data = Vec::<u8>::from(tx.input.clone()).into();
+    evm.env.tx.value = convert::u256(tx.value.into());
+    evm.env.tx.gas_limit = header.gas_limit.low_u64();
+    evm.env.tx.gas_price = U256::ZERO;
+    evm.env.tx.access_list = web3::types::AccessList::from(tx.access_list.clone())
+        .into_iter()
+        .map(|item| {
+            (
+                convert::address(item.address),
+                item.storage_keys
+                    .into_iter()
+                    .map(|key| U256::from_be_bytes(key.0))
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut tracer = trace::Tracer::default();
+    let ResultAndState { result, state } = evm.inspect(&mut tracer).map_err(|err| match err {
+        EVMError::Database(err) => Error::Rpc(err),
+        err => Error::Evm(format!("{err:?}")),
+    })?;
+    match result {
+        ExecutionResult::Success { gas_used, .. } => Ok(Simulation {
+            gas: eth::U256::from(gas_used).into(),
+            access_list: access_list(&tx, &state),
+            trace: tracer.into_trace(),
+        }),
+        ExecutionResult::Revert { output, .. } => Err(Error::Revert {
+            reason: trace::revert_reason(&output),
+            trace: tracer.into_trace(),
+        }),
+        ExecutionResult::Halt { reason, .. } => Err(Error::Halt {
+            reason: format!("{reason:?}"),
+            trace: tracer.into_trace(),
+        }),
+    }
+}
+
+/// The EVM version at a timestamp of a chain. Unknown chains are simulated with
+/// the latest version.
+fn spec(chain_id: u64, timestamp: u64) -> SpecId {
+    // The activation timestamps of the Shanghai and Cancun upgrades.
+    let (shanghai, cancun) = match chain_id {
+        1 => (1_681_338_455, 1_710_338_135),
+        5 => (1_678_832_736, 1_705_473_120),
+        100 => (1_690_889_660, 1_710_181_820),
+        11155111 => (1_677_557_088, 1_706_655_072),
+        _ => return SpecId::LATEST,
+    };
+    if timestamp >= cancun {
+        SpecId::CANCUN
+    } else if timestamp >= shanghai {
+        SpecId::SHANGHAI
+    } else {
+        SpecId::MERGE
+    }
+}
+
+/// The access list of all accounts and storage slots that were loaded while
+/// executing the transaction. Like `eth_createAccessList`, this excludes the
+/// sender, the recipient unless its storage was accessed, and precompiles.
+fn access_list(tx: &eth::Tx, state: &State) -> eth::AccessList {
+    let excluded = [tx.from, tx.to].map(|address| convert::address(address.into()));
+    let items = state
+        .iter()
+        .filter(|(address, account)| {
+            !is_precompile(address) && !(excluded.contains(address) && account.storage.is_empty())
+        })
+        .map(|(address, account)| web3::types::AccessListItem {
+            address: convert::h160(*address),
+            storage_keys: account
+                .storage
+                .keys()
+                .map(|key| eth::H256(key.to_be_bytes()))
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    items.into()
+}
+
+/// Precompiles and the zero address, which is the default block beneficiary.
+fn is_precompile(address: &revm::primitives::Address) -> bool {
+    let bytes = address.as_slice();
+    bytes[..19].iter().all(|byte| *byte == 0) && bytes[19] <= 0x0a
+}
+
+/// Conversions between the EVM types and the driver types.
+mod convert {
+    use {crate::domain::eth, revm::primitives};
+
+    pub fn address(address: eth::H160) -> primitives::Address {
+        primitives::Address::from(address.0)
+    }
+
+    pub fn h160(address: primitives::Address) -> eth::H160 {
+        eth::H160::from_slice(address.as_slice())
+    }
+
+    pub fn u256(value: eth::U256) -> primitives::U256 {
+        let mut bytes = [0; 32];
+        value.to_big_endian(&mut bytes);
+        primitives::U256::from_be_bytes(bytes)
+    }
+
+    pub fn eth_u256(value: primitives::U256) -> eth::U256 {
+        eth::U256::from_big_endian(&value.to_be_bytes::<32>())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("RPC error: {0:?}")]
+    Rpc(#[from] web3::Error),
+    #[error("EVM error: {0}")]
+    Evm(String),
+    #[error("block {0} not found")]
+    MissingBlock(u64),
+    #[error("execution reverted: {reason}")]
+    Revert {
+        reason: String,
+        trace: Option<trace::Call>,
+    },
+    #[error("execution halted: {reason}")]
+    Halt {
+        reason: String,
+        trace: Option<trace::Call>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ethrpc::{create_env_test_transport, Web3},
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn simulates_on_fork() {
+        let web3 = Web3::new(create_env_test_transport());
+        let block = web3.eth().block_number().await.unwrap().as_u64();
+        let header = web3
+            .eth()
+            .block(BlockId::Number(BlockNumber::Number(block.into())))
+            .await
+            .unwrap()
+            .unwrap();
+        let cache = Arc::new(Mutex::new(database::Cache::default()));
+        cache.lock().unwrap().at(block);
+        let database =
+            database::Database::new(web3, block, cache, tokio::runtime::Handle::current());
+
+        // WETH.balanceOf(WETH)
+        let weth = eth::H160(hex_literal::hex!(
+            "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+        ));
+        let input = [
+            hex_literal::hex!("70a08231").as_slice(),
+            &ethabi::encode(&[ethabi::Token::Address(weth)]),
+        ]
+        .concat();
+        let tx = eth::Tx {
+            from: eth::H160::repeat_byte(1).into(),
+            to: weth.into(),
+            value: eth::U256::zero().into(),
+            input: input.into(),
+            access_list: Default::default(),
+        };
+
+        let simulation = tokio::task::spawn_blocking(move || execute(database, 1, &header, tx))
+            .await
+            .unwrap()
+            .unwrap();
+        let trace = simulation.trace.unwrap();
+        assert!(trace.success);
+        assert_eq!(trace.to, weth);
+        assert_eq!(trace.output.len(), 32);
+        assert!(simulation.gas.0 > 21_000.into());
+        let access_list = web3::types::AccessList::from(simulation.access_list);
+        assert_eq!(access_list.len(), 1);
+        assert_eq!(access_list[0].address, weth);
+        assert_eq!(access_list[0].storage_keys.len(), 1);
+    }
+}
@@ -0,0 +1,171 @@
+//! Call traces of simulated transactions.
+
+use {
+    super::convert,
+    crate::domain::eth,
+    revm::{
+        interpreter::{CallInputs, Gas, InstructionResult},
+        primitives::Bytes,
+        EVMData,
+    },
+    std::fmt::{self, Display, Formatter},
+};
+
+/// A call of a simulated transaction, including all nested calls.
+#[derive(Clone)]
+pub struct Call {
+    pub from: eth::H160,
+    pub to: eth::H160,
+    pub value: eth::U256,
+    pub input: Vec<u8>,
+    pub output: Vec<u8>,
+    pub gas_used: u64,
+    pub success: bool,
+    pub calls: Vec<Call>,
+}
+
+impl Call {
+    /// The revert reason of this call, decoded if the output is an
+    /// `Error(string)` or `Panic(uint256)`.
+    pub fn revert_reason(&self) -> Option<String> {
+        (!self.success).then(|| revert_reason(&self.output))
+    }
+
+    fn fmt_indented(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        let selector = self.input.get(..4).map(hex::encode).unwrap_or_default();
+        write!(
+            f,
+            "{:indent$}{:?} -> {:?} 0x{selector} value={} gas={}",
+            "",
+            self.from,
+            self.to,
+            self.value,
+            self.gas_used,
+            indent = depth * 2,
+        )?;
+        if let Some(reason) = self.revert_reason() {
+            write!(f, " reverted: {reason}")?;
+        }
+        writeln!(f)?;
+        for call in &self.calls {
+            call.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl fmt::Debug for Call {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Decodes the revert reason from the output of a reverted call.
+pub fn revert_reason(output: &[u8]) -> String {
+    const ERROR: &[u8] = &hex_literal::hex!("08c379a0");
+    const PANIC: &[u8] = &hex_literal::hex!("4e487b71");
+
+    let (selector, data) = output.split_at(output.len().min(4));
+    let decoded = match selector {
+        ERROR => ethabi::decode(&[ethabi::ParamType::String], data)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_string()),
+        PANIC => ethabi::decode(&[ethabi::ParamType::Uint(256)], data)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next()?.into_uint())
+            .map(|code| format!("panic 0x{code:x}")),
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| format!("0x{}", hex::encode(output)))
+}
+
+/// Records the calls of a transaction.
+#[derive(Debug, Default)]
+pub(super) struct Tracer {
+    /// The calls which did not return yet. The first call is the transaction
+    /// itself.
+    stack: Vec<Call>,
+    root: Option<Call>,
+}
+
+impl Tracer {
+    /// The trace of the transaction. [`None`] if the transaction did not
+    /// execute any call.
+    pub(super) fn into_trace(self) -> Option<Call> {
+        self.root
+    }
+}
+
+impl<DB: revm::Database> revm::Inspector<DB> for &mut Tracer {
+    fn call(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.stack.push(Call {
+            from: convert::h160(inputs.context.caller),
+            to: convert::h160(inputs.contract),
+            value: convert::eth_u256(inputs.transfer.value),
+            input: inputs.input.to_vec(),
+            output: Default::default(),
+            gas_used: 0,
+            success: false,
+            calls: Default::default(),
+        });
+        (InstructionResult::Continue, Gas::new(0), Bytes::new())
+    }
+
+    fn call_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        inputs: &CallInputs,
+        remaining_gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+    ) -> (InstructionResult, Gas, Bytes) {
+        if let Some(mut call) = self.stack.pop() {
+            call.output = out.to_vec();
+            call.gas_used = inputs.gas_limit.saturating_sub(remaining_gas.remaining());
+            call.success = matches!(
+                ret,
+                InstructionResult::Continue
+                    | InstructionResult::Stop
+                    | InstructionResult::Return
+                    | InstructionResult::SelfDestruct
+            );
+            match self.stack.last_mut() {
+                Some(parent) => parent.calls.push(call),
+                None => self.root = Some(call),
+            }
+        }
+        (ret, remaining_gas, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_revert_reasons() {
+        let error = [
+            hex_literal::hex!("08c379a0").as_slice(),
+            &ethabi::encode(&[ethabi::Token::String("GPv2: order expired".to_owned())]),
+        ]
+        .concat();
+        assert_eq!(revert_reason(&error), "GPv2: order expired");
+
+        let panic =
+            hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011")
+                .unwrap();
+        assert_eq!(revert_reason(&panic), "panic 0x11");
+
+        assert_eq!(revert_reason(&[0xde, 0xad]), "0xdead");
+    }
+}
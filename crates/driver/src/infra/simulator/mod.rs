@@ -8,6 +8,7 @@ use {
 };
 
 pub mod enso;
+pub mod fork;
 pub mod tenderly;
 
 /// Ethereum transaction simulator.
@@ -22,10 +23,11 @@ pub struct Simulator {
 }
 
 /// Configuration of the transaction simulator.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Config {
     Tenderly(tenderly::Config),
     Enso(enso::Config),
+    Fork,
 }
 
 impl Simulator {
@@ -60,6 +62,17 @@ impl Simulator {
         }
     }
 
+    /// Simulate transactions with an embedded EVM on a local fork of the chain,
+    /// which fetches the state it needs from the Ethereum RPC API.
+    pub fn fork(eth: Ethereum) -> Self {
+        Self {
+            inner: Inner::Fork(fork::Fork::new(eth.clone())),
+            eth,
+            disable_access_lists: false,
+            disable_gas: None,
+        }
+    }
+
     /// Disable access list simulation. Some environments, such as less popular
     /// blockchains, don't support access list simulation.
     pub fn disable_access_lists(&mut self) {
@@ -98,6 +111,15 @@ impl Simulator {
                 .create_access_list(tx.clone())
                 .await
                 .map_err(with(tx.clone(), block))?,
+            Inner::Fork(fork) => {
+                let simulation = fork
+                    .simulate(tx.clone())
+                    .measure("fork_simulate_access_list")
+                    .await
+                    .map_err(with(tx.clone(), block))?;
+                tracing::trace!(trace = ?simulation.trace, "simulated access list");
+                simulation.access_list
+            }
         };
         Ok(tx.access_list.merge(access_list))
     }
@@ -127,6 +149,15 @@ impl Simulator {
                 .measure("enso_simulate_gas")
                 .await
                 .map_err(with(tx, block))?,
+            Inner::Fork(fork) => {
+                let simulation = fork
+                    .simulate(tx.clone())
+                    .measure("fork_simulate_gas")
+                    .await
+                    .map_err(with(tx, block))?;
+                tracing::trace!(trace = ?simulation.trace, "simulated gas");
+                simulation.gas
+            }
         })
     }
 }
@@ -136,6 +167,7 @@ enum Inner {
     Tenderly(tenderly::Tenderly),
     Ethereum,
     Enso(enso::Enso),
+    Fork(fork::Fork),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -146,6 +178,8 @@ pub enum SimulatorError {
     Blockchain(#[from] blockchain::Error),
     #[error("enso error: {0:?}")]
     Enso(#[from] enso::Error),
+    #[error("fork simulation error: {0:?}")]
+    Fork(#[from] fork::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -195,6 +229,10 @@ where
             SimulatorError::Blockchain(blockchain::Error::AccessList(_)) => Some(tx),
             SimulatorError::Enso(enso::Error::Http(_)) => None,
             SimulatorError::Enso(enso::Error::Revert(_)) => Some(tx),
+            SimulatorError::Fork(
+                fork::Error::Rpc(_) | fork::Error::Evm(_) | fork::Error::MissingBlock(_),
+            ) => None,
+            SimulatorError::Fork(fork::Error::Revert { .. } | fork::Error::Halt { .. }) => Some(tx),
         };
         match tx {
             Some(tx) => Error::Revert(RevertError { err, tx, block }),
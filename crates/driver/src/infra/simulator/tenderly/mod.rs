@@ -12,7 +12,7 @@ pub(super) struct Tenderly {
     network_id: eth::NetworkId,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     /// The URL of the Tenderly API.
     pub url: Option<reqwest::Url>,
@@ -0,0 +1,242 @@
+//! The accounts which sign the settlements of a solver.
+
+use {
+    crate::{domain::eth, infra::blockchain::Ethereum},
+    ethrpc::current_block,
+    futures::StreamExt,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex, Weak},
+    },
+    tracing::Instrument,
+};
+
+/// The accounts of a solver. A solver can have multiple pending settlements
+/// at a time, each of them is submitted by a different account.
+#[derive(Debug, Clone)]
+pub struct Accounts {
+    accounts: Arc<Vec<ethcontract::Account>>,
+    state: State,
+}
+
+impl Accounts {
+    /// Panics if no accounts are specified.
+    pub fn new(accounts: Vec<ethcontract::Account>, state: State) -> Self {
+        assert!(!accounts.is_empty(), "solver needs at least one account");
+        {
+            let mut inner = state.0.lock().unwrap();
+            for account in &accounts {
+                inner.entry(account.address().into()).or_default();
+            }
+        }
+        Self {
+            accounts: Arc::new(accounts),
+            state,
+        }
+    }
+
+    /// The primary account of the solver, which is the first configured
+    /// account.
+    pub fn primary(&self) -> &ethcontract::Account {
+        &self.accounts[0]
+    }
+
+    /// All accounts of the solver.
+    pub fn all(&self) -> &[ethcontract::Account] {
+        &self.accounts
+    }
+
+    /// The account with the specified address.
+    pub fn get(&self, address: eth::Address) -> Option<&ethcontract::Account> {
+        self.accounts
+            .iter()
+            .find(|account| eth::Address::from(account.address()) == address)
+    }
+
+    /// The accounts which don't have a settlement being submitted.
+    pub fn available(&self) -> Vec<&ethcontract::Account> {
+        let state = self.state.0.lock().unwrap();
+        self.accounts
+            .iter()
+            .filter(|account| {
+                state
+                    .get(&account.address().into())
+                    .map_or(true, |account| account.pending.is_none())
+            })
+            .collect()
+    }
+
+    /// The latest known balance of the account. This is updated in the
+    /// background on every new block, so it is unknown until the first
+    /// update after the account was added.
+    pub fn balance(&self, address: eth::Address) -> Option<eth::Ether> {
+        self.state
+            .0
+            .lock()
+            .unwrap()
+            .get(&address)
+            .and_then(|account| account.balance)
+    }
+
+    /// Reserves the account for a settlement which is submitted with the
+    /// specified on-chain `nonce`, until the returned guard is dropped. Fails
+    /// if the account is still submitting another settlement, since that
+    /// settlement would otherwise be replaced or block this one.
+    pub fn submitting(
+        &self,
+        address: eth::Address,
+        nonce: eth::U256,
+    ) -> Result<Pending, NoAccountAvailable> {
+        let mut state = self.state.0.lock().unwrap();
+        let account = state.entry(address).or_default();
+        if account.pending.is_some() {
+            return Err(NoAccountAvailable);
+        }
+        account.pending = Some(nonce);
+        Ok(Pending {
+            state: self.state.clone(),
+            address,
+            nonce,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("no account available")]
+pub struct NoAccountAvailable;
+
+/// The state of all solver accounts of the driver. It is shared by all
+/// solvers and kept when the configuration is reloaded, so accounts which are
+/// still submitting a settlement stay reserved.
+#[derive(Debug, Clone, Default)]
+pub struct State(Arc<Mutex<HashMap<eth::Address, Account>>>);
+
+#[derive(Debug, Default)]
+struct Account {
+    /// The nonce of the settlement which is currently being submitted.
+    pending: Option<eth::U256>,
+    balance: Option<eth::Ether>,
+}
+
+impl State {
+    /// Creates the account state and keeps the balances of the accounts up to
+    /// date in the background.
+    pub fn new(eth: &Ethereum) -> Self {
+        let state = Self::default();
+        tokio::task::spawn(
+            update_task(eth.clone(), Arc::downgrade(&state.0))
+                .instrument(tracing::info_span!("solver_accounts")),
+        );
+        state
+    }
+}
+
+/// Updates the balances of the accounts whenever a new block arrives until
+/// the state is dropped.
+async fn update_task(eth: Ethereum, state: Weak<Mutex<HashMap<eth::Address, Account>>>) {
+    let mut stream = current_block::into_stream(eth.current_block().clone());
+    while stream.next().await.is_some() {
+        let addresses = match state.upgrade() {
+            Some(state) => state.lock().unwrap().keys().copied().collect::<Vec<_>>(),
+            // State was dropped, stop update task.
+            None => break,
+        };
+        let balances = futures::future::join_all(addresses.into_iter().map(|address| {
+            let eth = &eth;
+            async move { (address, eth.balance(address).await) }
+        }))
+        .await;
+        let Some(state) = state.upgrade() else {
+            break;
+        };
+        let mut state = state.lock().unwrap();
+        for (address, balance) in balances {
+            let Some(account) = state.get_mut(&address) else {
+                continue;
+            };
+            match balance {
+                Ok(balance) => account.balance = Some(balance),
+                Err(err) => {
+                    tracing::warn!(?address, ?err, "failed to fetch solver account balance")
+                }
+            }
+        }
+    }
+}
+
+/// A settlement which is being submitted. The account is available again once
+/// this is dropped.
+#[derive(Debug)]
+pub struct Pending {
+    state: State,
+    address: eth::Address,
+    nonce: eth::U256,
+}
+
+impl Pending {
+    /// The nonce which the settlement is submitted with.
+    pub fn nonce(&self) -> eth::U256 {
+        self.nonce
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if let Some(account) = self.state.0.lock().unwrap().get_mut(&self.address) {
+            account.pending = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(address: u64) -> ethcontract::Account {
+        ethcontract::Account::Local(eth::H160::from_low_u64_be(address), None)
+    }
+
+    #[test]
+    fn tracks_pending_settlements() {
+        let accounts = Accounts::new(vec![account(1), account(2)], Default::default());
+        let address = eth::H160::from_low_u64_be(1).into();
+
+        let pending = accounts.submitting(address, 7.into()).unwrap();
+        assert_eq!(pending.nonce(), 7.into());
+        let available = accounts.available();
+        assert_eq!(available.len(), 1);
+        assert_eq!(available[0].address(), eth::H160::from_low_u64_be(2));
+
+        drop(pending);
+        assert_eq!(accounts.available().len(), 2);
+    }
+
+    #[test]
+    fn rejects_busy_accounts() {
+        let accounts = Accounts::new(vec![account(1)], Default::default());
+        let address = eth::H160::from_low_u64_be(1).into();
+
+        let first = accounts.submitting(address, 7.into()).unwrap();
+        assert!(accounts.submitting(address, 7.into()).is_err());
+        assert!(accounts.available().is_empty());
+
+        // The next settlement uses the on-chain nonce once the account is free.
+        drop(first);
+        let second = accounts.submitting(address, 8.into()).unwrap();
+        assert_eq!(second.nonce(), 8.into());
+    }
+
+    #[test]
+    fn keeps_state_across_reloads() {
+        let state = State::default();
+        let address = eth::H160::from_low_u64_be(1).into();
+        let accounts = Accounts::new(vec![account(1)], state.clone());
+        let pending = accounts.submitting(address, 7.into()).unwrap();
+
+        let reloaded = Accounts::new(vec![account(2), account(1)], state);
+        assert_eq!(reloaded.available().len(), 1);
+        assert!(reloaded.submitting(address, 7.into()).is_err());
+        drop(pending);
+        assert_eq!(reloaded.available().len(), 2);
+    }
+}
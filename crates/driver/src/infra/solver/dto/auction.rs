@@ -1,6 +1,7 @@
 use {
     crate::{
         domain::{competition, competition::order, eth, liquidity},
+        infra,
         util::{
             conv::{rational_to_big_decimal, u256::U256Ext},
             serialize,
@@ -17,6 +18,7 @@ impl Auction {
         auction: &competition::Auction,
         liquidity: &[liquidity::Liquidity],
         weth: eth::WethAddress,
+        feed: Option<infra::liquidity::feed::Reference>,
     ) -> Self {
         let mut tokens: HashMap<eth::H160, _> = auction
             .tokens()
@@ -47,6 +49,9 @@ impl Auction {
                 liquidity::Kind::Swapr(pool) => {
                     pool.base.reserves.iter().map(|r| r.token).collect()
                 }
+                liquidity::Kind::Solidly(pool) => {
+                    pool.reserves.iter().map(|r| r.asset.token).collect()
+                }
                 liquidity::Kind::ZeroEx(_) => todo!(),
             })
         {
@@ -77,126 +82,22 @@ impl Auction {
                             competition::order::Kind::Limit { .. } => Class::Limit,
                             competition::order::Kind::Liquidity => Class::Liquidity,
                         },
+                        cow_only: order.cow_only,
                     }
                 })
                 .collect(),
-            liquidity: liquidity
-                .iter()
-                .map(|liquidity| match &liquidity.kind {
-                    liquidity::Kind::UniswapV2(pool) => {
-                        Liquidity::ConstantProduct(ConstantProductPool {
-                            id: liquidity.id.into(),
-                            address: pool.address.into(),
-                            gas_estimate: liquidity.gas.into(),
-                            tokens: pool
-                                .reserves
-                                .iter()
-                                .map(|asset| {
-                                    (
-                                        asset.token.into(),
-                                        ConstantProductReserve {
-                                            balance: asset.amount.into(),
-                                        },
-                                    )
-                                })
-                                .collect(),
-                            fee: bigdecimal::BigDecimal::new(3.into(), 3),
-                        })
-                    }
-                    liquidity::Kind::UniswapV3(pool) => {
-                        Liquidity::ConcentratedLiquidity(ConcentratedLiquidityPool {
-                            id: liquidity.id.into(),
-                            address: pool.address.0,
-                            gas_estimate: liquidity.gas.0,
-                            tokens: vec![pool.tokens.get().0.into(), pool.tokens.get().1.into()],
-                            sqrt_price: pool.sqrt_price.0,
-                            liquidity: pool.liquidity.0,
-                            tick: pool.tick.0,
-                            liquidity_net: pool
-                                .liquidity_net
-                                .iter()
-                                .map(|(key, value)| (key.0, value.0))
-                                .collect(),
-                            fee: rational_to_big_decimal(&pool.fee.0),
-                        })
-                    }
-                    liquidity::Kind::BalancerV2Stable(pool) => Liquidity::Stable(StablePool {
-                        id: liquidity.id.into(),
-                        address: pool.id.address().into(),
-                        gas_estimate: liquidity.gas.into(),
-                        tokens: pool
-                            .reserves
-                            .iter()
-                            .map(|r| {
-                                (
-                                    r.asset.token.into(),
-                                    StableReserve {
-                                        balance: r.asset.amount.into(),
-                                        scaling_factor: scaling_factor_to_decimal(r.scale),
-                                    },
-                                )
-                            })
-                            .collect(),
-                        amplification_parameter: rational_to_big_decimal(&num::BigRational::new(
-                            pool.amplification_parameter.factor().to_big_int(),
-                            pool.amplification_parameter.precision().to_big_int(),
-                        )),
-                        fee: fee_to_decimal(pool.fee),
-                    }),
-                    liquidity::Kind::BalancerV2Weighted(pool) => {
-                        Liquidity::WeightedProduct(WeightedProductPool {
-                            id: liquidity.id.into(),
-                            address: pool.id.address().into(),
-                            gas_estimate: liquidity.gas.into(),
-                            tokens: pool
-                                .reserves
-                                .iter()
-                                .map(|r| {
-                                    (
-                                        r.asset.token.into(),
-                                        WeightedProductReserve {
-                                            balance: r.asset.amount.into(),
-                                            scaling_factor: scaling_factor_to_decimal(r.scale),
-                                            weight: weight_to_decimal(r.weight),
-                                        },
-                                    )
-                                })
-                                .collect(),
-                            fee: fee_to_decimal(pool.fee),
-                            version: match pool.version {
-                                liquidity::balancer::v2::weighted::Version::V0 => {
-                                    WeightedProductVersion::V0
-                                }
-                                liquidity::balancer::v2::weighted::Version::V3Plus => {
-                                    WeightedProductVersion::V3Plus
-                                }
-                            },
-                        })
-                    }
-                    liquidity::Kind::Swapr(pool) => {
-                        Liquidity::ConstantProduct(ConstantProductPool {
-                            id: liquidity.id.into(),
-                            address: pool.base.address.into(),
-                            gas_estimate: liquidity.gas.into(),
-                            tokens: pool
-                                .base
-                                .reserves
-                                .iter()
-                                .map(|asset| {
-                                    (
-                                        asset.token.into(),
-                                        ConstantProductReserve {
-                                            balance: asset.amount.into(),
-                                        },
-                                    )
-                                })
-                                .collect(),
-                            fee: bigdecimal::BigDecimal::new(pool.fee.bps().into(), 4),
-                        })
-                    }
-                    liquidity::Kind::ZeroEx(_) => todo!(),
-                })
-                .collect(),
+            liquidity: match feed {
+                Some(_) => Default::default(),
+                None => liquidity.iter().map(Liquidity::new).collect(),
+            },
+            liquidity_feed: feed.map(|feed| LiquidityFeed {
+                block: feed.block,
+                update: feed.update,
+                ids: liquidity
+                    .iter()
+                    .map(|liquidity| liquidity.id.into())
+                    .collect(),
+            }),
             tokens,
             effective_gas_price: auction.gas_price().effective().into(),
             deadline: auction.deadline().solvers(),
@@ -212,11 +113,53 @@ pub struct Auction {
     tokens: HashMap<eth::H160, Token>,
     orders: Vec<Order>,
     liquidity: Vec<Liquidity>,
+    /// Set instead of `liquidity` for solvers that keep their own liquidity
+    /// state from the liquidity feed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    liquidity_feed: Option<LiquidityFeed>,
     #[serde_as(as = "serialize::U256")]
     effective_gas_price: eth::U256,
     deadline: chrono::DateTime<chrono::Utc>,
 }
 
+/// An update of the liquidity feed. The liquidity has the same format as in
+/// auctions and is keyed by its ID, which is stable across updates.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityUpdate<'a> {
+    block: u64,
+    update: u64,
+    liquidity: &'a [serde_json::Value],
+    removed: Vec<usize>,
+}
+
+impl<'a> LiquidityUpdate<'a> {
+    pub fn new(update: &'a infra::liquidity::feed::Update) -> Self {
+        Self {
+            block: update.block,
+            update: update.update,
+            liquidity: &update.liquidity,
+            removed: update.removed.iter().copied().map(Into::into).collect(),
+        }
+    }
+
+    /// Serializes liquidity the same way as it is included in auctions.
+    pub fn serialize(liquidity: &liquidity::Liquidity) -> serde_json::Value {
+        serde_json::to_value(Liquidity::new(liquidity)).unwrap()
+    }
+}
+
+/// References the liquidity feed update that solvers need to have applied to
+/// their liquidity state before solving the auction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LiquidityFeed {
+    block: u64,
+    update: u64,
+    /// The IDs of the feed liquidity which can be used for the auction.
+    ids: Vec<usize>,
+}
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -234,6 +177,7 @@ struct Order {
     kind: Kind,
     partially_fillable: bool,
     class: Class,
+    cow_only: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -273,9 +217,145 @@ enum Liquidity {
     WeightedProduct(WeightedProductPool),
     Stable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
+    Solidly(SolidlyPool),
     LimitOrder(ForeignLimitOrder),
 }
 
+impl Liquidity {
+    fn new(liquidity: &liquidity::Liquidity) -> Self {
+        match &liquidity.kind {
+            liquidity::Kind::UniswapV2(pool) => Liquidity::ConstantProduct(ConstantProductPool {
+                id: liquidity.id.into(),
+                address: pool.address.into(),
+                gas_estimate: liquidity.gas.into(),
+                tokens: pool
+                    .reserves
+                    .iter()
+                    .map(|asset| {
+                        (
+                            asset.token.into(),
+                            ConstantProductReserve {
+                                balance: asset.amount.into(),
+                            },
+                        )
+                    })
+                    .collect(),
+                fee: bigdecimal::BigDecimal::new(3.into(), 3),
+            }),
+            liquidity::Kind::UniswapV3(pool) => {
+                Liquidity::ConcentratedLiquidity(ConcentratedLiquidityPool {
+                    id: liquidity.id.into(),
+                    address: pool.address.0,
+                    gas_estimate: liquidity.gas.0,
+                    tokens: vec![pool.tokens.get().0.into(), pool.tokens.get().1.into()],
+                    sqrt_price: pool.sqrt_price.0,
+                    liquidity: pool.liquidity.0,
+                    tick: pool.tick.0,
+                    liquidity_net: pool
+                        .liquidity_net
+                        .iter()
+                        .map(|(key, value)| (key.0, value.0))
+                        .collect(),
+                    fee: rational_to_big_decimal(&pool.fee.0),
+                })
+            }
+            liquidity::Kind::BalancerV2Stable(pool) => Liquidity::Stable(StablePool {
+                id: liquidity.id.into(),
+                address: pool.id.address().into(),
+                gas_estimate: liquidity.gas.into(),
+                tokens: pool
+                    .reserves
+                    .iter()
+                    .map(|r| {
+                        (
+                            r.asset.token.into(),
+                            StableReserve {
+                                balance: r.asset.amount.into(),
+                                scaling_factor: scaling_factor_to_decimal(r.scale),
+                            },
+                        )
+                    })
+                    .collect(),
+                amplification_parameter: rational_to_big_decimal(&num::BigRational::new(
+                    pool.amplification_parameter.factor().to_big_int(),
+                    pool.amplification_parameter.precision().to_big_int(),
+                )),
+                fee: fee_to_decimal(pool.fee),
+            }),
+            liquidity::Kind::BalancerV2Weighted(pool) => {
+                Liquidity::WeightedProduct(WeightedProductPool {
+                    id: liquidity.id.into(),
+                    address: pool.id.address().into(),
+                    gas_estimate: liquidity.gas.into(),
+                    tokens: pool
+                        .reserves
+                        .iter()
+                        .map(|r| {
+                            (
+                                r.asset.token.into(),
+                                WeightedProductReserve {
+                                    balance: r.asset.amount.into(),
+                                    scaling_factor: scaling_factor_to_decimal(r.scale),
+                                    weight: weight_to_decimal(r.weight),
+                                },
+                            )
+                        })
+                        .collect(),
+                    fee: fee_to_decimal(pool.fee),
+                    version: match pool.version {
+                        liquidity::balancer::v2::weighted::Version::V0 => {
+                            WeightedProductVersion::V0
+                        }
+                        liquidity::balancer::v2::weighted::Version::V3Plus => {
+                            WeightedProductVersion::V3Plus
+                        }
+                    },
+                })
+            }
+            liquidity::Kind::Swapr(pool) => Liquidity::ConstantProduct(ConstantProductPool {
+                id: liquidity.id.into(),
+                address: pool.base.address.into(),
+                gas_estimate: liquidity.gas.into(),
+                tokens: pool
+                    .base
+                    .reserves
+                    .iter()
+                    .map(|asset| {
+                        (
+                            asset.token.into(),
+                            ConstantProductReserve {
+                                balance: asset.amount.into(),
+                            },
+                        )
+                    })
+                    .collect(),
+                fee: bigdecimal::BigDecimal::new(pool.fee.bps().into(), 4),
+            }),
+            liquidity::Kind::Solidly(pool) => Liquidity::Solidly(SolidlyPool {
+                id: liquidity.id.into(),
+                address: pool.address.into(),
+                gas_estimate: liquidity.gas.into(),
+                tokens: pool
+                    .reserves
+                    .iter()
+                    .map(|r| {
+                        (
+                            r.asset.token.into(),
+                            SolidlyReserve {
+                                balance: r.asset.amount.into(),
+                                decimals: r.decimals,
+                            },
+                        )
+                    })
+                    .collect(),
+                stable: pool.stable,
+                fee: bigdecimal::BigDecimal::new(pool.fee.bps().into(), 4),
+            }),
+            liquidity::Kind::ZeroEx(_) => todo!(),
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -378,6 +458,29 @@ struct ConcentratedLiquidityPool {
     fee: bigdecimal::BigDecimal,
 }
 
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SolidlyPool {
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    id: usize,
+    address: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    gas_estimate: eth::U256,
+    tokens: BTreeMap<eth::H160, SolidlyReserve>,
+    stable: bool,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    fee: bigdecimal::BigDecimal,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+struct SolidlyReserve {
+    #[serde_as(as = "serialize::U256")]
+    balance: eth::U256,
+    decimals: u8,
+}
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
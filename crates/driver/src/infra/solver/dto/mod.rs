@@ -4,7 +4,11 @@ mod auction;
 mod notification;
 mod solution;
 
-pub use {auction::Auction, notification::Notification, solution::Solutions};
+pub use {
+    auction::{Auction, LiquidityUpdate},
+    notification::Notification,
+    solution::Solutions,
+};
 
 #[derive(Debug, thiserror::Error)]
 #[error("{0}")]
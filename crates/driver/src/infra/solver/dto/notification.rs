@@ -75,7 +75,9 @@ impl Notification {
                     notify::Settlement::Revert(hash) => Kind::Revert {
                         transaction: hash.0,
                     },
-                    notify::Settlement::SimulationRevert => Kind::Cancelled,
+                    notify::Settlement::SimulationRevert | notify::Settlement::Expired => {
+                        Kind::Cancelled
+                    }
                     notify::Settlement::Fail => Kind::Fail,
                 },
             },
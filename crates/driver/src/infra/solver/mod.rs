@@ -10,7 +10,7 @@ use {
             liquidity,
             time::Remaining,
         },
-        infra::blockchain::Ethereum,
+        infra::{self, blockchain::Ethereum},
         util,
     },
     tap::TapFallible,
@@ -18,8 +18,11 @@ use {
     tracing::Instrument,
 };
 
+pub mod accounts;
 pub mod dto;
 
+pub use accounts::{Accounts, NoAccountAvailable};
+
 const SOLVER_RESPONSE_MAX_BYTES: usize = 10_000_000;
 
 // TODO At some point I should be checking that the names are unique, I don't
@@ -62,6 +65,9 @@ pub enum Liquidity {
     /// The solver does not need liquidity, so fetching can be skipped for this
     /// solver.
     Skip,
+    /// Liquidity should be fetched and published to the liquidity feed which
+    /// this solver subscribes to. Auctions only reference the feed update.
+    Stream,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -81,6 +87,10 @@ pub struct Timeouts {
 pub struct Solver {
     client: reqwest::Client,
     config: Config,
+    accounts: Accounts,
+    /// The account which signs the settlements of this solver, see
+    /// [`Solver::select_account`].
+    account: ethcontract::Account,
     eth: Ethereum,
 }
 
@@ -93,14 +103,25 @@ pub struct Config {
     pub slippage: Slippage,
     /// Whether or not liquidity is used by this solver.
     pub liquidity: Liquidity,
-    /// The private key of this solver, used for settlement submission.
-    pub account: ethcontract::Account,
+    /// How the orders of auctions are prioritized and filtered before they
+    /// are sent to this solver.
+    pub pipeline: auction::Pipeline,
+    /// The private keys of this solver, used for settlement submission. The
+    /// first account is the primary account of the solver.
+    pub accounts: Vec<ethcontract::Account>,
+    /// Warn if the balance of a solver account falls below this threshold.
+    pub account_balance_threshold: Option<eth::Ether>,
     /// How much time to spend for each step of the solving and competition.
     pub timeouts: Timeouts,
+    /// Debug bundles are collected for the solutions of this solver if this
+    /// is set.
+    pub debug: Option<infra::debug::Config>,
 }
 
 impl Solver {
-    pub fn new(config: Config, eth: Ethereum) -> Self {
+    /// The pending settlements and balances of the solver accounts are kept
+    /// in the specified account `state`.
+    pub fn new(config: Config, eth: Ethereum, state: accounts::State) -> Self {
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
             reqwest::header::CONTENT_TYPE,
@@ -108,11 +129,14 @@ impl Solver {
         );
         headers.insert(reqwest::header::ACCEPT, "application/json".parse().unwrap());
         // TODO(#907) Also add an auth header
+        let accounts = Accounts::new(config.accounts.clone(), state);
         Self {
             client: reqwest::ClientBuilder::new()
                 .default_headers(headers)
                 .build()
                 .unwrap(),
+            account: accounts.primary().clone(),
+            accounts,
             config,
             eth,
         }
@@ -132,14 +156,56 @@ impl Solver {
         self.config.liquidity
     }
 
+    /// The order prioritization pipeline of this solver.
+    pub fn pipeline(&self) -> &auction::Pipeline {
+        &self.config.pipeline
+    }
+
     /// The blockchain address of this solver.
     pub fn address(&self) -> eth::Address {
-        self.config.account.address().into()
+        self.account.address().into()
     }
 
     /// The account which should be used to sign settlements for this solver.
     pub fn account(&self) -> ethcontract::Account {
-        self.config.account.clone()
+        self.account.clone()
+    }
+
+    /// All accounts which can sign settlements for this solver.
+    pub fn accounts(&self) -> &Accounts {
+        &self.accounts
+    }
+
+    /// Selects the account which signs a settlement. This is the account with
+    /// the largest Ether balance among the accounts without a settlement being
+    /// submitted whose balance covers the `required` amount. Accounts whose
+    /// balance wasn't fetched yet are only selected if no other account
+    /// qualifies.
+    pub fn select_account(&self, required: eth::Ether) -> Result<Self, NoAccountAvailable> {
+        let account = self
+            .accounts
+            .available()
+            .into_iter()
+            .filter_map(|account| {
+                let address = account.address().into();
+                let balance = self.accounts.balance(address);
+                super::observe::account_balance(
+                    self.name(),
+                    address,
+                    balance,
+                    self.config.account_balance_threshold,
+                );
+                balance
+                    .map_or(true, |balance| balance >= required)
+                    .then_some((account, balance))
+            })
+            .max_by_key(|(_, balance)| *balance)
+            .map(|(account, _)| account.clone())
+            .ok_or(NoAccountAvailable)?;
+        Ok(Self {
+            account,
+            ..self.clone()
+        })
     }
 
     /// Timeout configuration for this solver.
@@ -147,18 +213,27 @@ impl Solver {
         self.config.timeouts
     }
 
+    /// The debug bundle configuration of this solver.
+    pub fn debug(&self) -> Option<&infra::debug::Config> {
+        self.config.debug.as_ref()
+    }
+
     /// Make a POST request instructing the solver to solve an auction.
     /// Allocates at most `timeout` time for the solving.
     pub async fn solve(
         &self,
         auction: &Auction,
         liquidity: &[liquidity::Liquidity],
+        feed: Option<infra::liquidity::feed::Reference>,
+        debug: Option<&infra::debug::Recorder>,
     ) -> Result<Vec<Solution>, Error> {
         // Fetch the solutions from the solver.
         let weth = self.eth.contracts().weth_address();
-        let body = serde_json::to_string(&dto::Auction::new(auction, liquidity, weth)).unwrap();
+        let body =
+            serde_json::to_string(&dto::Auction::new(auction, liquidity, weth, feed)).unwrap();
         let url = shared::url::join(&self.config.endpoint, "solve");
         super::observe::solver_request(&url, &body);
+        let request = debug.map(|_| body.clone());
         let mut req = self
             .client
             .post(url.clone())
@@ -170,9 +245,21 @@ impl Solver {
         let res = util::http::send(SOLVER_RESPONSE_MAX_BYTES, req).await;
         super::observe::solver_response(&url, res.as_deref());
         let res = res?;
-        let res: dto::Solutions = serde_json::from_str(&res)
-            .tap_err(|err| tracing::warn!(res, ?err, "failed to parse solver response"))?;
-        let solutions = res.into_domain(auction, liquidity, weth, self.clone())?;
+        let solutions = serde_json::from_str::<dto::Solutions>(&res)
+            .tap_err(|err| tracing::warn!(res, ?err, "failed to parse solver response"))
+            .map_err(Error::from)
+            .and_then(|solutions| {
+                solutions
+                    .into_domain(auction, liquidity, weth, self.clone())
+                    .map_err(Error::from)
+            });
+        if let (Some(debug), Some(request)) = (debug, request) {
+            match &solutions {
+                Ok(solutions) => debug.solved(&request, &res, solutions),
+                Err(err) => debug.failed(&request, &res, err),
+            }
+        }
+        let solutions = solutions?;
 
         super::observe::solutions(&solutions);
         Ok(solutions)
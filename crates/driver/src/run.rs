@@ -1,6 +1,6 @@
 use {
     crate::{
-        domain::Mempools,
+        domain::{eth, Mempools},
         infra::{
             self,
             blockchain::{self, Ethereum},
@@ -9,15 +9,20 @@ use {
             liquidity,
             mempool,
             simulator::{self, Simulator},
-            solver::Solver,
+            solver::{self, Solver},
             Api,
             Mempool,
         },
     },
     clap::Parser,
     futures::future::join_all,
-    std::{net::SocketAddr, sync::Arc, time::Duration},
-    tokio::sync::oneshot,
+    std::{
+        net::SocketAddr,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::{Duration, SystemTime},
+    },
+    tokio::sync::{oneshot, watch},
 };
 
 /// The driver entry-point. This function exists in order to be able to run the
@@ -47,29 +52,33 @@ async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAdd
     crate::infra::observe::init(&args.log);
 
     let ethrpc = ethrpc(&args).await;
-    let config = config::file::load(ethrpc.network(), &args.config).await;
+    let config = config::file::load(ethrpc.network(), &args.config)
+        .await
+        .unwrap_or_else(|err| panic!("{err}"));
     tracing::info!("running driver with {config:#?}");
 
     let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let eth = ethereum(&config, ethrpc).await;
     let tx_pool = mempool::GlobalTxPool::default();
-    let serve = Api {
-        solvers: solvers(&config, &eth),
-        liquidity: liquidity(&config, &eth).await,
-        simulator: simulator(&config, &eth),
-        mempools: Mempools::new(
-            join_all(
-                config
-                    .mempools
-                    .iter()
-                    .map(|mempool| Mempool::new(mempool.to_owned(), eth.clone(), tx_pool.clone())),
-            )
+    let accounts = solver::accounts::State::new(&eth);
+    let (components_sender, components) = watch::channel(
+        components(&config, &eth, &tx_pool, &accounts, None)
             .await
-            .into_iter()
-            .flatten()
-            .collect(),
-        )
-        .unwrap(),
+            .expect("initialize driver components"),
+    );
+    if let Some(interval) = args.config_reload_interval {
+        tokio::spawn(reload(
+            args.config.clone(),
+            interval,
+            config,
+            eth.clone(),
+            tx_pool,
+            accounts,
+            components_sender,
+        ));
+    }
+    let serve = Api {
+        components,
         eth,
         addr: args.addr,
         addr_sender,
@@ -91,6 +100,139 @@ async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAdd
     };
 }
 
+/// Initializes the parts of the driver that can be replaced by reloading the
+/// configuration. Components whose configuration is the same as in the
+/// `previous` configuration are reused, so reloading doesn't throw away their
+/// state. This matters most for liquidity fetching, which can take minutes to
+/// initialize. The solver accounts always share the same `accounts` state, so
+/// settlements which are being submitted keep their nonces across reloads.
+async fn components(
+    config: &infra::Config,
+    eth: &Ethereum,
+    tx_pool: &mempool::GlobalTxPool,
+    accounts: &solver::accounts::State,
+    previous: Option<(&infra::Config, &infra::api::Components)>,
+) -> anyhow::Result<infra::api::Components> {
+    let liquidity = match previous {
+        Some((previous, components)) if previous.liquidity == config.liquidity => {
+            components.liquidity.clone()
+        }
+        _ => liquidity::Fetcher::new(eth, &config.liquidity).await?,
+    };
+    let simulator = match previous {
+        Some((previous, components)) if simulator_config(previous) == simulator_config(config) => {
+            components.simulator.clone()
+        }
+        _ => simulator(config, eth),
+    };
+    let mempools = match previous {
+        Some((previous, components)) if previous.mempools == config.mempools => {
+            components.mempools.clone()
+        }
+        _ => Mempools::new(
+            join_all(
+                config
+                    .mempools
+                    .iter()
+                    .map(|mempool| Mempool::new(mempool.to_owned(), eth.clone(), tx_pool.clone())),
+            )
+            .await
+            .into_iter()
+            .flatten()
+            .collect(),
+        )?,
+    };
+    // Keeping the authenticator keeps its record of used signatures, which
+    // prevents requests from being replayed across reloads.
+    let authenticator = match previous {
+        Some((previous, components)) if previous.authentication == config.authentication => {
+            components.authenticator.clone()
+        }
+        _ => config
+            .authentication
+            .clone()
+            .map(infra::api::auth::Authenticator::new),
+    };
+    Ok(infra::api::Components {
+        solvers: solvers(config, eth, accounts),
+        liquidity,
+        simulator,
+        mempools,
+        authenticator,
+    })
+}
+
+/// The parts of the configuration that the simulator is built from.
+fn simulator_config(
+    config: &infra::Config,
+) -> (&Option<simulator::Config>, bool, Option<eth::U256>) {
+    (
+        &config.simulator,
+        config.disable_access_list_simulation,
+        config.disable_gas_simulation.map(|gas| gas.0),
+    )
+}
+
+/// Periodically checks the configuration file for changes and sends the
+/// components of the new configuration to the API. Invalid configurations are
+/// ignored and the driver keeps running with the previous one.
+async fn reload(
+    path: PathBuf,
+    interval: Duration,
+    mut current: infra::Config,
+    eth: Ethereum,
+    tx_pool: mempool::GlobalTxPool,
+    accounts: solver::accounts::State,
+    sender: watch::Sender<infra::api::Components>,
+) {
+    async fn modified(path: &Path) -> Option<SystemTime> {
+        tokio::fs::metadata(path)
+            .await
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    let mut last_modified = modified(&path).await;
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let modified = modified(&path).await;
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        let config = match config::file::load(eth.network(), &path).await {
+            Ok(config) => config,
+            Err(err) => {
+                infra::observe::config_reload_failed(&path, &err);
+                continue;
+            }
+        };
+        let previous = sender.borrow().clone();
+        match components(
+            &config,
+            &eth,
+            &tx_pool,
+            &accounts,
+            Some((&current, &previous)),
+        )
+        .await
+        {
+            Ok(components) => {
+                if sender.send(components).is_err() {
+                    return;
+                }
+                infra::observe::config_reloaded(&path);
+                tracing::info!("running driver with {config:#?}");
+                current = config;
+            }
+            Err(err) => infra::observe::config_reload_failed(&path, &err),
+        }
+    }
+}
+
 fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
     let mut simulator = match &config.simulator {
         Some(infra::simulator::Config::Tenderly(tenderly)) => Simulator::tenderly(
@@ -111,6 +253,7 @@ fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
             },
             eth.to_owned(),
         ),
+        Some(infra::simulator::Config::Fork) => Simulator::fork(eth.to_owned()),
         None => Simulator::ethereum(eth.to_owned()),
     };
     if config.disable_access_list_simulation {
@@ -137,20 +280,18 @@ async fn ethereum(config: &infra::Config, ethrpc: blockchain::Rpc) -> Ethereum {
     Ethereum::new(ethrpc, config.contracts, gas).await
 }
 
-fn solvers(config: &config::Config, eth: &Ethereum) -> Vec<Solver> {
+fn solvers(
+    config: &config::Config,
+    eth: &Ethereum,
+    accounts: &solver::accounts::State,
+) -> Vec<Solver> {
     config
         .solvers
         .iter()
-        .map(|config| Solver::new(config.clone(), eth.clone()))
+        .map(|config| Solver::new(config.clone(), eth.clone(), accounts.clone()))
         .collect()
 }
 
-async fn liquidity(config: &config::Config, eth: &Ethereum) -> liquidity::Fetcher {
-    liquidity::Fetcher::new(eth, &config.liquidity)
-        .await
-        .expect("initialize liquidity fetcher")
-}
-
 #[cfg(unix)]
 async fn shutdown_signal() {
     // Intercept signals for graceful shutdown. Kubernetes sends sigterm, Ctrl-C
@@ -1011,7 +1011,8 @@ impl<'a> Settle<'a> {
         assert_eq!(self.status, hyper::StatusCode::OK);
         let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
         assert!(result.is_object());
-        assert_eq!(result.as_object().unwrap().len(), 2);
+        assert_eq!(result.as_object().unwrap().len(), 3);
+        assert_eq!(result.get("outcome").unwrap(), "mined");
         assert!(!result
             .get("calldata")
             .unwrap()
@@ -81,6 +81,7 @@ impl Solver {
                     order::Kind::Liquidity => "liquidity",
                     order::Kind::Limit { .. } => "limit",
                 },
+                "cowOnly": false,
             }));
         }
         for (i, solution) in config.solutions.iter().enumerate() {
@@ -2,7 +2,7 @@ use {
     e2e::{setup::*, tx},
     ethcontract::prelude::U256,
     model::{
-        order::{OrderCreation, OrderKind, SellTokenSource},
+        order::{BuyTokenDestination, OrderCreation, OrderKind, SellTokenSource},
         signature::EcdsaSigningScheme,
     },
     secp256k1::SecretKey,
@@ -16,6 +16,12 @@ async fn local_node_vault_balances() {
     run_test(vault_balances).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn local_node_vault_internal_balances() {
+    run_test(vault_internal_balances).await;
+}
+
 async fn vault_balances(web3: Web3) {
     let mut onchain = OnchainComponents::deploy(web3).await;
 
@@ -102,3 +108,94 @@ async fn vault_balances(web3: Web3) {
         .unwrap();
     assert!(balance_after.checked_sub(balance_before).unwrap() >= to_wei(8));
 }
+
+async fn vault_internal_balances(web3: Web3) {
+    let mut onchain = OnchainComponents::deploy(web3).await;
+
+    let [solver] = onchain.make_solvers(to_wei(1)).await;
+    let [trader] = onchain.make_accounts(to_wei(1)).await;
+    let [token] = onchain
+        .deploy_tokens_with_weth_uni_v2_pools(to_wei(1_000), to_wei(1_000))
+        .await;
+
+    token.mint(trader.address(), to_wei(10)).await;
+
+    // Deposit the tokens into the trader's Vault internal balance and approve
+    // GPv2 as a relayer for it.
+    let vault = &onchain.contracts().balancer_vault;
+    tx!(trader.account(), token.approve(vault.address(), to_wei(10)));
+    tx!(
+        trader.account(),
+        vault.manage_user_balance(vec![(
+            0, // DEPOSIT_INTERNAL
+            token.address(),
+            to_wei(10),
+            trader.address(),
+            trader.address(),
+        )])
+    );
+    tx!(
+        trader.account(),
+        vault.set_relayer_approval(trader.address(), onchain.contracts().allowance, true)
+    );
+
+    let services = Services::new(onchain.contracts()).await;
+    services.start_autopilot(vec![]);
+    services.start_api(vec![]).await;
+
+    // Place an order that sells from and buys into internal balances.
+    let order = OrderCreation {
+        kind: OrderKind::Sell,
+        sell_token: token.address(),
+        sell_amount: to_wei(9),
+        sell_token_balance: SellTokenSource::Internal,
+        fee_amount: to_wei(1),
+        buy_token: onchain.contracts().weth.address(),
+        buy_amount: to_wei(8),
+        buy_token_balance: BuyTokenDestination::Internal,
+        valid_to: model::time::now_in_epoch_seconds() + 300,
+        ..Default::default()
+    }
+    .sign(
+        EcdsaSigningScheme::Eip712,
+        &onchain.contracts().domain_separator,
+        SecretKeyRef::from(&SecretKey::from_slice(trader.private_key()).unwrap()),
+    );
+    services.create_order(&order).await.unwrap();
+
+    // Drive solution
+    tracing::info!("Waiting for trade.");
+    wait_for_condition(TIMEOUT, || async {
+        services.get_auction().await.auction.orders.len() == 1
+    })
+    .await
+    .unwrap();
+    services.start_old_driver(solver.private_key(), vec![]);
+    wait_for_condition(TIMEOUT, || async {
+        services.get_auction().await.auction.orders.is_empty()
+    })
+    .await
+    .unwrap();
+
+    // Check matching
+    let balances = vault
+        .get_internal_balance(
+            trader.address(),
+            vec![token.address(), onchain.contracts().weth.address()],
+        )
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(balances[0], U256::zero());
+    assert!(balances[1] >= to_wei(8));
+
+    // Bought tokens were not paid out as ERC20.
+    let weth = onchain
+        .contracts()
+        .weth
+        .balance_of(trader.address())
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(weth, U256::zero());
+}
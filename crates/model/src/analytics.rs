@@ -0,0 +1,85 @@
+//! Contains the types of the trade analytics endpoint as described by the
+//! openapi documentation.
+
+use {
+    chrono::{DateTime, Utc},
+    primitive_types::{H160, U256},
+    serde::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupBy {
+    AppCode,
+    Solver,
+    TokenPair,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Interval {
+    Hour,
+    Day,
+    Week,
+}
+
+/// Aggregated values of all trades in a group. Only the fields of the
+/// requested grouping are set.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeAggregate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_start: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solver: Option<H160>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sell_token: Option<H160>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_token: Option<H160>,
+    pub order_count: u64,
+    pub trade_count: u64,
+    /// Value of the sold tokens excluding fees in wei.
+    #[serde_as(as = "DisplayFromStr")]
+    pub volume: U256,
+    /// Surplus over the limit prices of the orders in wei.
+    #[serde_as(as = "DisplayFromStr")]
+    pub surplus: U256,
+    /// Fees taken by the protocol in wei.
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn serialization() {
+        let aggregate = TradeAggregate {
+            app_code: Some("CoW Swap".to_string()),
+            order_count: 2,
+            trade_count: 3,
+            volume: 400.into(),
+            surplus: 40.into(),
+            fee: 20.into(),
+            ..Default::default()
+        };
+        let expected = json!({
+            "appCode": "CoW Swap",
+            "orderCount": 2,
+            "tradeCount": 3,
+            "volume": "400",
+            "surplus": "40",
+            "fee": "20",
+        });
+        assert_eq!(serde_json::to_value(&aggregate).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<TradeAggregate>(expected).unwrap(),
+            aggregate
+        );
+    }
+}
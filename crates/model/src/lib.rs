@@ -1,10 +1,12 @@
 //! Contains models that are shared between the orderbook and the solver.
 
+pub mod analytics;
 pub mod app_data;
 pub mod auction;
 pub mod bytes_hex;
 pub mod interaction;
 pub mod order;
+pub mod order_history;
 pub mod quote;
 pub mod ratio_as_decimal;
 pub mod signature;
@@ -772,6 +772,11 @@ pub struct OrderMetadata {
     /// Full app data that `OrderData::app_data` is a hash of. Can be None if
     /// the backend doesn't know about the full app data.
     pub full_app_data: Option<String>,
+    /// Whether the order may only be matched peer-to-peer according to its
+    /// full app data. Not part of the API since it is derived from
+    /// `full_app_data` when the app data is stored.
+    #[serde(skip)]
+    pub cow_only: bool,
 }
 
 // uid as 56 bytes: 32 for orderDigest, 20 for ownerAddress and 4 for validTo
@@ -0,0 +1,97 @@
+//! Contains the types of the order history query as described by the openapi
+//! documentation.
+
+use {
+    crate::order::{Order, OrderUid},
+    anyhow::{Context, Result},
+    num::BigUint,
+    serde::{Deserialize, Serialize},
+    serde_with::{serde_as, DeserializeFromStr, DisplayFromStr, SerializeDisplay},
+    std::{
+        fmt::{self, Display, Formatter},
+        str::FromStr,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortField {
+    #[default]
+    CreationTimestamp,
+    ValidTo,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Opaque pagination cursor pointing right after the last order of a page.
+///
+/// Encoded as the value of the sort field of that order followed by its uid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, SerializeDisplay, DeserializeFromStr)]
+pub struct Cursor {
+    pub sort_key: i64,
+    pub uid: OrderUid,
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.sort_key, self.uid)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (sort_key, uid) = s.split_once('_').context("missing separator")?;
+        Ok(Self {
+            sort_key: sort_key.parse().context("invalid sort key")?,
+            uid: uid.parse().context("invalid order uid")?,
+        })
+    }
+}
+
+/// An order with information aggregated over all of its executions.
+#[serde_as]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderHistoryEntry {
+    #[serde(flatten)]
+    pub order: Order,
+    /// Surplus received compared to the order's limit price. Denominated in
+    /// the buy token for sell orders and in the sell token for buy orders.
+    #[serde_as(as = "DisplayFromStr")]
+    pub executed_surplus: BigUint,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderHistory {
+    pub orders: Vec<OrderHistoryEntry>,
+    /// Cursor to request the next page with. `None` if this is the last page.
+    pub next_cursor: Option<Cursor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = Cursor {
+            sort_key: 1_690_000_000_000_000,
+            uid: OrderUid([0x11; 56]),
+        };
+        let encoded = cursor.to_string();
+        assert_eq!(encoded, format!("1690000000000000_0x{}", "11".repeat(56)));
+        assert_eq!(encoded.parse::<Cursor>().unwrap(), cursor);
+
+        assert!("1690000000000000".parse::<Cursor>().is_err());
+        assert!("abc_0x11".parse::<Cursor>().is_err());
+    }
+}
@@ -302,6 +302,10 @@ pub struct OrderQuoteResponse {
     pub from: H160,
     pub expiration: DateTime<Utc>,
     pub id: Option<QuoteId>,
+    /// Suggested slippage tolerance in basis points for an order placed with
+    /// this quote.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_slippage_bps: Option<u32>,
 }
 
 impl OrderQuoteRequest {
@@ -95,6 +95,10 @@ pub struct SolverSettlement {
     #[serde_as(as = "BTreeMap<_, HexOrDecimalU256>")]
     pub clearing_prices: BTreeMap<H160, U256>,
     pub orders: Vec<Order>,
+    /// Just-in-time liquidity orders provided by the solver that are not part
+    /// of the auction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jit_orders: Vec<JitOrder>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde_as(as = "Option<BytesHex>")]
     pub call_data: Option<Vec<u8>>,
@@ -173,6 +177,15 @@ pub enum Order {
     },
 }
 
+/// A just-in-time liquidity order that was provided by a solver as part of
+/// its solution.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JitOrder {
+    pub id: OrderUid,
+    pub owner: H160,
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, maplit::btreemap};
@@ -224,6 +237,14 @@ mod tests {
                             "executedAmount": "14",
                         }
                     ],
+                    "jitOrders": [
+                        {
+                            "id": "0x5555555555555555555555555555555555555555555555555555555555555555\
+                                     5555555555555555555555555555555555555555\
+                                     55555555",
+                            "owner": "0x5555555555555555555555555555555555555555",
+                        }
+                    ],
                     "callData": "0x13",
                     "uninternalizedCallData": "0x1314",
                 },
@@ -267,6 +288,10 @@ mod tests {
                             executed_amount: 14.into(),
                         },
                     ],
+                    jit_orders: vec![JitOrder {
+                        id: OrderUid([0x55; 56]),
+                        owner: H160([0x55; 20]),
+                    }],
                     call_data: Some(vec![0x13]),
                     uninternalized_call_data: Some(vec![0x13, 0x14]),
                 }],
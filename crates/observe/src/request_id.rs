@@ -36,18 +36,22 @@ pub fn get_task_local_storage() -> Option<String> {
 /// initialized with some request id.
 /// Either that gets taken from the requests `X-REQUEST-ID` header of if that's
 /// missing a globally unique request number will be generated.
+/// The `SocketAddr` of the client gets stored in the request extensions.
 #[macro_export]
 macro_rules! make_service_with_task_local_storage {
     ($service:expr) => {{
         {
             let internal_request_id = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
-            hyper::service::make_service_fn(move |_| {
+            hyper::service::make_service_fn(move |connection: &hyper::server::conn::AddrStream| {
                 let warp_svc = $service.clone();
                 let internal_request_id = internal_request_id.clone();
+                let remote_addr = connection.remote_addr();
                 async move {
                     let svc =
-                        hyper::service::service_fn(move |req: hyper::Request<hyper::Body>| {
+                        hyper::service::service_fn(move |mut req: hyper::Request<hyper::Body>| {
                             let mut warp_svc = warp_svc.clone();
+                            // Make the client address available to the request handlers.
+                            req.extensions_mut().insert(remote_addr);
                             let id = if let Some(header) = req.headers().get("X-Request-ID") {
                                 String::from_utf8_lossy(header.as_bytes()).to_string()
                             } else {
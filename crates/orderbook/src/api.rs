@@ -1,5 +1,5 @@
 use {
-    crate::{app_data, database::Postgres, orderbook::Orderbook},
+    crate::{app_data, database::Postgres, orderbook::Orderbook, rate_limiting::RateLimiter},
     shared::{
         api::{box_filter, error, finalize_router, ApiReply},
         order_quoting::QuoteHandler,
@@ -15,14 +15,18 @@ mod get_app_data;
 mod get_auction;
 mod get_native_price;
 mod get_order_by_uid;
+mod get_order_history;
 mod get_orders_by_tx;
 mod get_solver_competition;
 mod get_total_surplus;
+mod get_trade_analytics;
 mod get_trades;
 mod get_user_orders;
 mod post_order;
 mod post_quote;
+mod post_quotes;
 mod put_app_data;
+mod rate_limit;
 mod replace_order;
 mod version;
 
@@ -32,6 +36,8 @@ pub fn handle_all_routes(
     quotes: Arc<QuoteHandler>,
     app_data: Arc<app_data::Registry>,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    rate_limiter: Arc<RateLimiter>,
+    max_quotes_per_batch: usize,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
     // Note that we add a string with endpoint's name to all responses.
     // This string will be used later to report metrics.
@@ -40,7 +46,10 @@ pub fn handle_all_routes(
     let routes = vec![
         (
             "v1/create_order",
-            box_filter(post_order::post_order(orderbook.clone())),
+            box_filter(post_order::post_order(
+                orderbook.clone(),
+                rate_limiter.clone(),
+            )),
         ),
         (
             "v1/get_order",
@@ -66,11 +75,26 @@ pub fn handle_all_routes(
             "v1/get_user_orders",
             box_filter(get_user_orders::get_user_orders(orderbook.clone())),
         ),
+        (
+            "v1/get_order_history",
+            box_filter(get_order_history::get_order_history(database.clone())),
+        ),
         (
             "v1/get_orders_by_tx",
             box_filter(get_orders_by_tx::get_orders_by_tx(orderbook.clone())),
         ),
-        ("v1/post_quote", box_filter(post_quote::post_quote(quotes))),
+        (
+            "v1/post_quote",
+            box_filter(post_quote::post_quote(quotes.clone(), rate_limiter.clone())),
+        ),
+        (
+            "v1/post_quotes",
+            box_filter(post_quotes::post_quotes(
+                quotes,
+                rate_limiter,
+                max_quotes_per_batch,
+            )),
+        ),
         (
             "v1/auction",
             box_filter(get_auction::get_auction(orderbook.clone())),
@@ -100,7 +124,11 @@ pub fn handle_all_routes(
         ),
         (
             "v1/get_total_surplus",
-            box_filter(get_total_surplus::get(database)),
+            box_filter(get_total_surplus::get(database.clone())),
+        ),
+        (
+            "v1/get_trade_analytics",
+            box_filter(get_trade_analytics::get_trade_analytics(database)),
         ),
     ];
 
@@ -0,0 +1,196 @@
+use {
+    crate::database::{order_history::OrderHistoryQuery, Postgres},
+    anyhow::{anyhow, Context, Result},
+    chrono::{DateTime, Utc},
+    model::{
+        order::{OrderClass, OrderStatus},
+        order_history::{Cursor, SortDirection, SortField},
+    },
+    primitive_types::H160,
+    serde::Deserialize,
+    shared::api::ApiReply,
+    std::{convert::Infallible, str::FromStr},
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+const DEFAULT_LIMIT: u64 = 10;
+const MIN_LIMIT: u64 = 1;
+const MAX_LIMIT: u64 = 1000;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    owner: Option<H160>,
+    /// Comma separated list of order statuses.
+    status: Option<String>,
+    /// Comma separated list of order classes.
+    class: Option<String>,
+    token: Option<H160>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    app_code: Option<String>,
+    partially_fillable: Option<bool>,
+    sort_by: Option<SortField>,
+    sort_direction: Option<SortDirection>,
+    cursor: Option<Cursor>,
+    limit: Option<u64>,
+}
+
+impl Query {
+    fn into_history_query(self) -> Result<OrderHistoryQuery> {
+        // Without an owner the query would have to scan all orders.
+        let owner = self.owner.context("The owner is required.")?;
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT);
+        if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+            return Err(anyhow!(
+                "The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."
+            ));
+        }
+
+        Ok(OrderHistoryQuery {
+            owner,
+            statuses: parse_list(self.status.as_deref(), parse_status)?,
+            classes: parse_list(self.class.as_deref(), |class| {
+                OrderClass::from_str(class).map_err(|_| anyhow!("invalid order class {class}"))
+            })?,
+            token: self.token,
+            created_after: self.created_after,
+            created_before: self.created_before,
+            app_code: self.app_code,
+            partially_fillable: self.partially_fillable,
+            sort_by: self.sort_by.unwrap_or_default(),
+            sort_direction: self.sort_direction.unwrap_or_default(),
+            cursor: self.cursor,
+            limit,
+        })
+    }
+}
+
+fn parse_list<T>(list: Option<&str>, parse: impl Fn(&str) -> Result<T>) -> Result<Vec<T>> {
+    list.into_iter()
+        .flat_map(|list| list.split(','))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(parse)
+        .collect()
+}
+
+fn parse_status(status: &str) -> Result<OrderStatus> {
+    Ok(match status {
+        "presignaturePending" => OrderStatus::PresignaturePending,
+        "open" => OrderStatus::Open,
+        "fulfilled" => OrderStatus::Fulfilled,
+        "cancelled" => OrderStatus::Cancelled,
+        "expired" => OrderStatus::Expired,
+        _ => return Err(anyhow!("invalid order status {status}")),
+    })
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get_order_history(
+    database: Postgres,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |query: Query| {
+        let database = database.clone();
+        async move {
+            let query = match query.into_history_query() {
+                Ok(query) => query,
+                Err(err) => {
+                    return Result::<_, Infallible>::Ok(with_status(
+                        super::error("InvalidOrderHistoryQuery", err.to_string()),
+                        StatusCode::BAD_REQUEST,
+                    ));
+                }
+            };
+            let result = database.order_history(&query).await;
+            Ok(match result {
+                Ok(history) => with_status(warp::reply::json(&history), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, ?query, "get_order_history");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, model::order::OrderUid, shared::addr};
+
+    #[tokio::test]
+    async fn request_() {
+        let result = warp::test::request()
+            .path("/v1/orders?owner=0x0000000000000000000000000000000000000001")
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        let query = result.into_history_query().unwrap();
+        assert_eq!(query.limit, DEFAULT_LIMIT);
+        assert_eq!(query.sort_by, SortField::CreationTimestamp);
+        assert_eq!(query.sort_direction, SortDirection::Desc);
+
+        let cursor = Cursor {
+            sort_key: 1,
+            uid: OrderUid([2; 56]),
+        };
+        let path = format!(
+            "/v1/orders?owner=0x0000000000000000000000000000000000000001&status=open,fulfilled&\
+             class=limit&createdAfter=2023-01-01T00:00:00Z&appCode=CoW%20Swap&\
+             partiallyFillable=true&sortBy=validTo&sortDirection=asc&cursor={cursor}&limit=5"
+        );
+        let result = warp::test::request()
+            .path(&path)
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        let query = result.into_history_query().unwrap();
+        assert_eq!(
+            query.owner,
+            addr!("0000000000000000000000000000000000000001")
+        );
+        assert_eq!(query.statuses, [OrderStatus::Open, OrderStatus::Fulfilled]);
+        assert_eq!(query.classes, [OrderClass::Limit(Default::default())]);
+        assert_eq!(query.app_code.as_deref(), Some("CoW Swap"));
+        assert_eq!(query.partially_fillable, Some(true));
+        assert_eq!(query.sort_by, SortField::ValidTo);
+        assert_eq!(query.sort_direction, SortDirection::Asc);
+        assert_eq!(query.cursor, Some(cursor));
+        assert_eq!(query.limit, 5);
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        let query = |query: Query| {
+            Query {
+                owner: Some(H160([1; 20])),
+                ..query
+            }
+            .into_history_query()
+        };
+        assert!(query(Query::default()).is_ok());
+        assert!(query(Query {
+            limit: Some(0),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(query(Query {
+            status: Some("open,unknown".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(query(Query {
+            class: Some("unknown".to_string()),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(Query::default().into_history_query().is_err());
+    }
+}
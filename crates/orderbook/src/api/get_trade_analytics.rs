@@ -0,0 +1,184 @@
+use {
+    crate::database::{analytics::AnalyticsQuery, Postgres},
+    anyhow::{anyhow, Result},
+    cached::{Cached, TimedSizedCache},
+    chrono::{DateTime, Duration, DurationRound, Utc},
+    model::analytics::{GroupBy, Interval, TradeAggregate},
+    primitive_types::H160,
+    serde::Deserialize,
+    shared::api::ApiReply,
+    std::{
+        convert::Infallible,
+        sync::{Arc, Mutex},
+    },
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+/// Time range that gets aggregated if none is specified.
+const DEFAULT_RANGE_DAYS: i64 = 7;
+/// Largest time range that can be aggregated at once to bound the cost of the
+/// query.
+const MAX_RANGE_DAYS: i64 = 31;
+/// How long aggregates are cached for. Ranges ending now get rounded to this
+/// duration so that repeated requests share cache entries.
+const CACHE_LIFESPAN_SECS: i64 = 60;
+const CACHE_SIZE: usize = 100;
+
+type Cache = Mutex<TimedSizedCache<AnalyticsQuery, Vec<TradeAggregate>>>;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    app_code: Option<String>,
+    solver: Option<H160>,
+    group_by: Option<GroupBy>,
+    interval: Option<Interval>,
+}
+
+impl Query {
+    fn into_analytics_query(self, now: DateTime<Utc>) -> Result<AnalyticsQuery> {
+        let to = match self.to {
+            Some(to) => to,
+            None => now.duration_trunc(Duration::seconds(CACHE_LIFESPAN_SECS))?,
+        };
+        let from = self
+            .from
+            .unwrap_or_else(|| to - Duration::days(DEFAULT_RANGE_DAYS));
+        if from >= to {
+            return Err(anyhow!(
+                "The start of the time range has to be before its end."
+            ));
+        }
+        if to - from > Duration::days(MAX_RANGE_DAYS) {
+            return Err(anyhow!(
+                "The time range can be at most {MAX_RANGE_DAYS} days."
+            ));
+        }
+
+        Ok(AnalyticsQuery {
+            from,
+            to,
+            app_code: self.app_code,
+            solver: self.solver,
+            group_by: self.group_by,
+            interval: self.interval,
+        })
+    }
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "analytics" / "trades")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get_trade_analytics(
+    database: Postgres,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    let cache: Arc<Cache> = Arc::new(Mutex::new(TimedSizedCache::with_size_and_lifespan(
+        CACHE_SIZE,
+        CACHE_LIFESPAN_SECS as u64,
+    )));
+    request().and_then(move |query: Query| {
+        let database = database.clone();
+        let cache = cache.clone();
+        async move {
+            let query = match query.into_analytics_query(Utc::now()) {
+                Ok(query) => query,
+                Err(err) => {
+                    return Result::<_, Infallible>::Ok(with_status(
+                        super::error("InvalidAnalyticsQuery", err.to_string()),
+                        StatusCode::BAD_REQUEST,
+                    ));
+                }
+            };
+            let cached = cache.lock().unwrap().cache_get(&query).cloned();
+            if let Some(aggregates) = cached {
+                return Ok(with_status(warp::reply::json(&aggregates), StatusCode::OK));
+            }
+            let result = database.trade_analytics(&query).await;
+            Ok(match result {
+                Ok(aggregates) => {
+                    let reply = with_status(warp::reply::json(&aggregates), StatusCode::OK);
+                    cache.lock().unwrap().cache_set(query, aggregates);
+                    reply
+                }
+                Err(err) => {
+                    tracing::error!(?err, ?query, "get_trade_analytics");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::TimeZone, shared::addr};
+
+    #[tokio::test]
+    async fn request_() {
+        let now = Utc.timestamp_opt(1_699_999_980, 0).unwrap();
+        let result = warp::test::request()
+            .path("/v1/analytics/trades")
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        let query = result.into_analytics_query(now).unwrap();
+        assert_eq!(query.to, now);
+
+        // Ranges ending now are rounded so that they can be cached.
+        let query = Query::default()
+            .into_analytics_query(now + Duration::seconds(10))
+            .unwrap();
+        assert_eq!(query.to, now);
+        assert_eq!(query.from, now - Duration::days(DEFAULT_RANGE_DAYS));
+        assert_eq!(query.group_by, None);
+        assert_eq!(query.interval, None);
+
+        let result = warp::test::request()
+            .path(
+                "/v1/analytics/trades?from=2023-01-01T00:00:00Z&to=2023-02-01T00:00:00Z&\
+                 appCode=CoW%20Swap&solver=0x0000000000000000000000000000000000000001&\
+                 groupBy=tokenPair&interval=day",
+            )
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        let query = result.into_analytics_query(now).unwrap();
+        assert_eq!(
+            query.from,
+            Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(query.to, Utc.with_ymd_and_hms(2023, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(query.app_code.as_deref(), Some("CoW Swap"));
+        assert_eq!(
+            query.solver,
+            Some(addr!("0000000000000000000000000000000000000001"))
+        );
+        assert_eq!(query.group_by, Some(GroupBy::TokenPair));
+        assert_eq!(query.interval, Some(Interval::Day));
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        let now = Utc::now();
+        assert!(Query {
+            from: Some(now),
+            to: Some(now),
+            ..Default::default()
+        }
+        .into_analytics_query(now)
+        .is_err());
+        assert!(Query {
+            from: Some(now - Duration::days(MAX_RANGE_DAYS + 1)),
+            ..Default::default()
+        }
+        .into_analytics_query(now)
+        .is_err());
+    }
+}
@@ -1,5 +1,9 @@
 use {
-    crate::orderbook::{AddOrderError, Orderbook},
+    super::rate_limit,
+    crate::{
+        orderbook::{AddOrderError, Orderbook},
+        rate_limiting::{RateLimiter, Requester},
+    },
     anyhow::Result,
     model::{
         order::{AppdataFromMismatch, OrderCreation, OrderUid},
@@ -16,7 +20,13 @@ use {
         },
     },
     std::{convert::Infallible, sync::Arc},
-    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+    warp::{
+        hyper::StatusCode,
+        reply::{with_status, Response},
+        Filter,
+        Rejection,
+        Reply,
+    },
 };
 
 pub fn create_order_request() -> impl Filter<Extract = (OrderCreation,), Error = Rejection> + Clone
@@ -70,7 +80,8 @@ impl IntoWarpReply for PartialValidationErrorWrapper {
             PartialValidationError::InvalidNativeSellToken => with_status(
                 error(
                     "InvalidNativeSellToken",
-                    "The chain's native token (Ether/xDai) cannot be used as the sell token",
+                    "The chain's native token (Ether/xDai) can only be used as the sell token by \
+                     smart contract wallets supporting wrapping it",
                 ),
                 StatusCode::BAD_REQUEST,
             ),
@@ -112,7 +123,7 @@ impl IntoWarpReply for AppDataValidationErrorWrapper {
                 StatusCode::BAD_REQUEST,
             ),
             AppDataValidationError::Invalid(err) => with_status(
-                error("InvalidAppData", format!("{:?}", err)),
+                error("InvalidAppData", err.to_string()),
                 StatusCode::BAD_REQUEST,
             ),
             AppDataValidationError::Mismatch { provided, actual } => with_status(
@@ -238,6 +249,10 @@ impl IntoWarpReply for ValidationErrorWrapper {
                 error("TooManyLimitOrders", "Too many limit orders"),
                 StatusCode::BAD_REQUEST,
             ),
+            ValidationError::InvalidPermit(err) => with_status(
+                error("InvalidPermit", err.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
 
             ValidationError::Other(err) => {
                 tracing::error!(?err, "ValidationErrorWrapper");
@@ -283,21 +298,35 @@ pub fn create_order_response(
 
 pub fn post_order(
     orderbook: Arc<Orderbook>,
-) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
-    create_order_request().and_then(move |order: OrderCreation| {
-        let orderbook = orderbook.clone();
-        async move {
-            let result = orderbook.add_order(order.clone()).await;
-            match &result {
-                Ok((order_uid, quote_id)) => {
-                    tracing::debug!(%order_uid, ?quote_id, "order created")
+    rate_limiter: Arc<RateLimiter>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    create_order_request()
+        .and(rate_limit::requester(rate_limiter.trusts_forwarded_for()))
+        .and_then(move |order: OrderCreation, requester: Requester| {
+            let orderbook = orderbook.clone();
+            let rate_limiter = rate_limiter.clone();
+            async move {
+                // Only owners recovered from the signature are trusted. Orders
+                // signed by smart contracts are only limited per client.
+                let requester = match orderbook.recover_owner(&order) {
+                    Some(owner) => requester.with_owner(owner),
+                    None => requester,
+                };
+                if let Err(err) = rate_limiter.check(&requester).await {
+                    return Result::<_, Infallible>::Ok(rate_limit::rate_limit_response(err));
                 }
-                Err(err) => tracing::debug!(?order, ?err, "error creating order"),
-            }
 
-            Result::<_, Infallible>::Ok(create_order_response(result))
-        }
-    })
+                let result = orderbook.add_order(order.clone()).await;
+                match &result {
+                    Ok((order_uid, quote_id)) => {
+                        tracing::debug!(%order_uid, ?quote_id, "order created")
+                    }
+                    Err(err) => tracing::debug!(?order, ?err, "error creating order"),
+                }
+
+                Ok(create_order_response(result).into_response())
+            }
+        })
 }
 
 #[cfg(test)]
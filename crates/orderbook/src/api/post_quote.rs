@@ -1,5 +1,9 @@
 use {
-    super::post_order::{AppDataValidationErrorWrapper, PartialValidationErrorWrapper},
+    super::{
+        post_order::{AppDataValidationErrorWrapper, PartialValidationErrorWrapper},
+        rate_limit,
+    },
+    crate::rate_limiting::{RateLimiter, Requester},
     anyhow::Result,
     model::quote::OrderQuoteRequest,
     reqwest::StatusCode,
@@ -8,7 +12,7 @@ use {
         order_quoting::{CalculateQuoteError, OrderQuoteError, QuoteHandler},
     },
     std::{convert::Infallible, sync::Arc},
-    warp::{Filter, Rejection},
+    warp::{reply::Response, Filter, Rejection, Reply},
 };
 
 fn post_quote_request() -> impl Filter<Extract = (OrderQuoteRequest,), Error = Rejection> + Clone {
@@ -19,20 +23,30 @@ fn post_quote_request() -> impl Filter<Extract = (OrderQuoteRequest,), Error = R
 
 pub fn post_quote(
     quotes: Arc<QuoteHandler>,
-) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
-    post_quote_request().and_then(move |request: OrderQuoteRequest| {
-        let quotes = quotes.clone();
-        async move {
-            let result = quotes
-                .calculate_quote(&request)
-                .await
-                .map_err(OrderQuoteErrorWrapper);
-            if let Err(err) = &result {
-                tracing::warn!(?err, ?request, "post_quote error");
+    rate_limiter: Arc<RateLimiter>,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    post_quote_request()
+        .and(rate_limit::requester(rate_limiter.trusts_forwarded_for()))
+        .and_then(move |request: OrderQuoteRequest, requester: Requester| {
+            let quotes = quotes.clone();
+            let rate_limiter = rate_limiter.clone();
+            async move {
+                // The `from` address of a quote isn't authenticated, so quotes
+                // are only limited per client.
+                if let Err(err) = rate_limiter.check(&requester).await {
+                    return Result::<_, Infallible>::Ok(rate_limit::rate_limit_response(err));
+                }
+
+                let result = quotes
+                    .calculate_quote(&request)
+                    .await
+                    .map_err(OrderQuoteErrorWrapper);
+                if let Err(err) = &result {
+                    tracing::warn!(?err, ?request, "post_quote error");
+                }
+                Ok(convert_json_response(result).into_response())
             }
-            Result::<_, Infallible>::Ok(convert_json_response(result))
-        }
-    })
+        })
 }
 
 #[derive(Debug)]
@@ -282,6 +296,7 @@ mod tests {
             from: H160::zero(),
             expiration: Utc.timestamp_millis_opt(0).unwrap(),
             id: Some(0),
+            suggested_slippage_bps: Some(50),
         };
         let response = convert_json_response::<OrderQuoteResponse, OrderQuoteErrorWrapper>(Ok(
             order_quote_response.clone(),
@@ -0,0 +1,131 @@
+use {
+    super::{post_quote::OrderQuoteErrorWrapper, rate_limit},
+    crate::rate_limiting::{RateLimiter, Requester},
+    anyhow::Result,
+    model::quote::OrderQuoteRequest,
+    reqwest::StatusCode,
+    shared::{
+        api::{self, error, response_body, ApiReply, IntoWarpReply},
+        order_quoting::QuoteHandler,
+    },
+    std::{convert::Infallible, sync::Arc},
+    warp::{
+        reply::{with_status, Response},
+        Filter,
+        Rejection,
+        Reply,
+    },
+};
+
+/// The maximum body size for every quote request in a batch. This is the same
+/// limit that is used for single quote requests.
+const MAX_QUOTE_REQUEST_SIZE: u64 = 1024 * 16;
+
+fn post_quotes_request(
+    max_batch_size: usize,
+) -> impl Filter<Extract = (Vec<OrderQuoteRequest>,), Error = Rejection> + Clone {
+    warp::path!("v1" / "quotes")
+        .and(warp::post())
+        .and(api::extract_payload_with_max_size(
+            MAX_QUOTE_REQUEST_SIZE * max_batch_size as u64,
+        ))
+}
+
+/// Every quote of a batch counts as a separate request towards the rate limit
+/// of the requesting IP address or API key.
+pub fn post_quotes(
+    quotes: Arc<QuoteHandler>,
+    rate_limiter: Arc<RateLimiter>,
+    max_batch_size: usize,
+) -> impl Filter<Extract = (Response,), Error = Rejection> + Clone {
+    post_quotes_request(max_batch_size)
+        .and(rate_limit::requester(rate_limiter.trusts_forwarded_for()))
+        .and_then(
+            move |requests: Vec<OrderQuoteRequest>, requester: Requester| {
+                let quotes = quotes.clone();
+                let rate_limiter = rate_limiter.clone();
+                async move {
+                    if requests.len() > max_batch_size {
+                        return Result::<_, Infallible>::Ok(
+                            with_status(
+                                error(
+                                    "TooManyQuotes",
+                                    format!(
+                                        "at most {max_batch_size} quotes can be requested at once"
+                                    ),
+                                ),
+                                StatusCode::BAD_REQUEST,
+                            )
+                            .into_response(),
+                        );
+                    }
+                    if let Err(err) = rate_limiter
+                        .check_many(&requester, requests.len() as u32)
+                        .await
+                    {
+                        return Ok(rate_limit::rate_limit_response(err));
+                    }
+
+                    let results = quotes.calculate_quotes(&requests).await;
+                    let mut items = Vec::with_capacity(results.len());
+                    for (result, request) in results.into_iter().zip(&requests) {
+                        let item = match result {
+                            Ok(response) => serde_json::json!(response),
+                            Err(err) => {
+                                tracing::warn!(?err, ?request, "post_quotes error");
+                                error_body(OrderQuoteErrorWrapper(err).into_warp_reply()).await
+                            }
+                        };
+                        items.push(item);
+                    }
+
+                    Ok(with_status(warp::reply::json(&items), StatusCode::OK).into_response())
+                }
+            },
+        )
+}
+
+/// Extracts the JSON body of an error reply so that batch items report errors
+/// in the exact same format as the single quote endpoint.
+async fn error_body(reply: ApiReply) -> serde_json::Value {
+    let body = response_body(reply.into_response()).await;
+    serde_json::from_slice(&body).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anyhow::anyhow,
+        shared::order_quoting::{CalculateQuoteError, OrderQuoteError},
+        warp::test::request,
+    };
+
+    #[tokio::test]
+    async fn post_quotes_request_ok() {
+        let filter = post_quotes_request(2);
+        let request_payload = vec![OrderQuoteRequest::default(), OrderQuoteRequest::default()];
+        let request = request()
+            .path("/v1/quotes")
+            .method("POST")
+            .header("content-type", "application/json")
+            .json(&request_payload);
+        let result = request.filter(&filter).await.unwrap();
+        assert_eq!(result, request_payload);
+    }
+
+    #[tokio::test]
+    async fn error_body_matches_single_quote_errors() {
+        let body = error_body(
+            OrderQuoteErrorWrapper(OrderQuoteError::CalculateQuote(CalculateQuoteError::Other(
+                anyhow!("Uh oh - error"),
+            )))
+            .into_warp_reply(),
+        )
+        .await;
+        assert_eq!(
+            body,
+            serde_json::json!({"errorType": "InternalServerError", "description": ""})
+        );
+    }
+}
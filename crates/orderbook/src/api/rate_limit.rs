@@ -0,0 +1,120 @@
+use {
+    crate::rate_limiting::{RateLimitError, Requester},
+    shared::api::error,
+    std::net::{IpAddr, SocketAddr},
+    warp::{
+        http::{header::RETRY_AFTER, StatusCode},
+        reply::{with_header, with_status, Response},
+        Filter,
+        Rejection,
+        Reply,
+    },
+};
+
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Extracts the information identifying the sender of a request. The owner of
+/// an order is only known after recovering it from the order signature and
+/// needs to be set by the route.
+pub fn requester(
+    trust_forwarded_for: bool,
+) -> impl Filter<Extract = (Requester,), Error = Rejection> + Clone {
+    warp::ext::optional::<SocketAddr>()
+        .and(warp::header::optional::<String>("X-Forwarded-For"))
+        .and(warp::header::optional::<String>(API_KEY_HEADER))
+        .map(
+            move |remote: Option<SocketAddr>,
+                  forwarded_for: Option<String>,
+                  api_key: Option<String>| {
+                let forwarded_ip = forwarded_for
+                    .filter(|_| trust_forwarded_for)
+                    .and_then(|header| header.split(',').next()?.trim().parse::<IpAddr>().ok());
+                Requester {
+                    ip: forwarded_ip.or(remote.map(|remote| remote.ip())),
+                    api_key,
+                    owner: None,
+                }
+            },
+        )
+}
+
+pub fn rate_limit_response(err: RateLimitError) -> Response {
+    match err {
+        RateLimitError::Limited { retry_after } => {
+            // Retry-After only supports whole seconds so round up.
+            let seconds = retry_after
+                .as_secs()
+                .saturating_add(u64::from(retry_after.subsec_nanos() > 0));
+            with_header(
+                with_status(
+                    error("TooManyRequests", err.to_string()),
+                    StatusCode::TOO_MANY_REQUESTS,
+                ),
+                RETRY_AFTER,
+                seconds.to_string(),
+            )
+            .into_response()
+        }
+        RateLimitError::ExceedsBurst { .. } => with_status(
+            error("TooManyRequestsAtOnce", err.to_string()),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response(),
+        RateLimitError::InvalidApiKey => with_status(
+            error("InvalidApiKey", "the API key is unknown or was revoked"),
+            StatusCode::UNAUTHORIZED,
+        )
+        .into_response(),
+        RateLimitError::Other(err) => {
+            tracing::error!(?err, "failed to check rate limit");
+            shared::api::internal_error_reply().into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[tokio::test]
+    async fn extracts_requester() {
+        let extracted = warp::test::request()
+            .header("X-Forwarded-For", "1.2.3.4, 5.6.7.8")
+            .header(API_KEY_HEADER, "key")
+            .filter(&requester(true))
+            .await
+            .unwrap();
+        assert_eq!(extracted.ip, Some("1.2.3.4".parse().unwrap()));
+        assert_eq!(extracted.api_key.as_deref(), Some("key"));
+
+        // The header is ignored unless it comes from a trusted proxy.
+        let extracted = warp::test::request()
+            .header("X-Forwarded-For", "1.2.3.4")
+            .filter(&requester(false))
+            .await
+            .unwrap();
+        assert_eq!(extracted.ip, None);
+        assert_eq!(extracted.api_key, None);
+    }
+
+    #[test]
+    fn limited_response_has_retry_after() {
+        let response = rate_limit_response(RateLimitError::Limited {
+            retry_after: Duration::from_millis(1500),
+        });
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "2");
+
+        let response = rate_limit_response(RateLimitError::Limited {
+            retry_after: Duration::MAX,
+        });
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], u64::MAX.to_string());
+    }
+
+    #[test]
+    fn oversized_batch_is_bad_request() {
+        let response = rate_limit_response(RateLimitError::ExceedsBurst { burst: 10 });
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
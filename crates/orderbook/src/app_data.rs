@@ -1,13 +1,18 @@
 use {
-    crate::database::{app_data::InsertError, Postgres},
+    crate::{
+        database::{app_data::InsertError, Postgres},
+        ipfs_pinning::IpfsPinning,
+    },
     model::app_data::AppDataHash,
     shared::app_data,
+    std::sync::Arc,
 };
 
 /// CoW Protocol API app-data registry.
 pub struct Registry {
     validator: app_data::Validator,
     database: Postgres,
+    pinning: Option<Arc<IpfsPinning>>,
 }
 
 impl Registry {
@@ -16,9 +21,16 @@ impl Registry {
         Self {
             validator,
             database,
+            pinning: None,
         }
     }
 
+    /// Pins newly registered app-data documents to IPFS.
+    pub fn with_ipfs_pinning(mut self, pinning: Arc<IpfsPinning>) -> Self {
+        self.pinning = Some(pinning);
+        self
+    }
+
     /// Returns the size limit, in bytes, of an app-data document.
     pub fn size_limit(&self) -> usize {
         self.validator.size_limit()
@@ -48,10 +60,22 @@ impl Registry {
 
         match self
             .database
-            .insert_full_app_data(&validated.hash, &validated.document)
+            .insert_full_app_data(
+                &validated.hash,
+                &validated.document,
+                validated.protocol.cow_only,
+            )
             .await
         {
-            Ok(()) => Ok(Registered::New),
+            Ok(()) => {
+                if let Some(pinning) = &self.pinning {
+                    let pinning = pinning.clone();
+                    tokio::spawn(async move {
+                        pinning.pin(&validated.hash, &validated.document).await;
+                    });
+                }
+                Ok(Registered::New)
+            }
             Err(InsertError::Duplicate) => Ok(Registered::AlreadyExisted),
             Err(InsertError::Mismatch(existing)) => Err(RegisterError::DataMismatch { existing }),
             Err(InsertError::Other(err)) => Err(RegisterError::Other(err)),
@@ -70,7 +94,7 @@ pub enum Registered {
 #[derive(Debug, thiserror::Error)]
 pub enum RegisterError {
     #[error("appData is invalid: {0}")]
-    Invalid(anyhow::Error),
+    Invalid(app_data::Error),
     #[error("computed appDataHash {computed:?} doesn't match expected {expected:?}")]
     HashMismatch {
         expected: AppDataHash,
@@ -153,6 +153,17 @@ pub struct Arguments {
     #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
     pub enable_custom_interactions: bool,
 
+    /// Accept EIP-2612 permit pre-hooks that approve the vault relayer for the
+    /// sell token, even if custom interactions are disabled.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub enable_permit_hooks: bool,
+
+    /// Accept orders selling the chain's native token from smart contract
+    /// wallets. These get converted into orders selling the wrapped native
+    /// token with a pre-hook calling the wallet's `wrapAll()` function.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub enable_native_sell_orders: bool,
+
     /// If set, the orderbook will use this IPFS gateway to fetch full app data
     /// for orders that only specify the contract app data hash.
     #[clap(long, env)]
@@ -162,6 +173,29 @@ pub struct Arguments {
     #[clap(long, env)]
     pub ipfs_pinata_auth: Option<String>,
 
+    /// If set, full app data registered with the orderbook gets pinned to IPFS
+    /// using the RPC API of this IPFS node or compatible pinning service.
+    #[clap(long, env)]
+    pub ipfs_pinning_url: Option<Url>,
+
+    /// Bearer token used to authenticate with the IPFS pinning API.
+    #[clap(long, env)]
+    pub ipfs_pinning_auth: Option<String>,
+
+    /// How often app data that failed to be pinned to IPFS is retried.
+    #[clap(
+        long,
+        env,
+        default_value = "1m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub ipfs_pinning_retry_interval: Duration,
+
+    /// The maximum number of attempts to pin a single app data document to
+    /// IPFS.
+    #[clap(long, env, default_value = "10")]
+    pub ipfs_pinning_max_attempts: u32,
+
     /// Override the address of the `HooksTrampoline` contract used for
     /// trampolining custom order interactions. If not specified, the default
     /// contract deployment for the current network will be used.
@@ -171,6 +205,66 @@ pub struct Arguments {
     /// Set the maximum size in bytes of order app data.
     #[clap(long, env, default_value = "8192")]
     pub app_data_size_limit: usize,
+
+    /// The maximum number of quotes that can be requested in a single batch
+    /// quote request.
+    #[clap(long, env, default_value = "20")]
+    pub max_quotes_per_batch: usize,
+
+    /// The maximum number of quotes of a batch that are computed concurrently.
+    #[clap(long, env, default_value = "5")]
+    pub quote_batch_parallelism: NonZeroUsize,
+
+    /// The smallest slippage tolerance in basis points that is suggested with
+    /// quotes.
+    #[clap(long, env, default_value = "50")]
+    pub suggested_slippage_min_bps: u32,
+
+    /// The largest slippage tolerance in basis points that is suggested with
+    /// quotes.
+    #[clap(long, env, default_value = "1000")]
+    pub suggested_slippage_max_bps: u32,
+
+    /// The factor to multiply a token pair's recent price volatility with when
+    /// suggesting slippage tolerances.
+    #[clap(long, env, default_value = "2")]
+    pub suggested_slippage_volatility_factor: f64,
+
+    /// How long the price volatility of a token pair and the gas price
+    /// headroom used for suggesting slippage tolerances are cached for.
+    #[clap(
+        long,
+        env,
+        default_value = "1m",
+        value_parser = humantime::parse_duration,
+    )]
+    pub suggested_slippage_cache_ttl: Duration,
+
+    /// The sustained number of order creation and quote requests per minute
+    /// allowed for every IP address.
+    #[clap(long, env, default_value = "600")]
+    pub ip_requests_per_minute: u32,
+
+    /// The number of order creation and quote requests every IP address can
+    /// make at once.
+    #[clap(long, env, default_value = "60")]
+    pub ip_request_burst: u32,
+
+    /// The sustained number of order creation requests per minute allowed for
+    /// every order owner.
+    #[clap(long, env, default_value = "120")]
+    pub owner_requests_per_minute: u32,
+
+    /// The number of order creation requests every order owner can make at
+    /// once.
+    #[clap(long, env, default_value = "20")]
+    pub owner_request_burst: u32,
+
+    /// Whether to take the client IP address for rate limiting from the
+    /// `X-Forwarded-For` header. Only enable this when the API is served behind
+    /// a proxy that sets the header.
+    #[clap(long, env, action = clap::ArgAction::Set, default_value = "false")]
+    pub trust_forwarded_for: bool,
 }
 
 impl std::fmt::Display for Arguments {
@@ -249,14 +343,67 @@ impl std::fmt::Display for Arguments {
             "enable_custom_interactions: {:?}",
             self.enable_custom_interactions
         )?;
+        writeln!(f, "enable_permit_hooks: {:?}", self.enable_permit_hooks)?;
+        writeln!(
+            f,
+            "enable_native_sell_orders: {:?}",
+            self.enable_native_sell_orders
+        )?;
         writeln!(f, "ipfs_gateway: {:?}", self.ipfs_gateway)?;
         display_secret_option(f, "ipfs_pinata_auth", &self.ipfs_pinata_auth)?;
+        writeln!(f, "ipfs_pinning_url: {:?}", self.ipfs_pinning_url)?;
+        display_secret_option(f, "ipfs_pinning_auth", &self.ipfs_pinning_auth)?;
+        writeln!(
+            f,
+            "ipfs_pinning_retry_interval: {:?}",
+            self.ipfs_pinning_retry_interval
+        )?;
+        writeln!(
+            f,
+            "ipfs_pinning_max_attempts: {}",
+            self.ipfs_pinning_max_attempts
+        )?;
         display_option(
             f,
             "hooks_contract_address",
             &self.hooks_contract_address.map(|a| format!("{a:?}")),
         )?;
         writeln!(f, "app_data_size_limit: {}", self.app_data_size_limit)?;
+        writeln!(f, "max_quotes_per_batch: {}", self.max_quotes_per_batch)?;
+        writeln!(
+            f,
+            "quote_batch_parallelism: {}",
+            self.quote_batch_parallelism
+        )?;
+        writeln!(
+            f,
+            "suggested_slippage_min_bps: {}",
+            self.suggested_slippage_min_bps
+        )?;
+        writeln!(
+            f,
+            "suggested_slippage_max_bps: {}",
+            self.suggested_slippage_max_bps
+        )?;
+        writeln!(
+            f,
+            "suggested_slippage_volatility_factor: {}",
+            self.suggested_slippage_volatility_factor
+        )?;
+        writeln!(
+            f,
+            "suggested_slippage_cache_ttl: {:?}",
+            self.suggested_slippage_cache_ttl
+        )?;
+        writeln!(f, "ip_requests_per_minute: {}", self.ip_requests_per_minute)?;
+        writeln!(f, "ip_request_burst: {}", self.ip_request_burst)?;
+        writeln!(
+            f,
+            "owner_requests_per_minute: {}",
+            self.owner_requests_per_minute
+        )?;
+        writeln!(f, "owner_request_burst: {}", self.owner_request_burst)?;
+        writeln!(f, "trust_forwarded_for: {}", self.trust_forwarded_for)?;
 
         Ok(())
     }
@@ -1,6 +1,10 @@
+pub mod analytics;
+pub mod api_keys;
 pub mod app_data;
 pub mod auctions;
+pub mod order_history;
 pub mod orders;
+pub mod pair_prices;
 pub mod quotes;
 pub mod solver_competition;
 pub mod total_surplus;
@@ -0,0 +1,65 @@
+use {
+    super::Postgres,
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    database::{analytics, byte_array::ByteArray},
+    model::analytics::{GroupBy, Interval, TradeAggregate},
+    primitive_types::{H160, U256},
+};
+
+/// Time range, filters and grouping of a trade analytics query.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct AnalyticsQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub app_code: Option<String>,
+    pub solver: Option<H160>,
+    pub group_by: Option<GroupBy>,
+    pub interval: Option<Interval>,
+}
+
+impl Postgres {
+    pub async fn trade_analytics(&self, query: &AnalyticsQuery) -> Result<Vec<TradeAggregate>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["trade_analytics"])
+            .start_timer();
+
+        let filter = analytics::Filter {
+            from: query.from,
+            to: query.to,
+            app_code: query.app_code.clone(),
+            solver: query.solver.map(|solver| ByteArray(solver.0)),
+        };
+        let group_by = query.group_by.map(|group_by| match group_by {
+            GroupBy::AppCode => analytics::GroupBy::AppCode,
+            GroupBy::Solver => analytics::GroupBy::Solver,
+            GroupBy::TokenPair => analytics::GroupBy::TokenPair,
+        });
+        let interval = query.interval.map(|interval| match interval {
+            Interval::Hour => analytics::Interval::Hour,
+            Interval::Day => analytics::Interval::Day,
+            Interval::Week => analytics::Interval::Week,
+        });
+
+        let mut ex = self.pool.acquire().await?;
+        let aggregates = analytics::aggregate(&mut ex, &filter, group_by, interval).await?;
+        Ok(aggregates
+            .into_iter()
+            .map(|aggregate| TradeAggregate {
+                bucket_start: aggregate.bucket_start,
+                app_code: aggregate.app_code,
+                solver: aggregate.solver.map(|solver| H160(solver.0)),
+                sell_token: aggregate.sell_token.map(|token| H160(token.0)),
+                buy_token: aggregate.buy_token.map(|token| H160(token.0)),
+                order_count: aggregate.order_count.try_into().unwrap_or_default(),
+                trade_count: aggregate.trade_count.try_into().unwrap_or_default(),
+                // Negative surplus can happen due to rounding of the limit
+                // price and gets clamped to 0 by the conversion.
+                volume: U256::from_f64_lossy(aggregate.volume_in_wei),
+                surplus: U256::from_f64_lossy(aggregate.surplus_in_wei),
+                fee: U256::from_f64_lossy(aggregate.fee_in_wei),
+            })
+            .collect())
+    }
+}
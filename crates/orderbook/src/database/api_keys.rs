@@ -0,0 +1,23 @@
+use {
+    super::Postgres,
+    crate::rate_limiting::{ApiKeyStoring, Quota},
+    anyhow::Result,
+    database::byte_array::ByteArray,
+};
+
+#[async_trait::async_trait]
+impl ApiKeyStoring for Postgres {
+    async fn api_key_quota(&self, key_hash: [u8; 32]) -> Result<Option<Quota>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["api_key_quota"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let key = database::api_keys::fetch_active(&mut ex, &ByteArray(key_hash)).await?;
+        Ok(key.map(|key| Quota {
+            requests_per_minute: key.requests_per_minute.try_into().unwrap_or_default(),
+            burst: key.burst.try_into().unwrap_or_default(),
+        }))
+    }
+}
@@ -1,3 +1,4 @@
+pub use database::app_data::PinStatus;
 use {
     anyhow::{Context, Result},
     database::byte_array::ByteArray,
@@ -29,6 +30,7 @@ impl super::Postgres {
         &self,
         contract_app_data: &AppDataHash,
         full_app_data: &str,
+        cow_only: bool,
     ) -> Result<(), InsertError> {
         let _timer = super::Metrics::get()
             .database_queries
@@ -40,6 +42,7 @@ impl super::Postgres {
             &mut ex,
             &ByteArray(contract_app_data.0),
             full_app_data.as_bytes(),
+            cow_only,
         )
         .await?
         {
@@ -53,6 +56,49 @@ impl super::Postgres {
 
         Ok(())
     }
+
+    pub async fn record_app_data_pin_attempt(
+        &self,
+        contract_app_data: &AppDataHash,
+        status: PinStatus,
+    ) -> Result<()> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["record_app_data_pin_attempt"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        database::app_data::record_pin_attempt(&mut ex, &ByteArray(contract_app_data.0), status)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns full app data documents that still need to be pinned to IPFS.
+    pub async fn unpinned_app_data(
+        &self,
+        max_attempts: u32,
+        limit: usize,
+    ) -> Result<Vec<(AppDataHash, String)>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["unpinned_app_data"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        database::app_data::fetch_unpinned(
+            &mut ex,
+            max_attempts.try_into().context("max attempts")?,
+            limit.try_into().context("limit")?,
+        )
+        .await?
+        .into_iter()
+        .map(|(contract_app_data, full_app_data)| {
+            let full_app_data =
+                String::from_utf8(full_app_data).context("app data is not utf-8")?;
+            Ok((AppDataHash(contract_app_data.0), full_app_data))
+        })
+        .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,115 @@
+use {
+    super::{orders::full_order_into_model_order, Postgres},
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    database::{byte_array::ByteArray, order_history},
+    model::{
+        order::{OrderClass, OrderStatus, OrderUid},
+        order_history::{Cursor, OrderHistory, OrderHistoryEntry, SortDirection, SortField},
+        time::now_in_epoch_seconds,
+    },
+    number::conversions::big_decimal_to_big_uint,
+    primitive_types::H160,
+    shared::db_order_conversions::order_class_into,
+};
+
+/// Filters, sorting and pagination of an order history query.
+#[derive(Clone, Debug, Default)]
+pub struct OrderHistoryQuery {
+    pub owner: H160,
+    pub statuses: Vec<OrderStatus>,
+    pub classes: Vec<OrderClass>,
+    pub token: Option<H160>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub app_code: Option<String>,
+    pub partially_fillable: Option<bool>,
+    pub sort_by: SortField,
+    pub sort_direction: SortDirection,
+    pub cursor: Option<Cursor>,
+    pub limit: u64,
+}
+
+impl Postgres {
+    pub async fn order_history(&self, query: &OrderHistoryQuery) -> Result<OrderHistory> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["order_history"])
+            .start_timer();
+
+        let filter = order_history::Filter {
+            owner: ByteArray(query.owner.0),
+            statuses: query.statuses.iter().map(status_into).collect(),
+            classes: query.classes.iter().map(order_class_into).collect(),
+            token: query.token.map(|token| ByteArray(token.0)),
+            created_after: query.created_after,
+            created_before: query.created_before,
+            app_code: query.app_code.clone(),
+            partially_fillable: query.partially_fillable,
+        };
+        let sorting = order_history::Sorting {
+            field: match query.sort_by {
+                SortField::CreationTimestamp => order_history::SortField::CreationTimestamp,
+                SortField::ValidTo => order_history::SortField::ValidTo,
+            },
+            direction: match query.sort_direction {
+                SortDirection::Asc => order_history::SortDirection::Ascending,
+                SortDirection::Desc => order_history::SortDirection::Descending,
+            },
+        };
+        let after = query.cursor.map(|cursor| order_history::Cursor {
+            sort_key: cursor.sort_key,
+            uid: ByteArray(cursor.uid.0),
+        });
+
+        let mut ex = self.pool.acquire().await?;
+        // Fetch one additional order to know whether there is a next page.
+        let mut entries = order_history::order_history(
+            &mut ex,
+            &filter,
+            sorting,
+            after,
+            now_in_epoch_seconds().into(),
+            i64::try_from(query.limit.saturating_add(1)).unwrap_or(i64::MAX),
+        )
+        .await?;
+
+        let next_cursor = if entries.len() as u64 > query.limit {
+            entries.truncate(query.limit as usize);
+            entries.last().map(|entry| {
+                let cursor = sorting.cursor(&entry.order);
+                Cursor {
+                    sort_key: cursor.sort_key,
+                    uid: OrderUid(cursor.uid.0),
+                }
+            })
+        } else {
+            None
+        };
+        let orders = entries
+            .into_iter()
+            .map(|entry| {
+                Ok(OrderHistoryEntry {
+                    executed_surplus: big_decimal_to_big_uint(&entry.executed_surplus)
+                        .context("executed surplus is not an unsigned integer")?,
+                    order: full_order_into_model_order(entry.order)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(OrderHistory {
+            orders,
+            next_cursor,
+        })
+    }
+}
+
+fn status_into(status: &OrderStatus) -> order_history::Status {
+    match status {
+        OrderStatus::PresignaturePending => order_history::Status::PresignaturePending,
+        OrderStatus::Open => order_history::Status::Open,
+        OrderStatus::Fulfilled => order_history::Status::Fulfilled,
+        OrderStatus::Cancelled => order_history::Status::Cancelled,
+        OrderStatus::Expired => order_history::Status::Expired,
+    }
+}
@@ -237,8 +237,13 @@ impl OrderStoring for Postgres {
         if let Some(full_app_data) = order.metadata.full_app_data {
             let contract_app_data = &ByteArray(order.data.app_data.0);
             let full_app_data = full_app_data.as_bytes();
-            if let Some(existing) =
-                database::app_data::insert(&mut ex, contract_app_data, full_app_data).await?
+            if let Some(existing) = database::app_data::insert(
+                &mut ex,
+                contract_app_data,
+                full_app_data,
+                order.metadata.cow_only,
+            )
+            .await?
             {
                 if full_app_data != existing {
                     return Err(InsertionError::AppDataMismatch(existing));
@@ -410,7 +415,7 @@ fn calculate_status(order: &FullOrder) -> OrderStatus {
     OrderStatus::Open
 }
 
-fn full_order_into_model_order(order: FullOrder) -> Result<Order> {
+pub(super) fn full_order_into_model_order(order: FullOrder) -> Result<Order> {
     let status = calculate_status(&order);
     let pre_interactions = extract_interactions(&order, database::orders::ExecutionTime::Pre)?;
     let post_interactions = extract_interactions(&order, database::orders::ExecutionTime::Post)?;
@@ -465,6 +470,7 @@ fn full_order_into_model_order(order: FullOrder) -> Result<Order> {
             .map(String::from_utf8)
             .transpose()
             .context("full app data isn't utf-8")?,
+        cow_only: order.cow_only,
     };
     let data = OrderData {
         sell_token: H160(order.sell_token.0),
@@ -570,6 +576,7 @@ mod tests {
             onchain_placement_error: None,
             executed_surplus_fee: Default::default(),
             full_app_data: Default::default(),
+            cow_only: false,
         };
 
         // Open - sell (filled - 0%)
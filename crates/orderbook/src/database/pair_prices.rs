@@ -0,0 +1,52 @@
+use {
+    super::Postgres,
+    anyhow::{Context, Result},
+    bigdecimal::{BigDecimal, ToPrimitive},
+    database::byte_array::ByteArray,
+    primitive_types::H160,
+    shared::order_quoting::slippage::PairPriceHistory,
+};
+
+/// The number of recent prices to consider for estimating the volatility of a
+/// token pair.
+const SAMPLES: i64 = 20;
+
+/// The minimum number of executed trades needed to base the price history on
+/// trades. For pairs that are not traded frequently enough quotes are used
+/// instead.
+const MIN_EXECUTIONS: usize = 5;
+
+#[async_trait::async_trait]
+impl PairPriceHistory for Postgres {
+    async fn recent_prices(&self, sell_token: H160, buy_token: H160) -> Result<Vec<f64>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["recent_pair_prices"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let sell_token = ByteArray(sell_token.0);
+        let buy_token = ByteArray(buy_token.0);
+        let executions =
+            database::trades::recent_pair_executions(&mut ex, &sell_token, &buy_token, SAMPLES)
+                .await
+                .context("failed to fetch recent executions")?;
+        let amounts = if executions.len() >= MIN_EXECUTIONS {
+            executions
+        } else {
+            database::quotes::recent_pair_quotes(&mut ex, &sell_token, &buy_token, SAMPLES)
+                .await
+                .context("failed to fetch recent quotes")?
+        };
+
+        Ok(amounts
+            .iter()
+            .filter_map(|(sell_amount, buy_amount)| price(sell_amount, buy_amount))
+            .collect())
+    }
+}
+
+fn price(sell_amount: &BigDecimal, buy_amount: &BigDecimal) -> Option<f64> {
+    let price = sell_amount.to_f64()? / buy_amount.to_f64()?;
+    (price.is_finite() && price > 0.).then_some(price)
+}
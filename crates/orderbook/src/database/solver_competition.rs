@@ -217,6 +217,7 @@ mod tests {
                     ranking: 1,
                     clearing_prices: [Default::default()].into_iter().collect(),
                     orders: vec![],
+                    jit_orders: vec![],
                     call_data: Some(vec![1, 2]),
                     uninternalized_call_data: Some(vec![1, 2, 3, 4]),
                 }],
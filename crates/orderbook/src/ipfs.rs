@@ -1,6 +1,12 @@
 use {
     anyhow::{Context, Result},
-    reqwest::{Client, ClientBuilder, StatusCode},
+    reqwest::{
+        multipart::{Form, Part},
+        Client,
+        ClientBuilder,
+        StatusCode,
+    },
+    serde::{de::DeserializeOwned, Deserialize},
     std::time::Duration,
     url::Url,
 };
@@ -51,6 +57,71 @@ impl Ipfs {
         }
     }
 
+    /// Stores the data as a raw block addressed by its keccak-256 hash and pins
+    /// it. The resulting CID is the one computed by
+    /// `app_data_hash::create_ipfs_cid`.
+    ///
+    /// This and [`Ipfs::add`] use the RPC API of an IPFS node (or a pinning
+    /// service that is compatible with it) instead of a gateway.
+    pub async fn put_block(&self, data: Vec<u8>) -> Result<String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Response {
+            key: String,
+        }
+
+        let params = [
+            ("cid-codec", "raw"),
+            ("mhtype", "keccak-256"),
+            ("pin", "true"),
+        ];
+        let response: Response = self.rpc("api/v0/block/put", &params, data).await?;
+        Ok(response.key)
+    }
+
+    /// Adds the data as a UnixFS file and pins it. The resulting CID is in the
+    /// legacy app data format, a CIDv1 of the sha2-256 hashed dag-pb node.
+    pub async fn add(&self, data: Vec<u8>) -> Result<String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Response {
+            hash: String,
+        }
+
+        let params = [
+            ("cid-version", "1"),
+            ("raw-leaves", "false"),
+            ("pin", "true"),
+        ];
+        let response: Response = self.rpc("api/v0/add", &params, data).await?;
+        Ok(response.hash)
+    }
+
+    async fn rpc<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+        data: Vec<u8>,
+    ) -> Result<T> {
+        let mut url = shared::url::join(&self.base, path);
+        url.query_pairs_mut().extend_pairs(params);
+        if let Some(query) = &self.query {
+            let query = format!("{}&{query}", url.query().unwrap_or_default());
+            url.set_query(Some(&query));
+        }
+        self.client
+            .post(url)
+            .multipart(Form::new().part("file", Part::bytes(data)))
+            .send()
+            .await
+            .context("send")?
+            .error_for_status()
+            .context("status")?
+            .json()
+            .await
+            .context("body")
+    }
+
     fn prepare_url(&self, cid: &str) -> Url {
         let mut url = shared::url::join(&self.base, &format!("ipfs/{cid}"));
         if let Some(query) = &self.query {
@@ -119,12 +119,12 @@ impl IpfsAppData {
     }
 }
 
-fn new_app_data_cid(contract_app_data: &AppDataHash) -> String {
+pub(crate) fn new_app_data_cid(contract_app_data: &AppDataHash) -> String {
     let raw_cid = app_data_hash::create_ipfs_cid(&contract_app_data.0);
     multibase::encode(multibase::Base::Base32Lower, raw_cid)
 }
 
-fn old_app_data_cid(contract_app_data: &AppDataHash) -> String {
+pub(crate) fn old_app_data_cid(contract_app_data: &AppDataHash) -> String {
     let mut raw_cid = [0u8; 4 + 32];
     raw_cid[0] = 1; // cid version
     raw_cid[1] = 0x70; // dag-pb
@@ -0,0 +1,172 @@
+use {
+    crate::{
+        database::{app_data::PinStatus, Postgres},
+        ipfs::Ipfs,
+        ipfs_app_data::{new_app_data_cid, old_app_data_cid},
+    },
+    anyhow::{ensure, Result},
+    model::app_data::AppDataHash,
+    std::{sync::Arc, time::Duration},
+};
+
+/// Pins full app data registered with the orderbook to IPFS so that third
+/// parties can resolve order metadata without going through our API.
+pub struct IpfsPinning {
+    ipfs: Ipfs,
+    database: Postgres,
+    max_attempts: u32,
+    metrics: &'static Metrics,
+}
+
+#[derive(prometheus_metric_storage::MetricStorage, Clone, Debug)]
+#[metric(subsystem = "ipfs")]
+struct Metrics {
+    /// Number of attempts to pin full app data to IPFS.
+    #[metric(labels("outcome"))]
+    pins: prometheus::IntCounterVec,
+}
+
+impl IpfsPinning {
+    /// The maximum number of unpinned documents retried at once.
+    const RETRY_BATCH_SIZE: usize = 100;
+
+    pub fn new(ipfs: Ipfs, database: Postgres, max_attempts: u32) -> Self {
+        let metrics = Metrics::instance(observe::metrics::get_storage_registry()).unwrap();
+        // Initialize metrics.
+        for outcome in &["pinned", "failed"] {
+            metrics.pins.with_label_values(&[outcome]);
+        }
+        Self {
+            ipfs,
+            database,
+            max_attempts,
+            metrics,
+        }
+    }
+
+    /// Pins the full app data and records the outcome in the database.
+    pub async fn pin(&self, contract_app_data: &AppDataHash, full_app_data: &str) {
+        let status = match self
+            .pin_raw(contract_app_data, full_app_data.as_bytes().to_vec())
+            .await
+        {
+            Ok(cid) => {
+                tracing::debug!(?contract_app_data, %cid, "pinned full app data");
+                PinStatus::Pinned
+            }
+            Err(err) => {
+                tracing::warn!(?contract_app_data, ?err, "failed to pin full app data");
+                PinStatus::Failed
+            }
+        };
+        let outcome = match status {
+            PinStatus::Pinned => "pinned",
+            _ => "failed",
+        };
+        self.metrics.pins.with_label_values(&[outcome]).inc();
+
+        if let Err(err) = self
+            .database
+            .record_app_data_pin_attempt(contract_app_data, status)
+            .await
+        {
+            tracing::warn!(
+                ?contract_app_data,
+                ?err,
+                "failed to record app data pin attempt"
+            );
+        }
+    }
+
+    /// Full app data whose contract app data follows the keccak scheme is
+    /// stored as a raw block, anything else is assumed to follow the legacy
+    /// UnixFS scheme. Either way, the CID reported by the node must match the
+    /// one derived from the contract app data, otherwise the document would
+    /// not be resolvable.
+    async fn pin_raw(
+        &self,
+        contract_app_data: &AppDataHash,
+        full_app_data: Vec<u8>,
+    ) -> Result<String> {
+        let (cid, expected) =
+            if app_data_hash::hash_full_app_data(&full_app_data) == contract_app_data.0 {
+                (
+                    self.ipfs.put_block(full_app_data).await?,
+                    new_app_data_cid(contract_app_data),
+                )
+            } else {
+                (
+                    self.ipfs.add(full_app_data).await?,
+                    old_app_data_cid(contract_app_data),
+                )
+            };
+        ensure!(
+            cid == expected,
+            "pinned CID {cid} doesn't match expected CID {expected}"
+        );
+        Ok(cid)
+    }
+
+    /// Periodically retries pinning full app data that failed to be pinned or
+    /// that was registered before pinning was enabled.
+    pub async fn retry_unpinned(self: Arc<Self>, interval: Duration) {
+        loop {
+            match self
+                .database
+                .unpinned_app_data(self.max_attempts, Self::RETRY_BATCH_SIZE)
+                .await
+            {
+                Ok(unpinned) => {
+                    for (contract_app_data, full_app_data) in unpinned {
+                        self.pin(&contract_app_data, &full_app_data).await;
+                    }
+                }
+                Err(err) => tracing::warn!(?err, "failed to fetch unpinned app data"),
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, warp::Filter};
+
+    /// Starts a local stand-in for the IPFS RPC API that responds to block
+    /// puts with the specified CID.
+    fn stand_in(cid: String) -> Ipfs {
+        let filter = warp::post()
+            .and(warp::path!("api" / "v0" / "block" / "put"))
+            .map(move || warp::reply::json(&serde_json::json!({ "Key": cid, "Size": 0 })));
+        let (addr, server) = warp::serve(filter).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        Ipfs::new(
+            Default::default(),
+            format!("http://{addr}").parse().unwrap(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn pins_to_stand_in() {
+        let full_app_data = br#"{"version":"1.1.0","metadata":{}}"#.to_vec();
+        let contract_app_data = AppDataHash(app_data_hash::hash_full_app_data(&full_app_data));
+        let database = Postgres::new("postgresql://").unwrap();
+
+        let ipfs = stand_in(new_app_data_cid(&contract_app_data));
+        let pinning = IpfsPinning::new(ipfs, database.clone(), 1);
+        let cid = pinning
+            .pin_raw(&contract_app_data, full_app_data.clone())
+            .await
+            .unwrap();
+        assert_eq!(cid, new_app_data_cid(&contract_app_data));
+
+        // A node that reports a different CID did not pin what we expect.
+        let ipfs = stand_in(old_app_data_cid(&contract_app_data));
+        let pinning = IpfsPinning::new(ipfs, database, 1);
+        assert!(pinning
+            .pin_raw(&contract_app_data, full_app_data)
+            .await
+            .is_err());
+    }
+}
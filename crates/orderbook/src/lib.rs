@@ -4,7 +4,9 @@ pub mod arguments;
 pub mod database;
 mod ipfs;
 mod ipfs_app_data;
+pub mod ipfs_pinning;
 pub mod orderbook;
+pub mod rate_limiting;
 pub mod run;
 pub mod solver_competition;
 
@@ -231,6 +231,16 @@ impl Orderbook {
         ipfs.fetch(contract_app_data).await.context("from ipfs")
     }
 
+    /// Recovers the owner of an order from its signature. Returns `None` for
+    /// signing schemes that don't allow recovering the owner.
+    pub fn recover_owner(&self, order: &OrderCreation) -> Option<H160> {
+        let recovered = order
+            .signature
+            .recover(&self.domain_separator, &order.data().hash_struct())
+            .ok()??;
+        Some(recovered.signer)
+    }
+
     pub async fn add_order(
         &self,
         payload: OrderCreation,
@@ -0,0 +1,401 @@
+//! Inbound rate limiting for the expensive API endpoints (order creation and
+//! quoting).
+//!
+//! Requests are limited with token buckets. Requests with a valid API key only
+//! use the bucket of that key which gets configured per key in the database.
+//! All other requests have to pass the bucket of the requesting IP address.
+//! Order creation additionally has to pass the bucket of the order owner if it
+//! can be recovered from the order signature. The owner is never taken from
+//! request fields that anybody can set, so nobody can exhaust the quota of
+//! someone else's address.
+
+use {
+    anyhow::Result,
+    cached::{Cached, TimedSizedCache},
+    primitive_types::H160,
+    std::{
+        collections::HashMap,
+        net::IpAddr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+    web3::signing::keccak256,
+};
+
+/// How long API key lookups get cached for.
+const API_KEY_CACHE_LIFESPAN: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Quota {
+    /// Sustained number of requests per minute.
+    pub requests_per_minute: u32,
+    /// Number of requests that can be made at once.
+    pub burst: u32,
+}
+
+impl Quota {
+    fn refill_rate(&self) -> f64 {
+        self.requests_per_minute as f64 / 60.
+    }
+}
+
+/// Storage of API keys and their quotas.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait ApiKeyStoring: Send + Sync {
+    /// Returns the quota of the active API key with the specified keccak-256
+    /// hash.
+    async fn api_key_quota(&self, key_hash: [u8; 32]) -> Result<Option<Quota>>;
+}
+
+/// Information identifying the sender of a request.
+#[derive(Clone, Debug, Default)]
+pub struct Requester {
+    pub ip: Option<IpAddr>,
+    pub api_key: Option<String>,
+    pub owner: Option<H160>,
+}
+
+impl Requester {
+    /// Sets the owner of the order. Must only be used with owners that are
+    /// authenticated by the request, i.e. recovered from a signature.
+    pub fn with_owner(self, owner: H160) -> Self {
+        Self {
+            owner: Some(owner),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RateLimitError {
+    #[error("too many requests, retry after {retry_after:?}")]
+    Limited { retry_after: Duration },
+    #[error("at most {burst} requests can be made at once")]
+    ExceedsBurst { burst: u32 },
+    #[error("invalid API key")]
+    InvalidApiKey,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum BucketKey {
+    Ip(IpAddr),
+    Owner(H160),
+    ApiKey([u8; 32]),
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(quota: &Quota, now: Instant) -> Self {
+        Self {
+            tokens: quota.burst as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, quota: &Quota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * quota.refill_rate()).min(quota.burst as f64);
+        self.updated = now;
+    }
+
+    /// Time until the bucket contains the specified number of tokens again.
+    /// Requests for more tokens than the burst size or for buckets that never
+    /// refill can never be served.
+    fn retry_after(&self, quota: &Quota, tokens: f64) -> Duration {
+        if tokens > quota.burst as f64 {
+            return Duration::MAX;
+        }
+        let missing = (tokens - self.tokens).max(0.);
+        if quota.refill_rate() <= 0. {
+            return Duration::MAX;
+        }
+        Duration::from_secs_f64(missing / quota.refill_rate())
+    }
+
+    fn is_full(&self, quota: &Quota) -> bool {
+        self.tokens >= quota.burst as f64
+    }
+}
+
+pub struct RateLimiter {
+    ip_quota: Quota,
+    owner_quota: Quota,
+    trust_forwarded_for: bool,
+    api_keys: Arc<dyn ApiKeyStoring>,
+    api_key_cache: Mutex<TimedSizedCache<[u8; 32], Option<Quota>>>,
+    buckets: Mutex<HashMap<BucketKey, (Quota, Bucket)>>,
+}
+
+impl RateLimiter {
+    pub fn new(
+        ip_quota: Quota,
+        owner_quota: Quota,
+        trust_forwarded_for: bool,
+        api_keys: Arc<dyn ApiKeyStoring>,
+    ) -> Self {
+        Self {
+            ip_quota,
+            owner_quota,
+            trust_forwarded_for,
+            api_keys,
+            api_key_cache: Mutex::new(TimedSizedCache::with_size_and_lifespan(
+                1000,
+                API_KEY_CACHE_LIFESPAN.as_secs(),
+            )),
+            buckets: Default::default(),
+        }
+    }
+
+    /// Periodically drops buckets that are full again, since they behave
+    /// exactly like new buckets.
+    pub async fn sweep_periodically(self: Arc<Self>, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+            self.sweep(Instant::now());
+        }
+    }
+
+    fn sweep(&self, now: Instant) {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.retain(|_, (quota, bucket)| {
+            bucket.refill(quota, now);
+            !bucket.is_full(quota)
+        });
+    }
+
+    /// Whether the client IP address should be taken from the
+    /// `X-Forwarded-For` header. Only safe when the API is served behind a
+    /// proxy that sets this header.
+    pub fn trusts_forwarded_for(&self) -> bool {
+        self.trust_forwarded_for
+    }
+
+    /// Consumes a request from the buckets of the requester.
+    pub async fn check(&self, requester: &Requester) -> Result<(), RateLimitError> {
+        self.check_many(requester, 1).await
+    }
+
+    /// Consumes multiple requests at once from the buckets of the requester.
+    /// Used for batch requests.
+    pub async fn check_many(
+        &self,
+        requester: &Requester,
+        requests: u32,
+    ) -> Result<(), RateLimitError> {
+        let buckets = match &requester.api_key {
+            Some(api_key) => {
+                let key_hash = keccak256(api_key.as_bytes());
+                let quota = self
+                    .api_key_quota(key_hash)
+                    .await?
+                    .ok_or(RateLimitError::InvalidApiKey)?;
+                vec![(BucketKey::ApiKey(key_hash), quota)]
+            }
+            None => requester
+                .ip
+                .map(|ip| (BucketKey::Ip(ip), self.ip_quota))
+                .into_iter()
+                .chain(
+                    requester
+                        .owner
+                        .map(|owner| (BucketKey::Owner(owner), self.owner_quota)),
+                )
+                .collect(),
+        };
+        self.acquire(&buckets, requests as f64, Instant::now())
+    }
+
+    async fn api_key_quota(&self, key_hash: [u8; 32]) -> Result<Option<Quota>> {
+        if let Some(quota) = self.api_key_cache.lock().unwrap().cache_get(&key_hash) {
+            return Ok(*quota);
+        }
+        let quota = self.api_keys.api_key_quota(key_hash).await?;
+        self.api_key_cache
+            .lock()
+            .unwrap()
+            .cache_set(key_hash, quota);
+        Ok(quota)
+    }
+
+    /// Takes the specified number of tokens from each of the buckets if all of
+    /// them contain enough.
+    fn acquire(
+        &self,
+        keys: &[(BucketKey, Quota)],
+        tokens: f64,
+        now: Instant,
+    ) -> Result<(), RateLimitError> {
+        // Retrying requests that need more tokens than a bucket can hold is
+        // pointless, so they are rejected outright.
+        if let Some((_, quota)) = keys.iter().find(|(_, quota)| tokens > quota.burst as f64) {
+            return Err(RateLimitError::ExceedsBurst { burst: quota.burst });
+        }
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let mut retry_after = Duration::ZERO;
+        for (key, quota) in keys {
+            let (stored_quota, bucket) = buckets
+                .entry(*key)
+                .or_insert_with(|| (*quota, Bucket::new(quota, now)));
+            // The quota of an API key can change in the database.
+            *stored_quota = *quota;
+            bucket.refill(quota, now);
+            if bucket.tokens < tokens {
+                retry_after = retry_after.max(bucket.retry_after(quota, tokens));
+            }
+        }
+        if !retry_after.is_zero() {
+            return Err(RateLimitError::Limited { retry_after });
+        }
+
+        for (key, _) in keys {
+            let (_, bucket) = buckets.get_mut(key).unwrap();
+            bucket.tokens -= tokens;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    fn quota(requests_per_minute: u32, burst: u32) -> Quota {
+        Quota {
+            requests_per_minute,
+            burst,
+        }
+    }
+
+    fn limiter(api_keys: MockApiKeyStoring) -> RateLimiter {
+        RateLimiter::new(quota(60, 2), quota(60, 1), false, Arc::new(api_keys))
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let quota = quota(60, 2);
+        let start = Instant::now();
+        let mut bucket = Bucket::new(&quota, start);
+        bucket.tokens = 0.;
+        assert_eq!(bucket.retry_after(&quota, 1.), Duration::from_secs(1));
+
+        bucket.refill(&quota, start + Duration::from_millis(500));
+        assert_eq!(bucket.retry_after(&quota, 1.), Duration::from_millis(500));
+
+        bucket.refill(&quota, start + Duration::from_secs(10));
+        assert!(bucket.is_full(&quota));
+        assert_eq!(bucket.tokens, 2.);
+        assert_eq!(bucket.retry_after(&quota, 3.), Duration::MAX);
+    }
+
+    #[test]
+    fn rejects_requests_exceeding_burst() {
+        let limiter = limiter(MockApiKeyStoring::new());
+        let ip = BucketKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let now = Instant::now();
+
+        assert!(matches!(
+            limiter.acquire(&[(ip, limiter.ip_quota)], 3., now),
+            Err(RateLimitError::ExceedsBurst { burst: 2 })
+        ));
+        // The rejected request didn't consume any tokens.
+        assert!(limiter.acquire(&[(ip, limiter.ip_quota)], 2., now).is_ok());
+    }
+
+    #[test]
+    fn sweeps_full_buckets() {
+        let limiter = limiter(MockApiKeyStoring::new());
+        let ip = BucketKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let owner = BucketKey::Owner(H160([1; 20]));
+        let now = Instant::now();
+
+        limiter.acquire(&[(ip, limiter.ip_quota)], 1., now).unwrap();
+        limiter
+            .acquire(&[(owner, limiter.owner_quota)], 1., now)
+            .unwrap();
+        limiter.sweep(now);
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 2);
+
+        // After a second the owner bucket is full again but the IP bucket,
+        // which holds two tokens, isn't.
+        limiter.sweep(now + Duration::from_secs(1));
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert!(buckets.contains_key(&ip));
+    }
+
+    #[test]
+    fn limits_ip_and_owner() {
+        let limiter = limiter(MockApiKeyStoring::new());
+        let ip = BucketKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let owner = BucketKey::Owner(H160([1; 20]));
+        let now = Instant::now();
+
+        assert!(limiter.acquire(&[(ip, limiter.ip_quota)], 1., now).is_ok());
+        // The owner bucket only allows a single request.
+        assert!(limiter
+            .acquire(
+                &[(ip, limiter.ip_quota), (owner, limiter.owner_quota)],
+                1.,
+                now
+            )
+            .is_ok());
+        let err = limiter
+            .acquire(&[(owner, limiter.owner_quota)], 1., now)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RateLimitError::Limited { retry_after } if retry_after == Duration::from_secs(1)
+        ));
+        // Rejected requests don't consume tokens of other buckets.
+        assert!(limiter
+            .acquire(
+                &[(ip, limiter.ip_quota), (owner, limiter.owner_quota)],
+                1.,
+                now + Duration::from_secs(1)
+            )
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn api_keys_use_their_own_quota() {
+        let mut api_keys = MockApiKeyStoring::new();
+        api_keys
+            .expect_api_key_quota()
+            .times(2)
+            .returning(|key_hash| Ok((key_hash == keccak256(b"valid")).then_some(quota(60, 3))));
+        let limiter = limiter(api_keys);
+        let requester = Requester {
+            ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            api_key: Some("valid".to_string()),
+            owner: None,
+        };
+
+        // The key allows more requests than the IP quota and is looked up once.
+        for _ in 0..3 {
+            limiter.check(&requester).await.unwrap();
+        }
+        assert!(matches!(
+            limiter.check(&requester).await,
+            Err(RateLimitError::Limited { .. })
+        ));
+
+        let requester = Requester {
+            api_key: Some("invalid".to_string()),
+            ..requester
+        };
+        assert!(matches!(
+            limiter.check(&requester).await,
+            Err(RateLimitError::InvalidApiKey)
+        ));
+    }
+}
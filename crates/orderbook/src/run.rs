@@ -6,7 +6,9 @@ use {
         database::Postgres,
         ipfs::Ipfs,
         ipfs_app_data::IpfsAppData,
+        ipfs_pinning::IpfsPinning,
         orderbook::Orderbook,
+        rate_limiting::{Quota, RateLimiter},
     },
     anyhow::{anyhow, Context, Result},
     clap::Parser,
@@ -32,7 +34,12 @@ use {
         metrics::{serve_metrics, DEFAULT_METRICS_PORT},
         network::network_name,
         oneinch_api::OneInchClientImpl,
-        order_quoting::{self, OrderQuoter, QuoteHandler},
+        order_quoting::{
+            self,
+            slippage::{self, SlippageEstimator},
+            OrderQuoter,
+            QuoteHandler,
+        },
         order_validation::{OrderValidPeriodConfiguration, OrderValidator, SignatureConfiguration},
         price_estimation::{
             factory::{self, PriceEstimatorFactory, PriceEstimatorSource},
@@ -48,6 +55,7 @@ use {
                 BalancerFactoryKind,
                 BalancerPoolFetcher,
             },
+            solidly::{self, SolidlyBaselineSourceParameters},
             uniswap_v2::{pool_cache::PoolCache, UniV2BaselineSourceParameters},
             uniswap_v3::pool_fetching::UniswapV3PoolFetcher,
             BaselineSource,
@@ -280,6 +288,36 @@ pub async fn run(args: Arguments) {
         .expect("failed to create pool cache"),
     );
     let block_retriever = args.shared.current_block.retriever(web3.clone());
+    let solidly_sources = baseline_sources
+        .iter()
+        .filter_map(|source: &BaselineSource| {
+            SolidlyBaselineSourceParameters::from_baseline_source(*source, &network)
+        });
+    let solidly_pool_fetchers: Vec<_> = futures::stream::iter(solidly_sources)
+        .then(|source: SolidlyBaselineSourceParameters| {
+            let (web3, block_retriever) = (&web3, &block_retriever);
+            async move {
+                source
+                    .into_source(web3, block_retriever.clone())
+                    .await
+                    .unwrap()
+                    .pool_fetching
+            }
+        })
+        .collect()
+        .await;
+    let solidly_pool_fetcher = (!solidly_pool_fetchers.is_empty()).then(|| {
+        Arc::new(
+            solidly::pool_cache::PoolCache::new(
+                cache_config,
+                Arc::new(solidly::pool_fetching::PoolAggregator {
+                    pool_fetchers: solidly_pool_fetchers,
+                }),
+                current_block_stream.clone(),
+            )
+            .expect("failed to create Solidly pool cache"),
+        )
+    });
     let token_info_fetcher = Arc::new(CachedTokenInfoFetcher::new(Arc::new(TokenInfoFetcher {
         web3: web3.clone(),
     })));
@@ -387,6 +425,7 @@ pub async fn run(args: Arguments) {
             uniswap_v2_pools: pool_fetcher.clone(),
             balancer_pools: balancer_pool_fetcher.clone().map(|a| a as _),
             uniswap_v3_pools: uniswap_v3_pool_fetcher.clone().map(|a| a as _),
+            solidly_pools: solidly_pool_fetcher.clone().map(|a| a as _),
             tokens: token_info_fetcher.clone(),
             gas_price: gas_price_estimator.clone(),
             zeroex: zeroex_api.clone(),
@@ -505,6 +544,9 @@ pub async fn run(args: Arguments) {
         .with_partially_fillable_limit_orders(args.allow_placing_partially_fillable_limit_orders)
         .with_eth_smart_contract_payments(args.enable_eth_smart_contract_payments)
         .with_custom_interactions(args.enable_custom_interactions)
+        .with_permit_hooks(args.enable_permit_hooks.then_some(vault_relayer))
+        .with_vault_balances(vault.is_some())
+        .with_native_sell_orders(args.enable_native_sell_orders)
         .with_verified_quotes(args.price_estimation.trade_simulator.is_some()),
     );
     let ipfs = args
@@ -532,12 +574,63 @@ pub async fn run(args: Arguments) {
     }
 
     check_database_connection(orderbook.as_ref()).await;
-    let quotes =
-        Arc::new(QuoteHandler::new(order_validator, optimal_quoter).with_fast_quoter(fast_quoter));
-    let app_data = Arc::new(app_data::Registry::new(
-        app_data_validator,
-        postgres.clone(),
+    let quotes = Arc::new(
+        QuoteHandler::new(order_validator, optimal_quoter)
+            .with_fast_quoter(fast_quoter)
+            .with_batch_parallelism(args.quote_batch_parallelism)
+            .with_slippage_estimator(Arc::new(SlippageEstimator::new(
+                Arc::new(postgres.clone()),
+                gas_price_estimator.clone(),
+                slippage::Configuration {
+                    min_bps: args.suggested_slippage_min_bps,
+                    max_bps: args.suggested_slippage_max_bps,
+                    volatility_factor: args.suggested_slippage_volatility_factor,
+                    cache_ttl: args.suggested_slippage_cache_ttl,
+                },
+            ))),
+    );
+    let mut app_data = app_data::Registry::new(app_data_validator, postgres.clone());
+    if let Some(url) = args.ipfs_pinning_url {
+        let mut client = http_factory.builder();
+        if let Some(auth) = &args.ipfs_pinning_auth {
+            let mut headers = reqwest::header::HeaderMap::new();
+            let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {auth}"))
+                .expect("invalid IPFS pinning auth");
+            value.set_sensitive(true);
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+            client = client.default_headers(headers);
+        }
+        let pinning = Arc::new(IpfsPinning::new(
+            Ipfs::new(client, url, None),
+            postgres.clone(),
+            args.ipfs_pinning_max_attempts,
+        ));
+        task::spawn(
+            pinning
+                .clone()
+                .retry_unpinned(args.ipfs_pinning_retry_interval),
+        );
+        app_data = app_data.with_ipfs_pinning(pinning);
+    }
+    let app_data = Arc::new(app_data);
+
+    let rate_limiter = Arc::new(RateLimiter::new(
+        Quota {
+            requests_per_minute: args.ip_requests_per_minute,
+            burst: args.ip_request_burst,
+        },
+        Quota {
+            requests_per_minute: args.owner_requests_per_minute,
+            burst: args.owner_request_burst,
+        },
+        args.trust_forwarded_for,
+        Arc::new(postgres.clone()),
     ));
+    task::spawn(
+        rate_limiter
+            .clone()
+            .sweep_periodically(Duration::from_secs(60)),
+    );
 
     let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let serve_api = serve_api(
@@ -550,6 +643,8 @@ pub async fn run(args: Arguments) {
             let _ = shutdown_receiver.await;
         },
         native_price_estimator,
+        rate_limiter,
+        args.max_quotes_per_batch,
     );
 
     let mut metrics_address = args.bind_address;
@@ -616,6 +711,8 @@ fn serve_api(
     address: SocketAddr,
     shutdown_receiver: impl Future<Output = ()> + Send + 'static,
     native_price_estimator: Arc<dyn NativePriceEstimating>,
+    rate_limiter: Arc<RateLimiter>,
+    max_quotes_per_batch: usize,
 ) -> JoinHandle<()> {
     let filter = api::handle_all_routes(
         database,
@@ -623,6 +720,8 @@ fn serve_api(
         quotes,
         app_data,
         native_price_estimator,
+        rate_limiter,
+        max_quotes_per_batch,
     )
     .boxed();
     tracing::info!(%address, "serving order book");
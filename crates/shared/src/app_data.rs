@@ -1,11 +1,15 @@
 use {
-    anyhow::{anyhow, Context, Result},
+    anyhow::Result,
     model::{app_data::AppDataHash, order::Hooks},
     primitive_types::H160,
-    serde::Deserialize,
+    serde::{de::DeserializeOwned, Deserialize},
+    serde_with::{serde_as, DisplayFromStr, PickFirst},
 };
 
 mod compat;
+mod schema;
+
+pub use schema::{Error as SchemaError, Violation};
 
 /// The minimum valid empty app data JSON string.
 pub const EMPTY: &str = "{}";
@@ -15,13 +19,57 @@ pub struct ValidatedAppData {
     pub hash: AppDataHash,
     pub document: String,
     pub protocol: ProtocolAppData,
+    pub sections: Sections,
 }
 
 #[derive(Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ProtocolAppData {
     #[serde(default)]
     pub hooks: Hooks,
     pub signer: Option<H160>,
+    /// The order may only be matched peer-to-peer, i.e. its traded amounts
+    /// must be fully covered by other orders or the settlement contract
+    /// buffers and never by on-chain liquidity.
+    #[serde(default)]
+    pub cow_only: bool,
+}
+
+/// Well-known, non-protocol sections of an app data document.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sections {
+    pub version: Option<String>,
+    pub app_code: Option<String>,
+    pub referrer: Option<H160>,
+    pub slippage_bips: Option<u32>,
+    pub order_class: Option<OrderClass>,
+    pub partner_fee: Option<PartnerFee>,
+    pub utm: Option<Utm>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderClass {
+    Market,
+    Limit,
+    Liquidity,
+    Twap,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub struct PartnerFee {
+    pub bps: u64,
+    pub recipient: H160,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Utm {
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_content: Option<String>,
+    pub utm_term: Option<String>,
 }
 
 #[derive(Clone)]
@@ -45,32 +93,44 @@ impl Validator {
         self.size_limit
     }
 
-    pub fn validate(&self, full_app_data: &[u8]) -> Result<ValidatedAppData> {
+    pub fn validate(&self, full_app_data: &[u8]) -> Result<ValidatedAppData, Error> {
         if full_app_data.len() > self.size_limit {
-            return Err(anyhow!(
-                "app data has byte size {} which is larger than limit {}",
-                full_app_data.len(),
-                self.size_limit
-            ));
+            return Err(Error::TooLarge {
+                size: full_app_data.len(),
+                limit: self.size_limit,
+            });
         }
 
         let document = String::from_utf8(full_app_data.to_vec())?;
-        let root = serde_json::from_str::<Root>(&document).context("invalid app data json")?;
-        let protocol = root
-            .metadata
-            .or_else(|| root.backend.map(ProtocolAppData::from))
-            // If the key doesn't exist, default. Makes life easier for API
-            // consumers, who don't care about protocol app data.
-            .unwrap_or_default();
+        let value = serde_json::from_str::<serde_json::Value>(&document).map_err(Error::Json)?;
+        schema::validate(&value)?;
+        let sections = Sections::from_document(&value);
+        let protocol = serde_json::from_value::<Root>(value)
+            .map_err(Error::Json)?
+            .into_protocol();
 
         Ok(ValidatedAppData {
             hash: AppDataHash(app_data_hash::hash_full_app_data(full_app_data)),
             document,
             protocol,
+            sections,
         })
     }
 }
 
+/// The reasons why an app data document is invalid.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("app data has byte size {size} which is larger than limit {limit}")]
+    TooLarge { size: usize, limit: usize },
+    #[error("app data is not valid utf-8")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("invalid app data json: {0}")]
+    Json(serde_json::Error),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+}
+
 /// The root app data JSON object.
 ///
 /// App data JSON is organised in an object of the form
@@ -119,6 +179,68 @@ struct Root {
     backend: Option<compat::BackendAppData>,
 }
 
+impl Root {
+    fn into_protocol(self) -> ProtocolAppData {
+        self.metadata
+            .or_else(|| self.backend.map(ProtocolAppData::from))
+            // If the key doesn't exist, default. Makes life easier for API
+            // consumers, who don't care about protocol app data.
+            .unwrap_or_default()
+    }
+}
+
+impl Sections {
+    /// Extracts the well-known sections from an app data document. This is
+    /// best-effort: documents of versions we don't have a schema for aren't
+    /// validated, so a section that doesn't have the expected shape is skipped
+    /// instead of rejecting the whole document.
+    fn from_document(document: &serde_json::Value) -> Self {
+        Self {
+            version: section(document, &["version"]),
+            app_code: section(document, &["appCode"]),
+            referrer: section(document, &["metadata", "referrer"])
+                .map(|referrer: Referrer| referrer.address),
+            slippage_bips: section(document, &["metadata", "quote"])
+                .map(|quote: Quote| quote.slippage_bips),
+            order_class: section(document, &["metadata", "orderClass"])
+                .map(|class: OrderClassSection| class.order_class),
+            partner_fee: section(document, &["metadata", "partnerFee"]),
+            utm: section(document, &["metadata", "utm"]),
+        }
+    }
+}
+
+/// Deserializes the value at `path` in the document, if it exists and has the
+/// expected shape.
+fn section<T: DeserializeOwned>(document: &serde_json::Value, path: &[&str]) -> Option<T> {
+    let value = path
+        .iter()
+        .try_fold(document, |value, key| value.get(key))?;
+    serde_json::from_value(value.clone())
+        .map_err(|err| tracing::debug!(?path, ?err, "ignoring invalid app data section"))
+        .ok()
+}
+
+#[derive(Deserialize)]
+struct Referrer {
+    address: H160,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Quote {
+    /// Older schema versions specify the slippage as a decimal string.
+    #[serde_as(as = "PickFirst<(_, DisplayFromStr)>")]
+    slippage_bips: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderClassSection {
+    order_class: OrderClass,
+}
+
 #[cfg(test)]
 mod tests {
     use {super::*, ethcontract::H160, model::order::Hook};
@@ -245,6 +367,22 @@ mod tests {
                 ..Default::default()
             },
         );
+
+        assert_app_data!(
+            r#"
+                {
+                    "appCode": "CoW Swap",
+                    "metadata": {
+                        "cowOnly": true
+                    },
+                    "version": "1.1.0"
+                }
+            "#,
+            ProtocolAppData {
+                cow_only: true,
+                ..Default::default()
+            },
+        );
     }
 
     #[test]
@@ -324,17 +462,127 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sections() {
+        let validated = Validator::default()
+            .validate(
+                br#"
+                {
+                    "version": "1.1.0",
+                    "appCode": "CoW Swap",
+                    "metadata": {
+                        "referrer": {
+                            "address": "0x4242424242424242424242424242424242424242"
+                        },
+                        "quote": {
+                            "slippageBips": 50
+                        },
+                        "orderClass": {
+                            "orderClass": "twap"
+                        },
+                        "partnerFee": {
+                            "bps": 25,
+                            "recipient": "0x0101010101010101010101010101010101010101"
+                        },
+                        "utm": {
+                            "utmSource": "cowswap"
+                        }
+                    }
+                }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(
+            validated.sections,
+            Sections {
+                version: Some("1.1.0".to_string()),
+                app_code: Some("CoW Swap".to_string()),
+                referrer: Some(H160([0x42; 20])),
+                slippage_bips: Some(50),
+                order_class: Some(OrderClass::Twap),
+                partner_fee: Some(PartnerFee {
+                    bps: 25,
+                    recipient: H160([1; 20]),
+                }),
+                utm: Some(Utm {
+                    utm_source: Some("cowswap".to_string()),
+                    ..Default::default()
+                }),
+            }
+        );
+
+        // Legacy versions specify the slippage as a string.
+        let validated = Validator::default()
+            .validate(br#"{"version":"0.9.0","metadata":{"quote":{"slippageBips":"50"}}}"#)
+            .unwrap();
+        assert_eq!(validated.sections.slippage_bips, Some(50));
+
+        let err = Validator::default()
+            .validate(br#"{"version":"1.1.0","metadata":{"quote":{"slippageBips":"50"}}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("/metadata/quote/slippageBips"));
+
+        // Documents of unknown versions aren't schema validated, so sections
+        // that don't have the expected shape are skipped.
+        let validated = Validator::default()
+            .validate(
+                br#"
+                {
+                    "version": "2.0.0",
+                    "appCode": "CoW Swap",
+                    "metadata": {
+                        "referrer": {
+                            "kind": "partner"
+                        },
+                        "quote": {
+                            "slippageBips": 1.5
+                        },
+                        "orderClass": {
+                            "orderClass": "auction"
+                        },
+                        "utm": {
+                            "utmSource": "cowswap"
+                        },
+                        "hooks": {
+                            "pre": [
+                                {
+                                    "target": "0x0000000000000000000000000000000000000000",
+                                    "callData": "0x",
+                                    "gasLimit": "21000"
+                                }
+                            ]
+                        }
+                    }
+                }
+                "#,
+            )
+            .unwrap();
+        assert_eq!(
+            validated.sections,
+            Sections {
+                version: Some("2.0.0".to_string()),
+                app_code: Some("CoW Swap".to_string()),
+                utm: Some(Utm {
+                    utm_source: Some("cowswap".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(validated.protocol.hooks.pre.len(), 1);
+    }
+
     #[test]
     fn misc() {
         let mut validator = Validator::default();
 
         let not_json = "hello world".as_bytes();
         let err = validator.validate(not_json).unwrap_err();
-        dbg!(err);
+        assert!(matches!(err, Error::Json(_)), "{err:?}");
 
         let not_object = "[]".as_bytes();
         let err = validator.validate(not_object).unwrap_err();
-        dbg!(err);
+        assert!(matches!(err, Error::Json(_)), "{err:?}");
 
         let object = "{}".as_bytes();
         let validated = validator.validate(object).unwrap();
@@ -345,7 +593,7 @@ mod tests {
 
         let bad_metadata = r#"{"hello":"world","metadata":[1]}"#.as_bytes();
         let err = validator.validate(bad_metadata).unwrap_err();
-        dbg!(err);
+        assert!(matches!(err, Error::Json(_)), "{err:?}");
 
         let ok_metadata = r#"{"hello":"world","metadata":{}}"#.as_bytes();
         validator.validate(ok_metadata).unwrap();
@@ -353,6 +601,6 @@ mod tests {
         validator.size_limit = 1;
         let size_limit = r#"{"hello":"world"}"#.as_bytes();
         let err = validator.validate(size_limit).unwrap_err();
-        dbg!(err);
+        assert!(matches!(err, Error::TooLarge { .. }), "{err:?}");
     }
 }
@@ -12,6 +12,7 @@ impl From<BackendAppData> for ProtocolAppData {
         Self {
             hooks: value.hooks,
             signer: None,
+            cow_only: false,
         }
     }
 }
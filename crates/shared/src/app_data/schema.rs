@@ -0,0 +1,153 @@
+//! Validation of app data documents against the bundled JSON schemas of the
+//! app data versions we support.
+
+use {
+    jsonschema::{Draft, JSONSchema},
+    lazy_static::lazy_static,
+    serde_json::Value,
+    std::{collections::HashMap, fmt},
+};
+
+/// The app data versions we have schemas for along with the JSON schema that
+/// documents declaring them must conform to.
+const SCHEMAS: &[(&str, &str)] = &[
+    ("0.9.0", include_str!("schemas/v0.9.0.json")),
+    ("1.0.0", include_str!("schemas/v1.0.0.json")),
+    ("1.1.0", include_str!("schemas/v1.1.0.json")),
+];
+
+lazy_static! {
+    static ref COMPILED: HashMap<&'static str, JSONSchema> = SCHEMAS
+        .iter()
+        .map(|(version, schema)| {
+            let schema = serde_json::from_str(schema).expect("bundled app data schema is json");
+            let compiled = JSONSchema::options()
+                .with_draft(Draft::Draft7)
+                .compile(&schema)
+                .expect("bundled app data schema is valid");
+            (*version, compiled)
+        })
+        .collect();
+}
+
+/// Validates the document against the schema of its declared `version`.
+/// Documents that don't declare a version, or declare a version we don't have
+/// a schema for, are not schema validated. Rejecting them would break the
+/// orders of every app that adopts a new version before we bundle its schema.
+pub fn validate(document: &Value) -> Result<(), Error> {
+    let version = match document.get("version") {
+        Some(Value::String(version)) => version,
+        Some(_) => return Err(Error::InvalidVersion),
+        None => return Ok(()),
+    };
+    let Some(schema) = COMPILED.get(version.as_str()) else {
+        tracing::debug!(%version, "no schema for app data version");
+        return Ok(());
+    };
+    schema.validate(document).map_err(|errors| Error::Schema {
+        version: version.clone(),
+        violations: errors
+            .map(|err| Violation {
+                path: err.instance_path.to_string(),
+                message: err.to_string(),
+            })
+            .collect(),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("app data version must be a string")]
+    InvalidVersion,
+    #[error(
+        "app data does not match schema version {version}: {}",
+        Violations(violations)
+    )]
+    Schema {
+        version: String,
+        violations: Vec<Violation>,
+    },
+}
+
+/// A single schema violation in an app data document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer to the offending value, empty for the document root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+struct Violations<'a>(&'a [Violation]);
+
+impl fmt::Display for Violations<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, violation) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_schemas_compile() {
+        assert_eq!(COMPILED.len(), SCHEMAS.len());
+    }
+
+    #[test]
+    fn reports_violation_paths() {
+        let document = serde_json::json!({
+            "version": "1.1.0",
+            "metadata": {
+                "quote": {
+                    "slippageBips": 10001,
+                },
+                "partnerFee": {
+                    "bps": 50,
+                },
+            },
+        });
+        let Err(Error::Schema { violations, .. }) = validate(&document) else {
+            panic!("expected schema violation");
+        };
+        let mut paths = violations
+            .iter()
+            .map(|violation| violation.path.as_str())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            ["/metadata/partnerFee", "/metadata/quote/slippageBips"]
+        );
+    }
+
+    #[test]
+    fn unknown_versions_are_not_validated() {
+        let document = serde_json::json!({
+            "version": "42.0.0",
+            "metadata": {
+                "quote": {
+                    "slippageBips": 10001,
+                },
+            },
+        });
+        assert!(validate(&document).is_ok());
+    }
+}
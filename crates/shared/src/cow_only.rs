@@ -0,0 +1,74 @@
+//! Checks for orders which may only be matched peer-to-peer ("CoW-only").
+//! Both the driver, before submitting a settlement, and the autopilot, after a
+//! settlement was mined, verify that the amounts traded by such orders are
+//! covered by the other trades of the settlement.
+
+use primitive_types::U256;
+
+/// Relative tolerance, in basis points, within which the amounts of a token
+/// bought by the trades of a settlement are considered to be covered by the
+/// amounts sold.
+pub const TOLERANCE_BPS: u64 = 10;
+
+/// The amounts of a token that the trades of a settlement sell and buy.
+#[derive(Clone, Debug, Default)]
+pub struct TokenFlow {
+    /// The amount sold, excluding fees.
+    sold: U256,
+    /// The fees taken in the token, which can remain in the settlement
+    /// contract.
+    fees: U256,
+    bought: U256,
+    trades: u64,
+}
+
+impl TokenFlow {
+    /// Records a trade selling `amount` of the token, excluding the `fee`.
+    pub fn sell(&mut self, amount: U256, fee: U256) {
+        self.sold = self.sold.saturating_add(amount);
+        self.fees = self.fees.saturating_add(fee);
+        self.trades += 1;
+    }
+
+    /// Records a trade buying `amount` of the token.
+    pub fn buy(&mut self, amount: U256) {
+        self.bought = self.bought.saturating_add(amount);
+        self.trades += 1;
+    }
+
+    /// Whether the amount bought is covered by the amount sold. The fees may
+    /// but don't have to be paid out to buyers, and every trade can be off by
+    /// one because of rounding when converting amounts with clearing prices.
+    pub fn is_covered(&self) -> bool {
+        let sold_with_fees = self.sold.saturating_add(self.fees);
+        let tolerance = (sold_with_fees.max(self.bought) * TOLERANCE_BPS / 10_000)
+            .saturating_add(self.trades.into());
+        self.bought.saturating_add(tolerance) >= self.sold
+            && self.bought <= sold_with_fees.saturating_add(tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coverage() {
+        let flow = |sold: u64, fees: u64, bought: u64| {
+            let mut flow = TokenFlow::default();
+            flow.sell(sold.into(), fees.into());
+            flow.buy(bought.into());
+            flow
+        };
+
+        assert!(flow(100_000, 0, 100_000).is_covered());
+        // Rounding and small differences are tolerated.
+        assert!(flow(100_000, 0, 99_990).is_covered());
+        assert!(flow(100_000, 0, 100_010).is_covered());
+        // Fees can be, but don't have to be, paid out to buyers.
+        assert!(flow(100_000, 1_000, 101_000).is_covered());
+        // Larger imbalances have to be covered by on-chain liquidity.
+        assert!(!flow(100_000, 1_000, 102_000).is_covered());
+        assert!(!flow(100_000, 0, 50_000).is_covered());
+    }
+}
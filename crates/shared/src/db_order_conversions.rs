@@ -106,6 +106,7 @@ pub fn full_order_into_model_order(order: database::orders::FullOrder) -> Result
             .map(String::from_utf8)
             .transpose()
             .context("full app data isn't utf-8")?,
+        cow_only: order.cow_only,
     };
     let data = OrderData {
         sell_token: H160(order.sell_token.0),
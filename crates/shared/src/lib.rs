@@ -12,6 +12,7 @@ pub mod code_fetching;
 pub mod code_simulation;
 pub mod contracts;
 pub mod conversions;
+pub mod cow_only;
 pub mod current_block;
 pub mod db_order_conversions;
 pub mod encoded_settlement;
@@ -28,11 +29,13 @@ pub mod http_solver;
 pub mod interaction;
 pub mod maintenance;
 pub mod metrics;
+pub mod native_sell;
 pub mod network;
 pub mod oneinch_api;
 pub mod order_quoting;
 pub mod order_validation;
 pub mod paraswap_api;
+pub mod permit;
 pub mod price_estimation;
 pub mod recent_block_cache;
 pub mod remaining_amounts;
@@ -0,0 +1,65 @@
+//! Native ETH sell orders for smart-contract wallets. Instead of going through
+//! the EthFlow contract, orders selling the `BUY_ETH_ADDRESS` sentinel are
+//! converted into orders selling the wrapped native token, with a pre-hook
+//! that makes the wallet wrap its native token balance right before the
+//! settlement pulls the sell token.
+//!
+//! Wallets opting into this need to implement the same `wrapAll()` function
+//! as the EthFlow contract, wrapping their entire native token balance.
+
+use {
+    ethcontract::H160,
+    hex_literal::hex,
+    model::{order::Hook, signature::SigningScheme},
+};
+
+/// The selector of `wrapAll()`.
+const SELECTOR: [u8; 4] = hex!("4c84c1c8");
+
+/// The gas limit of the wrap pre-hook. This covers the `WETH.deposit()` call
+/// of the wallet with some overhead for the wallet's own logic.
+pub const GAS_LIMIT: u64 = 50_000;
+
+/// Returns the pre-hook that wraps the native token balance of the order
+/// owner.
+pub fn hook(owner: H160) -> Hook {
+    Hook {
+        target: owner,
+        call_data: SELECTOR.to_vec(),
+        gas_limit: GAS_LIMIT,
+    }
+}
+
+/// Returns whether the hook wraps the native token balance of the order owner.
+pub fn is_wrap_hook(hook: &Hook, owner: H160) -> bool {
+    hook.target == owner && hook.call_data == SELECTOR
+}
+
+/// Returns whether orders with the specified signing scheme can sell native
+/// ETH. The owner of these orders needs to be a smart contract which can be
+/// called by the wrap pre-hook, so ECDSA signed orders are not supported.
+pub fn supports_signing_scheme(signing_scheme: SigningScheme) -> bool {
+    matches!(
+        signing_scheme,
+        SigningScheme::Eip1271 | SigningScheme::PreSign
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_wrap_hooks() {
+        let owner = H160([1; 20]);
+        assert!(is_wrap_hook(&hook(owner), owner));
+        assert!(!is_wrap_hook(&hook(owner), H160([2; 20])));
+        assert!(!is_wrap_hook(
+            &Hook {
+                call_data: hex!("4c84c1c800").to_vec(),
+                ..hook(owner)
+            },
+            owner,
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use {
     super::price_estimation::{
         self,
-        native::NativePriceEstimating,
+        native::{NativePriceEstimateResult, NativePriceEstimating},
         PriceEstimating,
         PriceEstimationError,
     },
@@ -17,14 +17,14 @@ use {
         price_estimation::Verification,
         trade_finding,
     },
-    anyhow::{Context, Result},
+    anyhow::{anyhow, Context, Result},
     chrono::{DateTime, Duration, TimeZone as _, Utc},
     database::quotes::{Quote as QuoteRow, QuoteKind},
     ethcontract::{H160, U256},
-    futures::TryFutureExt as _,
-    gas_estimation::GasPriceEstimating,
+    futures::{FutureExt as _, StreamExt as _, TryFutureExt as _},
+    gas_estimation::{GasPrice1559, GasPriceEstimating},
     model::{
-        order::{OrderClass, OrderCreationAppData, OrderKind},
+        order::{OrderClass, OrderCreationAppData, OrderKind, BUY_ETH_ADDRESS},
         quote::{
             OrderQuote,
             OrderQuoteRequest,
@@ -37,15 +37,24 @@ use {
         },
     },
     number::conversions::big_decimal_to_u256,
-    std::sync::Arc,
+    slippage::SlippageEstimator,
+    std::{
+        collections::{HashMap, HashSet},
+        num::NonZeroUsize,
+        sync::Arc,
+    },
     thiserror::Error,
 };
 
+pub mod slippage;
+
 /// A high-level interface for handling API quote requests.
 pub struct QuoteHandler {
     order_validator: Arc<dyn OrderValidating>,
     optimal_quoter: Arc<dyn OrderQuoting>,
     fast_quoter: Arc<dyn OrderQuoting>,
+    batch_parallelism: NonZeroUsize,
+    slippage_estimator: Option<Arc<SlippageEstimator>>,
 }
 
 impl QuoteHandler {
@@ -54,6 +63,8 @@ impl QuoteHandler {
             order_validator,
             optimal_quoter: quoter.clone(),
             fast_quoter: quoter,
+            batch_parallelism: NonZeroUsize::new(10).unwrap(),
+            slippage_estimator: None,
         }
     }
 
@@ -61,6 +72,19 @@ impl QuoteHandler {
         self.fast_quoter = fast_quoter;
         self
     }
+
+    /// Sets the maximum number of quotes of a batch that get computed
+    /// concurrently.
+    pub fn with_batch_parallelism(mut self, batch_parallelism: NonZeroUsize) -> Self {
+        self.batch_parallelism = batch_parallelism;
+        self
+    }
+
+    /// Enables suggesting slippage tolerances with computed quotes.
+    pub fn with_slippage_estimator(mut self, slippage_estimator: Arc<SlippageEstimator>) -> Self {
+        self.slippage_estimator = Some(slippage_estimator);
+        self
+    }
 }
 
 impl QuoteHandler {
@@ -70,37 +94,7 @@ impl QuoteHandler {
     ) -> Result<OrderQuoteResponse, OrderQuoteError> {
         tracing::debug!(?request, "calculating quote");
 
-        let app_data = self
-            .order_validator
-            .validate_app_data(&request.app_data, &None)?;
-
-        let order = PreOrderData::from(request);
-        let valid_to = order.valid_to;
-        self.order_validator.partial_validate(order).await?;
-
-        let params = {
-            let verification = match request.price_quality {
-                PriceQuality::Verified => Some(Verification {
-                    from: request.from,
-                    receiver: request.receiver.unwrap_or(request.from),
-                    sell_token_source: request.sell_token_balance,
-                    buy_token_destination: request.buy_token_balance,
-                    pre_interactions: trade_finding::map_interactions(&app_data.interactions.pre),
-                    post_interactions: trade_finding::map_interactions(&app_data.interactions.post),
-                }),
-                PriceQuality::Fast | PriceQuality::Optimal => None,
-            };
-
-            QuoteParameters {
-                sell_token: request.sell_token,
-                buy_token: request.buy_token,
-                side: request.side,
-                verification,
-                signing_scheme: request.signing_scheme,
-                additional_gas: app_data.inner.protocol.hooks.gas_limit(),
-            }
-        };
-
+        let (params, valid_to) = self.quote_parameters(request).await?;
         let quote = match request.price_quality {
             PriceQuality::Optimal | PriceQuality::Verified => {
                 let quote = self.optimal_quoter.calculate_quote(params).await?;
@@ -110,44 +104,201 @@ impl QuoteHandler {
                     .map_err(CalculateQuoteError::Other)?
             }
             PriceQuality::Fast => {
-                let mut quote = self.fast_quoter.calculate_quote(params).await?;
-                // We maintain an API guarantee that fast quotes always have an expiry of zero,
-                // because they're not very accurate and can be considered to
-                // expire immediately.
-                quote.data.expiration = Utc.timestamp_millis_opt(0).unwrap();
-                quote
+                let quote = self.fast_quoter.calculate_quote(params).await?;
+                with_immediate_expiration(quote)
             }
         };
 
-        let response = OrderQuoteResponse {
-            quote: OrderQuote {
-                sell_token: request.sell_token,
-                buy_token: request.buy_token,
-                receiver: request.receiver,
-                sell_amount: quote.sell_amount,
-                buy_amount: quote.buy_amount,
-                valid_to,
-                app_data: match &request.app_data {
-                    OrderCreationAppData::Full { full } => OrderCreationAppData::Both {
-                        full: full.clone(),
-                        expected: request.app_data.hash(),
-                    },
-                    app_data => app_data.clone(),
-                },
-                fee_amount: quote.fee_amount,
-                kind: quote.data.kind,
-                partially_fillable: request.partially_fillable,
-                sell_token_balance: request.sell_token_balance,
-                buy_token_balance: request.buy_token_balance,
-                signing_scheme: request.signing_scheme.into(),
+        let suggested_slippage_bps = self.suggested_slippage(&quote).await;
+        let response = quote_response(request, valid_to, quote, suggested_slippage_bps);
+        tracing::debug!(?response, "finished computing quote");
+        Ok(response)
+    }
+
+    /// Computes quotes for a batch of requests. Gas and native price
+    /// estimates are shared between all quotes computed by the same quoter.
+    ///
+    /// Results are returned in the same order as the requests.
+    pub async fn calculate_quotes(
+        &self,
+        requests: &[OrderQuoteRequest],
+    ) -> Vec<Result<OrderQuoteResponse, OrderQuoteError>> {
+        tracing::debug!(?requests, "calculating quote batch");
+
+        let prepared: Vec<_> = futures::stream::iter(requests)
+            .map(|request| self.quote_parameters(request))
+            .buffered(self.batch_parallelism.get())
+            .collect()
+            .await;
+
+        let (fast, optimal): (Vec<_>, Vec<_>) = prepared
+            .iter()
+            .zip(requests)
+            .enumerate()
+            .filter_map(|(index, (prepared, request))| {
+                let (params, _) = prepared.as_ref().ok()?;
+                Some((index, request.price_quality, params.clone()))
+            })
+            .partition(|(_, quality, _)| *quality == PriceQuality::Fast);
+        let (fast_indices, fast_params): (Vec<_>, Vec<_>) = fast
+            .into_iter()
+            .map(|(index, _, params)| (index, params))
+            .unzip();
+        let (optimal_indices, optimal_params): (Vec<_>, Vec<_>) = optimal
+            .into_iter()
+            .map(|(index, _, params)| (index, params))
+            .unzip();
+
+        let (fast_quotes, optimal_quotes) = futures::join!(
+            self.fast_quoter
+                .calculate_quotes(fast_params, self.batch_parallelism),
+            async {
+                futures::stream::iter(
+                    self.optimal_quoter
+                        .calculate_quotes(optimal_params, self.batch_parallelism)
+                        .await,
+                )
+                .map(|quote| async move {
+                    self.optimal_quoter
+                        .store_quote(quote?)
+                        .await
+                        .map_err(CalculateQuoteError::Other)
+                })
+                .buffered(self.batch_parallelism.get())
+                .collect::<Vec<_>>()
+                .await
             },
-            from: request.from,
-            expiration: quote.data.expiration,
-            id: quote.id,
+        );
+
+        let mut quotes: HashMap<usize, Result<Quote, CalculateQuoteError>> = fast_indices
+            .into_iter()
+            .zip(
+                fast_quotes
+                    .into_iter()
+                    .map(|quote| quote.map(with_immediate_expiration)),
+            )
+            .chain(optimal_indices.into_iter().zip(optimal_quotes))
+            .collect();
+
+        let responses =
+            prepared
+                .into_iter()
+                .zip(requests)
+                .enumerate()
+                .map(|(index, (prepared, request))| {
+                    let quote = quotes.remove(&index);
+                    async move {
+                        let (_, valid_to) = prepared?;
+                        let quote = quote.expect("quote computed for every prepared request")?;
+                        let suggested_slippage_bps = self.suggested_slippage(&quote).await;
+                        Ok(quote_response(
+                            request,
+                            valid_to,
+                            quote,
+                            suggested_slippage_bps,
+                        ))
+                    }
+                });
+        let responses = futures::stream::iter(responses)
+            .buffered(self.batch_parallelism.get())
+            .collect::<Vec<_>>()
+            .await;
+
+        tracing::debug!(?responses, "finished computing quote batch");
+        responses
+    }
+
+    /// Validates a quote request and returns the parameters to compute the
+    /// quote with as well as the `valid_to` of the order.
+    async fn quote_parameters(
+        &self,
+        request: &OrderQuoteRequest,
+    ) -> Result<(QuoteParameters, u32), OrderQuoteError> {
+        let mut app_data = self
+            .order_validator
+            .validate_app_data(&request.app_data, &None)?;
+
+        let order = PreOrderData::from(request);
+        let valid_to = order.valid_to;
+        self.order_validator.partial_validate(order).await?;
+
+        // Quote native asset sell orders as they are going to be placed, i.e.
+        // selling the wrapped native token with a wrap pre-hook.
+        let sell_token = if request.sell_token == BUY_ETH_ADDRESS {
+            self.order_validator
+                .wrap_native_sell(request.from, &mut app_data)
+        } else {
+            request.sell_token
         };
 
-        tracing::debug!(?response, "finished computing quote");
-        Ok(response)
+        let verification = match request.price_quality {
+            PriceQuality::Verified => Some(Verification {
+                from: request.from,
+                receiver: request.receiver.unwrap_or(request.from),
+                sell_token_source: request.sell_token_balance,
+                buy_token_destination: request.buy_token_balance,
+                pre_interactions: trade_finding::map_interactions(&app_data.interactions.pre),
+                post_interactions: trade_finding::map_interactions(&app_data.interactions.post),
+            }),
+            PriceQuality::Fast | PriceQuality::Optimal => None,
+        };
+
+        let params = QuoteParameters {
+            sell_token,
+            buy_token: request.buy_token,
+            side: request.side,
+            verification,
+            signing_scheme: request.signing_scheme,
+            additional_gas: app_data.inner.protocol.hooks.gas_limit(),
+        };
+        Ok((params, valid_to))
+    }
+
+    async fn suggested_slippage(&self, quote: &Quote) -> Option<u32> {
+        Some(self.slippage_estimator.as_ref()?.suggest(quote).await)
+    }
+}
+
+/// We maintain an API guarantee that fast quotes always have an expiry of
+/// zero, because they're not very accurate and can be considered to expire
+/// immediately.
+fn with_immediate_expiration(mut quote: Quote) -> Quote {
+    quote.data.expiration = Utc.timestamp_millis_opt(0).unwrap();
+    quote
+}
+
+fn quote_response(
+    request: &OrderQuoteRequest,
+    valid_to: u32,
+    quote: Quote,
+    suggested_slippage_bps: Option<u32>,
+) -> OrderQuoteResponse {
+    OrderQuoteResponse {
+        quote: OrderQuote {
+            sell_token: request.sell_token,
+            buy_token: request.buy_token,
+            receiver: request.receiver,
+            sell_amount: quote.sell_amount,
+            buy_amount: quote.buy_amount,
+            valid_to,
+            app_data: match &request.app_data {
+                OrderCreationAppData::Full { full } => OrderCreationAppData::Both {
+                    full: full.clone(),
+                    expected: request.app_data.hash(),
+                },
+                app_data => app_data.clone(),
+            },
+            fee_amount: quote.fee_amount,
+            kind: quote.data.kind,
+            partially_fillable: request.partially_fillable,
+            sell_token_balance: request.sell_token_balance,
+            buy_token_balance: request.buy_token_balance,
+            signing_scheme: request.signing_scheme.into(),
+        },
+        from: request.from,
+        expiration: quote.data.expiration,
+        id: quote.id,
+        suggested_slippage_bps,
     }
 }
 
@@ -241,6 +392,10 @@ pub struct Quote {
     /// the order on chain. This is the fee in full without any subsidies. The
     /// fee is denoted in the sell token.
     pub full_fee_amount: U256,
+    /// The spread in basis points between the competing price estimates that
+    /// were considered when computing this quote. This is zero for quotes that
+    /// were loaded from storage.
+    pub spread_bps: u32,
 }
 
 impl Quote {
@@ -252,6 +407,7 @@ impl Quote {
             buy_amount: data.quoted_buy_amount,
             fee_amount: data.fee_parameters.unsubsidized(),
             full_fee_amount: data.fee_parameters.unsubsidized(),
+            spread_bps: 0,
             data,
         }
     }
@@ -366,6 +522,24 @@ pub trait OrderQuoting: Send + Sync {
         parameters: QuoteParameters,
     ) -> Result<Quote, CalculateQuoteError>;
 
+    /// Computes quotes for a batch of order parameters, computing at most
+    /// `parallelism` quotes concurrently. Results are returned in the same
+    /// order as the parameters. Doesn't store the quotes.
+    ///
+    /// By default, every quote is computed individually. Implementations can
+    /// override this to share work between the quotes of a batch.
+    async fn calculate_quotes(
+        &self,
+        parameters: Vec<QuoteParameters>,
+        parallelism: NonZeroUsize,
+    ) -> Vec<Result<Quote, CalculateQuoteError>> {
+        futures::stream::iter(parameters)
+            .map(|parameters| self.calculate_quote(parameters))
+            .buffered(parallelism.get())
+            .collect()
+            .await
+    }
+
     /// Stores a quote.
     async fn store_quote(&self, quote: Quote) -> Result<Quote>;
 
@@ -533,21 +707,12 @@ impl OrderQuoter {
         }
     }
 
+    /// Computes the quote data along with the spread between competing price
+    /// estimates for the trade.
     async fn compute_quote_data(
         &self,
         parameters: &QuoteParameters,
-    ) -> Result<QuoteData, CalculateQuoteError> {
-        let expiration = match parameters.signing_scheme {
-            QuoteSigningScheme::Eip1271 {
-                onchain_order: true,
-                ..
-            } => self.now.now() + self.validity.eip1271_onchain_quote,
-            QuoteSigningScheme::PreSign {
-                onchain_order: true,
-            } => self.now.now() + self.validity.presign_onchain_quote,
-            _ => self.now.now() + self.validity.standard_quote,
-        };
-
+    ) -> Result<(QuoteData, u32), CalculateQuoteError> {
         let trade_query = Arc::new(parameters.to_price_query());
         let (gas_estimate, trade_estimate, sell_token_price, _) = futures::try_join!(
             self.gas_estimator
@@ -563,6 +728,37 @@ impl OrderQuoter {
                 .estimate_native_price(parameters.buy_token),
         )?;
 
+        let data = self.quote_data(
+            parameters,
+            &trade_query,
+            &trade_estimate,
+            &gas_estimate,
+            sell_token_price,
+        );
+        Ok((data, trade_estimate.spread_bps))
+    }
+
+    /// Assembles the quote data from the already computed trade, gas and
+    /// native price estimates.
+    fn quote_data(
+        &self,
+        parameters: &QuoteParameters,
+        trade_query: &price_estimation::Query,
+        trade_estimate: &price_estimation::Estimate,
+        gas_estimate: &GasPrice1559,
+        sell_token_price: f64,
+    ) -> QuoteData {
+        let expiration = match parameters.signing_scheme {
+            QuoteSigningScheme::Eip1271 {
+                onchain_order: true,
+                ..
+            } => self.now.now() + self.validity.eip1271_onchain_quote,
+            QuoteSigningScheme::PreSign {
+                onchain_order: true,
+            } => self.now.now() + self.validity.presign_onchain_quote,
+            _ => self.now.now() + self.validity.standard_quote,
+        };
+
         let (quoted_sell_amount, quoted_buy_amount) = match &parameters.side {
             OrderQuoteSide::Sell {
                 sell_amount: SellAmount::BeforeFee { value: sell_amount },
@@ -581,7 +777,7 @@ impl OrderQuoter {
         };
 
         let quote_kind = quote_kind_from_signing_scheme(&parameters.signing_scheme);
-        let quote = QuoteData {
+        QuoteData {
             sell_token: parameters.sell_token,
             buy_token: parameters.buy_token,
             quoted_sell_amount,
@@ -591,12 +787,93 @@ impl OrderQuoter {
             expiration,
             quote_kind,
             solver: trade_estimate.solver,
+        }
+    }
+
+    /// Computes the quote data for an item of a quote batch, using the gas
+    /// and native price estimates that were fetched once for the whole batch.
+    async fn compute_batched_quote_data(
+        &self,
+        parameters: &QuoteParameters,
+        gas_estimate: &Result<GasPrice1559, PriceEstimationError>,
+        native_prices: &HashMap<H160, NativePriceEstimateResult>,
+    ) -> Result<(QuoteData, u32), CalculateQuoteError> {
+        let native_price = |token: &H160| {
+            native_prices.get(token).cloned().unwrap_or_else(|| {
+                Err(PriceEstimationError::ProtocolInternal(anyhow!(
+                    "missing native price for token {token:?}"
+                )))
+            })
         };
+        let gas_estimate = gas_estimate.clone()?;
+        let sell_token_price = native_price(&parameters.sell_token)?;
+        // See `compute_quote_data` for why the buy token price is required.
+        native_price(&parameters.buy_token)?;
 
-        Ok(quote)
+        let trade_query = Arc::new(parameters.to_price_query());
+        let trade_estimate = self.price_estimator.estimate(trade_query.clone()).await?;
+
+        let data = self.quote_data(
+            parameters,
+            &trade_query,
+            &trade_estimate,
+            &gas_estimate,
+            sell_token_price,
+        );
+        Ok((data, trade_estimate.spread_bps))
+    }
+
+    async fn subsidy(&self, parameters: &QuoteParameters) -> Result<Subsidy, CalculateQuoteError> {
+        self.fee_subsidy
+            .subsidy(SubsidyParameters {
+                from: parameters
+                    .verification
+                    .as_ref()
+                    .map(|v| v.from)
+                    .unwrap_or_default(),
+            })
+            .await
+            .map_err(From::from)
     }
 }
 
+/// Creates the final quote from the computed quote data by applying fee
+/// subsidies and scaling amounts for sell orders with amounts before fees.
+fn quote_with_subsidy(
+    parameters: &QuoteParameters,
+    (data, spread_bps): (QuoteData, u32),
+    subsidy: &Subsidy,
+) -> Result<Quote, CalculateQuoteError> {
+    let mut quote = Quote {
+        spread_bps,
+        ..Quote::new(Default::default(), data)
+    }
+    .with_subsidy_and_additional_cost(subsidy, parameters.additional_cost());
+
+    // Make sure to scale the sell and buy amounts for quotes for sell
+    // amounts before fees.
+    if let OrderQuoteSide::Sell {
+        sell_amount: SellAmount::BeforeFee {
+            value: sell_amount_before_fee,
+        },
+    } = &parameters.side
+    {
+        let sell_amount =
+            Into::<U256>::into(*sell_amount_before_fee).saturating_sub(quote.fee_amount);
+        if sell_amount == U256::zero() {
+            // We want a sell_amount of at least 1!
+            return Err(CalculateQuoteError::SellAmountDoesNotCoverFee {
+                fee_amount: quote.fee_amount,
+            });
+        }
+
+        quote = quote.with_scaled_sell_amount(sell_amount);
+    }
+
+    tracing::debug!(?quote, ?subsidy, "computed quote");
+    Ok(quote)
+}
+
 #[async_trait::async_trait]
 impl OrderQuoting for OrderQuoter {
     async fn calculate_quote(
@@ -605,43 +882,55 @@ impl OrderQuoting for OrderQuoter {
     ) -> Result<Quote, CalculateQuoteError> {
         let (data, subsidy) = futures::try_join!(
             self.compute_quote_data(&parameters),
-            self.fee_subsidy
-                .subsidy(SubsidyParameters {
-                    from: parameters
-                        .verification
-                        .as_ref()
-                        .map(|v| v.from)
-                        .unwrap_or_default(),
-                })
-                .map_err(From::from),
+            self.subsidy(&parameters),
         )?;
 
-        let mut quote = Quote::new(Default::default(), data)
-            .with_subsidy_and_additional_cost(&subsidy, parameters.additional_cost());
-
-        // Make sure to scale the sell and buy amounts for quotes for sell
-        // amounts before fees.
-        if let OrderQuoteSide::Sell {
-            sell_amount:
-                SellAmount::BeforeFee {
-                    value: sell_amount_before_fee,
-                },
-        } = &parameters.side
-        {
-            let sell_amount =
-                Into::<U256>::into(*sell_amount_before_fee).saturating_sub(quote.fee_amount);
-            if sell_amount == U256::zero() {
-                // We want a sell_amount of at least 1!
-                return Err(CalculateQuoteError::SellAmountDoesNotCoverFee {
-                    fee_amount: quote.fee_amount,
-                });
-            }
+        quote_with_subsidy(&parameters, data, &subsidy)
+    }
 
-            quote = quote.with_scaled_sell_amount(sell_amount);
+    async fn calculate_quotes(
+        &self,
+        parameters: Vec<QuoteParameters>,
+        parallelism: NonZeroUsize,
+    ) -> Vec<Result<Quote, CalculateQuoteError>> {
+        if parameters.is_empty() {
+            return Vec::new();
         }
 
-        tracing::debug!(?quote, ?subsidy, "computed quote");
-        Ok(quote)
+        // Quotes in a batch very often share tokens, so fetch the gas price
+        // and native prices once for the whole batch instead of per quote.
+        let tokens = parameters
+            .iter()
+            .flat_map(|parameters| [parameters.sell_token, parameters.buy_token])
+            .collect::<HashSet<_>>();
+        let (gas_estimate, native_prices) = futures::join!(
+            self.gas_estimator
+                .estimate()
+                .map_err(PriceEstimationError::ProtocolInternal),
+            futures::stream::iter(tokens)
+                .map(|token| {
+                    self.native_price_estimator
+                        .estimate_native_price(token)
+                        .map(move |price| (token, price))
+                })
+                .buffer_unordered(parallelism.get())
+                .collect::<HashMap<_, _>>(),
+        );
+
+        futures::stream::iter(parameters)
+            .map(|parameters| {
+                let (gas_estimate, native_prices) = (&gas_estimate, &native_prices);
+                async move {
+                    let (data, subsidy) = futures::try_join!(
+                        self.compute_batched_quote_data(&parameters, gas_estimate, native_prices),
+                        self.subsidy(&parameters),
+                    )?;
+                    quote_with_subsidy(&parameters, data, &subsidy)
+                }
+            })
+            .buffered(parallelism.get())
+            .collect()
+            .await
     }
 
     async fn store_quote(&self, quote: Quote) -> Result<Quote> {
@@ -838,6 +1127,7 @@ mod tests {
                         out_amount: 42.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        spread_bps: 0,
                     })
                 }
                 .boxed()
@@ -917,6 +1207,7 @@ mod tests {
                 buy_amount: 29.into(),
                 fee_amount: 30.into(),
                 full_fee_amount: 30.into(),
+                spread_bps: 0,
             }
         );
     }
@@ -970,6 +1261,7 @@ mod tests {
                         out_amount: 42.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        spread_bps: 0,
                     })
                 }
                 .boxed()
@@ -1052,6 +1344,7 @@ mod tests {
                 buy_amount: 42.into(),
                 fee_amount: 30.into(),
                 full_fee_amount: 60.into(),
+                spread_bps: 0,
             }
         );
     }
@@ -1100,6 +1393,7 @@ mod tests {
                         out_amount: 100.into(),
                         gas: 3,
                         solver: H160([1; 20]),
+                        spread_bps: 0,
                     })
                 }
                 .boxed()
@@ -1183,6 +1477,7 @@ mod tests {
                 buy_amount: 42.into(),
                 fee_amount: 9.into(),
                 full_fee_amount: 30.into(),
+                spread_bps: 0,
             }
         );
     }
@@ -1217,6 +1512,7 @@ mod tests {
                     out_amount: 100.into(),
                     gas: 200,
                     solver: H160([1; 20]),
+                    spread_bps: 0,
                 })
             }
             .boxed()
@@ -1286,6 +1582,7 @@ mod tests {
                     out_amount: 100.into(),
                     gas: 200,
                     solver: H160([1; 20]),
+                    spread_bps: 0,
                 })
             }
             .boxed()
@@ -1325,6 +1622,90 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn batch_quotes_share_native_prices() {
+        let parameters = |buy_token| QuoteParameters {
+            sell_token: H160([1; 20]),
+            buy_token,
+            side: OrderQuoteSide::Sell {
+                sell_amount: SellAmount::AfterFee {
+                    value: NonZeroU256::try_from(100).unwrap(),
+                },
+            },
+            ..Default::default()
+        };
+        let gas_price = GasPrice1559 {
+            base_fee_per_gas: 1.,
+            max_fee_per_gas: 2.,
+            max_priority_fee_per_gas: 0.,
+        };
+
+        let mut price_estimator = MockPriceEstimating::new();
+        price_estimator.expect_estimate().times(2).returning(|_| {
+            async {
+                Ok(price_estimation::Estimate {
+                    out_amount: 42.into(),
+                    gas: 3,
+                    solver: H160([1; 20]),
+                    spread_bps: 0,
+                })
+            }
+            .boxed()
+        });
+
+        // Every token only gets priced once, even though the sell token is
+        // shared by all quotes.
+        let mut native_price_estimator = MockNativePriceEstimating::new();
+        native_price_estimator
+            .expect_estimate_native_price()
+            .with(eq(H160([1; 20])))
+            .times(1)
+            .returning(|_| async { Ok(1.) }.boxed());
+        native_price_estimator
+            .expect_estimate_native_price()
+            .with(eq(H160([2; 20])))
+            .times(1)
+            .returning(|_| async { Ok(1.) }.boxed());
+        native_price_estimator
+            .expect_estimate_native_price()
+            .with(eq(H160([3; 20])))
+            .times(1)
+            .returning(|_| async { Err(PriceEstimationError::NoLiquidity) }.boxed());
+
+        let gas_estimator = FakeGasPriceEstimator(Arc::new(Mutex::new(gas_price)));
+
+        let quoter = OrderQuoter {
+            price_estimator: Arc::new(price_estimator),
+            native_price_estimator: Arc::new(native_price_estimator),
+            gas_estimator: Arc::new(gas_estimator),
+            fee_subsidy: Arc::new(Subsidy::default()),
+            storage: Arc::new(MockQuoteStoring::new()),
+            now: Arc::new(Utc::now),
+            validity: Validity::default(),
+        };
+
+        let quotes = quoter
+            .calculate_quotes(
+                vec![
+                    parameters(H160([2; 20])),
+                    parameters(H160([3; 20])),
+                    parameters(H160([2; 20])),
+                ],
+                NonZeroUsize::new(2).unwrap(),
+            )
+            .await;
+
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[0].as_ref().unwrap().buy_amount, 42.into());
+        assert!(matches!(
+            quotes[1],
+            Err(CalculateQuoteError::Price(
+                PriceEstimationError::NoLiquidity
+            )),
+        ));
+        assert_eq!(quotes[2].as_ref().unwrap().buy_amount, 42.into());
+    }
+
     #[tokio::test]
     async fn finds_quote_by_id() {
         let now = Utc::now();
@@ -1406,6 +1787,7 @@ mod tests {
                 // create orders with fees that aren't fully subsidized.
                 fee_amount: 8.into(),
                 full_fee_amount: 30.into(),
+                spread_bps: 0,
             }
         );
     }
@@ -1481,6 +1863,7 @@ mod tests {
                 buy_amount: 42.into(),
                 fee_amount: 30.into(),
                 full_fee_amount: 30.into(),
+                spread_bps: 0,
             }
         );
     }
@@ -1561,6 +1944,7 @@ mod tests {
                 buy_amount: 42.into(),
                 fee_amount: 30.into(),
                 full_fee_amount: 30.into(),
+                spread_bps: 0,
             }
         );
     }
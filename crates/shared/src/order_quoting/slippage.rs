@@ -0,0 +1,284 @@
+//! Suggested slippage tolerances for quotes.
+//!
+//! A quote is only a point estimate of the price an order will be executed
+//! at. The suggested slippage tolerance is derived from three sources of
+//! uncertainty:
+//! - the spread between competing price estimates for the quote,
+//! - the recent volatility of the price of the traded token pair and
+//! - the volatility of the network fee, which is estimated from how much
+//!   headroom the gas estimator reserves above the current gas price.
+//!
+//! The pair volatility and the gas price headroom change slowly compared to
+//! how often quotes are requested, so they are cached for a configurable
+//! amount of time.
+
+use {
+    super::Quote,
+    anyhow::Result,
+    cached::{Cached, TimedCache, TimedSizedCache},
+    ethcontract::H160,
+    gas_estimation::{GasPrice1559, GasPriceEstimating},
+    std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+};
+
+/// Source of recent execution prices for token pairs.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait PairPriceHistory: Send + Sync {
+    /// Returns recent prices of the token pair denominated as sell amount per
+    /// buy amount, most recent prices first.
+    async fn recent_prices(&self, sell_token: H160, buy_token: H160) -> Result<Vec<f64>>;
+}
+
+/// Parameters for computing suggested slippage tolerances.
+#[derive(Clone, Debug)]
+pub struct Configuration {
+    /// The smallest slippage tolerance to suggest in basis points.
+    pub min_bps: u32,
+    /// The largest slippage tolerance to suggest in basis points.
+    pub max_bps: u32,
+    /// Factor to multiply the pair's price volatility with. The volatility is
+    /// measured as the standard deviation of relative price changes, so a
+    /// factor of 2 covers roughly 95% of the observed price movements.
+    pub volatility_factor: f64,
+    /// How long the volatility of a token pair and the gas price headroom are
+    /// cached for.
+    pub cache_ttl: Duration,
+}
+
+/// Computes suggested slippage tolerances for quotes.
+pub struct SlippageEstimator {
+    history: Arc<dyn PairPriceHistory>,
+    gas_estimator: Arc<dyn GasPriceEstimating>,
+    config: Configuration,
+    volatilities: Mutex<TimedSizedCache<(H160, H160), Option<f64>>>,
+    gas_price_headroom: Mutex<TimedCache<(), f64>>,
+}
+
+impl SlippageEstimator {
+    const CACHE_SIZE: usize = 10_000;
+
+    /// Panics if the smallest slippage tolerance to suggest is larger than the
+    /// largest one.
+    pub fn new(
+        history: Arc<dyn PairPriceHistory>,
+        gas_estimator: Arc<dyn GasPriceEstimating>,
+        config: Configuration,
+    ) -> Self {
+        assert!(
+            config.min_bps <= config.max_bps,
+            "minimum suggested slippage of {} bps is larger than the maximum of {} bps",
+            config.min_bps,
+            config.max_bps,
+        );
+        let ttl = config.cache_ttl.as_secs();
+        Self {
+            history,
+            gas_estimator,
+            config,
+            volatilities: Mutex::new(TimedSizedCache::with_size_and_lifespan(
+                Self::CACHE_SIZE,
+                ttl,
+            )),
+            gas_price_headroom: Mutex::new(TimedCache::with_lifespan(ttl)),
+        }
+    }
+
+    /// Returns the suggested slippage tolerance in basis points for a quote.
+    pub async fn suggest(&self, quote: &Quote) -> u32 {
+        let (volatility, gas_price_headroom) = futures::join!(
+            self.pair_volatility(quote.data.sell_token, quote.data.buy_token),
+            self.gas_price_headroom(),
+        );
+        let fee_volatility = gas_price_headroom.unwrap_or_default() * fee_share(quote);
+
+        let slippage = self
+            .config
+            .suggest(quote.spread_bps, volatility, fee_volatility);
+        tracing::debug!(
+            spread_bps = quote.spread_bps,
+            ?volatility,
+            fee_volatility,
+            slippage,
+            "suggested slippage"
+        );
+        slippage
+    }
+
+    /// Returns the recent price volatility of a token pair. Failures to fetch
+    /// the price history are not cached so they get retried with the next
+    /// quote.
+    async fn pair_volatility(&self, sell_token: H160, buy_token: H160) -> Option<f64> {
+        let pair = (sell_token, buy_token);
+        if let Some(volatility) = self.volatilities.lock().unwrap().cache_get(&pair) {
+            return *volatility;
+        }
+
+        let volatility = match self.history.recent_prices(sell_token, buy_token).await {
+            Ok(prices) => volatility(&prices),
+            Err(err) => {
+                tracing::warn!(?err, "failed to fetch recent prices for slippage");
+                return None;
+            }
+        };
+        self.volatilities
+            .lock()
+            .unwrap()
+            .cache_set(pair, volatility);
+        volatility
+    }
+
+    /// Returns the current gas price headroom.
+    async fn gas_price_headroom(&self) -> Option<f64> {
+        if let Some(headroom) = self.gas_price_headroom.lock().unwrap().cache_get(&()) {
+            return Some(*headroom);
+        }
+
+        let headroom = match self.gas_estimator.estimate().await {
+            Ok(gas_price) => gas_price_headroom(&gas_price),
+            Err(err) => {
+                tracing::warn!(?err, "failed to estimate gas price for slippage");
+                return None;
+            }
+        };
+        self.gas_price_headroom
+            .lock()
+            .unwrap()
+            .cache_set((), headroom);
+        Some(headroom)
+    }
+}
+
+impl Configuration {
+    fn suggest(&self, spread_bps: u32, volatility: Option<f64>, fee_volatility: f64) -> u32 {
+        let variable_bps =
+            (volatility.unwrap_or_default() * self.volatility_factor + fee_volatility) * 10_000.;
+        let bps = spread_bps as f64 + variable_bps;
+        (bps.ceil() as u32).clamp(self.min_bps, self.max_bps)
+    }
+}
+
+/// Computes the standard deviation of relative changes between consecutive
+/// prices. Returns `None` if there aren't enough prices to compute it.
+fn volatility(prices: &[f64]) -> Option<f64> {
+    let returns = prices
+        .windows(2)
+        .filter(|window| window[0] > 0. && window[1] > 0.)
+        .map(|window| (window[0] / window[1]).ln())
+        .collect::<Vec<_>>();
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let count = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / count;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (count - 1.);
+    Some(variance.sqrt())
+}
+
+/// The relative amount by which the gas price could increase until it
+/// reaches the maximum fee per gas that the gas estimator suggests.
+fn gas_price_headroom(gas_price: &GasPrice1559) -> f64 {
+    let effective = gas_price.effective_gas_price();
+    if effective <= 0. {
+        return 0.;
+    }
+    (gas_price.max_fee_per_gas / effective - 1.).max(0.)
+}
+
+/// The share of the traded sell amount that is spent on fees.
+fn fee_share(quote: &Quote) -> f64 {
+    let total = quote.sell_amount.to_f64_lossy() + quote.fee_amount.to_f64_lossy();
+    if total <= 0. {
+        return 0.;
+    }
+    quote.fee_amount.to_f64_lossy() / total
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::gas_price_estimation::FakeGasPriceEstimator, std::sync::Mutex};
+
+    fn config() -> Configuration {
+        Configuration {
+            min_bps: 50,
+            max_bps: 500,
+            volatility_factor: 2.,
+            cache_ttl: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn volatility_requires_enough_prices() {
+        assert_eq!(volatility(&[]), None);
+        assert_eq!(volatility(&[1., 1.1]), None);
+        assert_eq!(volatility(&[1., 1., 1.]), Some(0.));
+        assert!(volatility(&[1., 1.1, 1., 1.1]).unwrap() > 0.09);
+    }
+
+    #[test]
+    fn gas_price_headroom_is_relative_to_effective_price() {
+        let gas_price = GasPrice1559 {
+            base_fee_per_gas: 10.,
+            max_fee_per_gas: 30.,
+            max_priority_fee_per_gas: 5.,
+        };
+        assert_eq!(gas_price_headroom(&gas_price), 1.);
+        assert_eq!(gas_price_headroom(&GasPrice1559::default()), 0.);
+    }
+
+    #[test]
+    fn suggestion_is_clamped() {
+        let config = config();
+        assert_eq!(config.suggest(0, None, 0.), 50);
+        assert_eq!(config.suggest(100, None, 0.), 100);
+        assert_eq!(config.suggest(100, Some(0.01), 0.005), 350);
+        assert_eq!(config.suggest(10_000, None, 0.), 500);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_inverted_bounds() {
+        SlippageEstimator::new(
+            Arc::new(MockPairPriceHistory::new()),
+            Arc::new(FakeGasPriceEstimator::default()),
+            Configuration {
+                min_bps: 500,
+                max_bps: 50,
+                ..config()
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_slippage_for_quote() {
+        let mut history = MockPairPriceHistory::new();
+        // The pair volatility is cached, so the price history of the pair is
+        // only fetched once.
+        history
+            .expect_recent_prices()
+            .times(1)
+            .returning(|_, _| Ok(vec![1., 1., 1.]));
+        let gas_estimator = FakeGasPriceEstimator(Arc::new(Mutex::new(GasPrice1559 {
+            base_fee_per_gas: 10.,
+            max_fee_per_gas: 20.,
+            max_priority_fee_per_gas: 0.,
+        })));
+        let estimator =
+            SlippageEstimator::new(Arc::new(history), Arc::new(gas_estimator), config());
+
+        // 1% of the sell amount is spent on fees and the gas price can double,
+        // so the fee volatility accounts for 100 bps.
+        let quote = Quote {
+            sell_amount: 99.into(),
+            fee_amount: 1.into(),
+            spread_bps: 20,
+            ..Default::default()
+        };
+        assert_eq!(estimator.suggest(&quote).await, 120);
+        assert_eq!(estimator.suggest(&quote).await, 120);
+    }
+}
@@ -1,9 +1,10 @@
 use {
     crate::{
         account_balances::{self, BalanceFetching, TransferSimulationError},
-        app_data::{ProtocolAppData, ValidatedAppData},
+        app_data::ValidatedAppData,
         bad_token::{BadTokenDetecting, TokenQuality},
         code_fetching::CodeFetching,
+        native_sell,
         order_quoting::{
             CalculateQuoteError,
             FindQuoteError,
@@ -12,6 +13,7 @@ use {
             QuoteParameters,
             QuoteSearchParameters,
         },
+        permit::{Permit, PermitError, PermittedOrder},
         price_estimation::{PriceEstimationError, Verification},
         signature_validator::{SignatureCheck, SignatureValidating, SignatureValidationError},
         trade_finding,
@@ -60,7 +62,8 @@ pub trait OrderValidating: Send + Sync {
     ///
     /// Specifically, but *does* verify:
     ///     - if buy token is native asset, receiver is not a smart contract,
-    ///     - the sell token is not the native asset,
+    ///     - the sell token is not the native asset (unless native sell orders
+    ///       are enabled and the owner is a smart contract wallet),
     ///     - the sender is not a banned user,
     ///     - the order validity is appropriate,
     ///     - buy_token is not the same as sell_token,
@@ -77,6 +80,14 @@ pub trait OrderValidating: Send + Sync {
         full_app_data_override: &Option<String>,
     ) -> Result<OrderAppData, AppDataValidationError>;
 
+    /// Converts an order selling the native asset into an order selling the
+    /// wrapped native token by attaching a pre-hook that wraps the owner's
+    /// native asset balance to the app data interactions. Returns the token
+    /// that actually gets sold.
+    ///
+    /// This assumes that the order already passed partial validation.
+    fn wrap_native_sell(&self, owner: H160, app_data: &mut OrderAppData) -> H160;
+
     /// This is the full order validation performed at the time of order
     /// placement (i.e. once all the required fields on an Order are
     /// provided). Specifically, verifying that
@@ -129,7 +140,7 @@ pub enum AppDataValidationError {
         provided: AppDataHash,
         actual: AppDataHash,
     },
-    Invalid(anyhow::Error),
+    Invalid(crate::app_data::Error),
     UnsupportedCustomInteraction,
 }
 
@@ -164,6 +175,9 @@ pub enum ValidationError {
     ZeroAmount,
     IncompatibleSigningScheme,
     TooManyLimitOrders,
+    /// An EIP-2612 permit pre-hook does not grant the vault relayer the
+    /// allowance required by the order.
+    InvalidPermit(PermitError),
     Other(anyhow::Error),
 }
 
@@ -257,6 +271,16 @@ pub struct OrderValidator {
     pub code_fetcher: Arc<dyn CodeFetching>,
     pub enable_eth_smart_contract_payments: bool,
     enable_custom_interactions: bool,
+    /// The vault relayer that EIP-2612 permit pre-hooks have to approve. When
+    /// set, permit pre-hooks are accepted even if custom interactions are
+    /// disabled.
+    permit_hooks: Option<H160>,
+    /// Whether orders using Balancer V2 Vault balances (external or internal)
+    /// are accepted. This requires the Vault to be deployed on the network.
+    enable_vault_balances: bool,
+    /// Whether smart contract wallets can sell the native asset by having it
+    /// wrapped with a pre-hook.
+    enable_native_sell_orders: bool,
     app_data_validator: crate::app_data::Validator,
     request_verified_quotes: bool,
 }
@@ -351,6 +375,9 @@ impl OrderValidator {
             code_fetcher,
             enable_eth_smart_contract_payments: false,
             enable_custom_interactions: false,
+            permit_hooks: None,
+            enable_vault_balances: false,
+            enable_native_sell_orders: false,
             app_data_validator,
             request_verified_quotes: false,
         }
@@ -376,6 +403,21 @@ impl OrderValidator {
         self
     }
 
+    pub fn with_permit_hooks(mut self, vault_relayer: Option<H160>) -> Self {
+        self.permit_hooks = vault_relayer;
+        self
+    }
+
+    pub fn with_vault_balances(mut self, enable: bool) -> Self {
+        self.enable_vault_balances = enable;
+        self
+    }
+
+    pub fn with_native_sell_orders(mut self, enable: bool) -> Self {
+        self.enable_native_sell_orders = enable;
+        self
+    }
+
     pub fn with_verified_quotes(mut self, enable: bool) -> Self {
         self.request_verified_quotes = enable;
         self
@@ -399,6 +441,30 @@ impl OrderValidator {
         Ok(())
     }
 
+    /// Returns whether the order is allowed to sell the native asset. This is
+    /// only possible for smart contract wallets which can wrap their balance
+    /// in a pre-hook.
+    async fn can_sell_native_asset(&self, order: &PreOrderData) -> Result<bool> {
+        if !self.enable_native_sell_orders
+            || !native_sell::supports_signing_scheme(order.signing_scheme)
+            || order.sell_token_balance != SellTokenSource::Erc20
+        {
+            return Ok(false);
+        }
+        Ok(self.code_fetcher.code_size(order.owner).await? != 0)
+    }
+
+    /// Returns whether the hooks only consist of EIP-2612 permit pre-hooks that
+    /// are accepted regardless of custom interactions being enabled.
+    fn only_permit_hooks(&self, hooks: &Hooks) -> bool {
+        self.permit_hooks.is_some()
+            && hooks.post.is_empty()
+            && hooks
+                .pre
+                .iter()
+                .all(|hook| Permit::from_hook(hook).is_some())
+    }
+
     fn custom_interactions(&self, hooks: &Hooks) -> Interactions {
         let to_interactions = |hooks: &[Hook]| -> Vec<InteractionData> {
             if hooks.is_empty() {
@@ -460,15 +526,12 @@ impl OrderValidating for OrderValidator {
             OrderClass::Liquidity => (),
         }
 
-        if order.buy_token_balance != BuyTokenDestination::Erc20 {
+        if order.buy_token_balance != BuyTokenDestination::Erc20 && !self.enable_vault_balances {
             return Err(PartialValidationError::UnsupportedBuyTokenDestination(
                 order.buy_token_balance,
             ));
         }
-        if !matches!(
-            order.sell_token_balance,
-            SellTokenSource::Erc20 | SellTokenSource::External
-        ) {
+        if order.sell_token_balance != SellTokenSource::Erc20 && !self.enable_vault_balances {
             return Err(PartialValidationError::UnsupportedSellTokenSource(
                 order.sell_token_balance,
             ));
@@ -487,7 +550,12 @@ impl OrderValidating for OrderValidator {
         if has_same_buy_and_sell_token(&order, &self.native_token) {
             return Err(PartialValidationError::SameBuyAndSellToken);
         }
-        if order.sell_token == BUY_ETH_ADDRESS {
+        if order.sell_token == BUY_ETH_ADDRESS
+            && !self
+                .can_sell_native_asset(&order)
+                .await
+                .map_err(PartialValidationError::Other)?
+        {
             return Err(PartialValidationError::InvalidNativeSellToken);
         }
         if !self.enable_eth_smart_contract_payments && order.buy_token == BUY_ETH_ADDRESS {
@@ -542,23 +610,28 @@ impl OrderValidating for OrderValidator {
             OrderCreationAppData::Hash { hash } => {
                 // Eventually we're not going to accept orders that set only a
                 // hash and where we can't find full app data elsewhere.
-                let protocol = if let Some(full) = full_app_data_override {
-                    validate(full)?.protocol
+                let (protocol, sections) = if let Some(full) = full_app_data_override {
+                    let validated = validate(full)?;
+                    (validated.protocol, validated.sections)
                 } else {
                     tracing::warn!(hash = hex::encode(hash.0), "Unknown appData pre-image");
-                    ProtocolAppData::default()
+                    Default::default()
                 };
 
                 ValidatedAppData {
                     hash: *hash,
                     document: String::new(),
                     protocol,
+                    sections,
                 }
             }
             OrderCreationAppData::Full { full } => validate(full)?,
         };
 
-        if !self.enable_custom_interactions && !app_data.protocol.hooks.is_empty() {
+        if !self.enable_custom_interactions
+            && !app_data.protocol.hooks.is_empty()
+            && !self.only_permit_hooks(&app_data.protocol.hooks)
+        {
             // contains some custom interactions while feature is disabled
             return Err(AppDataValidationError::UnsupportedCustomInteraction);
         }
@@ -571,6 +644,19 @@ impl OrderValidating for OrderValidator {
         })
     }
 
+    fn wrap_native_sell(&self, owner: H160, app_data: &mut OrderAppData) -> H160 {
+        let hooks = &mut app_data.inner.protocol.hooks;
+        if !hooks
+            .pre
+            .iter()
+            .any(|hook| native_sell::is_wrap_hook(hook, owner))
+        {
+            hooks.pre.insert(0, native_sell::hook(owner));
+        }
+        app_data.interactions = self.custom_interactions(hooks);
+        self.native_token.address()
+    }
+
     async fn validate_and_construct_order(
         &self,
         order: OrderCreation,
@@ -580,15 +666,37 @@ impl OrderValidating for OrderValidator {
     ) -> Result<(Order, Option<Quote>), ValidationError> {
         // Happens before signature verification because a miscalculated app data hash
         // by the API user would lead to being unable to validate the signature below.
-        let app_data = self.validate_app_data(&order.app_data, &full_app_data_override)?;
+        let mut app_data = self.validate_app_data(&order.app_data, &full_app_data_override)?;
         let app_data_signer = app_data.inner.protocol.signer;
 
         let owner = order.verify_owner(domain_separator, app_data_signer)?;
         let signing_scheme = order.signature.scheme();
-        let data = OrderData {
+        let mut data = OrderData {
             app_data: app_data.inner.hash,
             ..order.data()
         };
+
+        // Orders selling the native asset are converted into orders selling
+        // the wrapped native token before computing their UID, so signatures
+        // have to be for the converted order.
+        if data.sell_token == BUY_ETH_ADDRESS {
+            let pre_order = PreOrderData::from_order_creation(
+                owner,
+                &data,
+                signing_scheme,
+                self.liquidity_order_owners.contains(&owner),
+            );
+            if !self
+                .can_sell_native_asset(&pre_order)
+                .await
+                .map_err(ValidationError::Other)?
+            {
+                return Err(ValidationError::Partial(
+                    PartialValidationError::InvalidNativeSellToken,
+                ));
+            }
+            data.sell_token = self.wrap_native_sell(owner, &mut app_data);
+        }
         let uid = data.uid(domain_separator, &owner);
 
         let verification_gas_limit = if let Signature::Eip1271(signature) = &order.signature {
@@ -689,6 +797,31 @@ impl OrderValidating for OrderValidator {
 
         let min_balance = minimum_balance(&data).ok_or(ValidationError::SellAmountOverflow)?;
 
+        if let Some(vault_relayer) = self.permit_hooks {
+            let permitted = PermittedOrder {
+                owner,
+                sell_token: data.sell_token,
+                vault_relayer,
+                amount: data
+                    .sell_amount
+                    .checked_add(data.fee_amount)
+                    .ok_or(ValidationError::SellAmountOverflow)?,
+                valid_to: data.valid_to,
+            };
+            for permit in app_data
+                .inner
+                .protocol
+                .hooks
+                .pre
+                .iter()
+                .filter_map(Permit::from_hook)
+            {
+                permit
+                    .validate(&permitted)
+                    .map_err(ValidationError::InvalidPermit)?;
+            }
+        }
+
         // Fast path to check if transfer is possible with a single node query.
         // If not, run extra queries for additional information.
         match self
@@ -767,6 +900,7 @@ impl OrderValidating for OrderValidator {
                     | OrderCreationAppData::Full { full } => Some(full),
                     OrderCreationAppData::Hash { .. } => full_app_data_override,
                 },
+                cow_only: app_data.inner.protocol.cow_only,
                 ..Default::default()
             },
             signature: order.signature.clone(),
@@ -876,6 +1010,7 @@ impl SignatureConfiguration {
 fn has_same_buy_and_sell_token(order: &PreOrderData, native_token: &WETH9) -> bool {
     order.sell_token == order.buy_token
         || (order.sell_token == native_token.address() && order.buy_token == BUY_ETH_ADDRESS)
+        || (order.sell_token == BUY_ETH_ADDRESS && order.buy_token == native_token.address())
 }
 
 /// Min balance user must have in sell token for order to be accepted.
@@ -1013,7 +1148,10 @@ mod tests {
         anyhow::anyhow,
         chrono::Utc,
         contracts::dummy_contract,
-        ethcontract::web3::signing::SecretKeyRef,
+        ethcontract::{
+            common::abi::{self, Token},
+            web3::signing::SecretKeyRef,
+        },
         futures::FutureExt,
         maplit::hashset,
         mockall::predicate::{always, eq},
@@ -1070,9 +1208,9 @@ mod tests {
             },
             &native_token,
         ));
-        // Sell token set to 0xeee...eee has no special meaning, so it isn't
-        // considered buying and selling the same token.
-        assert!(!has_same_buy_and_sell_token(
+        // Selling 0xeee...eee gets converted into selling the wrapped native
+        // token, so it is considered buying and selling the same token.
+        assert!(has_same_buy_and_sell_token(
             &PreOrderData {
                 sell_token: BUY_ETH_ADDRESS,
                 buy_token: native_token.address(),
@@ -1273,7 +1411,8 @@ mod tests {
             Default::default(),
         )
         .with_fill_or_kill_limit_orders(true)
-        .with_partially_fillable_limit_orders(true);
+        .with_partially_fillable_limit_orders(true)
+        .with_vault_balances(true);
         let order = || PreOrderData {
             valid_to: time::now_in_epoch_seconds()
                 + validity_configuration.min.as_secs() as u32
@@ -1313,6 +1452,138 @@ mod tests {
             })
             .await
             .is_ok());
+        assert!(validator
+            .partial_validate(PreOrderData {
+                sell_token_balance: SellTokenSource::Internal,
+                buy_token_balance: BuyTokenDestination::Internal,
+                ..order()
+            })
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn pre_validate_native_sell_orders() {
+        let wallet = H160::from_low_u64_be(0x42);
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        bad_token_detector
+            .expect_detect()
+            .returning(|_| Ok(TokenQuality::Good));
+        let mut code_fetcher = MockCodeFetching::new();
+        code_fetcher
+            .expect_code_size()
+            .returning(move |address| Ok(if address == wallet { 1 } else { 0 }));
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            hashset!(),
+            hashset!(),
+            OrderValidPeriodConfiguration::any(),
+            SignatureConfiguration::all(),
+            Arc::new(bad_token_detector),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(MockOrderQuoting::new()),
+            Arc::new(MockBalanceFetching::new()),
+            Arc::new(MockSignatureValidating::new()),
+            Arc::new(MockLimitOrderCounting::new()),
+            0,
+            Arc::new(code_fetcher),
+            Default::default(),
+        )
+        .with_native_sell_orders(true);
+        let order = || PreOrderData {
+            owner: wallet,
+            valid_to: u32::MAX,
+            sell_token: BUY_ETH_ADDRESS,
+            buy_token: H160::from_low_u64_be(2),
+            signing_scheme: SigningScheme::Eip1271,
+            ..Default::default()
+        };
+
+        assert!(validator.partial_validate(order()).await.is_ok());
+        assert!(validator
+            .partial_validate(PreOrderData {
+                signing_scheme: SigningScheme::PreSign,
+                ..order()
+            })
+            .await
+            .is_ok());
+        assert!(matches!(
+            validator
+                .partial_validate(PreOrderData {
+                    signing_scheme: SigningScheme::Eip712,
+                    ..order()
+                })
+                .await,
+            Err(PartialValidationError::InvalidNativeSellToken)
+        ));
+        assert!(matches!(
+            validator
+                .partial_validate(PreOrderData {
+                    owner: H160::from_low_u64_be(0x43),
+                    ..order()
+                })
+                .await,
+            Err(PartialValidationError::InvalidNativeSellToken)
+        ));
+        assert!(matches!(
+            validator
+                .partial_validate(PreOrderData {
+                    sell_token_balance: SellTokenSource::External,
+                    ..order()
+                })
+                .await,
+            Err(PartialValidationError::InvalidNativeSellToken)
+        ));
+    }
+
+    #[test]
+    fn wrap_native_sell_attaches_wrap_hook_once() {
+        let native_token = dummy_contract!(WETH9, [0xef; 20]);
+        let validator = OrderValidator::new(
+            native_token.clone(),
+            hashset!(),
+            hashset!(),
+            OrderValidPeriodConfiguration::any(),
+            SignatureConfiguration::all(),
+            Arc::new(MockBadTokenDetecting::new()),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(MockOrderQuoting::new()),
+            Arc::new(MockBalanceFetching::new()),
+            Arc::new(MockSignatureValidating::new()),
+            Arc::new(MockLimitOrderCounting::new()),
+            0,
+            Arc::new(MockCodeFetching::new()),
+            Default::default(),
+        )
+        .with_native_sell_orders(true);
+        let owner = H160([1; 20]);
+        let mut app_data = validator
+            .validate_app_data(
+                &OrderCreationAppData::Full {
+                    full: "{}".to_string(),
+                },
+                &None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            validator.wrap_native_sell(owner, &mut app_data),
+            native_token.address()
+        );
+        assert_eq!(
+            validator.wrap_native_sell(owner, &mut app_data),
+            native_token.address()
+        );
+        assert_eq!(
+            app_data.inner.protocol.hooks.pre,
+            vec![native_sell::hook(owner)]
+        );
+        assert_eq!(app_data.interactions.pre.len(), 1);
+        assert_eq!(app_data.interactions.pre[0].target, H160([0xcf; 20]));
+        assert_eq!(
+            app_data.inner.protocol.hooks.gas_limit(),
+            native_sell::GAS_LIMIT
+        );
     }
 
     #[tokio::test]
@@ -2064,6 +2335,147 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn post_validate_permit_hooks() {
+        let mut order_quoter = MockOrderQuoting::new();
+        let mut bad_token_detector = MockBadTokenDetecting::new();
+        let mut balance_fetcher = MockBalanceFetching::new();
+        let mut signature_validator = MockSignatureValidating::new();
+        order_quoter
+            .expect_find_quote()
+            .returning(|_, _| Ok(Default::default()));
+        bad_token_detector
+            .expect_detect()
+            .returning(|_| Ok(TokenQuality::Good));
+        balance_fetcher
+            .expect_can_transfer()
+            .returning(|_, _| Ok(()));
+        signature_validator
+            .expect_validate_signature_and_get_additional_gas()
+            .returning(|_| Ok(0u64));
+        let vault_relayer = H160([3; 20]);
+        let validator = OrderValidator::new(
+            dummy_contract!(WETH9, [0xef; 20]),
+            hashset!(),
+            hashset!(),
+            OrderValidPeriodConfiguration::any(),
+            SignatureConfiguration::all(),
+            Arc::new(bad_token_detector),
+            dummy_contract!(HooksTrampoline, [0xcf; 20]),
+            Arc::new(order_quoter),
+            Arc::new(balance_fetcher),
+            Arc::new(signature_validator),
+            Arc::new(MockLimitOrderCounting::new()),
+            0,
+            Arc::new(MockCodeFetching::new()),
+            Default::default(),
+        )
+        .with_permit_hooks(Some(vault_relayer));
+
+        let owner = H160([1; 20]);
+        let sell_token = H160::from_low_u64_be(1);
+        let permit = |spender: H160, value: U256, deadline: U256| {
+            json!({
+                "target": sell_token,
+                "callData": format!(
+                    "0xd505accf{}",
+                    hex::encode(abi::encode(&[
+                        Token::Address(owner),
+                        Token::Address(spender),
+                        Token::Uint(value),
+                        Token::Uint(deadline),
+                        Token::Uint(27.into()),
+                        Token::FixedBytes(vec![2; 32]),
+                        Token::FixedBytes(vec![3; 32]),
+                    ])),
+                ),
+                "gasLimit": "50000",
+            })
+        };
+        let other = json!({
+            "target": "0x1111111111111111111111111111111111111111",
+            "callData": "0x112233",
+            "gasLimit": "42",
+        });
+        let creation = |pre: Vec<serde_json::Value>, post: Vec<serde_json::Value>| OrderCreation {
+            valid_to: time::now_in_epoch_seconds() + 2,
+            sell_token,
+            buy_token: H160::from_low_u64_be(2),
+            buy_amount: U256::from(1),
+            sell_amount: U256::from(1),
+            fee_amount: U256::from(1),
+            from: Some(owner),
+            signature: Signature::Eip1271(vec![1, 2, 3]),
+            app_data: OrderCreationAppData::Full {
+                full: json!({ "metadata": { "hooks": { "pre": pre, "post": post } } }).to_string(),
+            },
+            ..Default::default()
+        };
+        let validate = |creation: OrderCreation| {
+            validator.validate_and_construct_order(
+                creation,
+                &Default::default(),
+                Default::default(),
+                None,
+            )
+        };
+
+        // Permits are accepted even though custom interactions are disabled.
+        let (order, _) = validate(creation(
+            vec![permit(vault_relayer, 2.into(), U256::MAX)],
+            vec![],
+        ))
+        .await
+        .unwrap();
+        assert_eq!(order.interactions.pre.len(), 1);
+
+        // The permit has to cover the order.
+        assert!(matches!(
+            validate(creation(
+                vec![permit(H160([4; 20]), 2.into(), U256::MAX)],
+                vec![],
+            ))
+            .await
+            .unwrap_err(),
+            ValidationError::InvalidPermit(PermitError::Spender(_)),
+        ));
+        assert!(matches!(
+            validate(creation(
+                vec![permit(vault_relayer, 1.into(), U256::MAX)],
+                vec![]
+            ))
+            .await
+            .unwrap_err(),
+            ValidationError::InvalidPermit(PermitError::Value(_)),
+        ));
+        assert!(matches!(
+            validate(creation(
+                vec![permit(vault_relayer, 2.into(), 1.into())],
+                vec![]
+            ))
+            .await
+            .unwrap_err(),
+            ValidationError::InvalidPermit(PermitError::Deadline(_)),
+        ));
+
+        // Other hooks still require custom interactions.
+        for (pre, post) in [
+            (
+                vec![permit(vault_relayer, 2.into(), U256::MAX), other.clone()],
+                vec![],
+            ),
+            (
+                vec![permit(vault_relayer, 2.into(), U256::MAX)],
+                vec![other.clone()],
+            ),
+        ] {
+            assert!(matches!(
+                validate(creation(pre, post)).await.unwrap_err(),
+                ValidationError::AppData(AppDataValidationError::UnsupportedCustomInteraction),
+            ));
+        }
+    }
+
     #[test]
     fn allows_insufficient_allowance_and_balance_for_presign_orders() {
         fn assert_allows_failed_transfer(
@@ -0,0 +1,192 @@
+//! EIP-2612 `permit` pre-hooks. These allow users that never approved the
+//! vault relayer to place orders without sending an approval transaction, by
+//! including a signed permit for the sell token as a pre-hook that gets
+//! executed as part of the settlement.
+
+use {
+    ethcontract::{
+        common::abi::{self, ParamType, Token},
+        H160,
+        U256,
+    },
+    hex_literal::hex,
+    model::order::Hook,
+};
+
+/// The selector of
+/// `permit(address owner, address spender, uint256 value, uint256 deadline,
+/// uint8 v, bytes32 r, bytes32 s)`.
+const SELECTOR: [u8; 4] = hex!("d505accf");
+
+/// A decoded EIP-2612 permit hook.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Permit {
+    pub token: H160,
+    pub owner: H160,
+    pub spender: H160,
+    pub value: U256,
+    pub deadline: U256,
+}
+
+impl Permit {
+    /// Decodes a hook as an EIP-2612 permit. Returns `None` if the hook does
+    /// not call `permit`.
+    pub fn from_hook(hook: &Hook) -> Option<Self> {
+        let params = hook.call_data.strip_prefix(&SELECTOR)?;
+        let tokens = abi::decode(
+            &[
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(8),
+                ParamType::FixedBytes(32),
+                ParamType::FixedBytes(32),
+            ],
+            params,
+        )
+        .ok()?;
+        match tokens.as_slice() {
+            [Token::Address(owner), Token::Address(spender), Token::Uint(value), Token::Uint(deadline), ..] => {
+                Some(Self {
+                    token: hook.target,
+                    owner: *owner,
+                    spender: *spender,
+                    value: *value,
+                    deadline: *deadline,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Verifies that the permit allows the vault relayer to transfer at least
+    /// `amount` of the order's sell token on behalf of the owner for as long
+    /// as the order is valid.
+    pub fn validate(&self, order: &PermittedOrder) -> Result<(), PermitError> {
+        if self.token != order.sell_token {
+            return Err(PermitError::Token(self.token));
+        }
+        if self.owner != order.owner {
+            return Err(PermitError::Owner(self.owner));
+        }
+        if self.spender != order.vault_relayer {
+            return Err(PermitError::Spender(self.spender));
+        }
+        if self.value < order.amount {
+            return Err(PermitError::Value(self.value));
+        }
+        if self.deadline < order.valid_to.into() {
+            return Err(PermitError::Deadline(self.deadline));
+        }
+        Ok(())
+    }
+}
+
+/// The order parameters a permit hook is validated against.
+#[derive(Clone, Copy, Debug)]
+pub struct PermittedOrder {
+    pub owner: H160,
+    pub sell_token: H160,
+    pub vault_relayer: H160,
+    /// The total amount of sell token that gets transferred for the order.
+    pub amount: U256,
+    pub valid_to: u32,
+}
+
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum PermitError {
+    #[error("permit is for token {0:?} which is not the sell token")]
+    Token(H160),
+    #[error("permit owner {0:?} is not the order owner")]
+    Owner(H160),
+    #[error("permit spender {0:?} is not the vault relayer")]
+    Spender(H160),
+    #[error("permit value {0} does not cover the sell amount")]
+    Value(U256),
+    #[error("permit deadline {0} is before the order expires")]
+    Deadline(U256),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(owner: H160, spender: H160, value: U256, deadline: U256) -> Hook {
+        Hook {
+            target: H160([1; 20]),
+            call_data: SELECTOR
+                .into_iter()
+                .chain(abi::encode(&[
+                    Token::Address(owner),
+                    Token::Address(spender),
+                    Token::Uint(value),
+                    Token::Uint(deadline),
+                    Token::Uint(27.into()),
+                    Token::FixedBytes(vec![2; 32]),
+                    Token::FixedBytes(vec![3; 32]),
+                ]))
+                .collect(),
+            gas_limit: 50_000,
+        }
+    }
+
+    #[test]
+    fn decodes_and_validates_permits() {
+        let order = PermittedOrder {
+            owner: H160([2; 20]),
+            sell_token: H160([1; 20]),
+            vault_relayer: H160([3; 20]),
+            amount: 1000.into(),
+            valid_to: 100,
+        };
+
+        let permit = Permit::from_hook(&hook(
+            order.owner,
+            order.vault_relayer,
+            1000.into(),
+            100.into(),
+        ))
+        .unwrap();
+        assert_eq!(permit.token, order.sell_token);
+        assert_eq!(permit.validate(&order), Ok(()));
+
+        let permit =
+            Permit::from_hook(&hook(order.owner, H160([4; 20]), 1000.into(), 100.into())).unwrap();
+        assert_eq!(
+            permit.validate(&order),
+            Err(PermitError::Spender(H160([4; 20])))
+        );
+
+        let permit = Permit::from_hook(&hook(
+            order.owner,
+            order.vault_relayer,
+            999.into(),
+            100.into(),
+        ))
+        .unwrap();
+        assert_eq!(permit.validate(&order), Err(PermitError::Value(999.into())));
+
+        let permit = Permit::from_hook(&hook(
+            order.owner,
+            order.vault_relayer,
+            1000.into(),
+            99.into(),
+        ))
+        .unwrap();
+        assert_eq!(
+            permit.validate(&order),
+            Err(PermitError::Deadline(99.into()))
+        );
+    }
+
+    #[test]
+    fn ignores_other_hooks() {
+        let hook = Hook {
+            target: H160([1; 20]),
+            call_data: vec![1, 2, 3, 4],
+            gas_limit: 0,
+        };
+        assert_eq!(Permit::from_hook(&hook), None);
+    }
+}
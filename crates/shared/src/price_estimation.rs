@@ -460,6 +460,11 @@ pub struct Estimate {
     pub gas: u64,
     /// Address of the solver that provided the quote.
     pub solver: H160,
+    /// Relative spread in basis points between the out amounts of the best
+    /// and the worst successful estimates of competing price estimators. This
+    /// is zero if the estimate did not compete with any other estimates.
+    #[serde(default)]
+    pub spread_bps: u32,
 }
 
 impl Estimate {
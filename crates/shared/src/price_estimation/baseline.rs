@@ -1,6 +1,12 @@
 use {
     crate::{
-        baseline_solver::{self, estimate_buy_amount, estimate_sell_amount, BaseTokens},
+        baseline_solver::{
+            self,
+            estimate_buy_amount,
+            estimate_sell_amount,
+            BaseTokens,
+            BaselineSolvable,
+        },
         conversions::U256Ext,
         price_estimation::{
             gas,
@@ -11,7 +17,10 @@ use {
             Query,
         },
         recent_block_cache::Block,
-        sources::uniswap_v2::pool_fetching::{Pool, PoolFetching},
+        sources::{
+            solidly,
+            uniswap_v2::pool_fetching::{Pool, PoolFetching},
+        },
     },
     anyhow::Result,
     ethcontract::{H160, U256},
@@ -25,6 +34,7 @@ use {
 
 pub struct BaselinePriceEstimator {
     pool_fetcher: Arc<dyn PoolFetching>,
+    solidly_pool_fetcher: Option<Arc<dyn solidly::pool_fetching::PoolFetching>>,
     gas_estimator: Arc<dyn GasPriceEstimating>,
     base_tokens: Arc<BaseTokens>,
     native_token: H160,
@@ -43,6 +53,7 @@ impl BaselinePriceEstimator {
     ) -> Self {
         Self {
             pool_fetcher,
+            solidly_pool_fetcher: None,
             gas_estimator,
             base_tokens,
             native_token,
@@ -50,9 +61,69 @@ impl BaselinePriceEstimator {
             solver,
         }
     }
+
+    /// Additionally considers Solidly pools when estimating prices.
+    pub fn with_solidly_pools(
+        mut self,
+        pool_fetcher: Arc<dyn solidly::pool_fetching::PoolFetching>,
+    ) -> Self {
+        self.solidly_pool_fetcher = Some(pool_fetcher);
+        self
+    }
+}
+
+/// Liquidity the baseline price estimator can route through.
+#[derive(Clone, Debug)]
+enum Liquidity {
+    UniswapV2(Pool),
+    Solidly(solidly::pool_fetching::Pool),
+}
+
+impl Liquidity {
+    fn tokens(&self) -> TokenPair {
+        match self {
+            Self::UniswapV2(pool) => pool.tokens,
+            Self::Solidly(pool) => pool.tokens,
+        }
+    }
+}
+
+impl From<Pool> for Liquidity {
+    fn from(pool: Pool) -> Self {
+        Self::UniswapV2(pool)
+    }
+}
+
+impl From<solidly::pool_fetching::Pool> for Liquidity {
+    fn from(pool: solidly::pool_fetching::Pool) -> Self {
+        Self::Solidly(pool)
+    }
+}
+
+impl BaselineSolvable for Liquidity {
+    fn get_amount_out(&self, out_token: H160, input: (U256, H160)) -> Option<U256> {
+        match self {
+            Self::UniswapV2(pool) => pool.get_amount_out(out_token, input),
+            Self::Solidly(pool) => pool.get_amount_out(out_token, input),
+        }
+    }
+
+    fn get_amount_in(&self, in_token: H160, output: (U256, H160)) -> Option<U256> {
+        match self {
+            Self::UniswapV2(pool) => pool.get_amount_in(in_token, output),
+            Self::Solidly(pool) => pool.get_amount_in(in_token, output),
+        }
+    }
+
+    fn gas_cost(&self) -> usize {
+        match self {
+            Self::UniswapV2(pool) => pool.gas_cost(),
+            Self::Solidly(pool) => pool.gas_cost(),
+        }
+    }
 }
 
-type Pools = HashMap<TokenPair, Vec<Pool>>;
+type Pools = HashMap<TokenPair, Vec<Liquidity>>;
 
 impl PriceEstimating for BaselinePriceEstimator {
     fn estimate(&self, query: Arc<Query>) -> futures::future::BoxFuture<'_, PriceEstimateResult> {
@@ -78,6 +149,7 @@ impl PriceEstimating for BaselinePriceEstimator {
                 out_amount,
                 gas,
                 solver: self.solver,
+                spread_bps: 0,
             })
         }
         .boxed()
@@ -89,8 +161,22 @@ impl BaselinePriceEstimator {
         let pairs = self
             .base_tokens
             .relevant_pairs(TokenPair::new(query.buy_token, query.sell_token).into_iter());
-        let pools = self.pool_fetcher.fetch(pairs, Block::Recent).await?;
-        Ok(pools_vec_to_map(pools))
+        let solidly_pools = async {
+            match &self.solidly_pool_fetcher {
+                Some(pool_fetcher) => pool_fetcher.fetch(pairs.clone(), Block::Recent).await,
+                None => Ok(Vec::new()),
+            }
+        };
+        let (pools, solidly_pools) = futures::try_join!(
+            self.pool_fetcher.fetch(pairs.clone(), Block::Recent),
+            solidly_pools,
+        )?;
+        Ok(pools_vec_to_map(
+            pools
+                .into_iter()
+                .map(Liquidity::from)
+                .chain(solidly_pools.into_iter().map(Liquidity::from)),
+        ))
     }
 
     /// Returns the path and the out amount.
@@ -190,7 +276,7 @@ impl BaselinePriceEstimator {
         buy_token_price_in_native_token: Option<BigRational>,
         pools: &Pools,
     ) -> Result<(Vec<H160>, U256), PriceEstimationError> {
-        let path_comparison = |buy_estimate: baseline_solver::Estimate<U256, Pool>| {
+        let path_comparison = |buy_estimate: baseline_solver::Estimate<U256, Liquidity>| {
             if let Some(buy_token_price_in_native_token) = &buy_token_price_in_native_token {
                 let buy_amount_in_native_token =
                     buy_estimate.value.to_big_rational() * buy_token_price_in_native_token;
@@ -231,7 +317,7 @@ impl BaselinePriceEstimator {
         sell_token_price_in_native_token: Option<BigRational>,
         pools: &Pools,
     ) -> Result<(Vec<H160>, U256), PriceEstimationError> {
-        let path_comparison = |sell_estimate: baseline_solver::Estimate<U256, Pool>| {
+        let path_comparison = |sell_estimate: baseline_solver::Estimate<U256, Liquidity>| {
             if let Some(sell_token_price_in_native_token) = &sell_token_price_in_native_token {
                 let sell_amount_in_native_token =
                     sell_estimate.value.to_big_rational() * sell_token_price_in_native_token;
@@ -270,8 +356,8 @@ impl BaselinePriceEstimator {
         pools: &Pools,
     ) -> Result<(Vec<H160>, Amount), PriceEstimationError>
     where
-        AmountFn: Fn(U256, &[H160], &HashMap<TokenPair, Vec<Pool>>) -> Option<Amount>,
-        CompareFn: Fn(U256, &[H160], &HashMap<TokenPair, Vec<Pool>>) -> O,
+        AmountFn: Fn(U256, &[H160], &Pools) -> Option<Amount>,
+        CompareFn: Fn(U256, &[H160], &Pools) -> O,
         O: Ord,
     {
         debug_assert!(sell_token != buy_token);
@@ -287,9 +373,10 @@ impl BaselinePriceEstimator {
     }
 }
 
-fn pools_vec_to_map(pools: Vec<Pool>) -> Pools {
+fn pools_vec_to_map(pools: impl IntoIterator<Item = impl Into<Liquidity>>) -> Pools {
     pools.into_iter().fold(Pools::new(), |mut pools, pool| {
-        pools.entry(pool.tokens).or_default().push(pool);
+        let pool = pool.into();
+        pools.entry(pool.tokens()).or_default().push(pool);
         pools
     })
 }
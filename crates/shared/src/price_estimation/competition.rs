@@ -87,7 +87,7 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
             + 'static,
         compare_results: impl Fn(&Result<R, E>, &Result<R, E>, &C) -> Ordering + Send + 'static,
         provide_comparison_context: impl Future<Output = Result<C, E>> + Send + 'static,
-    ) -> futures::future::BoxFuture<'_, Result<R, E>> {
+    ) -> futures::future::BoxFuture<'_, (Result<R, E>, Vec<R>)> {
         let start = Instant::now();
         async move {
             let mut results = vec![];
@@ -145,7 +145,10 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
                 }
             }
 
-            let context = provide_comparison_context.await?;
+            let context = match provide_comparison_context.await {
+                Ok(context) => context,
+                Err(err) => return (Err(err), Vec::new()),
+            };
 
             let best_index = results
                 .iter()
@@ -182,7 +185,11 @@ impl<T: Send + Sync + 'static> RacingCompetitionEstimator<T> {
                     .with_label_values(&[estimator, kind.label()])
                     .inc();
             }
-            result.clone()
+            let successes = results
+                .iter()
+                .filter_map(|(_, result)| result.as_ref().ok().cloned())
+                .collect();
+            (result.clone(), successes)
         }
         .boxed()
     }
@@ -212,7 +219,27 @@ impl PriceEstimating for RacingCompetitionEstimator<Arc<dyn PriceEstimating>> {
             },
             context_future,
         )
+        .map(|(result, estimates)| {
+            result.map(|estimate| Estimate {
+                spread_bps: spread_bps(&estimates),
+                ..estimate
+            })
+        })
+        .boxed()
+    }
+}
+
+/// Computes the relative spread in basis points between the largest and the
+/// smallest out amounts of the specified estimates.
+fn spread_bps(estimates: &[Estimate]) -> u32 {
+    let out_amounts = estimates.iter().map(|estimate| estimate.out_amount);
+    let (Some(max), Some(min)) = (out_amounts.clone().max(), out_amounts.min()) else {
+        return 0;
+    };
+    if max.is_zero() {
+        return 0;
     }
+    ((max - min).to_f64_lossy() / max.to_f64_lossy() * 10_000.).round() as u32
 }
 
 impl NativePriceEstimating for RacingCompetitionEstimator<Arc<dyn NativePriceEstimating>> {
@@ -234,6 +261,8 @@ impl NativePriceEstimating for RacingCompetitionEstimator<Arc<dyn NativePriceEst
             },
             context_future,
         )
+        .map(|(result, _)| result)
+        .boxed()
     }
 }
 
@@ -538,11 +567,23 @@ mod tests {
 
         let result = priority.estimate(queries[1].clone()).await;
         // buy 2 is better than buy 1
-        assert_eq!(result.as_ref().unwrap(), &estimates[1]);
+        assert_eq!(
+            result.as_ref().unwrap(),
+            &Estimate {
+                spread_bps: 5_000,
+                ..estimates[1]
+            }
+        );
 
         let result = priority.estimate(queries[2].clone()).await;
         // pay 1 is better than pay 2
-        assert_eq!(result.as_ref().unwrap(), &estimates[0]);
+        assert_eq!(
+            result.as_ref().unwrap(),
+            &Estimate {
+                spread_bps: 5_000,
+                ..estimates[0]
+            }
+        );
 
         let result = priority.estimate(queries[3].clone()).await;
         // arbitrarily returns one of equal priority errors
@@ -694,7 +735,13 @@ mod tests {
             );
 
         let result = racing.estimate(query).await;
-        assert_eq!(result.as_ref().unwrap(), &estimate(3));
+        assert_eq!(
+            result.as_ref().unwrap(),
+            &Estimate {
+                spread_bps: 6_667,
+                ..estimate(3)
+            }
+        );
     }
 
     #[tokio::test]
@@ -835,7 +882,30 @@ mod tests {
                     ..Default::default()
                 }))
                 .await;
-            assert_eq!(result.unwrap(), preferred_estimate);
+            assert_eq!(
+                result.unwrap(),
+                Estimate {
+                    spread_bps: spread_bps(&[preferred_estimate, worse_estimate]),
+                    ..preferred_estimate
+                }
+            );
         }
     }
+
+    #[test]
+    fn computes_spread_between_estimates() {
+        let estimate = |out_amount: u64| Estimate {
+            out_amount: out_amount.into(),
+            ..Default::default()
+        };
+
+        assert_eq!(spread_bps(&[]), 0);
+        assert_eq!(spread_bps(&[estimate(100)]), 0);
+        assert_eq!(spread_bps(&[estimate(0), estimate(0)]), 0);
+        assert_eq!(spread_bps(&[estimate(99), estimate(100)]), 100);
+        assert_eq!(
+            spread_bps(&[estimate(100), estimate(50), estimate(75)]),
+            5_000
+        );
+    }
 }
@@ -34,6 +34,7 @@ use {
         price_estimation::{competition::PriceRanking, native::NativePriceEstimating},
         sources::{
             balancer_v2::BalancerPoolFetching,
+            solidly::pool_fetching::PoolFetching as SolidlyPoolFetching,
             uniswap_v2::pool_fetching::PoolFetching as UniswapV2PoolFetching,
             uniswap_v3::pool_fetching::PoolFetching as UniswapV3PoolFetching,
         },
@@ -87,6 +88,7 @@ pub struct Components {
     pub uniswap_v2_pools: Arc<dyn UniswapV2PoolFetching>,
     pub balancer_pools: Option<Arc<dyn BalancerPoolFetching>>,
     pub uniswap_v3_pools: Option<Arc<dyn UniswapV3PoolFetching>>,
+    pub solidly_pools: Option<Arc<dyn SolidlyPoolFetching>>,
     pub tokens: Arc<dyn TokenInfoFetching>,
     pub gas_price: Arc<dyn GasPriceEstimating>,
     pub zeroex: Arc<dyn ZeroExApi>,
@@ -429,14 +431,18 @@ impl PriceEstimatorCreating for BaselinePriceEstimator {
     type Params = H160;
 
     fn init(factory: &PriceEstimatorFactory, _name: &str, solver: Self::Params) -> Result<Self> {
-        Ok(BaselinePriceEstimator::new(
+        let estimator = BaselinePriceEstimator::new(
             factory.components.uniswap_v2_pools.clone(),
             factory.components.gas_price.clone(),
             factory.network.base_tokens.clone(),
             factory.network.native_token,
             factory.native_token_price_estimation_amount()?,
             solver,
-        ))
+        );
+        Ok(match &factory.components.solidly_pools {
+            Some(solidly_pools) => estimator.with_solidly_pools(solidly_pools.clone()),
+            None => estimator,
+        })
     }
 }
 impl PriceEstimatorCreating for ParaswapPriceEstimator {
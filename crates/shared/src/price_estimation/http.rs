@@ -259,6 +259,7 @@ impl HttpPriceEstimator {
             },
             gas,
             solver: self.solver,
+            spread_bps: 0,
         })
     }
 
@@ -32,7 +32,7 @@ use {
     futures::future::{BoxFuture, FutureExt as _},
     maplit::hashmap,
     model::{
-        order::{OrderData, OrderKind, BUY_ETH_ADDRESS},
+        order::{BuyTokenDestination, OrderData, OrderKind, BUY_ETH_ADDRESS},
         signature::{Signature, SigningScheme},
     },
     number::nonzero::U256 as NonZeroU256,
@@ -128,6 +128,7 @@ impl Inner {
                     out_amount: quote.out_amount,
                     gas: quote.gas_estimate,
                     solver: quote.solver,
+                    spread_bps: 0,
                 })
             }
         }
@@ -188,8 +189,13 @@ fn encode_settlement(
         fee_amount: 0.into(),
         kind: query.kind,
         partially_fillable: false,
+        // The trader's actual sell token source is used so that the simulation
+        // pulls funds from where the order would (e.g. Vault internal balances).
         sell_token_balance: verification.sell_token_source,
-        buy_token_balance: verification.buy_token_destination,
+        // Bought tokens are always paid out as ERC20 since the balance queries
+        // can only track ERC20 balances of the receiver. Depositing them into
+        // the Vault's internal balances instead would not change the out amount.
+        buy_token_balance: BuyTokenDestination::Erc20,
     };
 
     let fake_signature = Signature::default_with(SigningScheme::Eip1271);
@@ -337,6 +343,7 @@ impl TradeVerifier {
             out_amount: summary.out_amount(query.kind)?,
             gas: summary.gas_used.as_u64(),
             solver: trade.solver,
+            spread_bps: 0,
         };
         tracing::debug!(
             ?query,
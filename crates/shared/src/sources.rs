@@ -1,6 +1,7 @@
 //! Top-level module organizing all baseline liquidity sources.
 
 pub mod balancer_v2;
+pub mod solidly;
 pub mod swapr;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
@@ -27,6 +28,8 @@ pub enum BaselineSource {
     ZeroEx,
     UniswapV3,
     TestnetUniswapV2,
+    VelodromeV2,
+    Aerodrome,
 }
 
 pub fn defaults_for_chain(chain_id: u64) -> Result<Vec<BaselineSource>> {
@@ -0,0 +1,69 @@
+//! Solidly like liquidity source implementation.
+//!
+//! Solidly forks (such as Velodrome V2 and Aerodrome) can have two pools for
+//! every token pair: a "volatile" one using the Uniswap V2 constant product
+//! invariant and a "stable" one using the `x³y + y³x = k` invariant. Fees are
+//! configured per pool on the pool factory.
+
+pub mod pool_cache;
+pub mod pool_fetching;
+
+use {
+    self::pool_fetching::{PoolFetcher, PoolFetching},
+    crate::{ethrpc::Web3, sources::BaselineSource},
+    anyhow::{Context, Result},
+    contracts::{ISolidlyPoolFactory, ISolidlyRouter},
+    ethcontract::H160,
+    ethrpc::current_block::BlockRetrieving,
+    std::sync::Arc,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct SolidlyBaselineSourceParameters {
+    router: H160,
+}
+
+pub struct SolidlyBaselineSource {
+    pub router: ISolidlyRouter,
+    pub factory: H160,
+    pub pool_fetching: Arc<dyn PoolFetching>,
+}
+
+impl SolidlyBaselineSourceParameters {
+    pub fn from_baseline_source(source: BaselineSource, net_version: &str) -> Option<Self> {
+        use BaselineSource as BS;
+        let contract = match source {
+            BS::VelodromeV2 => Some(contracts::VelodromeV2Router::raw_contract()),
+            BS::Aerodrome => Some(contracts::AerodromeRouter::raw_contract()),
+            _ => None,
+        }?;
+        Some(Self {
+            router: contract.networks.get(net_version)?.address,
+        })
+    }
+
+    pub async fn into_source(
+        &self,
+        web3: &Web3,
+        block_retriever: Arc<dyn BlockRetrieving>,
+    ) -> Result<SolidlyBaselineSource> {
+        let router = ISolidlyRouter::at(web3, self.router);
+        let factory = router
+            .default_factory()
+            .call()
+            .await
+            .context("default factory")?;
+        let fetcher = PoolFetcher::new(
+            ISolidlyPoolFactory::at(web3, factory),
+            web3.clone(),
+            block_retriever,
+        )
+        .await
+        .context("pool fetcher")?;
+        Ok(SolidlyBaselineSource {
+            router,
+            factory,
+            pool_fetching: Arc::new(fetcher),
+        })
+    }
+}
@@ -0,0 +1,670 @@
+use {
+    crate::{
+        baseline_solver::BaselineSolvable,
+        ethrpc::Web3,
+        event_handling::{EventHandler, EventRetrieving, EventStoring},
+        maintenance::Maintaining,
+        recent_block_cache::Block,
+        sources::uniswap_v2::pool_fetching::handle_contract_error,
+    },
+    anyhow::{Context, Result},
+    contracts::{i_solidly_pool_factory, ISolidlyPool, ISolidlyPoolFactory},
+    ethcontract::{dyns::DynAllEventsBuilder, BlockId, Event, H160, U256},
+    ethrpc::current_block::{BlockRetrieving, RangeInclusive},
+    futures::future,
+    model::TokenPair,
+    num::rational::Ratio,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
+    tokio::sync::Mutex,
+};
+
+const POOL_SWAP_GAS_COST: usize = 120_000;
+
+/// Maximum number of Newton iterations the pool contract performs when
+/// solving the stable swap invariant before reverting.
+const MAX_NEWTON_ITERATIONS: usize = 255;
+
+/// Fee denominator used by Solidly pool factories, i.e. fees are in basis
+/// points.
+const FEE_DENOMINATOR: u32 = 10_000;
+
+lazy_static::lazy_static! {
+    static ref ONE: U256 = U256::exp10(18);
+}
+
+#[async_trait::async_trait]
+pub trait PoolFetching: Send + Sync {
+    async fn fetch(&self, token_pairs: HashSet<TokenPair>, at_block: Block) -> Result<Vec<Pool>>;
+}
+
+/// A Solidly pool. Every token pair can have up to two pools: a "volatile"
+/// one using the `x * y = k` invariant and a "stable" one using the
+/// `x³y + y³x = k` invariant.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, Debug)]
+pub struct Pool {
+    pub address: H160,
+    pub tokens: TokenPair,
+    pub reserves: (U256, U256),
+    pub decimals: (u8, u8),
+    pub stable: bool,
+    pub fee: Ratio<u32>,
+}
+
+impl Pool {
+    /// Given an input amount and token, returns the maximum output amount and
+    /// address of the other asset. Returns None if operation not possible
+    /// due to arithmetic issues (e.g. over or underflow)
+    fn get_amount_out(&self, token_in: H160, amount_in: U256) -> Option<(U256, H160)> {
+        let token_out = self.other(token_in);
+        Some((self.amount_out(token_in, amount_in)?, token_out))
+    }
+
+    /// Given an output amount and token, returns a required input amount and
+    /// address of the other asset. Returns None if operation not possible
+    /// due to arithmetic issues (e.g. over or underflow, reserve too small)
+    fn get_amount_in(&self, token_out: H160, amount_out: U256) -> Option<(U256, H160)> {
+        let token_in = self.other(token_out);
+        Some((self.amount_in(token_in, amount_out)?, token_in))
+    }
+
+    fn other(&self, token: H160) -> H160 {
+        let (token0, token1) = self.tokens.get();
+        if token == token0 {
+            token1
+        } else {
+            assert_eq!(token, token1, "Token not part of pool");
+            token0
+        }
+    }
+
+    /// Returns the decimal scaling factors of the pool's tokens.
+    fn scales(&self) -> (U256, U256) {
+        (
+            U256::exp10(self.decimals.0.into()),
+            U256::exp10(self.decimals.1.into()),
+        )
+    }
+
+    /// Returns the reserves and decimal scaling factors ordered such that the
+    /// specified token comes first.
+    fn relative(&self, token: H160) -> ((U256, U256), (U256, U256)) {
+        let scales = self.scales();
+        if token == self.tokens.get().0 {
+            (self.reserves, scales)
+        } else {
+            ((self.reserves.1, self.reserves.0), (scales.1, scales.0))
+        }
+    }
+
+    /// Computes the output amount exactly like the pool contract's
+    /// `getAmountOut` function.
+    fn amount_out(&self, token_in: H160, amount_in: U256) -> Option<U256> {
+        let ((reserve_in, reserve_out), (scale_in, scale_out)) = self.relative(token_in);
+        if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+            return None;
+        }
+
+        let fee = amount_in
+            .checked_mul((*self.fee.numer()).into())?
+            .checked_div((*self.fee.denom()).into())?;
+        let amount_in = amount_in.checked_sub(fee)?;
+
+        let amount_out = if self.stable {
+            let xy = k(reserve_in, reserve_out, scale_in, scale_out)?;
+            let reserve_in = scale_up(reserve_in, scale_in)?;
+            let reserve_out = scale_up(reserve_out, scale_out)?;
+            let amount_in = scale_up(amount_in, scale_in)?;
+            let y = get_y(
+                amount_in.checked_add(reserve_in)?,
+                xy,
+                reserve_out,
+                self.scales(),
+            )?;
+            reserve_out
+                .checked_sub(y)?
+                .checked_mul(scale_out)?
+                .checked_div(*ONE)?
+        } else {
+            amount_in
+                .checked_mul(reserve_out)?
+                .checked_div(reserve_in.checked_add(amount_in)?)?
+        };
+
+        if amount_out >= reserve_out {
+            return None;
+        }
+        Some(amount_out)
+    }
+
+    /// Computes the input amount required to receive the specified output
+    /// amount. Pool contracts don't offer an exact output function, so we
+    /// estimate the input by inverting the invariant and then adjust it
+    /// until `amount_out` yields at least the requested amount.
+    fn amount_in(&self, token_in: H160, amount_out: U256) -> Option<U256> {
+        let ((reserve_in, reserve_out), (scale_in, scale_out)) = self.relative(token_in);
+        if amount_out.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+            return None;
+        }
+
+        let amount_in_after_fee = if self.stable {
+            let xy = k(reserve_in, reserve_out, scale_in, scale_out)?;
+            let reserve_in = scale_up(reserve_in, scale_in)?;
+            let reserve_out = scale_up(reserve_out, scale_out)?;
+            let amount_out = scale_up(amount_out, scale_out)?;
+            // The invariant is symmetric, so we can solve for the new input
+            // reserve the same way we solve for the new output reserve.
+            let x = get_y(
+                reserve_out.checked_sub(amount_out)?,
+                xy,
+                reserve_in,
+                self.scales(),
+            )?;
+            ceil_div(x.checked_sub(reserve_in)?.checked_mul(scale_in)?, *ONE)?
+        } else {
+            ceil_div(
+                amount_out.checked_mul(reserve_in)?,
+                reserve_out.checked_sub(amount_out)?,
+            )?
+        };
+
+        let numer = U256::from(*self.fee.numer());
+        let denom = U256::from(*self.fee.denom());
+        let mut amount_in = ceil_div(
+            amount_in_after_fee.checked_mul(denom)?,
+            denom.checked_sub(numer)?,
+        )?;
+
+        // Account for rounding errors in the estimate.
+        for _ in 0..3 {
+            if self.amount_out(token_in, amount_in)? >= amount_out {
+                return Some(amount_in);
+            }
+            amount_in = amount_in.checked_add(1.into())?;
+        }
+        None
+    }
+}
+
+impl BaselineSolvable for Pool {
+    fn get_amount_out(&self, out_token: H160, (in_amount, in_token): (U256, H160)) -> Option<U256> {
+        self.get_amount_out(in_token, in_amount)
+            .map(|(out_amount, token)| {
+                assert_eq!(token, out_token);
+                out_amount
+            })
+    }
+
+    fn get_amount_in(&self, in_token: H160, (out_amount, out_token): (U256, H160)) -> Option<U256> {
+        self.get_amount_in(out_token, out_amount)
+            .map(|(in_amount, token)| {
+                assert_eq!(token, in_token);
+                in_amount
+            })
+    }
+
+    fn gas_cost(&self) -> usize {
+        POOL_SWAP_GAS_COST
+    }
+}
+
+fn scale_up(amount: U256, scale: U256) -> Option<U256> {
+    amount.checked_mul(*ONE)?.checked_div(scale)
+}
+
+fn ceil_div(numerator: U256, denominator: U256) -> Option<U256> {
+    let quotient = numerator.checked_div(denominator)?;
+    if quotient * denominator == numerator {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1.into())
+    }
+}
+
+/// The stable swap invariant `x³y + y³x` for unscaled token amounts.
+fn k(x: U256, y: U256, scale_x: U256, scale_y: U256) -> Option<U256> {
+    f(scale_up(x, scale_x)?, scale_up(y, scale_y)?)
+}
+
+/// The stable swap invariant `x³y + y³x` for amounts scaled to 18 decimals.
+fn f(x0: U256, y: U256) -> Option<U256> {
+    let a = x0.checked_mul(y)?.checked_div(*ONE)?;
+    let b = x0
+        .checked_mul(x0)?
+        .checked_div(*ONE)?
+        .checked_add(y.checked_mul(y)?.checked_div(*ONE)?)?;
+    a.checked_mul(b)?.checked_div(*ONE)
+}
+
+/// The derivative of the stable swap invariant with respect to `y`.
+fn d(x0: U256, y: U256) -> Option<U256> {
+    U256::from(3)
+        .checked_mul(x0)?
+        .checked_mul(y.checked_mul(y)?.checked_div(*ONE)?)?
+        .checked_div(*ONE)?
+        .checked_add(
+            x0.checked_mul(x0)?
+                .checked_div(*ONE)?
+                .checked_mul(x0)?
+                .checked_div(*ONE)?,
+        )
+}
+
+/// Solves `f(x0, y) = xy` for `y` using Newton's method, starting from the
+/// specified `y`. This mirrors the pool contract's `_get_y` implementation,
+/// including its rounding behaviour, so that computed amounts match on-chain
+/// amounts exactly.
+///
+/// Note that the contract rescales the already scaled amounts once more (by
+/// the pool's `token0` and `token1` decimals, regardless of the swap
+/// direction) when checking for convergence while increasing `y`, which is why
+/// the pool's scaling factors are needed here. The check while decreasing `y`
+/// uses the scaled amounts as they are.
+fn get_y(x0: U256, xy: U256, mut y: U256, (scale0, scale1): (U256, U256)) -> Option<U256> {
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let k_current = f(x0, y)?;
+        if k_current < xy {
+            let mut dy = (xy - k_current).checked_mul(*ONE)?.checked_div(d(x0, y)?)?;
+            if dy.is_zero() {
+                if k(x0, y.checked_add(1.into())?, scale0, scale1)? > xy {
+                    return y.checked_add(1.into());
+                }
+                dy = 1.into();
+            }
+            y = y.checked_add(dy)?;
+        } else {
+            let mut dy = (k_current - xy).checked_mul(*ONE)?.checked_div(d(x0, y)?)?;
+            if dy.is_zero() {
+                if k_current == xy || f(x0, y.checked_sub(1.into())?)? < xy {
+                    return Some(y);
+                }
+                dy = 1.into();
+            }
+            y = y.checked_sub(dy)?;
+        }
+    }
+    None
+}
+
+impl EventRetrieving for ISolidlyPoolFactory {
+    type Event = i_solidly_pool_factory::Event;
+
+    fn get_events(&self) -> DynAllEventsBuilder<Self::Event> {
+        self.all_events()
+    }
+}
+
+/// The pools of a Solidly pool factory by token pair and pool type.
+#[derive(Debug, Default)]
+pub struct PoolIndex {
+    pools: HashMap<(TokenPair, bool), IndexedPool>,
+    last_event_block: u64,
+}
+
+#[derive(Debug)]
+struct IndexedPool {
+    address: H160,
+    /// The block in which the pool was created, `0` for pools which already
+    /// existed when indexing started. These are never removed on reorgs.
+    block_created: u64,
+}
+
+impl PoolIndex {
+    /// Creates an index of the pools which already exist at `block`.
+    fn new(pools: impl IntoIterator<Item = (TokenPair, bool, H160)>, block: u64) -> Self {
+        Self {
+            pools: pools
+                .into_iter()
+                .map(|(pair, stable, address)| {
+                    (
+                        (pair, stable),
+                        IndexedPool {
+                            address,
+                            block_created: 0,
+                        },
+                    )
+                })
+                .collect(),
+            last_event_block: block,
+        }
+    }
+
+    /// Returns the address of the pool for the token pair, if it exists.
+    fn get(&self, pair: TokenPair, stable: bool) -> Option<H160> {
+        self.pools.get(&(pair, stable)).map(|pool| pool.address)
+    }
+}
+
+#[async_trait::async_trait]
+impl EventStoring<i_solidly_pool_factory::Event> for PoolIndex {
+    async fn replace_events(
+        &mut self,
+        events: Vec<Event<i_solidly_pool_factory::Event>>,
+        range: RangeInclusive<u64>,
+    ) -> Result<()> {
+        self.pools
+            .retain(|_, pool| pool.block_created < *range.start());
+        self.append_events(events).await
+    }
+
+    async fn append_events(
+        &mut self,
+        events: Vec<Event<i_solidly_pool_factory::Event>>,
+    ) -> Result<()> {
+        for event in events {
+            let block_created = event.meta.context("event missing metadata")?.block_number;
+            let i_solidly_pool_factory::Event::PoolCreated(created) = event.data;
+            self.last_event_block = self.last_event_block.max(block_created);
+            let Some(pair) = TokenPair::new(created.token_0, created.token_1) else {
+                continue;
+            };
+            self.pools.insert(
+                (pair, created.stable),
+                IndexedPool {
+                    address: created.pool,
+                    block_created,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    async fn last_event_block(&self) -> Result<u64> {
+        Ok(self.last_event_block)
+    }
+}
+
+/// Fetches Solidly pools for token pairs through the pool factory. The pools
+/// of the factory are indexed from its `PoolCreated` events, so only pairs
+/// which actually have a pool are fetched.
+pub struct PoolFetcher {
+    web3: Web3,
+    factory: ISolidlyPoolFactory,
+    events: Mutex<EventHandler<ISolidlyPoolFactory, PoolIndex>>,
+}
+
+impl PoolFetcher {
+    /// Creates a new pool fetcher. The pools which already exist are read
+    /// from the factory once, newer pools are indexed from events.
+    pub async fn new(
+        factory: ISolidlyPoolFactory,
+        web3: Web3,
+        block_retriever: Arc<dyn BlockRetrieving>,
+    ) -> Result<Self> {
+        let current = block_retriever.current_block().await?;
+        let block = BlockId::Number(current.number.into());
+        let count = factory
+            .all_pools_length()
+            .block(block)
+            .call()
+            .await
+            .context("all pools length")?;
+        let pools = future::try_join_all((0..count.as_u64()).map(|i| {
+            let (factory, web3) = (&factory, &web3);
+            async move {
+                let address = factory.all_pools(i.into()).block(block).call().await?;
+                let (.., stable, token0, token1) = ISolidlyPool::at(web3, address)
+                    .metadata()
+                    .block(block)
+                    .call()
+                    .await?;
+                Ok::<_, anyhow::Error>(
+                    TokenPair::new(token0, token1).map(|pair| (pair, stable, address)),
+                )
+            }
+        }))
+        .await
+        .context("existing pools")?;
+        tracing::debug!(factory = ?factory.address(), pools = %pools.len(), "indexed Solidly pools");
+
+        let events = EventHandler::new(
+            block_retriever,
+            factory.clone(),
+            PoolIndex::new(pools.into_iter().flatten(), current.number),
+            Some((current.number, current.hash)),
+        );
+        Ok(Self {
+            web3,
+            factory,
+            events: Mutex::new(events),
+        })
+    }
+
+    async fn read_state(
+        &self,
+        pair: TokenPair,
+        stable: bool,
+        address: H160,
+        block: BlockId,
+    ) -> Result<Option<Pool>> {
+        let (token0, token1) = pair.get();
+        let pool = ISolidlyPool::at(&self.web3, address);
+        let (metadata, fee) = futures::join!(
+            pool.metadata().block(block).call(),
+            self.factory.get_fee(address, stable).block(block).call(),
+        );
+        let (Some(metadata), Some(fee)) = (
+            handle_contract_error(metadata)?,
+            handle_contract_error(fee)?,
+        ) else {
+            return Ok(None);
+        };
+        let (scale0, scale1, reserve0, reserve1, is_stable, pool_token0, pool_token1) = metadata;
+
+        let (Some(decimals0), Some(decimals1)) = (decimals(scale0), decimals(scale1)) else {
+            return Ok(None);
+        };
+        if (pool_token0, pool_token1) != (token0, token1)
+            || is_stable != stable
+            || fee >= FEE_DENOMINATOR.into()
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(Pool {
+            address,
+            tokens: pair,
+            reserves: (reserve0, reserve1),
+            decimals: (decimals0, decimals1),
+            stable,
+            fee: Ratio::new(fee.as_u32(), FEE_DENOMINATOR),
+        }))
+    }
+}
+
+/// Converts a decimal scaling factor (i.e. `10**decimals`) as returned by the
+/// pool's `metadata` function back into decimals.
+fn decimals(scale: U256) -> Option<u8> {
+    (0..=77_u8).find(|decimals| U256::exp10((*decimals).into()) == scale)
+}
+
+#[async_trait::async_trait]
+impl PoolFetching for PoolFetcher {
+    async fn fetch(&self, token_pairs: HashSet<TokenPair>, at_block: Block) -> Result<Vec<Pool>> {
+        // Index the pools created up to the requested block. Pools are created
+        // rarely, so failing to do so only means that the newest pools are
+        // missing for a while.
+        if let Block::Number(number) = at_block {
+            let last_handled_block = self
+                .events
+                .lock()
+                .await
+                .last_handled_block()
+                .unwrap_or_default()
+                .0;
+            if number > last_handled_block {
+                if let Err(err) = self.events.run_maintenance().await {
+                    tracing::debug!(?err, "failed to index Solidly pools");
+                }
+            }
+        }
+
+        let candidates = {
+            let events = self.events.lock().await;
+            token_pairs
+                .into_iter()
+                .flat_map(|pair| [(pair, false), (pair, true)])
+                .filter_map(|(pair, stable)| {
+                    Some((pair, stable, events.store().get(pair, stable)?))
+                })
+                .collect::<Vec<_>>()
+        };
+        let block = BlockId::Number(at_block.into());
+        let pools = future::try_join_all(
+            candidates
+                .into_iter()
+                .map(|(pair, stable, address)| self.read_state(pair, stable, address, block)),
+        )
+        .await?;
+        Ok(pools.into_iter().flatten().collect())
+    }
+}
+
+/// Combines the pools of multiple Solidly deployments.
+pub struct PoolAggregator {
+    pub pool_fetchers: Vec<Arc<dyn PoolFetching>>,
+}
+
+#[async_trait::async_trait]
+impl PoolFetching for PoolAggregator {
+    async fn fetch(&self, token_pairs: HashSet<TokenPair>, at_block: Block) -> Result<Vec<Pool>> {
+        let results = future::try_join_all(
+            self.pool_fetchers
+                .iter()
+                .map(|pool_fetcher| pool_fetcher.fetch(token_pairs.clone(), at_block)),
+        )
+        .await?;
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable_pool() -> Pool {
+        // USDC/DAI-like pool with a 0.05% fee.
+        Pool {
+            address: H160::from_low_u64_be(3),
+            tokens: TokenPair::new(H160::from_low_u64_be(1), H160::from_low_u64_be(2)).unwrap(),
+            reserves: (
+                U256::from(10_000_000) * U256::exp10(6),
+                U256::from(10_100_000) * U256::exp10(18),
+            ),
+            decimals: (6, 18),
+            stable: true,
+            fee: Ratio::new(5, 10_000),
+        }
+    }
+
+    #[test]
+    fn stable_amount_out() {
+        let pool = stable_pool();
+        let (usdc, dai) = pool.tokens.get();
+
+        assert_eq!(
+            pool.get_amount_out(usdc, U256::from(1_000) * U256::exp10(6)),
+            Some((U256::from_dec_str("999500238880720585580").unwrap(), dai))
+        );
+        assert_eq!(
+            pool.get_amount_out(dai, U256::from(1_000) * U256::exp10(18)),
+            Some((999_499_746.into(), usdc))
+        );
+        assert_eq!(
+            pool.get_amount_out(usdc, U256::exp10(6)),
+            Some((U256::from_dec_str("999500246156695412").unwrap(), dai))
+        );
+        assert_eq!(
+            pool.get_amount_out(dai, U256::from(54_321) * U256::exp10(18)),
+            Some((54_293_784_300_u64.into(), usdc))
+        );
+    }
+
+    #[test]
+    fn stable_amount_in() {
+        let pool = stable_pool();
+        let (usdc, dai) = pool.tokens.get();
+
+        assert_eq!(
+            pool.get_amount_in(dai, U256::from_dec_str("999500238880720585580").unwrap()),
+            Some((U256::from(1_000) * U256::exp10(6), usdc))
+        );
+        assert_eq!(
+            pool.get_amount_in(usdc, U256::from(1_000) * U256::exp10(6)),
+            Some((U256::from_dec_str("1000500504026859561105").unwrap(), dai))
+        );
+    }
+
+    #[test]
+    fn volatile_amounts() {
+        let pool = Pool {
+            address: H160::from_low_u64_be(3),
+            tokens: TokenPair::new(H160::from_low_u64_be(1), H160::from_low_u64_be(2)).unwrap(),
+            reserves: (
+                U256::from(100) * U256::exp10(18),
+                U256::from(200_000) * U256::exp10(6),
+            ),
+            decimals: (18, 6),
+            stable: false,
+            fee: Ratio::new(30, 10_000),
+        };
+        let (weth, usdc) = pool.tokens.get();
+
+        assert_eq!(
+            pool.get_amount_out(weth, U256::exp10(18)),
+            Some((1_974_316_068.into(), usdc))
+        );
+        assert_eq!(
+            pool.get_amount_in(usdc, 1_974_316_068.into()),
+            Some((U256::from(999_999_999_593_763_121_u64), weth))
+        );
+    }
+
+    #[tokio::test]
+    async fn indexes_created_pools() {
+        let token = H160::from_low_u64_be;
+        let pair = TokenPair::new(token(1), token(2)).unwrap();
+        let created = |stable: bool, pool: u64, block_number: u64| Event {
+            data: i_solidly_pool_factory::Event::PoolCreated(
+                i_solidly_pool_factory::event_data::PoolCreated {
+                    token_0: token(1),
+                    token_1: token(2),
+                    stable,
+                    pool: token(pool),
+                    ..Default::default()
+                },
+            ),
+            meta: Some(ethcontract::EventMetadata {
+                block_number,
+                ..Default::default()
+            }),
+        };
+
+        let mut index = PoolIndex::new([(pair, false, token(10))], 100);
+        index
+            .append_events(vec![created(true, 11, 101)])
+            .await
+            .unwrap();
+        assert_eq!(index.get(pair, false), Some(token(10)));
+        assert_eq!(index.get(pair, true), Some(token(11)));
+        assert_eq!(index.last_event_block().await.unwrap(), 101);
+
+        // A reorg removes the pools created in the reorged blocks, but keeps
+        // the pools which existed before indexing started.
+        index
+            .replace_events(vec![], RangeInclusive::try_new(90, 110).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(index.get(pair, false), Some(token(10)));
+        assert_eq!(index.get(pair, true), None);
+    }
+
+    #[test]
+    fn decimals_from_scale() {
+        assert_eq!(decimals(U256::exp10(6)), Some(6));
+        assert_eq!(decimals(1.into()), Some(0));
+        assert_eq!(decimals(42.into()), None);
+    }
+}
@@ -1,4 +1,5 @@
 //! Uniswap V3 baseline liquidity source implementation.
 pub mod event_fetching;
 pub mod graph_api;
+pub mod onchain;
 pub mod pool_fetching;
@@ -2,14 +2,17 @@ use {
     crate::event_handling::{EventRetrieving, EventStoring},
     anyhow::{Context, Result},
     contracts::{
+        i_uniswap_v3_factory::event_data::PoolCreated,
         uniswap_v3_pool::event_data::{Burn, Mint, Swap},
+        IUniswapV3Factory,
         UniswapV3Pool,
     },
     ethcontract::{
-        common::abi::Error,
+        common::abi::{self, Error, EventParam, ParamType, Token},
         contract::ParseLog,
         dyns::DynAllEventsBuilder,
         errors::ExecutionError,
+        tokens::Tokenize,
         Event,
         RawLog,
         H160,
@@ -22,16 +25,43 @@ use {
 
 const SWAP_TOPIC: [u8; 32] =
     hex!("c42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67");
+/// PancakeSwap V3 pools emit the protocol fees as two additional `uint128`
+/// fields of the `Swap` event, which changes its signature.
+const PANCAKE_SWAP_V3_SWAP_TOPIC: [u8; 32] =
+    hex!("19b47279256b2a23a1665c810c8d55a1758940ee09377d4f8d26497a3577dc83");
 const BURN_TOPIC: [u8; 32] =
     hex!("0c396cd989a39f4459b5fa1aed6a9a8dcdbc45908acfd67e028cd568da98982c");
 const MINT_TOPIC: [u8; 32] =
     hex!("7a53080ba414158be7ec69b987b5fb7d07dee101fe85488f0853ae16239d0bde");
+const POOL_CREATED_TOPIC: [u8; 32] =
+    hex!("783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118");
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum UniswapV3Event {
     Burn(Burn),
     Mint(Mint),
     Swap(Swap),
+    /// Emitted by Uniswap V3 compatible factories. This is used for
+    /// discovering new pools when they aren't fetched from a subgraph.
+    PoolCreated(PoolCreated),
+}
+
+/// The `Swap` event emitted by the pools of a Uniswap V3 fork.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SwapEvent {
+    UniswapV3,
+    /// The PancakeSwap V3 event, which additionally contains the protocol
+    /// fees. These are ignored when decoding it.
+    PancakeSwapV3,
+}
+
+impl SwapEvent {
+    fn topic(self) -> H256 {
+        match self {
+            Self::UniswapV3 => H256(SWAP_TOPIC),
+            Self::PancakeSwapV3 => H256(PANCAKE_SWAP_V3_SWAP_TOPIC),
+        }
+    }
 }
 
 impl ParseLog for UniswapV3Event {
@@ -62,6 +92,17 @@ impl ParseLog for UniswapV3Event {
                             .expect("generated event decode"),
                     )?,
                 )),
+                H256(PANCAKE_SWAP_V3_SWAP_TOPIC) => Ok(UniswapV3Event::Swap(
+                    decode_pancake_swap_v3_swap(log.clone())?,
+                )),
+                H256(POOL_CREATED_TOPIC) => Ok(UniswapV3Event::PoolCreated(
+                    log.clone().decode(
+                        IUniswapV3Factory::raw_contract()
+                            .abi
+                            .event("PoolCreated")
+                            .expect("generated event decode"),
+                    )?,
+                )),
                 _ => Err(ExecutionError::from(Error::InvalidData)),
             });
         if let Some(Ok(data)) = standard_event {
@@ -71,14 +112,52 @@ impl ParseLog for UniswapV3Event {
     }
 }
 
-pub struct UniswapV3PoolEventFetcher(pub Web3);
+/// Decodes a PancakeSwap V3 `Swap` event. It has the same fields as the
+/// Uniswap V3 event followed by the protocol fees, which are dropped.
+fn decode_pancake_swap_v3_swap(log: RawLog) -> Result<Swap, ExecutionError> {
+    let mut event = UniswapV3Pool::raw_contract()
+        .abi
+        .event("Swap")
+        .expect("generated event decode")
+        .clone();
+    let fields = event.inputs.len();
+    event.inputs.extend(
+        ["protocolFeesToken0", "protocolFeesToken1"].map(|name| EventParam {
+            name: name.to_owned(),
+            kind: ParamType::Uint(128),
+            indexed: false,
+        }),
+    );
+    let log = event.parse_log(abi::RawLog {
+        topics: log.topics,
+        data: log.data,
+    })?;
+    let tokens = log
+        .params
+        .into_iter()
+        .take(fields)
+        .map(|param| param.value)
+        .collect();
+    Swap::from_token(Token::Tuple(tokens)).map_err(|_| ExecutionError::from(Error::InvalidData))
+}
+
+pub struct UniswapV3PoolEventFetcher {
+    pub web3: Web3,
+    /// The `Swap` event emitted by the indexed pools.
+    pub swap_event: SwapEvent,
+}
 
 impl EventRetrieving for UniswapV3PoolEventFetcher {
     type Event = UniswapV3Event;
 
     fn get_events(&self) -> DynAllEventsBuilder<Self::Event> {
-        let mut events = DynAllEventsBuilder::new(self.0.clone(), H160::default(), None);
-        let events_signatures = vec![H256(SWAP_TOPIC), H256(BURN_TOPIC), H256(MINT_TOPIC)];
+        let mut events = DynAllEventsBuilder::new(self.web3.clone(), H160::default(), None);
+        let events_signatures = vec![
+            self.swap_event.topic(),
+            H256(BURN_TOPIC),
+            H256(MINT_TOPIC),
+            H256(POOL_CREATED_TOPIC),
+        ];
         events.filter = events
             .filter
             .address(vec![])
@@ -159,6 +238,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decodes_pancake_swap_v3_swap() {
+        let word = |value: u64| H256::from_low_u64_be(value).0.to_vec();
+        let log = RawLog {
+            topics: vec![
+                H256(PANCAKE_SWAP_V3_SWAP_TOPIC),
+                H256::from_low_u64_be(1),
+                H256::from_low_u64_be(2),
+            ],
+            data: [
+                word(3),
+                vec![0xff; 32],
+                word(4),
+                word(5),
+                word(6),
+                word(7),
+                word(8),
+            ]
+            .concat(),
+        };
+
+        assert_eq!(
+            UniswapV3Event::parse_log(log).unwrap(),
+            UniswapV3Event::Swap(Swap {
+                sender: H160::from_low_u64_be(1),
+                recipient: H160::from_low_u64_be(2),
+                amount_0: 3.into(),
+                amount_1: (-1).into(),
+                sqrt_price_x96: 4.into(),
+                liquidity: 5,
+                tick: 6,
+            })
+        );
+    }
+
     #[test]
     fn remove_events_older_than_block_test_empty() {
         let mut cache = RecentEventsCache::default();
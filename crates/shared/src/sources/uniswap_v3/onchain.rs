@@ -0,0 +1,356 @@
+//! On-chain Uniswap V3 pool discovery and state fetching.
+//!
+//! This is used for Uniswap V3 forks and chains without a Uniswap V3 subgraph.
+//! Instead of querying a registry of all pools up front, pool addresses are
+//! computed deterministically from the factory configuration for requested
+//! token pairs, and their state (including initialized ticks) is read directly
+//! from the node.
+
+use {
+    super::{
+        event_fetching::SwapEvent,
+        graph_api::{PoolData, TickData, Token},
+    },
+    crate::sources::{
+        uniswap_v2::pool_fetching::handle_contract_error,
+        uniswap_v3_pair_provider::pool_address,
+    },
+    anyhow::Result,
+    contracts::{UniswapV3Pool, ERC20},
+    ethcontract::{BlockId, H160, H256, U256},
+    ethrpc::{Web3, Web3CallBatch, MAX_BATCH_SIZE},
+    futures::{stream, StreamExt, TryStreamExt},
+    model::TokenPair,
+    web3::types::CallRequest,
+};
+
+/// The lowest tick supported by Uniswap V3 pools.
+const MIN_TICK: i32 = -887_272;
+
+/// The highest tick supported by Uniswap V3 pools.
+const MAX_TICK: i32 = 887_272;
+
+/// The maximum number of concurrent node requests issued when reading pool
+/// state.
+const MAX_CONCURRENT_REQUESTS: usize = 100;
+
+/// The number of tick bitmap words scanned on each side of the word containing
+/// the current tick. Liquidity further away from the current price is not
+/// relevant for settling orders, and scanning the full bitmap would take
+/// thousands of calls for pools with a small tick spacing.
+const MAX_WORDS_AROUND_CURRENT_TICK: i16 = 64;
+
+/// A Uniswap V3 compatible factory whose pools are discovered on-chain.
+#[derive(Clone, Debug)]
+pub struct Factory {
+    /// The factory contract address. This is the contract emitting the
+    /// `PoolCreated` events.
+    pub address: H160,
+    /// The address that deploys pools with `CREATE2`. This is the factory
+    /// itself for Uniswap V3, but a separate pool deployer contract for some
+    /// forks such as PancakeSwap V3.
+    pub deployer: H160,
+    /// The digest of the pool initialization code.
+    pub init_code_digest: H256,
+    /// The supported fee tiers, in hundredths of a basis point.
+    pub fee_tiers: Vec<u32>,
+    /// The `Swap` event emitted by the pools.
+    pub swap_event: SwapEvent,
+}
+
+impl Factory {
+    /// Returns the deterministic pool addresses for a token pair, one for each
+    /// of the supported fee tiers.
+    pub fn pool_addresses<'a>(&'a self, pair: &'a TokenPair) -> impl Iterator<Item = H160> + 'a {
+        self.fee_tiers
+            .iter()
+            .map(|fee| pool_address(&self.deployer, &self.init_code_digest, pair, *fee))
+    }
+}
+
+/// Reads Uniswap V3 pool state directly from the node.
+pub struct PoolReader {
+    web3: Web3,
+}
+
+impl PoolReader {
+    pub fn new(web3: Web3) -> Self {
+        Self { web3 }
+    }
+
+    /// Retrieves the pool data, including ticks, for the specified pool
+    /// addresses at a given block. Pools that don't exist (or aren't Uniswap V3
+    /// compatible) are returned as `None`.
+    pub async fn get_pools_with_ticks_by_ids(
+        &self,
+        ids: &[H160],
+        block_number: u64,
+    ) -> Result<Vec<(H160, Option<PoolData>)>> {
+        let block = BlockId::Number(block_number.into());
+        stream::iter(ids)
+            .map(|id| async move { Ok::<_, anyhow::Error>((*id, self.pool(*id, block).await?)) })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await
+    }
+
+    async fn pool(&self, address: H160, block: BlockId) -> Result<Option<PoolData>> {
+        let pool = UniswapV3Pool::at(&self.web3, address);
+        let Some((sqrt_price, tick)) = self.slot0(&pool, block).await? else {
+            return Ok(None);
+        };
+
+        let (token0, token1, fee, tick_spacing, liquidity) = futures::join!(
+            pool.token_0().block(block).call(),
+            pool.token_1().block(block).call(),
+            pool.fee().block(block).call(),
+            pool.tick_spacing().block(block).call(),
+            pool.liquidity().block(block).call(),
+        );
+        let (Some(token0), Some(token1), Some(fee), Some(tick_spacing), Some(liquidity)) = (
+            handle_contract_error(token0)?,
+            handle_contract_error(token1)?,
+            handle_contract_error(fee)?,
+            handle_contract_error(tick_spacing)?,
+            handle_contract_error(liquidity)?,
+        ) else {
+            return Ok(None);
+        };
+        if tick_spacing <= 0 {
+            return Ok(None);
+        }
+
+        let (decimals0, decimals1) = futures::join!(
+            ERC20::at(&self.web3, token0).decimals().block(block).call(),
+            ERC20::at(&self.web3, token1).decimals().block(block).call(),
+        );
+        let (Some(decimals0), Some(decimals1)) = (
+            handle_contract_error(decimals0)?,
+            handle_contract_error(decimals1)?,
+        ) else {
+            return Ok(None);
+        };
+
+        let ticks = self
+            .ticks(&pool, tick, tick_spacing, liquidity, block)
+            .await?
+            .into_iter()
+            .map(|(tick_idx, liquidity_net)| TickData {
+                id: format!("{address:#x}#{tick_idx}"),
+                tick_idx: tick_idx.into(),
+                liquidity_net: liquidity_net.into(),
+                pool_address: address,
+            })
+            .collect();
+
+        Ok(Some(PoolData {
+            id: address,
+            token0: Token {
+                id: token0,
+                decimals: decimals0,
+            },
+            token1: Token {
+                id: token1,
+                decimals: decimals1,
+            },
+            fee_tier: fee.into(),
+            liquidity: liquidity.into(),
+            sqrt_price,
+            tick: tick.into(),
+            // Not known on-chain, pools are initialized lazily instead.
+            total_value_locked_eth: 0.,
+            ticks: Some(ticks),
+        }))
+    }
+
+    /// Reads the current square root price and tick of a pool. Only the first
+    /// two words of `slot0` are decoded, as forks differ in the remaining
+    /// fields (e.g. PancakeSwap V3 uses a `uint32` protocol fee). Returns
+    /// `None` if there is no pool deployed at the address.
+    async fn slot0(&self, pool: &UniswapV3Pool, block: BlockId) -> Result<Option<(U256, i32)>> {
+        let call = CallRequest {
+            to: Some(pool.address()),
+            data: pool.slot_0().tx.data,
+            ..Default::default()
+        };
+        let output = self.web3.eth().call(call, Some(block)).await?;
+        if output.0.len() < 64 {
+            return Ok(None);
+        }
+
+        let sqrt_price = U256::from_big_endian(&output.0[0..32]);
+        // `int24` values are sign extended to the full word, so reading the
+        // lowest 4 bytes yields the correct value.
+        let tick = i32::from_be_bytes(output.0[60..64].try_into().unwrap());
+        Ok(Some((sqrt_price, tick)))
+    }
+
+    /// Reads the net liquidity of the initialized ticks around the current
+    /// tick of a pool by scanning its tick bitmap. The calls are sent to the
+    /// node in batches. Ticks bringing the liquidity to zero are added at the
+    /// edges of the scanned range, so that swaps can't use liquidity outside
+    /// of it.
+    async fn ticks(
+        &self,
+        pool: &UniswapV3Pool,
+        current_tick: i32,
+        tick_spacing: i32,
+        liquidity: u128,
+        block: BlockId,
+    ) -> Result<Vec<(i32, i128)>> {
+        let words = word_range(current_tick, tick_spacing).collect::<Vec<_>>();
+        let mut batch = Web3CallBatch::new(self.web3.transport().clone());
+        let bitmaps = words
+            .iter()
+            .map(|word| pool.tick_bitmap(*word).block(block).batch_call(&mut batch))
+            .collect::<Vec<_>>();
+        batch.execute_all(MAX_BATCH_SIZE).await;
+        let bitmaps = futures::future::try_join_all(bitmaps).await?;
+
+        let ticks = words
+            .into_iter()
+            .zip(bitmaps)
+            .flat_map(|(word, bitmap)| initialized_ticks(word, bitmap, tick_spacing))
+            .collect::<Vec<_>>();
+        let mut batch = Web3CallBatch::new(self.web3.transport().clone());
+        let liquidity_net = ticks
+            .iter()
+            .map(|tick| pool.ticks(*tick).block(block).batch_call(&mut batch))
+            .collect::<Vec<_>>();
+        batch.execute_all(MAX_BATCH_SIZE).await;
+        let liquidity_net = futures::future::try_join_all(liquidity_net).await?;
+
+        let mut ticks = ticks
+            .into_iter()
+            .zip(liquidity_net)
+            .map(|(tick, (_, liquidity_net, ..))| (tick, liquidity_net))
+            .collect::<Vec<_>>();
+        let boundaries = boundary_ticks(
+            &ticks,
+            liquidity,
+            current_tick,
+            words[0]..=words[words.len() - 1],
+            tick_spacing,
+        );
+        ticks.extend(boundaries);
+        Ok(ticks)
+    }
+}
+
+/// Returns the position in the tick bitmap of the word containing the
+/// specified tick.
+fn word_position(tick: i32, tick_spacing: i32) -> i16 {
+    (tick.div_euclid(tick_spacing) >> 8) as i16
+}
+
+/// Returns the positions of the tick bitmap words that are scanned for
+/// initialized ticks around the current tick.
+fn word_range(current_tick: i32, tick_spacing: i32) -> std::ops::RangeInclusive<i16> {
+    let current = word_position(current_tick, tick_spacing);
+    let start = current
+        .saturating_sub(MAX_WORDS_AROUND_CURRENT_TICK)
+        .max(word_position(MIN_TICK, tick_spacing));
+    let end = current
+        .saturating_add(MAX_WORDS_AROUND_CURRENT_TICK)
+        .min(word_position(MAX_TICK, tick_spacing));
+    start..=end
+}
+
+/// Returns the ticks just outside of the scanned tick bitmap words whose net
+/// liquidity brings the liquidity of the pool to zero. The ticks outside of the
+/// scanned words are unknown, so the liquidity that is still active at the
+/// edges of the scanned range can't be assumed to continue beyond them.
+fn boundary_ticks(
+    ticks: &[(i32, i128)],
+    liquidity: u128,
+    current_tick: i32,
+    words: std::ops::RangeInclusive<i16>,
+    tick_spacing: i32,
+) -> Vec<(i32, i128)> {
+    let liquidity = i128::try_from(liquidity).unwrap_or(i128::MAX);
+    // Crossing a tick upwards adds its net liquidity and crossing it downwards
+    // subtracts it. Ticks at or below the current tick have been crossed.
+    let (below, above) = ticks
+        .iter()
+        .fold((0i128, 0i128), |(below, above), (tick, net)| {
+            if *tick <= current_tick {
+                (below.saturating_add(*net), above)
+            } else {
+                (below, above.saturating_add(*net))
+            }
+        });
+    let lower = (i32::from(*words.start()) * 256 - 1) * tick_spacing;
+    let upper = (i32::from(*words.end()) + 1) * 256 * tick_spacing;
+    [
+        (lower, liquidity.saturating_sub(below)),
+        (upper, liquidity.saturating_add(above).saturating_neg()),
+    ]
+    .into_iter()
+    .filter(|(tick, net)| *net != 0 && (MIN_TICK..=MAX_TICK).contains(tick))
+    .collect()
+}
+
+/// Returns the initialized ticks flagged in a tick bitmap word.
+fn initialized_ticks(word: i16, bitmap: U256, tick_spacing: i32) -> impl Iterator<Item = i32> {
+    (0..256)
+        .filter(move |bit| bitmap.bit(*bit))
+        .map(move |bit| (i32::from(word) * 256 + bit as i32) * tick_spacing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_bitmap_word_positions() {
+        assert_eq!(word_position(MIN_TICK, 1), -3466);
+        assert_eq!(word_position(MAX_TICK, 1), 3465);
+        assert_eq!(word_position(MIN_TICK, 60), -58);
+        assert_eq!(word_position(MAX_TICK, 60), 57);
+        assert_eq!(word_position(-1, 10), -1);
+        assert_eq!(word_position(0, 10), 0);
+    }
+
+    #[test]
+    fn tick_bitmap_word_range() {
+        assert_eq!(word_range(0, 1), -64..=64);
+        assert_eq!(word_range(-2560, 10), -65..=63);
+        assert_eq!(word_range(MIN_TICK, 1), -3466..=-3402);
+        assert_eq!(word_range(MAX_TICK, 1), 3401..=3465);
+        // The full bitmap fits within the range for large tick spacings.
+        assert_eq!(word_range(0, 200), -18..=17);
+    }
+
+    #[test]
+    fn liquidity_ends_at_scanned_range() {
+        // A position in [-100, 100) and one in [50, 200) with the current tick
+        // at 60, so both are active and the liquidity is already zero at the
+        // edges of the scanned range.
+        let ticks = [(-100, 10), (50, 5), (100, -10), (200, -5)];
+        assert!(boundary_ticks(&ticks, 15, 60, -1..=0, 1).is_empty());
+
+        // Only the ticks around the current tick were scanned, the positions
+        // extend beyond the range and are cut off at its edges.
+        let ticks = [(50, 5)];
+        assert_eq!(
+            boundary_ticks(&ticks, 15, 60, -1..=0, 1),
+            [(-257, 10), (256, -15)],
+        );
+
+        // There are no ticks beyond the lowest and highest ticks.
+        assert!(boundary_ticks(&[], 15, 0, word_range(0, 200), 200).is_empty());
+    }
+
+    #[test]
+    fn initialized_ticks_from_bitmap() {
+        let bitmap = U256::from(0b101) | (U256::one() << 255);
+        assert_eq!(
+            initialized_ticks(0, bitmap, 10).collect::<Vec<_>>(),
+            [0, 20, 2550],
+        );
+        assert_eq!(
+            initialized_ticks(-1, bitmap, 10).collect::<Vec<_>>(),
+            [-2560, -2540, -10],
+        );
+    }
+}
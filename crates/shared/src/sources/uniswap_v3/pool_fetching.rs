@@ -1,7 +1,8 @@
 use {
     super::{
-        event_fetching::{RecentEventsCache, UniswapV3Event, UniswapV3PoolEventFetcher},
+        event_fetching::{RecentEventsCache, SwapEvent, UniswapV3Event, UniswapV3PoolEventFetcher},
         graph_api::{PoolData, Token, UniV3SubgraphClient},
+        onchain::{Factory, PoolReader},
     },
     crate::{
         event_handling::{EventHandler, EventStoring, MAX_REORG_BLOCK_COUNT},
@@ -118,10 +119,23 @@ struct PoolsCheckpoint {
     missing_pools: HashSet<H160>,
 }
 
+/// Where pools are discovered and their state is initialized from.
+enum PoolSource {
+    /// A Uniswap V3 subgraph listing all pools.
+    Subgraph(UniV3SubgraphClient),
+    /// A Uniswap V3 compatible factory, where pools are discovered on-chain.
+    Factory {
+        factory: Factory,
+        reader: PoolReader,
+        /// The maximum number of pools initialized per maintenance run.
+        max_pools_to_initialize: usize,
+    },
+}
+
 struct PoolsCheckpointHandler {
-    graph_api: UniV3SubgraphClient,
+    source: PoolSource,
     /// H160 is pool id while TokenPair is a pair or tokens for each pool.
-    pools_by_token_pair: HashMap<TokenPair, HashSet<H160>>,
+    pools_by_token_pair: Mutex<HashMap<TokenPair, HashSet<H160>>>,
     /// Pools state on a specific block number in history considered reorg safe
     pools_checkpoint: Mutex<PoolsCheckpoint>,
 }
@@ -179,19 +193,57 @@ impl PoolsCheckpointHandler {
         });
 
         Ok(Self {
-            graph_api,
-            pools_by_token_pair,
+            source: PoolSource::Subgraph(graph_api),
+            pools_by_token_pair: Mutex::new(pools_by_token_pair),
             pools_checkpoint,
         })
     }
 
+    /// Creates a checkpoint handler discovering pools on-chain. Since there is
+    /// no registry of existing pools to initialize from, the checkpoint starts
+    /// out empty at the specified block and pools are added lazily for the
+    /// token pairs that get requested. At most `max_pools_to_initialize` of
+    /// these pools are initialized at a time, the remaining ones are
+    /// initialized in later maintenance runs.
+    pub fn from_factory(
+        factory: Factory,
+        web3: Web3,
+        block_number: u64,
+        max_pools_to_initialize: usize,
+    ) -> Self {
+        Self {
+            source: PoolSource::Factory {
+                factory,
+                reader: PoolReader::new(web3),
+                max_pools_to_initialize,
+            },
+            pools_by_token_pair: Default::default(),
+            pools_checkpoint: Mutex::new(PoolsCheckpoint {
+                block_number,
+                ..Default::default()
+            }),
+        }
+    }
+
     /// For a given list of token pairs, fetches the pools for the ones that
     /// exist in the checkpoint. For the ones that don't exist, flag as
     /// missing and expect to exist after the next maintenance run.
     fn get(&self, token_pairs: &HashSet<TokenPair>) -> (HashMap<H160, PoolInfo>, u64) {
+        let mut pools_by_token_pair = self.pools_by_token_pair.lock().unwrap();
+        if let PoolSource::Factory { factory, .. } = &self.source {
+            // Pools of a factory are only known once their token pair was
+            // requested, so compute the candidate pool addresses for new pairs.
+            // The ones that don't exist get removed when updating missing pools.
+            for pair in token_pairs {
+                pools_by_token_pair
+                    .entry(*pair)
+                    .or_insert_with(|| factory.pool_addresses(pair).collect());
+            }
+        }
+
         let mut pool_ids = token_pairs
             .iter()
-            .filter_map(|pair| self.pools_by_token_pair.get(pair))
+            .filter_map(|pair| pools_by_token_pair.get(pair))
             .flatten()
             .peekable();
 
@@ -226,12 +278,31 @@ impl PoolsCheckpointHandler {
         };
         tracing::debug!("currently missing pools are {:?}", missing_pools);
 
-        let pool_ids = missing_pools.into_iter().collect::<Vec<_>>();
+        let limit = match &self.source {
+            PoolSource::Subgraph(_) => usize::MAX,
+            PoolSource::Factory {
+                max_pools_to_initialize,
+                ..
+            } => *max_pools_to_initialize,
+        };
+        let pool_ids = missing_pools.into_iter().take(limit).collect::<Vec<_>>();
         let start = std::time::Instant::now();
-        let pools = self
-            .graph_api
-            .get_pools_with_ticks_by_ids(&pool_ids, block_number)
-            .await;
+        let pools = match &self.source {
+            PoolSource::Subgraph(graph_api) => graph_api
+                .get_pools_with_ticks_by_ids(&pool_ids, block_number)
+                .await
+                .map(|pools| {
+                    pools
+                        .into_iter()
+                        .map(|pool| (pool.id, Some(pool)))
+                        .collect()
+                }),
+            PoolSource::Factory { reader, .. } => {
+                reader
+                    .get_pools_with_ticks_by_ids(&pool_ids, block_number)
+                    .await
+            }
+        };
         tracing::debug!(
             requested_pools = pool_ids.len(),
             time = ?start.elapsed(),
@@ -239,21 +310,66 @@ impl PoolsCheckpointHandler {
             "fetched pool ticks"
         );
 
-        let mut checkpoint = self.pools_checkpoint.lock().unwrap();
-        for pool in pools? {
-            checkpoint.missing_pools.remove(&pool.id);
-            checkpoint.pools.insert(pool.id, pool.try_into()?);
+        let mut non_existent_pools = HashSet::new();
+        {
+            let mut checkpoint = self.pools_checkpoint.lock().unwrap();
+            for (id, pool) in pools? {
+                checkpoint.missing_pools.remove(&id);
+                match pool {
+                    Some(pool) => {
+                        checkpoint.pools.insert(id, pool.try_into()?);
+                    }
+                    None => {
+                        non_existent_pools.insert(id);
+                    }
+                }
+            }
+
+            tracing::debug!("number of cached pools is {}", checkpoint.pools.len());
+            if !checkpoint.missing_pools.is_empty() {
+                tracing::warn!(
+                    "not all missing pools updated: {:?}",
+                    checkpoint.missing_pools
+                );
+            }
         }
 
-        tracing::debug!("number of cached pools is {}", checkpoint.pools.len());
-        if !checkpoint.missing_pools.is_empty() {
-            tracing::warn!(
-                "not all missing pools updated: {:?}",
-                checkpoint.missing_pools
-            );
+        if !non_existent_pools.is_empty() {
+            tracing::debug!("removing non-existent pools {:?}", non_existent_pools);
+            for pools in self.pools_by_token_pair.lock().unwrap().values_mut() {
+                pools.retain(|pool| !non_existent_pools.contains(pool));
+            }
         }
         Ok(())
     }
+
+    /// Registers pools created by the factory for token pairs that were
+    /// already requested. Pools for token pairs that weren't requested yet
+    /// don't need to be registered, as they are computed once their pair is
+    /// requested.
+    fn register_created_pools(&self, events: &[Event<UniswapV3Event>]) {
+        let PoolSource::Factory { factory, .. } = &self.source else {
+            return;
+        };
+
+        let mut pools_by_token_pair = self.pools_by_token_pair.lock().unwrap();
+        for event in events {
+            let (UniswapV3Event::PoolCreated(created), Some(meta)) = (&event.data, &event.meta)
+            else {
+                continue;
+            };
+            if meta.address != factory.address || !factory.fee_tiers.contains(&created.fee) {
+                continue;
+            }
+            let Some(pair) = TokenPair::new(created.token_0, created.token_1) else {
+                continue;
+            };
+            if let Some(pools) = pools_by_token_pair.get_mut(&pair) {
+                tracing::debug!(pool = ?created.pool, ?pair, "registering created pool");
+                pools.insert(created.pool);
+            }
+        }
+    }
 }
 
 pub struct UniswapV3PoolFetcher {
@@ -282,7 +398,43 @@ impl UniswapV3PoolFetcher {
 
         let events = tokio::sync::Mutex::new(EventHandler::new(
             block_retriever,
-            UniswapV3PoolEventFetcher(web3),
+            UniswapV3PoolEventFetcher {
+                web3,
+                swap_event: SwapEvent::UniswapV3,
+            },
+            RecentEventsCache::default(),
+            Some(init_block),
+        ));
+
+        Ok(Self { checkpoint, events })
+    }
+
+    /// Creates a pool fetcher discovering the pools of a Uniswap V3 compatible
+    /// factory on-chain. This doesn't rely on a subgraph, and can therefore be
+    /// used for forks and on chains without a Uniswap V3 subgraph. Pools are
+    /// initialized lazily, at most `max_pools_to_initialize` per maintenance
+    /// run.
+    pub async fn from_factory(
+        factory: Factory,
+        web3: Web3,
+        block_retriever: Arc<dyn BlockRetrieving>,
+        max_pools_to_initialize: usize,
+    ) -> Result<Self> {
+        let current_block = block_retriever.current_block().await?.number;
+        let init_block = block_retriever
+            .block(current_block.saturating_sub(MAX_REORG_BLOCK_COUNT))
+            .await?;
+        let swap_event = factory.swap_event;
+        let checkpoint = PoolsCheckpointHandler::from_factory(
+            factory,
+            web3.clone(),
+            init_block.0,
+            max_pools_to_initialize,
+        );
+
+        let events = tokio::sync::Mutex::new(EventHandler::new(
+            block_retriever,
+            UniswapV3PoolEventFetcher { web3, swap_event },
             RecentEventsCache::default(),
             Some(init_block),
         ));
@@ -309,6 +461,7 @@ impl UniswapV3PoolFetcher {
                 let block_range =
                     RangeInclusive::try_new(old_checkpoint_block + 1, new_checkpoint_block)?;
                 let events = self.events.lock().await.store().get_events(block_range);
+                self.checkpoint.register_created_pools(&events);
                 let mut checkpoint = self.checkpoint.pools_checkpoint.lock().unwrap();
                 append_events(&mut checkpoint.pools, events);
                 checkpoint.block_number = new_checkpoint_block;
@@ -461,6 +614,7 @@ fn append_events(pools: &mut HashMap<H160, PoolInfo>, events: Vec<Event<UniswapV
                     pool.liquidity = swap.liquidity.into();
                     pool.sqrt_price = swap.sqrt_price_x96;
                 }
+                UniswapV3Event::PoolCreated(_) => {}
             }
         }
     }
@@ -732,7 +886,12 @@ mod tests {
                 .await
                 .unwrap();
 
-        assert!(!fetcher.checkpoint.pools_by_token_pair.is_empty());
+        assert!(!fetcher
+            .checkpoint
+            .pools_by_token_pair
+            .lock()
+            .unwrap()
+            .is_empty());
         assert!(!fetcher
             .checkpoint
             .pools_checkpoint
@@ -742,6 +901,40 @@ mod tests {
             .is_empty());
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn uniswap_v3_pool_fetcher_from_factory_test() {
+        let transport = ethrpc::create_env_test_transport();
+        let web3 = Web3::new(transport);
+        let block_retriever = Arc::new(web3.clone());
+        let factory = Factory {
+            address: addr!("1F98431c8aD98523631AE4a59f267346ea31F984"),
+            deployer: addr!("1F98431c8aD98523631AE4a59f267346ea31F984"),
+            init_code_digest: crate::sources::uniswap_v3_pair_provider::POOL_INIT_CODE_HASH,
+            fee_tiers: vec![500, 3000],
+            swap_event: SwapEvent::UniswapV3,
+        };
+        let fetcher = UniswapV3PoolFetcher::from_factory(factory, web3, block_retriever, 100)
+            .await
+            .unwrap();
+
+        let pair = TokenPair::new(testlib::tokens::WETH, testlib::tokens::USDC).unwrap();
+        let token_pairs = HashSet::from([pair]);
+        // the first fetch only flags the pools as missing
+        assert!(fetcher
+            .fetch(&token_pairs, Block::Recent)
+            .await
+            .unwrap()
+            .is_empty());
+
+        fetcher.run_maintenance().await.unwrap();
+        let pools = fetcher.fetch(&token_pairs, Block::Recent).await.unwrap();
+        assert_eq!(pools.len(), 2);
+        for pool in pools {
+            assert!(!pool.state.liquidity_net.is_empty());
+        }
+    }
+
     #[tokio::test]
     #[ignore]
     async fn fetch_test() {
@@ -1,10 +1,26 @@
-use {ethcontract::H160, hex_literal::hex, model::TokenPair, web3::signing::keccak256};
+use {
+    ethcontract::{H160, H256},
+    hex_literal::hex,
+    model::TokenPair,
+    web3::signing::keccak256,
+};
+
+/// The digest of the Uniswap V3 pool initialization code.
+pub const POOL_INIT_CODE_HASH: H256 = H256(hex!(
+    "e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54"
+));
 
 /// Calculates deterministic Uniswapv3 pool address.
 /// https://github.com/Uniswap/v3-periphery/blob/main/contracts/libraries/PoolAddress.sol
 pub fn pair_address(factory: &H160, pair: &TokenPair, fee: u32) -> H160 {
-    const INIT: [u8; 32] = hex!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54");
+    pool_address(factory, &POOL_INIT_CODE_HASH, pair, fee)
+}
 
+/// Calculates the deterministic pool address for a Uniswap V3 compatible
+/// deployment. Note that some forks (e.g. PancakeSwap V3) deploy pools from a
+/// contract other than the factory, in which case `deployer` must be that
+/// contract's address.
+pub fn pool_address(deployer: &H160, init_code_digest: &H256, pair: &TokenPair, fee: u32) -> H160 {
     let (token0, token1) = pair.get();
     let mut buffer = [0u8; 32 * 3];
     buffer[12..32].copy_from_slice(&token0.0);
@@ -14,9 +30,9 @@ pub fn pair_address(factory: &H160, pair: &TokenPair, fee: u32) -> H160 {
 
     let mut buffer = [0u8; 1 + 20 + 32 + 32];
     buffer[0] = 0xff;
-    buffer[1..21].copy_from_slice(&factory.0);
+    buffer[1..21].copy_from_slice(&deployer.0);
     buffer[21..53].copy_from_slice(&hash);
-    buffer[53..85].copy_from_slice(&INIT);
+    buffer[53..85].copy_from_slice(&init_code_digest.0);
     let hash = keccak256(&buffer);
 
     H160::from_slice(&hash[12..])
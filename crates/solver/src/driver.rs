@@ -377,6 +377,7 @@ impl Driver {
                             executed_amount: trade.executed_amount,
                         })
                         .collect(),
+                    jit_orders: Default::default(),
                     call_data: Some(settlement_simulation::call_data(
                         rated_settlement
                             .settlement
@@ -293,6 +293,7 @@ impl SolutionSubmitter {
             target_confirm_time: self.target_confirm_time,
             gas_estimate,
             deadline: Some(Instant::now() + self.max_confirm_time),
+            deadline_block: None,
             retry_interval: self.retry_interval,
             network_id,
             additional_call_data: Default::default(),
@@ -61,6 +61,9 @@ pub struct SubmitterParams {
     pub gas_estimate: U256,
     /// Maximum duration of a single run loop
     pub deadline: Option<Instant>,
+    /// Block after which the submission stops, even if the `deadline` was not
+    /// reached yet.
+    pub deadline_block: Option<u64>,
     /// Re-simulate and resend transaction on every retry_interval
     pub retry_interval: Duration,
     /// Network id (mainnet, goerli, sepolia, gnosis chain)
@@ -246,6 +249,7 @@ impl<'a> Submitter<'a> {
             .unwrap_or_default();
 
         let deadline = params.deadline;
+        let deadline_block = params.deadline_block;
 
         // Continually simulate and submit transactions
         let submit_future = self.submit_with_increasing_gas_prices_until_simulation_fails(
@@ -258,10 +262,21 @@ impl<'a> Submitter<'a> {
         let nonce_future = self.wait_for_nonce_to_change(self.nonce);
 
         // If specified, deadline future stops submitting when deadline is reached
-        let deadline_future = tokio::time::sleep(match deadline {
-            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
-            None => Duration::from_secs(u64::MAX),
-        });
+        let deadline_future = async {
+            let timeout = tokio::time::sleep(match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::from_secs(u64::MAX),
+            });
+            match deadline_block {
+                Some(block) => {
+                    tokio::select! {
+                        _ = timeout => (),
+                        _ = self.wait_for_block(block) => (),
+                    }
+                }
+                None => timeout.await,
+            }
+        };
 
         let fallback_result = tokio::select! {
             method_error = submit_future => {
@@ -386,6 +401,19 @@ impl<'a> Submitter<'a> {
         }
     }
 
+    /// Keep polling the current block until it reaches the specified block.
+    async fn wait_for_block(&self, block: u64) {
+        const POLL_INTERVAL: Duration = Duration::from_secs(1);
+        loop {
+            match self.web3.eth().block_number().await {
+                Ok(current) if current.as_u64() >= block => return,
+                Ok(_) => (),
+                Err(err) => tracing::error!("web3 error while getting block number: {:?}", err),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
     /// Keep submitting the settlement transaction to the network as gas price
     /// changes.
     ///
@@ -711,11 +739,16 @@ fn track_strategy_outcome(strategy: &str, outcome: &str) {
 #[cfg(test)]
 mod tests {
     use {
-        super::{super::submitter::flashbots_api::FlashbotsApi, *},
+        super::{
+            super::{submitter::flashbots_api::FlashbotsApi, GlobalTxPool},
+            *,
+        },
         crate::settlement_access_list::{create_priority_estimator, AccessListEstimatorType},
-        ethcontract::PrivateKey,
+        ethcontract::{dyns::DynTransport, PrivateKey, H160},
+        ethrpc::mock::MockTransport,
         gas_estimation::blocknative::BlockNative,
         reqwest::Client,
+        serde_json::{json, Value},
         shared::{
             code_fetching::MockCodeFetching,
             ethrpc::create_env_test_transport,
@@ -809,6 +842,7 @@ mod tests {
             target_confirm_time: Duration::from_secs(0),
             gas_estimate,
             deadline: Some(Instant::now() + Duration::from_secs(90)),
+            deadline_block: None,
             retry_interval: Duration::from_secs(5),
             network_id: "1".to_string(),
             additional_call_data: Default::default(),
@@ -818,6 +852,122 @@ mod tests {
         tracing::debug!("finished with result {:?}", result);
     }
 
+    #[tokio::test]
+    async fn deadline_block_cancels_submission() {
+        let account = Account::Local(H160([2; 20]), None);
+        let pending_hash = H256([3; 32]);
+        let cancel_hash = H256([4; 32]);
+
+        let transport = MockTransport::new();
+        transport
+            .mock()
+            .expect_execute()
+            .returning(|method, _| match method.as_str() {
+                "eth_blockNumber" => Ok(json!("0x64")),
+                "eth_getTransactionCount" => Ok(json!("0x0")),
+                _ => panic!("unexpected call {method}"),
+            });
+        transport.mock().expect_execute_batch().returning({
+            let address = account.address();
+            move |calls| {
+                Ok(calls
+                    .into_iter()
+                    .map(|(method, params)| {
+                        assert_eq!(method, "eth_getTransactionReceipt");
+                        let hash = serde_json::from_value::<H256>(params[0].clone()).unwrap();
+                        if hash == cancel_hash {
+                            Ok(serde_json::to_value(TransactionReceipt {
+                                transaction_hash: cancel_hash,
+                                block_hash: Some(H256([5; 32])),
+                                status: Some(1.into()),
+                                from: address,
+                                to: Some(address),
+                                ..Default::default()
+                            })
+                            .unwrap())
+                        } else {
+                            Ok(Value::Null)
+                        }
+                    })
+                    .collect())
+            }
+        });
+        let web3 = Web3::new(DynTransport::new(transport));
+        let contract = GPv2Settlement::at(&web3, H160([1; 20]));
+
+        let mut submit_api = MockTransactionSubmitting::new();
+        submit_api
+            .expect_submission_status()
+            .returning(|_, _| SubmissionLoopStatus::Enabled);
+        submit_api
+            .expect_name()
+            .returning(|| Strategy::PublicMempool);
+        submit_api
+            .expect_cancel_transaction()
+            .times(1)
+            .returning(move |_| {
+                Ok(TransactionHandle {
+                    handle: cancel_hash,
+                    tx_hash: cancel_hash,
+                })
+            });
+
+        // Gas prices stay above the cap, so no new transaction gets submitted
+        // and only the transaction of a previous submission loop is pending.
+        let gas_price = GasPrice1559 {
+            base_fee_per_gas: 100.,
+            max_fee_per_gas: 200.,
+            max_priority_fee_per_gas: 1.,
+        };
+        let gas_price_estimator = SubmitterGasPriceEstimator {
+            inner: &FakeGasPriceEstimator::new(gas_price),
+            additional_tip_percentage_of_max_fee: 0.,
+            max_additional_tip: 0.,
+            max_fee_per_gas: 50.,
+        };
+        let submitted_transactions = GlobalTxPool::default().add_sub_pool(Strategy::PublicMempool);
+        submitted_transactions.update(
+            account.address(),
+            0.into(),
+            vec![(
+                TransactionHandle {
+                    handle: pending_hash,
+                    tx_hash: pending_hash,
+                },
+                gas_price,
+            )],
+        );
+        let access_list_estimator = create_priority_estimator(
+            &web3,
+            &[AccessListEstimatorType::Web3],
+            None,
+            "1".to_string(),
+        )
+        .unwrap();
+        let code_fetcher = MockCodeFetching::new();
+
+        let submitter = Submitter::new(
+            &contract,
+            &account,
+            0.into(),
+            &submit_api,
+            &gas_price_estimator,
+            &access_list_estimator,
+            submitted_transactions.clone(),
+            web3.clone(),
+            &code_fetcher,
+        );
+        let params = SubmitterParams {
+            deadline_block: Some(100),
+            retry_interval: Duration::from_millis(10),
+            network_id: "1".to_string(),
+            ..Default::default()
+        };
+
+        let result = submitter.submit(Settlement::default(), params).await;
+        assert!(matches!(result, Err(SubmissionError::Canceled(hash)) if hash == cancel_hash));
+    }
+
     #[tokio::test]
     async fn gas_price_estimator_includes_additional_tip() {
         let gas_price_estimator = SubmitterGasPriceEstimator {
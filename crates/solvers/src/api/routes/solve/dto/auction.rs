@@ -64,6 +64,7 @@ impl Auction {
                     },
                     fee: order::Fee(order.fee_amount),
                     partially_fillable: order.partially_fillable,
+                    cow_only: order.cow_only,
                 })
                 .collect(),
             liquidity: self
@@ -74,6 +75,7 @@ impl Auction {
                     Liquidity::WeightedProduct(liquidity) => liquidity.to_domain(),
                     Liquidity::Stable(liquidity) => liquidity.to_domain(),
                     Liquidity::ConcentratedLiquidity(liquidity) => liquidity.to_domain(),
+                    Liquidity::Solidly(liquidity) => liquidity.to_domain(),
                     Liquidity::LimitOrder(liquidity) => Ok(liquidity.to_domain()),
                 })
                 .try_collect()?,
@@ -114,6 +116,8 @@ struct Order {
     kind: Kind,
     partially_fillable: bool,
     class: Class,
+    #[serde(default)]
+    cow_only: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -152,6 +156,7 @@ enum Liquidity {
     WeightedProduct(WeightedProductPool),
     Stable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
+    Solidly(SolidlyPool),
     LimitOrder(ForeignLimitOrder),
 }
 
@@ -330,6 +335,59 @@ impl StablePool {
     }
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SolidlyPool {
+    id: String,
+    address: H160,
+    #[serde_as(as = "serialize::U256")]
+    gas_estimate: U256,
+    tokens: HashMap<H160, SolidlyReserve>,
+    stable: bool,
+    fee: BigDecimal,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SolidlyReserve {
+    #[serde_as(as = "serialize::U256")]
+    balance: U256,
+    decimals: u8,
+}
+
+impl SolidlyPool {
+    fn to_domain(&self) -> Result<liquidity::Liquidity, Error> {
+        let reserves = {
+            let (a, b) = self
+                .tokens
+                .iter()
+                .map(|(token, reserve)| liquidity::solidly::Reserve {
+                    asset: eth::Asset {
+                        token: eth::TokenAddress(*token),
+                        amount: reserve.balance,
+                    },
+                    decimals: reserve.decimals,
+                })
+                .collect_tuple()
+                .ok_or("invalid number of Solidly pool tokens")?;
+            liquidity::solidly::Reserves::new(a, b).ok_or("invalid Solidly pool reserves")?
+        };
+
+        Ok(liquidity::Liquidity {
+            id: liquidity::Id(self.id.clone()),
+            address: self.address,
+            gas: eth::Gas(self.gas_estimate),
+            state: liquidity::State::Solidly(liquidity::solidly::Pool {
+                reserves,
+                stable: self.stable,
+                fee: conv::decimal_to_rational(&self.fee).ok_or("invalid Solidly pool fee")?,
+            }),
+        })
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -187,6 +187,17 @@ fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Ve
                         }
                     }
                 }
+                liquidity::State::Solidly(pool) => {
+                    if let Some(boundary_pool) =
+                        boundary::liquidity::solidly::to_boundary_pool(liquidity.address, pool)
+                    {
+                        amms.entry(boundary_pool.tokens).or_default().push(Amm {
+                            id: liquidity.id.clone(),
+                            token_pair: boundary_pool.tokens,
+                            pool: Pool::Solidly(boundary_pool),
+                        });
+                    }
+                }
                 // The baseline solver does not currently support other AMMs.
                 _ => {}
             };
@@ -206,6 +217,7 @@ enum Pool {
     ConstantProduct(boundary::liquidity::constant_product::Pool),
     WeightedProduct(boundary::liquidity::weighted_product::Pool),
     Stable(boundary::liquidity::stable::Pool),
+    Solidly(boundary::liquidity::solidly::Pool),
 }
 
 impl BaselineSolvable for Amm {
@@ -214,6 +226,7 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.get_amount_out(out_token, input),
             Pool::WeightedProduct(pool) => pool.get_amount_out(out_token, input),
             Pool::Stable(pool) => pool.get_amount_out(out_token, input),
+            Pool::Solidly(pool) => pool.get_amount_out(out_token, input),
         }
     }
 
@@ -222,6 +235,7 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.get_amount_in(in_token, out),
             Pool::WeightedProduct(pool) => pool.get_amount_in(in_token, out),
             Pool::Stable(pool) => pool.get_amount_in(in_token, out),
+            Pool::Solidly(pool) => pool.get_amount_in(in_token, out),
         }
     }
 
@@ -230,6 +244,7 @@ impl BaselineSolvable for Amm {
             Pool::ConstantProduct(pool) => pool.gas_cost(),
             Pool::WeightedProduct(pool) => pool.gas_cost(),
             Pool::Stable(pool) => pool.gas_cost(),
+            Pool::Solidly(pool) => pool.gas_cost(),
         }
     }
 }
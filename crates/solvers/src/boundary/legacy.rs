@@ -338,6 +338,9 @@ fn to_boundary_auction(
                     to_big_rational(&state.fee.0),
                 )
             }
+            // The legacy HTTP solver model has no representation for
+            // Solidly pools, so they are not forwarded.
+            liquidity::State::Solidly(_) => continue,
             liquidity::State::LimitOrder(state) => {
                 let index = mapping.orders.len();
                 mapping.orders.push(Order::Liquidity(liquidity, state));
@@ -1,3 +1,4 @@
 pub mod constant_product;
+pub mod solidly;
 pub mod stable;
 pub mod weighted_product;
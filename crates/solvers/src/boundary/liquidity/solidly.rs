@@ -0,0 +1,25 @@
+pub use shared::sources::solidly::pool_fetching::Pool;
+use {crate::domain::liquidity, ethereum_types::H160, model::TokenPair};
+
+/// Converts a domain pool into a [`shared`] Solidly pool. Returns `None` if
+/// the domain pool cannot be represented as a boundary pool.
+pub fn to_boundary_pool(address: H160, pool: &liquidity::solidly::Pool) -> Option<Pool> {
+    let reserves = pool.reserves.get();
+    let tokens = TokenPair::new(reserves.0.asset.token.0, reserves.1.asset.token.0)
+        .expect("tokens are distinct by construction");
+
+    if *pool.fee.numer() > u32::MAX.into() || *pool.fee.denom() > u32::MAX.into() {
+        return None;
+    }
+    let fee = num::rational::Ratio::new(pool.fee.numer().as_u32(), pool.fee.denom().as_u32());
+
+    Some(Pool {
+        address,
+        tokens,
+        // reserves are ordered by construction.
+        reserves: (reserves.0.asset.amount, reserves.1.asset.amount),
+        decimals: (reserves.0.decimals, reserves.1.decimals),
+        stable: pool.stable,
+        fee,
+    })
+}
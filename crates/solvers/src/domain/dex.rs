@@ -0,0 +1,222 @@
+//! Types for solving single orders with swaps from external DEX aggregators.
+
+use {
+    crate::domain::{auction, eth, order, solution},
+    ethereum_types::U256,
+};
+
+/// An order for which a swap is requested from a DEX aggregator.
+#[derive(Clone, Debug)]
+pub struct Order {
+    pub sell: eth::TokenAddress,
+    pub buy: eth::TokenAddress,
+    pub side: order::Side,
+    /// The amount to swap. This is the sell amount for sell orders and the buy
+    /// amount for buy orders.
+    pub amount: U256,
+}
+
+impl Order {
+    pub fn new(order: &order::Order) -> Self {
+        Self {
+            sell: order.sell.token,
+            buy: order.buy.token,
+            side: order.side,
+            amount: match order.side {
+                order::Side::Buy => order.buy.amount,
+                order::Side::Sell => order.sell.amount,
+            },
+        }
+    }
+
+    /// The asset that is swapped in the exact amount.
+    pub fn amount(&self) -> eth::Asset {
+        eth::Asset {
+            token: match self.side {
+                order::Side::Buy => self.buy,
+                order::Side::Sell => self.sell,
+            },
+            amount: self.amount,
+        }
+    }
+}
+
+/// Limits for the slippage tolerance of swaps.
+#[derive(Clone, Debug)]
+pub struct SlippageLimits {
+    /// The relative slippage tolerance as a factor, i.e. `0.01` for 1%.
+    pub relative: f64,
+    /// An optional cap for the slippage in wei. Large swaps use a smaller
+    /// relative slippage so they don't exceed this value.
+    pub absolute: Option<eth::Ether>,
+}
+
+impl SlippageLimits {
+    /// Computes the relative slippage to use for swapping the specified asset.
+    /// The absolute cap is only applied if the native price of the asset is
+    /// known.
+    pub fn relative(&self, asset: &eth::Asset, tokens: &auction::Tokens) -> Slippage {
+        let absolute = self.absolute.and_then(|absolute| {
+            let price = tokens.reference_price(&asset.token)?;
+            let amount = price.ether_value(absolute)?;
+            Some(amount.to_f64_lossy() / asset.amount.to_f64_lossy())
+        });
+        Slippage(match absolute {
+            Some(absolute) if absolute.is_finite() => self.relative.min(absolute),
+            _ => self.relative,
+        })
+    }
+}
+
+/// A relative slippage tolerance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slippage(f64);
+
+impl Slippage {
+    pub fn as_factor(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_percentage(&self) -> f64 {
+        self.0 * 100.
+    }
+
+    /// Adds the slippage to an input amount, rounding up.
+    pub fn add(&self, amount: U256) -> U256 {
+        let slippage = U256::from_f64_lossy((amount.to_f64_lossy() * self.0).ceil());
+        amount.saturating_add(slippage)
+    }
+
+    /// Subtracts the slippage from an output amount, rounding down.
+    pub fn sub(&self, amount: U256) -> U256 {
+        let slippage = U256::from_f64_lossy((amount.to_f64_lossy() * self.0).ceil());
+        amount.saturating_sub(slippage)
+    }
+}
+
+/// A swap from a DEX aggregator that executes an order.
+#[derive(Debug)]
+pub struct Swap {
+    /// The call that executes the swap.
+    pub call: Call,
+    /// The quoted input of the swap.
+    pub input: eth::Asset,
+    /// The quoted output of the swap.
+    pub output: eth::Asset,
+    /// The allowance of the input token that the settlement contract needs to
+    /// grant for executing the swap. This includes the slippage for buy
+    /// orders.
+    pub allowance: Allowance,
+    /// The estimated gas needed for executing the swap.
+    pub gas: eth::Gas,
+}
+
+impl Swap {
+    /// Creates a solution executing the order with this swap. Returns `None` if
+    /// the swap does not satisfy the order's limit price.
+    pub fn into_solution(
+        self,
+        order: order::Order,
+        gas_price: auction::GasPrice,
+        sell_token: Option<auction::Price>,
+        score: solution::Score,
+    ) -> Option<solution::Solution> {
+        // Aggregators can return more than the requested amount for buy
+        // orders, which is fine for the swap but must not be used as the
+        // executed amount.
+        let mut output = self.output;
+        if let order::Side::Buy = order.side {
+            output.amount = output.amount.min(order.buy.amount);
+        }
+
+        let interaction = solution::Interaction::Custom(solution::CustomInteraction {
+            target: self.call.to.0,
+            value: eth::Ether::default(),
+            calldata: self.call.calldata,
+            internalize: false,
+            inputs: vec![self.input],
+            outputs: vec![self.output],
+            allowances: vec![solution::Allowance {
+                spender: self.allowance.spender.0,
+                asset: eth::Asset {
+                    token: self.input.token,
+                    amount: self.allowance.amount,
+                },
+            }],
+        });
+
+        solution::Single {
+            order,
+            input: self.input,
+            output,
+            interactions: vec![interaction],
+            gas: self.gas,
+        }
+        .into_solution(gas_price, sell_token, score)
+    }
+}
+
+/// A call to a smart contract.
+#[derive(Debug)]
+pub struct Call {
+    pub to: eth::ContractAddress,
+    pub calldata: Vec<u8>,
+}
+
+/// An ERC20 allowance required for executing a swap.
+#[derive(Debug)]
+pub struct Allowance {
+    pub spender: eth::ContractAddress,
+    pub amount: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashMap};
+
+    #[test]
+    fn slippage_is_capped_by_absolute_limit() {
+        let token = eth::TokenAddress(eth::H160([1; 20]));
+        let tokens = auction::Tokens(HashMap::from([(
+            token,
+            auction::Token {
+                decimals: Some(18),
+                symbol: None,
+                // The token is worth as much as ETH.
+                reference_price: Some(auction::Price(eth::Ether(U256::exp10(18)))),
+                available_balance: U256::zero(),
+                trusted: false,
+            },
+        )]));
+        let limits = SlippageLimits {
+            relative: 0.01,
+            absolute: Some(eth::Ether(U256::exp10(17))),
+        };
+
+        let small = eth::Asset {
+            token,
+            amount: U256::exp10(18),
+        };
+        assert_eq!(limits.relative(&small, &tokens), Slippage(0.01));
+
+        let large = eth::Asset {
+            token,
+            amount: U256::exp10(20),
+        };
+        assert_eq!(limits.relative(&large, &tokens), Slippage(0.001));
+
+        let unknown = eth::Asset {
+            token: eth::TokenAddress(eth::H160([2; 20])),
+            amount: U256::exp10(20),
+        };
+        assert_eq!(limits.relative(&unknown, &tokens), Slippage(0.01));
+    }
+
+    #[test]
+    fn applies_slippage() {
+        let slippage = Slippage(0.01);
+        assert_eq!(slippage.add(1_000.into()), 1_010.into());
+        assert_eq!(slippage.sub(1_000.into()), 990.into());
+        assert_eq!(slippage.sub(1.into()), 0.into());
+    }
+}
@@ -0,0 +1,54 @@
+//! Solidly stable and volatile pools.
+
+use {
+    crate::domain::{eth, liquidity},
+    std::cmp::Ordering,
+};
+
+/// Solidly pool state.
+///
+/// Volatile pools use the constant product invariant `x · y = k`, while stable
+/// pools use the invariant `x³y + y³x = k` for correlated assets.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub reserves: Reserves,
+    pub stable: bool,
+    pub fee: eth::Rational,
+}
+
+impl Pool {
+    /// Returns the pool's token pair.
+    pub fn tokens(&self) -> liquidity::TokenPair {
+        liquidity::TokenPair::new(self.reserves.0.asset.token, self.reserves.1.asset.token)
+            .expect("pool reserve assets have different tokens")
+    }
+}
+
+/// Solidly pool reserves.
+#[derive(Clone, Debug)]
+pub struct Reserves(Reserve, Reserve);
+
+/// A Solidly pool token reserve. The token decimals are needed for computing
+/// stable pool swaps.
+#[derive(Clone, Copy, Debug)]
+pub struct Reserve {
+    pub asset: eth::Asset,
+    pub decimals: u8,
+}
+
+impl Reserves {
+    /// Creates new Solidly pool reserves with the specified assets. Returns
+    /// `None` if the assets are denominated in the same token.
+    pub fn new(a: Reserve, b: Reserve) -> Option<Self> {
+        match a.asset.token.cmp(&b.asset.token) {
+            Ordering::Less => Some(Self(a, b)),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(Self(b, a)),
+        }
+    }
+
+    /// Get the reserves.
+    pub fn get(&self) -> (Reserve, Reserve) {
+        (self.0, self.1)
+    }
+}
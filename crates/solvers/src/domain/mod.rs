@@ -1,6 +1,7 @@
 //! Core solver engine logic.
 
 pub mod auction;
+pub mod dex;
 pub mod eth;
 pub mod liquidity;
 pub mod notification;
@@ -16,6 +16,9 @@ pub struct Order {
     pub side: Side,
     pub class: Class,
     pub partially_fillable: bool,
+    /// The order may only be matched peer-to-peer, so its traded amounts can't
+    /// be covered by on-chain liquidity.
+    pub cow_only: bool,
 }
 
 impl Order {
@@ -117,6 +117,11 @@ impl Inner {
             let Some(user_order) = UserOrder::new(&order) else {
                 continue;
             };
+            // Orders are routed through on-chain liquidity, which CoW-only
+            // orders don't allow.
+            if order.cow_only {
+                continue;
+            }
             let solution = self.requests_for_order(user_order).find_map(|request| {
                 tracing::trace!(order =% order.uid, ?request, "finding route");
 
@@ -0,0 +1,116 @@
+//! A simple solver that matches orders directly with swaps from the external
+//! DEX and DEX aggregator APIs.
+
+use {
+    crate::{
+        domain::{self, auction, dex, order::UserOrder, solution},
+        infra::{self, config, metrics},
+    },
+    futures::{future, stream, FutureExt, StreamExt},
+    std::num::NonZeroUsize,
+};
+
+pub struct Dex {
+    /// The DEX API client.
+    dex: infra::dex::Dex,
+
+    /// The slippage configuration to use for the solver.
+    slippage: dex::SlippageLimits,
+
+    /// The number of concurrent requests to make to the DEX aggregator API.
+    concurrent_requests: NonZeroUsize,
+
+    /// Parameters used to calculate the revert risk of a solution.
+    risk: domain::Risk,
+}
+
+/// The amount of time we aim the solver to finish before the final deadline is
+/// reached.
+const DEADLINE_SLACK: chrono::Duration = chrono::Duration::milliseconds(500);
+
+impl Dex {
+    pub fn new(dex: infra::dex::Dex, config: config::dex::Config) -> Self {
+        Self {
+            dex,
+            slippage: config.slippage,
+            concurrent_requests: config.concurrent_requests,
+            risk: config.risk,
+        }
+    }
+
+    /// Solves the specified auction, returning a vector of all possible
+    /// solutions.
+    pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        let deadline = auction
+            .deadline
+            .clone()
+            .reduce(DEADLINE_SLACK)
+            .remaining()
+            .unwrap_or_default();
+
+        let mut solutions = Vec::new();
+        let solve_orders = async {
+            let stream = stream::iter(auction.orders.iter().enumerate())
+                .filter_map(|(i, order)| future::ready(UserOrder::new(order).map(|o| (i, o))))
+                // Orders are swapped on external DEXs, which CoW-only orders
+                // don't allow.
+                .filter(|(_, order)| future::ready(!order.get().cow_only))
+                .map(|(i, order)| {
+                    self.solve_order(order, &auction.tokens, auction.gas_price)
+                        .map(move |solution| solution.map(|s| s.with_id(solution::Id(i as u64))))
+                })
+                .buffer_unordered(self.concurrent_requests.get())
+                .filter_map(future::ready);
+            futures::pin_mut!(stream);
+            while let Some(solution) = stream.next().await {
+                solutions.push(solution);
+            }
+        };
+
+        if tokio::time::timeout(deadline, solve_orders).await.is_err() {
+            tracing::debug!("reached timeout while solving orders");
+        }
+        solutions
+    }
+
+    async fn solve_order(
+        &self,
+        order: UserOrder<'_>,
+        tokens: &auction::Tokens,
+        gas_price: auction::GasPrice,
+    ) -> Option<solution::Solution> {
+        let order = order.get();
+        let dex_order = dex::Order::new(order);
+        let slippage = self.slippage.relative(&dex_order.amount(), tokens);
+        let swap = match self
+            .dex
+            .swap(&dex_order, &slippage, tokens, gas_price)
+            .await
+        {
+            Ok(swap) => swap,
+            Err(err @ infra::dex::Error::NotFound) => {
+                tracing::debug!(order =% order.uid, ?err, "skipping order");
+                return None;
+            }
+            Err(err @ infra::dex::Error::OrderNotSupported) => {
+                tracing::debug!(order =% order.uid, ?err, "skipping unsupported order");
+                return None;
+            }
+            Err(err) => {
+                metrics::solve_error(err.format_variant());
+                tracing::warn!(order =% order.uid, ?err, "failed to get swap");
+                return None;
+            }
+        };
+
+        let score = solution::Score::RiskAdjusted(solution::SuccessProbability(
+            self.risk.success_probability(swap.gas, gas_price, 1),
+        ));
+        let sell_token = tokens.reference_price(&order.sell.token);
+        let Some(solution) = swap.into_solution(order.clone(), gas_price, sell_token, score) else {
+            tracing::debug!(order =% order.uid, "swap does not satisfy the order");
+            return None;
+        };
+        Some(solution.with_buffers_internalizations(tokens))
+    }
+}
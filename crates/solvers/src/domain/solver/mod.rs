@@ -4,15 +4,17 @@ use crate::{
 };
 
 pub mod baseline;
+pub mod dex;
 pub mod legacy;
 pub mod naive;
 
-pub use self::{baseline::Baseline, legacy::Legacy, naive::Naive};
+pub use self::{baseline::Baseline, dex::Dex, legacy::Legacy, naive::Naive};
 
 pub enum Solver {
     Baseline(Baseline),
     Naive(Naive),
     Legacy(Legacy),
+    Dex(Dex),
 }
 
 impl Solver {
@@ -26,6 +28,7 @@ impl Solver {
             Solver::Baseline(solver) => solver.solve(auction).await,
             Solver::Naive(solver) => solver.solve(auction).await,
             Solver::Legacy(solver) => solver.solve(auction).await,
+            Solver::Dex(solver) => solver.solve(auction).await,
         };
         metrics::solved(&deadline, &solutions);
         solutions
@@ -38,6 +41,7 @@ impl Solver {
             Solver::Baseline(_) => (),
             Solver::Naive(_) => (),
             Solver::Legacy(solver) => solver.notify(notification),
+            Solver::Dex(_) => (),
         }
     }
 }
@@ -105,6 +105,11 @@ fn group_by_token_pair(auction: &auction::Auction) -> Groups {
         if order.sell.amount.is_zero() || order.buy.amount.is_zero() {
             continue;
         }
+        // Any amounts that can't be matched within a group are swapped with
+        // the group's pool, which CoW-only orders don't allow.
+        if order.cow_only {
+            continue;
+        }
 
         let tokens = match liquidity::TokenPair::new(order.sell.token, order.buy.token) {
             Some(value) => value,
@@ -45,4 +45,28 @@ pub enum Command {
         #[clap(long, env)]
         config: PathBuf,
     },
+    /// solve individual orders using the 0x API
+    #[clap(name = "zeroex")]
+    ZeroEx {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
+    /// solve individual orders using the 1Inch API
+    #[clap(name = "oneinch")]
+    OneInch {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
+    /// solve individual orders using the ParaSwap API
+    #[clap(name = "paraswap")]
+    ParaSwap {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
+    /// solve individual orders using the Balancer SOR API
+    #[clap(name = "balancer")]
+    Balancer {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
 }
@@ -0,0 +1,41 @@
+use {
+    crate::{
+        domain::eth,
+        infra::{contracts, dex::balancer},
+        util::serialize,
+    },
+    serde::Deserialize,
+    serde_with::serde_as,
+    std::path::Path,
+};
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Chain ID used to determine the addresses of the Balancer vault and the
+    /// settlement contract.
+    #[serde_as(as = "serialize::ChainId")]
+    chain_id: eth::ChainId,
+
+    /// The URL of the Balancer SOR API.
+    endpoint: String,
+}
+
+/// Load the Balancer SOR solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> (super::Config, balancer::Config) {
+    let (base, config) = super::file::load::<Config>(path).await;
+    let contracts = contracts::Contracts::for_chain(config.chain_id);
+
+    let config = balancer::Config {
+        endpoint: config.endpoint.parse().unwrap(),
+        chain_id: config.chain_id,
+        vault: contracts.balancer_vault,
+        settlement: contracts.settlement,
+    };
+    (base, config)
+}
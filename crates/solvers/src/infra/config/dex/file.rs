@@ -0,0 +1,76 @@
+use {
+    crate::{
+        domain::{dex, eth, Risk},
+        infra::config::unwrap_or_log,
+        util::serialize,
+    },
+    ethereum_types::U256,
+    serde::{de::DeserializeOwned, Deserialize},
+    serde_with::serde_as,
+    std::{num::NonZeroUsize, path::Path},
+    tokio::fs,
+};
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// The relative slippage tolerance to apply to swaps.
+    #[serde(default = "default_relative_slippage")]
+    relative_slippage: f64,
+
+    /// The absolute slippage tolerance in wei. Caps the relative slippage for
+    /// large swaps.
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(default)]
+    absolute_slippage: Option<U256>,
+
+    /// The number of swaps to request from the aggregator concurrently.
+    #[serde(default = "default_concurrent_requests")]
+    concurrent_requests: NonZeroUsize,
+
+    /// Parameters used to calculate the revert risk of a solution.
+    /// (gas_amount_factor, gas_price_factor, nmb_orders_factor, intercept)
+    risk_parameters: (f64, f64, f64, f64),
+
+    /// Configuration specific to the DEX aggregator.
+    dex: toml::Value,
+}
+
+fn default_relative_slippage() -> f64 {
+    0.01
+}
+
+fn default_concurrent_requests() -> NonZeroUsize {
+    NonZeroUsize::new(1).unwrap()
+}
+
+/// Loads the configuration of a DEX aggregator solver from a TOML file. The
+/// aggregator specific configuration is read from the `[dex]` table.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load<T: DeserializeOwned>(path: &Path) -> (super::Config, T) {
+    let data = fs::read_to_string(path)
+        .await
+        .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
+    // Not printing detailed error because it could potentially leak secrets.
+    let config = unwrap_or_log(toml::de::from_str::<Config>(&data), &path);
+    let dex = unwrap_or_log(config.dex.try_into::<T>(), &path);
+
+    let config = super::Config {
+        slippage: dex::SlippageLimits {
+            relative: config.relative_slippage,
+            absolute: config.absolute_slippage.map(eth::Ether),
+        },
+        concurrent_requests: config.concurrent_requests,
+        risk: Risk {
+            gas_amount_factor: config.risk_parameters.0,
+            gas_price_factor: config.risk_parameters.1,
+            nmb_orders_factor: config.risk_parameters.2,
+            intercept: config.risk_parameters.3,
+        },
+    };
+    (config, dex)
+}
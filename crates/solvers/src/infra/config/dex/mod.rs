@@ -0,0 +1,17 @@
+use {
+    crate::domain::{dex, Risk},
+    std::num::NonZeroUsize,
+};
+
+pub mod balancer;
+pub mod file;
+pub mod oneinch;
+pub mod paraswap;
+pub mod zeroex;
+
+/// Configuration shared by all DEX aggregator solvers.
+pub struct Config {
+    pub slippage: dex::SlippageLimits,
+    pub concurrent_requests: NonZeroUsize,
+    pub risk: Risk,
+}
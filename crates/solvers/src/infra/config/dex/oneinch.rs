@@ -0,0 +1,56 @@
+use {
+    crate::{
+        domain::eth,
+        infra::{contracts, dex::oneinch},
+        util::serialize,
+    },
+    ethereum_types::H160,
+    serde::Deserialize,
+    serde_with::serde_as,
+    std::path::Path,
+};
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Chain ID used to determine the address of the settlement contract and
+    /// for requests to the 1Inch API.
+    #[serde_as(as = "serialize::ChainId")]
+    chain_id: eth::ChainId,
+
+    /// The base URL of the 1Inch API.
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// Liquidity protocols that 1Inch should not route through.
+    #[serde(default)]
+    excluded_protocols: Vec<String>,
+
+    /// The referrer address to include in swap requests.
+    #[serde(default)]
+    referrer: Option<H160>,
+}
+
+fn default_endpoint() -> String {
+    shared::oneinch_api::OneInchClientImpl::DEFAULT_URL.to_owned()
+}
+
+/// Load the 1Inch solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> (super::Config, oneinch::Config) {
+    let (base, config) = super::file::load::<Config>(path).await;
+    let contracts = contracts::Contracts::for_chain(config.chain_id);
+
+    let config = oneinch::Config {
+        endpoint: config.endpoint.parse().unwrap(),
+        chain_id: config.chain_id,
+        excluded_protocols: config.excluded_protocols,
+        referrer: config.referrer,
+        settlement: contracts.settlement,
+    };
+    (base, config)
+}
@@ -0,0 +1,46 @@
+use {crate::infra::dex::paraswap, ethereum_types::H160, serde::Deserialize, std::path::Path};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// The base URL of the ParaSwap API.
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// Liquidity sources that ParaSwap should not route through.
+    #[serde(default)]
+    exclude_dexs: Vec<String>,
+
+    /// The address of the solver account. ParaSwap builds the swap transaction
+    /// for this address.
+    address: H160,
+
+    /// The partner name to identify requests with.
+    #[serde(default = "default_partner")]
+    partner: String,
+}
+
+fn default_endpoint() -> String {
+    shared::paraswap_api::DEFAULT_URL.to_owned()
+}
+
+fn default_partner() -> String {
+    "GPv2".to_owned()
+}
+
+/// Load the ParaSwap solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> (super::Config, paraswap::Config) {
+    let (base, config) = super::file::load::<Config>(path).await;
+
+    let config = paraswap::Config {
+        endpoint: config.endpoint,
+        exclude_dexs: config.exclude_dexs,
+        address: config.address,
+        partner: config.partner,
+    };
+    (base, config)
+}
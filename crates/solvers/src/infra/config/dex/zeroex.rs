@@ -0,0 +1,64 @@
+use {
+    crate::{
+        domain::eth,
+        infra::{contracts, dex::zeroex},
+        util::serialize,
+    },
+    serde::Deserialize,
+    serde_with::serde_as,
+    std::path::Path,
+};
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Chain ID used to determine the address of the settlement contract.
+    #[serde_as(as = "serialize::ChainId")]
+    chain_id: eth::ChainId,
+
+    /// The base URL of the 0x API.
+    #[serde(default = "default_endpoint")]
+    endpoint: String,
+
+    /// The API key to use.
+    #[serde(default)]
+    api_key: Option<String>,
+
+    /// Liquidity sources that 0x should not route through.
+    #[serde(default)]
+    excluded_sources: Vec<String>,
+
+    /// Whether to request RFQ-T liquidity which requires the swap to be
+    /// executed shortly after requesting it.
+    #[serde(default)]
+    enable_rfqt: bool,
+
+    /// Whether to request routes that aim to protect against high slippage.
+    #[serde(default)]
+    enable_slippage_protection: bool,
+}
+
+fn default_endpoint() -> String {
+    shared::zeroex_api::DefaultZeroExApi::DEFAULT_URL.to_owned()
+}
+
+/// Load the 0x solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> (super::Config, zeroex::Config) {
+    let (base, config) = super::file::load::<Config>(path).await;
+    let contracts = contracts::Contracts::for_chain(config.chain_id);
+
+    let config = zeroex::Config {
+        endpoint: config.endpoint.parse().unwrap(),
+        api_key: config.api_key,
+        excluded_sources: config.excluded_sources,
+        settlement: contracts.settlement,
+        enable_rfqt: config.enable_rfqt,
+        enable_slippage_protection: config.enable_slippage_protection,
+    };
+    (base, config)
+}
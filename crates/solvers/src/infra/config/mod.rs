@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 pub mod baseline;
+pub mod dex;
 pub mod legacy;
 pub mod naive;
 
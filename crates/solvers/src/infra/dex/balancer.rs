@@ -0,0 +1,170 @@
+use {
+    super::Error,
+    crate::domain::{auction, dex, eth, order},
+    contracts::ethcontract::{Bytes, I256},
+    ethereum_types::U256,
+    model::order::OrderKind,
+    shared::balancer_sor_api::{self, BalancerSorApi, DefaultBalancerSorApi, Query, Quote},
+};
+
+/// Solves orders with swaps through the Balancer vault found by the Balancer
+/// smart order router (SOR).
+pub struct Sor {
+    api: DefaultBalancerSorApi,
+    vault: contracts::BalancerV2Vault,
+    settlement: eth::ContractAddress,
+}
+
+pub struct Config {
+    pub endpoint: reqwest::Url,
+    pub chain_id: eth::ChainId,
+    pub vault: eth::ContractAddress,
+    /// The settlement contract which sends and receives the swapped tokens.
+    pub settlement: eth::ContractAddress,
+}
+
+/// The Balancer SOR does not estimate gas, so use a conservative value per
+/// swap in the route. Matches the cost of a stable pool swap which is the
+/// most expensive pool type.
+const GAS_PER_SWAP: u64 = 183_520;
+
+impl Sor {
+    pub fn new(config: Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            api: DefaultBalancerSorApi::new(
+                reqwest::Client::new(),
+                config.endpoint,
+                config.chain_id.value().as_u64(),
+            )?,
+            vault: contracts::dummy_contract!(contracts::BalancerV2Vault, config.vault.0),
+            settlement: config.settlement,
+        })
+    }
+
+    pub async fn swap(
+        &self,
+        order: &dex::Order,
+        slippage: &dex::Slippage,
+        gas_price: auction::GasPrice,
+    ) -> Result<dex::Swap, Error> {
+        let query = Query {
+            sell_token: order.sell.0,
+            buy_token: order.buy.0,
+            order_kind: match order.side {
+                order::Side::Buy => OrderKind::Buy,
+                order::Side::Sell => OrderKind::Sell,
+            },
+            amount: order.amount,
+            gas_price: gas_price.0 .0,
+        };
+        let quote = self.api.quote(query).await?.ok_or(Error::NotFound)?;
+
+        let (input, output) = match order.side {
+            order::Side::Buy => (quote.return_amount, quote.swap_amount),
+            order::Side::Sell => (quote.swap_amount, quote.return_amount),
+        };
+        let (max_input, min_output) = match order.side {
+            order::Side::Buy => (slippage.add(input), output),
+            order::Side::Sell => (input, slippage.sub(output)),
+        };
+        let limits = swap_limits(&quote, max_input, min_output)?;
+        let calldata = self.batch_swap(order.side, &quote, limits);
+
+        Ok(dex::Swap {
+            call: dex::Call {
+                to: eth::ContractAddress(self.vault.address()),
+                calldata,
+            },
+            input: eth::Asset {
+                token: order.sell,
+                amount: input,
+            },
+            output: eth::Asset {
+                token: order.buy,
+                amount: output,
+            },
+            allowance: dex::Allowance {
+                spender: eth::ContractAddress(self.vault.address()),
+                amount: max_input,
+            },
+            gas: eth::Gas(U256::from(GAS_PER_SWAP) * quote.swaps.len()),
+        })
+    }
+
+    /// Encodes the `batchSwap` call on the Balancer vault.
+    fn batch_swap(&self, side: order::Side, quote: &Quote, limits: Vec<I256>) -> Vec<u8> {
+        // Values of the `IVault.SwapKind` enum.
+        let kind = match side {
+            order::Side::Sell => 0,
+            order::Side::Buy => 1,
+        };
+        let swaps = quote
+            .swaps
+            .iter()
+            .map(|swap| {
+                (
+                    Bytes(swap.pool_id.0),
+                    swap.asset_in_index.into(),
+                    swap.asset_out_index.into(),
+                    swap.amount,
+                    Bytes(swap.user_data.clone()),
+                )
+            })
+            .collect();
+        let funds = (
+            self.settlement.0, // sender
+            false,             // fromInternalBalance
+            self.settlement.0, // recipient
+            false,             // toInternalBalance
+        );
+        // The swap gets executed in the same transaction, so it never expires.
+        let deadline = U256::one() << 255;
+
+        self.vault
+            .methods()
+            .batch_swap(
+                kind,
+                swaps,
+                quote.token_addresses.clone(),
+                funds,
+                limits,
+                deadline,
+            )
+            .tx
+            .data
+            .expect("no calldata")
+            .0
+    }
+}
+
+/// Computes the vault limits for each token of the route. Positive values are
+/// the maximum amount transferred into the vault and negative values the
+/// minimum amount transferred out of it.
+fn swap_limits(quote: &Quote, max_input: U256, min_output: U256) -> anyhow::Result<Vec<I256>> {
+    quote
+        .token_addresses
+        .iter()
+        .map(|&token| {
+            let limit = if token == quote.token_in {
+                I256::try_from(max_input)?
+            } else if token == quote.token_out {
+                I256::try_from(min_output)?
+                    .checked_neg()
+                    .expect("positive integer can't overflow negation")
+            } else {
+                // Intermediate tokens must not be transferred in or out.
+                I256::zero()
+            };
+            Ok(limit)
+        })
+        .collect()
+}
+
+impl From<balancer_sor_api::Error> for Error {
+    fn from(err: balancer_sor_api::Error) -> Self {
+        match err {
+            balancer_sor_api::Error::RateLimited => Self::RateLimited,
+            balancer_sor_api::Error::Other(err) => Self::Other(err),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Clients for external DEX aggregators that are used for solving single
+//! orders.
+
+use crate::domain::{auction, dex};
+
+pub mod balancer;
+pub mod oneinch;
+pub mod paraswap;
+pub mod zeroex;
+
+/// A DEX aggregator to request swaps from.
+pub enum Dex {
+    Balancer(balancer::Sor),
+    OneInch(oneinch::OneInch),
+    ParaSwap(paraswap::ParaSwap),
+    ZeroEx(zeroex::ZeroEx),
+}
+
+impl Dex {
+    /// Requests a swap executing the order with the specified slippage
+    /// tolerance.
+    pub async fn swap(
+        &self,
+        order: &dex::Order,
+        slippage: &dex::Slippage,
+        tokens: &auction::Tokens,
+        gas_price: auction::GasPrice,
+    ) -> Result<dex::Swap, Error> {
+        match self {
+            Dex::Balancer(sor) => sor.swap(order, slippage, gas_price).await,
+            Dex::OneInch(oneinch) => oneinch.swap(order, slippage).await,
+            Dex::ParaSwap(paraswap) => paraswap.swap(order, slippage, tokens).await,
+            Dex::ZeroEx(zeroex) => zeroex.swap(order, slippage).await,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("order type is not supported")]
+    OrderNotSupported,
+    #[error("no valid swap interaction could be found")]
+    NotFound,
+    #[error("rate limited")]
+    RateLimited,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl Error {
+    /// Returns a label for the error to be used in metrics.
+    pub fn format_variant(&self) -> &'static str {
+        match self {
+            Self::OrderNotSupported => "OrderNotSupported",
+            Self::NotFound => "NotFound",
+            Self::RateLimited => "RateLimited",
+            Self::Other(_) => "Other",
+        }
+    }
+}
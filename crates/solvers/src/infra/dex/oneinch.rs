@@ -0,0 +1,111 @@
+use {
+    super::Error,
+    crate::domain::{dex, eth, order},
+    ethereum_types::H160,
+    ethrpc::current_block::BlockInfo,
+    shared::oneinch_api::{
+        Cache,
+        OneInchClient,
+        OneInchClientImpl,
+        OneInchError,
+        Slippage,
+        SwapQuery,
+    },
+    tokio::sync::watch,
+};
+
+/// Solves sell orders with swaps from the 1Inch API.
+pub struct OneInch {
+    client: OneInchClientImpl,
+    cache: Cache,
+    settlement: eth::ContractAddress,
+    excluded_protocols: Vec<String>,
+    referrer: Option<H160>,
+}
+
+pub struct Config {
+    pub endpoint: reqwest::Url,
+    pub chain_id: eth::ChainId,
+    pub excluded_protocols: Vec<String>,
+    pub referrer: Option<H160>,
+    /// The settlement contract which executes the swaps.
+    pub settlement: eth::ContractAddress,
+}
+
+impl OneInch {
+    pub fn new(config: Config) -> anyhow::Result<Self> {
+        // The block stream is only used for setting the current block header
+        // which solver engines don't do.
+        let (_, block_stream) = watch::channel(BlockInfo::default());
+        let client = OneInchClientImpl::new(
+            config.endpoint,
+            reqwest::Client::new(),
+            config.chain_id.value().as_u64(),
+            block_stream,
+        )?;
+        Ok(Self {
+            client,
+            cache: Cache::default(),
+            settlement: config.settlement,
+            excluded_protocols: config.excluded_protocols,
+            referrer: config.referrer,
+        })
+    }
+
+    pub async fn swap(
+        &self,
+        order: &dex::Order,
+        slippage: &dex::Slippage,
+    ) -> Result<dex::Swap, Error> {
+        // 1Inch only supports sell orders.
+        if order.side != order::Side::Sell {
+            return Err(Error::OrderNotSupported);
+        }
+
+        let protocols = self
+            .cache
+            .allowed_protocols(&self.excluded_protocols, &self.client)
+            .await?;
+        let spender = self.cache.spender(&self.client).await?;
+        let query = SwapQuery::with_default_options(
+            order.sell.0,
+            order.buy.0,
+            order.amount,
+            self.settlement.0,
+            protocols,
+            Slippage::percentage(slippage.as_percentage())?,
+            self.referrer,
+        );
+        let swap = self.client.get_swap(query, false).await?;
+
+        Ok(dex::Swap {
+            call: dex::Call {
+                to: eth::ContractAddress(swap.tx.to),
+                calldata: swap.tx.data,
+            },
+            input: eth::Asset {
+                token: order.sell,
+                amount: swap.from_token_amount,
+            },
+            output: eth::Asset {
+                token: order.buy,
+                amount: swap.to_token_amount,
+            },
+            allowance: dex::Allowance {
+                spender: eth::ContractAddress(spender.address),
+                amount: swap.from_token_amount,
+            },
+            gas: eth::Gas(swap.tx.gas.into()),
+        })
+    }
+}
+
+impl From<OneInchError> for Error {
+    fn from(err: OneInchError) -> Self {
+        match err {
+            err if err.is_insuffucient_liquidity() => Self::NotFound,
+            OneInchError::Api(err) if err.status_code == 429 => Self::RateLimited,
+            err => Self::Other(err.into()),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    super::Error,
+    crate::domain::{auction, dex, eth, order},
+    anyhow::anyhow,
+    ethereum_types::H160,
+    ethrpc::current_block::BlockInfo,
+    shared::paraswap_api::{
+        DefaultParaswapApi,
+        ParaswapApi,
+        ParaswapResponseError,
+        PriceQuery,
+        Side,
+        TradeAmount,
+        TransactionBuilderQuery,
+    },
+    tokio::sync::watch,
+};
+
+/// Solves orders with swaps from the ParaSwap API.
+pub struct ParaSwap {
+    api: DefaultParaswapApi,
+    exclude_dexs: Vec<String>,
+    address: H160,
+}
+
+pub struct Config {
+    pub endpoint: String,
+    pub exclude_dexs: Vec<String>,
+    /// The solver account for which swap transactions are built.
+    pub address: H160,
+    pub partner: String,
+}
+
+impl ParaSwap {
+    pub fn new(config: Config) -> Self {
+        // The block stream is only used for setting the current block header
+        // which solver engines don't do.
+        let (_, block_stream) = watch::channel(BlockInfo::default());
+        Self {
+            api: DefaultParaswapApi {
+                client: reqwest::Client::new(),
+                base_url: config.endpoint,
+                partner: config.partner,
+                block_stream,
+            },
+            exclude_dexs: config.exclude_dexs,
+            address: config.address,
+        }
+    }
+
+    pub async fn swap(
+        &self,
+        order: &dex::Order,
+        slippage: &dex::Slippage,
+        tokens: &auction::Tokens,
+    ) -> Result<dex::Swap, Error> {
+        let decimals = |token: &eth::TokenAddress| {
+            tokens
+                .decimals(token)
+                .ok_or_else(|| anyhow!("decimals for token {:?} not found", token.0))
+        };
+        let src_decimals = decimals(&order.sell)?;
+        let dest_decimals = decimals(&order.buy)?;
+
+        let price = self
+            .api
+            .price(
+                PriceQuery {
+                    src_token: order.sell.0,
+                    dest_token: order.buy.0,
+                    src_decimals,
+                    dest_decimals,
+                    amount: order.amount,
+                    side: match order.side {
+                        order::Side::Buy => Side::Buy,
+                        order::Side::Sell => Side::Sell,
+                    },
+                    exclude_dexs: Some(self.exclude_dexs.clone()),
+                },
+                false,
+            )
+            .await?;
+
+        let (src_amount, dest_amount) = match order.side {
+            order::Side::Buy => (slippage.add(price.src_amount), price.dest_amount),
+            order::Side::Sell => (price.src_amount, slippage.sub(price.dest_amount)),
+        };
+        let transaction = self
+            .api
+            .transaction(
+                TransactionBuilderQuery {
+                    src_token: order.sell.0,
+                    dest_token: order.buy.0,
+                    trade_amount: TradeAmount::Exact {
+                        src_amount,
+                        dest_amount,
+                    },
+                    src_decimals,
+                    dest_decimals,
+                    price_route: price.price_route_raw,
+                    user_address: self.address,
+                },
+                false,
+            )
+            .await?;
+
+        Ok(dex::Swap {
+            call: dex::Call {
+                to: eth::ContractAddress(transaction.to),
+                calldata: transaction.data,
+            },
+            input: eth::Asset {
+                token: order.sell,
+                amount: price.src_amount,
+            },
+            output: eth::Asset {
+                token: order.buy,
+                amount: price.dest_amount,
+            },
+            allowance: dex::Allowance {
+                spender: eth::ContractAddress(price.token_transfer_proxy),
+                amount: src_amount,
+            },
+            gas: eth::Gas(price.gas_cost.into()),
+        })
+    }
+}
+
+impl From<ParaswapResponseError> for Error {
+    fn from(err: ParaswapResponseError) -> Self {
+        match err {
+            ParaswapResponseError::InsufficientLiquidity(_) => Self::NotFound,
+            ParaswapResponseError::RateLimited => Self::RateLimited,
+            err => Self::Other(err.into()),
+        }
+    }
+}
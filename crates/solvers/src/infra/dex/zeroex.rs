@@ -0,0 +1,109 @@
+use {
+    super::Error,
+    crate::domain::{dex, eth, order},
+    ethrpc::current_block::BlockInfo,
+    shared::{
+        http_client::HttpClientFactory,
+        zeroex_api::{DefaultZeroExApi, Slippage, SwapQuery, ZeroExApi, ZeroExResponseError},
+    },
+    tokio::sync::watch,
+};
+
+/// Solves orders with swaps from the 0x API.
+pub struct ZeroEx {
+    api: Box<dyn ZeroExApi>,
+    settlement: eth::ContractAddress,
+    excluded_sources: Vec<String>,
+    enable_rfqt: bool,
+    enable_slippage_protection: bool,
+}
+
+pub struct Config {
+    pub endpoint: reqwest::Url,
+    pub api_key: Option<String>,
+    pub excluded_sources: Vec<String>,
+    /// The settlement contract which is the taker of the swaps.
+    pub settlement: eth::ContractAddress,
+    pub enable_rfqt: bool,
+    pub enable_slippage_protection: bool,
+}
+
+impl ZeroEx {
+    pub fn new(config: Config) -> anyhow::Result<Self> {
+        // The block stream is only used for setting the current block header
+        // which solver engines don't do.
+        let (_, block_stream) = watch::channel(BlockInfo::default());
+        let api = DefaultZeroExApi::new(
+            &HttpClientFactory::default(),
+            config.endpoint,
+            config.api_key,
+            block_stream,
+        )?;
+        Ok(Self {
+            api: Box::new(api),
+            settlement: config.settlement,
+            excluded_sources: config.excluded_sources,
+            enable_rfqt: config.enable_rfqt,
+            enable_slippage_protection: config.enable_slippage_protection,
+        })
+    }
+
+    pub async fn swap(
+        &self,
+        order: &dex::Order,
+        slippage: &dex::Slippage,
+    ) -> Result<dex::Swap, Error> {
+        let (sell_amount, buy_amount) = match order.side {
+            order::Side::Buy => (None, Some(order.amount)),
+            order::Side::Sell => (Some(order.amount), None),
+        };
+        let query = SwapQuery {
+            sell_token: order.sell.0,
+            buy_token: order.buy.0,
+            sell_amount,
+            buy_amount,
+            slippage_percentage: Some(Slippage::new(slippage.as_factor())),
+            taker_address: Some(self.settlement.0),
+            excluded_sources: self.excluded_sources.clone(),
+            intent_on_filling: self.enable_rfqt,
+            enable_slippage_protection: self.enable_slippage_protection,
+        };
+        let swap = self.api.get_swap(query, false).await?;
+
+        // 0x adds the slippage to the sell amount of buy orders, so the
+        // allowance needs to cover it.
+        let allowance = match order.side {
+            order::Side::Buy => slippage.add(swap.price.sell_amount),
+            order::Side::Sell => swap.price.sell_amount,
+        };
+        Ok(dex::Swap {
+            call: dex::Call {
+                to: eth::ContractAddress(swap.to),
+                calldata: swap.data,
+            },
+            input: eth::Asset {
+                token: order.sell,
+                amount: swap.price.sell_amount,
+            },
+            output: eth::Asset {
+                token: order.buy,
+                amount: swap.price.buy_amount,
+            },
+            allowance: dex::Allowance {
+                spender: eth::ContractAddress(swap.price.allowance_target),
+                amount: allowance,
+            },
+            gas: eth::Gas(swap.price.estimated_gas.into()),
+        })
+    }
+}
+
+impl From<ZeroExResponseError> for Error {
+    fn from(err: ZeroExResponseError) -> Self {
+        match err {
+            ZeroExResponseError::InsufficientLiquidity => Self::NotFound,
+            ZeroExResponseError::RateLimited => Self::RateLimited,
+            err => Self::Other(err.into()),
+        }
+    }
+}
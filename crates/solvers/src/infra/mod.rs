@@ -2,4 +2,5 @@ pub mod blockchain;
 pub mod cli;
 pub mod config;
 pub mod contracts;
+pub mod dex;
 pub mod metrics;
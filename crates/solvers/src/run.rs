@@ -3,7 +3,7 @@ use tokio::signal::unix::{self, SignalKind};
 use {
     crate::{
         domain::solver::{self, Solver},
-        infra::{cli, config},
+        infra::{cli, config, dex},
     },
     clap::Parser,
     std::net::SocketAddr,
@@ -41,6 +41,26 @@ async fn run_with(args: cli::Args, bind: Option<oneshot::Sender<SocketAddr>>) {
             let config = config::legacy::load(&config).await;
             Solver::Legacy(solver::Legacy::new(config))
         }
+        cli::Command::ZeroEx { config } => {
+            let (config, zeroex) = config::dex::zeroex::load(&config).await;
+            let zeroex = dex::zeroex::ZeroEx::new(zeroex).expect("invalid 0x configuration");
+            Solver::Dex(solver::Dex::new(dex::Dex::ZeroEx(zeroex), config))
+        }
+        cli::Command::OneInch { config } => {
+            let (config, oneinch) = config::dex::oneinch::load(&config).await;
+            let oneinch = dex::oneinch::OneInch::new(oneinch).expect("invalid 1Inch configuration");
+            Solver::Dex(solver::Dex::new(dex::Dex::OneInch(oneinch), config))
+        }
+        cli::Command::ParaSwap { config } => {
+            let (config, paraswap) = config::dex::paraswap::load(&config).await;
+            let paraswap = dex::paraswap::ParaSwap::new(paraswap);
+            Solver::Dex(solver::Dex::new(dex::Dex::ParaSwap(paraswap), config))
+        }
+        cli::Command::Balancer { config } => {
+            let (config, sor) = config::dex::balancer::load(&config).await;
+            let sor = dex::balancer::Sor::new(sor).expect("invalid Balancer SOR configuration");
+            Solver::Dex(solver::Dex::new(dex::Dex::Balancer(sor), config))
+        }
     };
 
     crate::api::Api {
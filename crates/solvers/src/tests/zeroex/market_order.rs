@@ -0,0 +1,126 @@
+//! Simple test case that verifies the solver can handle market orders with
+//! swaps from the 0x API.
+
+use {
+    crate::tests::{self, mock, zeroex},
+    serde_json::json,
+};
+
+#[tokio::test]
+async fn sell() {
+    let api = mock::http::setup(vec![mock::http::Expectation::Get {
+        path: mock::http::Path::glob(
+            "swap/v1/quote[?]sellToken=0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2&\
+             buyToken=0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab&intentOnFilling=false&\
+             enableSlippageProtection=false&sellAmount=1000000000000000000&slippagePercentage=0.\
+             01&takerAddress=0x9008d19f58aabd9ed0d60971565aa8510560ab41*",
+        ),
+        res: json!({
+            "price": "6000",
+            "to": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+            "data": "0x0102030405",
+            "value": "0",
+            "estimatedGas": "111000",
+            "allowanceTarget": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+            "sellAmount": "1000000000000000000",
+            "buyAmount": "6000000000000000000000",
+        }),
+    }])
+    .await;
+
+    let engine = tests::SolverEngine::new("zeroex", zeroex::config(&api.address)).await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "166666666666666",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "1000000000000000000",
+                    "buyAmount": "5000000000000000000000",
+                    "feeAmount": "1000000000000000",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                }
+            ],
+            "liquidity": [],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "6000000000000000000000",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "interactions": [
+                    {
+                        "kind": "custom",
+                        "internalize": false,
+                        "target": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+                        "value": "0",
+                        "callData": "0x0102030405",
+                        "allowances": [
+                            {
+                                "token": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                                "spender": "0xdef1c0ded9bec7f1a1670819833240f027b25eff",
+                                "amount": "1000000000000000000"
+                            }
+                        ],
+                        "inputs": [
+                            {
+                                "token": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                                "amount": "1000000000000000000"
+                            }
+                        ],
+                        "outputs": [
+                            {
+                                "token": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                                "amount": "6000000000000000000000"
+                            }
+                        ]
+                    }
+                ],
+                "score": {
+                    "kind": "riskadjusted",
+                    "successProbability": 0.5,
+                }
+            }]
+        }),
+    );
+}
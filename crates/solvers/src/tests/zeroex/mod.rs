@@ -0,0 +1,15 @@
+use {crate::tests, std::net::SocketAddr};
+
+mod market_order;
+
+/// Creates a 0x solver configuration for the specified API host.
+pub fn config(solver_addr: &SocketAddr) -> tests::Config {
+    tests::Config::String(format!(
+        r"
+risk-parameters = [0,0,0,0]
+[dex]
+chain-id = '1'
+endpoint = 'http://{solver_addr}/'
+        ",
+    ))
+}
@@ -0,0 +1,7 @@
+-- Indexes supporting the order history query. Orders are paginated by a sort
+-- key with the order uid as tie breaker so the uid is part of every index.
+CREATE INDEX order_creation_timestamp_uid ON orders USING BTREE (creation_timestamp, uid);
+CREATE INDEX user_order_creation_timestamp_uid ON orders USING BTREE (owner, creation_timestamp, uid);
+CREATE INDEX order_valid_to_uid ON orders USING BTREE (valid_to, uid);
+CREATE INDEX order_sell_token_creation_timestamp ON orders USING BTREE (sell_token, creation_timestamp);
+CREATE INDEX order_buy_token_creation_timestamp ON orders USING BTREE (buy_token, creation_timestamp);
@@ -0,0 +1,9 @@
+-- API keys for clients of the orderbook API with their own rate limit quotas.
+-- Only hashes of the keys are stored.
+CREATE TABLE api_keys (
+    key_hash bytea PRIMARY KEY,
+    name text NOT NULL,
+    requests_per_minute integer NOT NULL,
+    burst integer NOT NULL,
+    revoked boolean NOT NULL DEFAULT false
+);
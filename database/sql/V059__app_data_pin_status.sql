@@ -0,0 +1,15 @@
+CREATE TYPE AppDataPinStatus AS ENUM (
+  'pending',
+  'pinned',
+  'failed'
+);
+
+-- Tracks whether the full app data has been pinned to IPFS so that it can be
+-- resolved without going through our API. Existing rows start out as pending so
+-- that they get pinned once pinning is enabled.
+ALTER TABLE app_data
+    ADD COLUMN pin_status AppDataPinStatus NOT NULL DEFAULT 'pending',
+    ADD COLUMN pin_attempts integer NOT NULL DEFAULT 0;
+
+-- Allows quickly finding documents that still need to be pinned.
+CREATE INDEX app_data_unpinned ON app_data USING BTREE (pin_status) WHERE pin_status <> 'pinned';
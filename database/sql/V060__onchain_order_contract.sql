@@ -0,0 +1,10 @@
+-- Onchain orders can be placed through any number of `CoWSwapOnchainOrders`
+-- compatible contracts, so we keep track of which contract emitted each event.
+-- Rows indexed before this migration have no contract and are attributed to
+-- the EthFlow contract by the autopilot on startup.
+ALTER TABLE onchain_placed_orders ADD COLUMN contract bytea;
+ALTER TABLE onchain_order_invalidations ADD COLUMN contract bytea;
+
+-- Allows quickly finding the last indexed block and handling reorgs per contract.
+CREATE INDEX onchain_placed_orders_contract ON onchain_placed_orders USING BTREE (contract, block_number);
+CREATE INDEX onchain_order_invalidations_contract ON onchain_order_invalidations USING BTREE (contract, block_number);
@@ -0,0 +1,11 @@
+-- Solvers may settle just-in-time (JIT) liquidity orders that were never part
+-- of the auction. These are reported by the driver when revealing the winning
+-- solution so that their trades can be attributed to the solver instead of
+-- being treated as unknown user trades.
+CREATE TABLE jit_orders (
+  auction_id bigint NOT NULL,
+  uid bytea NOT NULL,
+  owner bytea NOT NULL,
+
+  PRIMARY KEY (auction_id, uid)
+);
@@ -0,0 +1,13 @@
+-- Whether orders with this app data may only be matched peer-to-peer. The flag
+-- is derived from the full app data when it is stored so that it doesn't have
+-- to be parsed again whenever the orders are read.
+ALTER TABLE app_data ADD COLUMN cow_only boolean NOT NULL DEFAULT false;
+
+UPDATE app_data
+SET cow_only = true
+WHERE convert_from(full_app_data, 'UTF8')::jsonb -> 'metadata' ->> 'cowOnly' = 'true';
+
+-- The surplus, converted to ETH, of CoW-only orders that were matched against
+-- on-chain liquidity in the settlement of the auction. Populated after the
+-- settlement occurs on chain.
+ALTER TABLE settlement_scores ADD COLUMN cow_only_penalty numeric(78,0) NOT NULL DEFAULT 0;